@@ -0,0 +1,64 @@
+//! Project configuration loaded from `embargo.toml`.
+//!
+//! Every key is optional; command-line flags take precedence over file values.
+//!
+//! ```toml
+//! languages = ["rust", "python"]
+//! exclude = ["vendor/**", "*.test.ts"]
+//! format = "llm-optimized"
+//! verbosity = "verbose"
+//! output = "docs/EMBARGO.md"
+//! semantic_prefixes = ["handlers/=H", "repositories/=RP"]
+//! clusters = ["src/billing/**=BILLING", "src/auth/**=AUTH"]
+//! call_priorities = ["log_*=9", "*_service=0"]
+//! root_relative_paths = true
+//! ```
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// File name looked up in the input directory when `--config` is not given.
+pub const CONFIG_FILE_NAME: &str = "embargo.toml";
+
+/// Settings read from `embargo.toml`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub languages: Option<Vec<String>>,
+    /// Globs added to any `--exclude` flags
+    pub exclude: Vec<String>,
+    /// Output format name as accepted by `--format`
+    pub format: Option<String>,
+    /// Verbosity name as accepted by `--verbosity`
+    pub verbosity: Option<String>,
+    pub output: Option<PathBuf>,
+    /// `pattern=prefix` pairs as accepted by `--prefix`
+    pub semantic_prefixes: Option<Vec<String>>,
+    /// `glob=NAME` pairs as accepted by `--cluster`
+    pub clusters: Option<Vec<String>>,
+    /// `pattern=priority` pairs as accepted by `--call-priority`
+    pub call_priorities: Option<Vec<String>>,
+    /// Store paths relative to the input as `--root-relative-paths` does; `--absolute-paths`
+    /// turns it back off
+    pub root_relative_paths: Option<bool>,
+}
+
+impl Config {
+    /// Parse a config file; unknown keys are rejected so typos do not go unnoticed.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Invalid config {}", path.display()))
+    }
+
+    /// Load `embargo.toml` from `dir` if it exists.
+    pub fn discover(dir: &Path) -> Result<Option<(PathBuf, Self)>> {
+        let path = dir.join(CONFIG_FILE_NAME);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let config = Self::from_file(&path)?;
+        Ok(Some((path, config)))
+    }
+}
@@ -3,10 +3,20 @@
 //! Coordinates file scanning, parsing, and dependency graph construction.
 
 use anyhow::Result;
-use std::path::Path;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 
-use super::{DependencyGraph, FileScanner, FunctionResolver};
-use crate::parsers::{cache::ParseCache, ParserFactory};
+use super::scanner::{FileInfo, SkippedFile};
+use super::{
+    CallResolution, DependencyGraph, DependencyGraphExt, Edge, EdgeType, FileScanner,
+    FunctionResolver, Node, NodeType,
+};
+use crate::parsers::{cache::ParseCache, LanguageParser, ParseResult, ParserFactory, SyntaxError};
 
 /// Main orchestrator for codebase analysis.
 ///
@@ -17,6 +27,100 @@ pub struct CodebaseAnalyzer {
     parser_factory: ParserFactory,
     function_resolver: FunctionResolver,
     parse_cache: ParseCache,
+    /// Send progress messages to stderr, keeping stdout free for piped output
+    log_to_stderr: bool,
+    /// Report files parsed so far on stderr during the parse phase
+    show_progress: bool,
+    /// Call sites matched and missed by the last analysis
+    call_resolution: CallResolution,
+    /// Link files to what they import with `Import` edges
+    import_edges: bool,
+    /// Link classes to the types of their fields and properties with `Composes` edges
+    composition_edges: bool,
+    /// Suppress progress messages; warnings are still printed
+    quiet: bool,
+    /// Time spent in each phase of the last analysis
+    timings: PhaseTimings,
+    /// Files of the last analysis that had syntax errors, in scan order
+    syntax_errors: Vec<(PathBuf, Vec<SyntaxError>)>,
+    /// Files the last analysis's scan passed over as too large, minified or binary
+    skipped_files: Vec<SkippedFile>,
+}
+
+/// Wall-clock time of each analysis phase
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PhaseTimings {
+    /// Walking the input and mapping files to languages
+    pub scan: Duration,
+    /// Parsing files, or loading them from the cache, and adding their nodes to the graph
+    pub parse: Duration,
+    /// Building the resolver's name and class indexes
+    pub index: Duration,
+    /// Resolving call sites into edges, caching the results, and linking overrides and imports
+    pub resolve: Duration,
+}
+
+/// Throttled `parsed N/total` reporting, shared by the parallel parse workers
+struct ParseProgress {
+    total: usize,
+    done: AtomicUsize,
+    start: Instant,
+    /// Milliseconds after `start` of the last report
+    last_report_ms: AtomicU64,
+}
+
+impl ParseProgress {
+    const EVERY_FILES: usize = 100;
+    const EVERY_MS: u64 = 200;
+
+    fn new(total: usize) -> Self {
+        Self {
+            total,
+            done: AtomicUsize::new(0),
+            start: Instant::now(),
+            last_report_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// Count one finished file; reports every 100 files or 200ms, whichever comes first
+    fn file_done(&self, language: &str) {
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        let now_ms = self.start.elapsed().as_millis() as u64;
+        let last_ms = self.last_report_ms.load(Ordering::Relaxed);
+        let due = done.is_multiple_of(Self::EVERY_FILES)
+            || done == self.total
+            || now_ms.saturating_sub(last_ms) >= Self::EVERY_MS;
+        // Only the worker that claims the slot prints, so reports never pile up
+        if due
+            && self
+                .last_report_ms
+                .compare_exchange(last_ms, now_ms, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            eprintln!("Parsed {}/{} files ({})", done, self.total, language);
+        }
+    }
+}
+
+/// Files parsed per batch. Each batch's results move into the graph before the next is
+/// parsed, so at most this many `ParseResult`s are held at once.
+const PARSE_BATCH_FILES: usize = 512;
+
+/// One file's parse result, and what the cache knew about the file
+struct ParsedFile {
+    path: PathBuf,
+    result: ParseResult,
+    from_cache: bool,
+    /// Names the file defined before it was reparsed; empty on a cache hit or a new file
+    previous_names: Vec<String>,
+}
+
+/// What call resolution needs of a file once its nodes and edges are in the graph
+struct FileSummary {
+    path: PathBuf,
+    from_cache: bool,
+    /// Names the file defines now or defined before it was reparsed; empty on a cache hit
+    changed_names: Vec<String>,
 }
 
 impl CodebaseAnalyzer {
@@ -30,6 +134,102 @@ impl CodebaseAnalyzer {
                 eprintln!("Warning: Failed to initialize disk parse cache: {err}");
                 ParseCache::in_memory_only()
             }),
+            log_to_stderr: false,
+            show_progress: false,
+            call_resolution: CallResolution::default(),
+            import_edges: false,
+            composition_edges: false,
+            quiet: false,
+            timings: PhaseTimings::default(),
+            syntax_errors: Vec::new(),
+            skipped_files: Vec::new(),
+        }
+    }
+
+    /// Replaces the file scanner, e.g. to apply exclude globs or disable `.gitignore`.
+    pub fn with_file_scanner(mut self, file_scanner: FileScanner) -> Self {
+        self.file_scanner = file_scanner;
+        self
+    }
+
+    /// Replaces the parser factory, e.g. one with custom parsers added via
+    /// `ParserFactory::register`.
+    #[allow(dead_code)]
+    pub fn with_parser_factory(mut self, parser_factory: ParserFactory) -> Self {
+        self.parser_factory = parser_factory;
+        self
+    }
+
+    /// Replaces the parse cache, e.g. with a persistent `ParseCache::from_file` or
+    /// `ParseCache::in_memory_only` to disable caching across runs.
+    pub fn with_parse_cache(mut self, parse_cache: ParseCache) -> Self {
+        self.parse_cache = parse_cache;
+        self
+    }
+
+    /// Routes progress messages to stderr instead of stdout.
+    pub fn with_log_to_stderr(mut self, log_to_stderr: bool) -> Self {
+        self.log_to_stderr = log_to_stderr;
+        self
+    }
+
+    /// Reports parse progress on stderr (throttled) for long runs on large trees.
+    pub fn with_progress(mut self, show_progress: bool) -> Self {
+        self.show_progress = show_progress;
+        self
+    }
+
+    /// Adds a `Module` node per importing file and `Import` edges from it to the files it
+    /// imports, or to the import statement's node when it names something outside the
+    /// codebase. Off by default since it adds a node per file.
+    pub fn with_import_edges(mut self, import_edges: bool) -> Self {
+        self.import_edges = import_edges;
+        self
+    }
+
+    /// Adds `Composes` edges from each class to the classes its fields and properties hold
+    /// (Java, C#, TypeScript, Go), matched by name across files. Off by default since
+    /// field types often name library classes and shared names can only be guessed at.
+    pub fn with_composition_edges(mut self, composition_edges: bool) -> Self {
+        self.composition_edges = composition_edges;
+        self
+    }
+
+    /// Drops progress messages, e.g. when analyzing the same tree repeatedly to time it.
+    pub fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// How many call sites the last analysis resolved, and the names it could not
+    pub fn call_resolution(&self) -> &CallResolution {
+        &self.call_resolution
+    }
+
+    /// How long each phase of the last analysis took
+    pub fn timings(&self) -> PhaseTimings {
+        self.timings
+    }
+
+    /// Files the last analysis could not fully parse, with where each error starts. Their
+    /// nodes and edges are incomplete around those spots.
+    pub fn syntax_errors(&self) -> &[(PathBuf, Vec<SyntaxError>)] {
+        &self.syntax_errors
+    }
+
+    /// Files the last analysis did not parse because of their size or content, in path order
+    pub fn skipped_files(&self) -> &[SkippedFile] {
+        &self.skipped_files
+    }
+
+    fn log(&self, message: &str) {
+        if self.quiet {
+            return;
+        }
+        if self.log_to_stderr {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
         }
     }
 
@@ -37,126 +237,527 @@ impl CodebaseAnalyzer {
     ///
     /// Scans the directory for source files, parses them using language-specific
     /// parsers, and constructs a graph of code entities and their relationships.
+    #[allow(dead_code)]
     pub fn analyze(&mut self, root_path: &Path, languages: &[&str]) -> Result<DependencyGraph> {
-        println!("Scanning files...");
-        let files = self.file_scanner.scan_directory(root_path, languages)?;
-        println!("Found {} files to analyze", files.len());
+        self.analyze_roots(&[root_path.to_path_buf()], languages)
+    }
 
-        let mut graph_builder = super::graph::GraphBuilder::new();
+    /// Analyzes several directories as one codebase.
+    ///
+    /// Files from every root land in a single graph, so calls are resolved across roots,
+    /// e.g. between services checked out side by side.
+    pub fn analyze_roots(
+        &mut self,
+        roots: &[PathBuf],
+        languages: &[&str],
+    ) -> Result<DependencyGraph> {
+        self.log("Scanning files...");
+        let scan_start = Instant::now();
+        // Drop whatever an earlier scan with the same scanner left behind
+        self.file_scanner.take_skipped();
+        let files = self.file_scanner.scan_directories(roots, languages)?;
+        self.timings = PhaseTimings {
+            scan: scan_start.elapsed(),
+            ..PhaseTimings::default()
+        };
+        self.log(&format!("Found {} files to analyze", files.len()));
+        self.log_skipped();
 
-        println!("Parsing files with cache optimization...");
-
-        // Check which files need reparsing
-        let mut cached_count = 0;
-        let mut parse_results = Vec::with_capacity(files.len());
-
-        // Process files with cache checking (sequential for cache access)
-        for file_info in &files {
-            match self.parse_cache.needs_update(&file_info.path) {
-                Ok(needs_update) => {
-                    if !needs_update {
-                        if let Some(cached_result) = self.parse_cache.get(&file_info.path) {
-                            parse_results.push(cached_result);
-                            cached_count += 1;
-                            continue;
-                        }
-                    }
+        // Forget files that disappeared since the cache was written
+        let live_files: HashSet<PathBuf> = files.iter().map(|f| f.path.clone()).collect();
+        self.parse_cache.retain_files(&live_files);
+
+        self.build_graph(&files)
+    }
+
+    /// Analyzes an explicit list of files instead of walking a directory.
+    ///
+    /// Paths are deduplicated and mapped to parsers by extension; files in languages
+    /// that were not requested are skipped. Cache entries for other files are kept,
+    /// since a partial file list says nothing about what was deleted.
+    pub fn analyze_files(
+        &mut self,
+        paths: &[PathBuf],
+        languages: &[&str],
+    ) -> Result<DependencyGraph> {
+        let scan_start = Instant::now();
+        self.file_scanner.take_skipped();
+        let files = self.file_scanner.scan_files(paths, languages);
+        self.timings = PhaseTimings {
+            scan: scan_start.elapsed(),
+            ..PhaseTimings::default()
+        };
+        self.log(&format!("Found {} files to analyze", files.len()));
+        self.log_skipped();
+
+        self.build_graph(&files)
+    }
+
+    /// Collect what the scan just skipped and mention it, so missing nodes can be explained
+    fn log_skipped(&mut self) {
+        self.skipped_files = self.file_scanner.take_skipped();
+        if !self.skipped_files.is_empty() {
+            self.log(&format!(
+                "Skipped {} files that are too large, minified or binary",
+                self.skipped_files.len()
+            ));
+        }
+    }
+
+    /// Parse a single file, or load it from the cache; failures are reported as warnings
+    /// and yield `None`.
+    fn parse_one(
+        file_info: &FileInfo,
+        parse_cache: &ParseCache,
+        parser_factory: &ParserFactory,
+        parsers: &mut HashMap<String, Box<dyn LanguageParser + Send + Sync>>,
+    ) -> Option<ParsedFile> {
+        match parse_cache.needs_update(&file_info.path) {
+            Ok(false) => {
+                if let Some(cached_result) = parse_cache.get(&file_info.path) {
+                    return Some(ParsedFile {
+                        path: file_info.path.clone(),
+                        result: cached_result,
+                        from_cache: true,
+                        previous_names: Vec::new(),
+                    });
+                }
+            }
+            Ok(true) => {}
+            Err(err) => {
+                eprintln!(
+                    "Warning: Failed to validate cache entry for {}: {}",
+                    file_info.path.display(),
+                    err
+                );
+            }
+        }
+
+        // Parsers are reused per worker and per language
+        if !parsers.contains_key(&file_info.language) {
+            match parser_factory.get_parser(&file_info.language) {
+                Ok(parser) => {
+                    parsers.insert(file_info.language.clone(), parser);
                 }
-                Err(err) => {
+                Err(_) => {
                     eprintln!(
-                        "Warning: Failed to validate cache entry for {}: {}",
-                        file_info.path.display(),
-                        err
+                        "Warning: Unsupported language '{}' for file {}",
+                        file_info.language,
+                        file_info.path.display()
                     );
+                    return None;
                 }
             }
+        }
+        let parser = &parsers[&file_info.language];
 
-            // Parse file if not cached or cache miss
-            if let Ok(parser) = self.parser_factory.get_parser(&file_info.language) {
-                match parser.parse_file(&file_info.path) {
-                    Ok(result) => {
-                        // Store in cache for next time
-                        if let Err(e) = self.parse_cache.store(&file_info.path, &result) {
-                            eprintln!(
-                                "Warning: Failed to cache {}: {}",
-                                file_info.path.display(),
-                                e
-                            );
-                        }
-                        parse_results.push(result);
-                    }
-                    Err(e) => {
-                        eprintln!(
-                            "Warning: Failed to parse {}: {}",
-                            file_info.path.display(),
-                            e
-                        );
-                    }
+        // Callers in other files may have resolved to what the stale entry defined
+        let previous_names = parse_cache
+            .get(&file_info.path)
+            .map(|stale| stale.nodes.into_iter().map(|node| node.name).collect())
+            .unwrap_or_default();
+
+        match parser.parse_file(&file_info.path) {
+            Ok(result) => {
+                // Store in cache for next time
+                if let Err(e) = parse_cache.store(&file_info.path, &result) {
+                    eprintln!(
+                        "Warning: Failed to cache {}: {}",
+                        file_info.path.display(),
+                        e
+                    );
                 }
-            } else {
+                Some(ParsedFile {
+                    path: file_info.path.clone(),
+                    result,
+                    from_cache: false,
+                    previous_names,
+                })
+            }
+            Err(e) => {
                 eprintln!(
-                    "Warning: Unsupported language '{}' for file {}",
-                    file_info.language,
-                    file_info.path.display()
+                    "Warning: Failed to parse {}: {}",
+                    file_info.path.display(),
+                    e
                 );
+                None
             }
         }
+    }
 
-        println!(
-            "Cache hits: {}, Parsed: {}",
-            cached_count,
-            parse_results.len() - cached_count
-        );
+    /// Parse (or load) every file, then resolve calls across all of them.
+    ///
+    /// Call resolution is incremental. Each cache entry keeps how its call sites resolved,
+    /// and the cache records which files made up the index at the time. The index itself
+    /// is rebuilt every run, since that is linear in the (cached) nodes; resolving calls
+    /// against it, fuzzy matching especially, is the expensive part that gets reused.
+    ///
+    /// The invariant: a stored result stays valid until a definition it could match
+    /// changes. A reparsed file can add, remove or move definitions (ids carry line
+    /// numbers), creating or breaking targets for callers in files that did not change.
+    /// So every name a reparsed file defines now or defined before marks the cached call
+    /// sites that may match it for re-resolution, see
+    /// [`FunctionResolver::may_resolve_differently`]. When files joined or left the index
+    /// without being reparsed (deletions, a different file list), everything is resolved again.
+    fn build_graph(&mut self, files: &[FileInfo]) -> Result<DependencyGraph> {
+        let mut graph_builder = super::graph::GraphBuilder::new();
 
-        println!("Building dependency graph...");
+        self.log("Parsing files with cache optimization...");
+        let parse_start = Instant::now();
 
-        // Pre-calculate total capacity to avoid reallocations
-        let total_nodes: usize = parse_results.iter().map(|r| r.nodes.len()).sum();
-        let _total_edges: usize = parse_results.iter().map(|r| r.edges.len()).sum();
+        let parse_cache = &self.parse_cache;
+        let parser_factory = &self.parser_factory;
+        let progress = self.show_progress.then(|| ParseProgress::new(files.len()));
+        // Without stored results to reuse, which names changed does not matter
+        let track_changes = self.parse_cache.resolution_scope().is_some();
 
-        // Pre-allocate collections with known capacity
-        let mut all_nodes = Vec::with_capacity(total_nodes);
+        let mut summaries: Vec<FileSummary> = Vec::with_capacity(files.len());
         let mut all_call_sites: Vec<crate::core::CallSite> = Vec::new();
+        // Where each file's call sites sit in `all_call_sites`
+        let mut call_site_ranges = Vec::with_capacity(files.len());
+        // Parsers create `external:*` placeholders per file; nodes sharing an id are merged
+        // into the first one, so every file's edges land on a single node
+        let mut merged_nodes = 0usize;
+        // Field types mostly name classes of other files, so these wait for every node
+        let mut compositions: Vec<Edge> = Vec::new();
+        self.syntax_errors.clear();
 
-        for mut parse_result in parse_results {
-            for node in &parse_result.nodes {
-                // Retain a separate copy for the resolver indexes
-                all_nodes.push(node.clone());
-            }
+        // Parse files in parallel, a batch at a time, moving each result into the graph as
+        // soon as its batch is done; the cache is a DashMap and each parser builds its own
+        // tree-sitter state, so workers share nothing mutable. Results keep file order.
+        for batch in files.chunks(PARSE_BATCH_FILES) {
+            let parsed_files: Vec<Option<ParsedFile>> = batch
+                .par_iter()
+                .map_init(HashMap::new, |parsers, file_info| {
+                    let outcome = Self::parse_one(file_info, parse_cache, parser_factory, parsers);
+                    if let Some(progress) = &progress {
+                        progress.file_done(&file_info.language);
+                    }
+                    outcome
+                })
+                .collect();
 
-            for node in parse_result.nodes.drain(..) {
-                graph_builder.add_node(node);
-            }
+            for parsed_file in parsed_files.into_iter().flatten() {
+                let ParsedFile {
+                    path,
+                    result,
+                    from_cache,
+                    previous_names,
+                } = parsed_file;
+                // Taken before the nodes move into the graph
+                let changed_names = if from_cache || !track_changes {
+                    Vec::new()
+                } else {
+                    result
+                        .nodes
+                        .iter()
+                        .map(|node| node.name.clone())
+                        .chain(previous_names)
+                        .collect()
+                };
 
-            for edge in parse_result.edges {
-                graph_builder.add_edge(edge);
-            }
+                for node in result.nodes {
+                    if graph_builder.get_node_index(&node.id).is_some() {
+                        merged_nodes += 1;
+                        continue;
+                    }
+                    graph_builder.add_node(node);
+                }
 
-            if let Some(call_sites) = parse_result.call_sites {
-                all_call_sites.extend(call_sites);
+                for edge in result.edges {
+                    if edge.edge_type == EdgeType::Composes {
+                        if self.composition_edges {
+                            compositions.push(edge);
+                        }
+                        continue;
+                    }
+                    graph_builder.add_edge(edge);
+                }
+
+                if !result.syntax_errors.is_empty() {
+                    self.syntax_errors
+                        .push((path.clone(), result.syntax_errors));
+                }
+
+                let start = all_call_sites.len();
+                all_call_sites.extend(result.call_sites.into_iter().flatten());
+                call_site_ranges.push(start..all_call_sites.len());
+
+                summaries.push(FileSummary {
+                    path,
+                    from_cache,
+                    changed_names,
+                });
             }
         }
 
-        println!("Resolving function calls...");
+        self.timings.parse = parse_start.elapsed();
+        let cached_count = summaries.iter().filter(|file| file.from_cache).count();
+        self.log(&format!(
+            "Cache hits: {}, Parsed: {}",
+            cached_count,
+            summaries.len() - cached_count
+        ));
+        if merged_nodes > 0 {
+            self.log(&format!("Merged {} duplicate nodes", merged_nodes));
+        }
+
+        let scope: HashSet<PathBuf> = summaries.iter().map(|f| f.path.clone()).collect();
+        let changed_names = self.changed_definitions(&summaries, &scope);
+
+        self.log("Resolving function calls...");
 
-        // Build function resolution index using optimized parallel processing
+        // Build function resolution index using optimized parallel processing, straight
+        // from the graph rather than a copy of its nodes. Class membership (`Contains`
+        // edges) lets the resolver pick methods by receiver type.
+        let index_start = Instant::now();
         let mut resolver = self.function_resolver.clone();
-        resolver.build_indexes(&all_nodes)?;
+        {
+            let nodes: Vec<&Node> = graph_builder.nodes().collect();
+            let containment_edges: Vec<&Edge> = graph_builder
+                .edges()
+                .filter(|edge| edge.edge_type == EdgeType::Contains)
+                .collect();
+            resolver.build_indexes_with_edges(&nodes, &containment_edges)?;
+        }
+        self.timings.index = index_start.elapsed();
+        let resolve_start = Instant::now();
+
+        // Start from the last run's per-site results and resolve only what may have changed
+        let mut results: Vec<Option<Edge>> = vec![None; all_call_sites.len()];
+        let mut pending: Vec<usize> = Vec::new();
+        for (summary, range) in summaries.iter().zip(&call_site_ranges) {
+            let previous = match &changed_names {
+                Some(_) if summary.from_cache => self
+                    .parse_cache
+                    .resolved_calls(&summary.path)
+                    .filter(|previous| previous.len() == range.len()),
+                _ => None,
+            };
+            let (Some(previous), Some(changed_names)) = (previous, &changed_names) else {
+                pending.extend(range.clone());
+                continue;
+            };
+            for (index, previous) in range.clone().zip(previous) {
+                if resolver.may_resolve_differently(
+                    &all_call_sites[index],
+                    &previous,
+                    changed_names,
+                ) {
+                    pending.push(index);
+                } else {
+                    results[index] = previous;
+                }
+            }
+        }
+        let pending_sites: Vec<&crate::core::CallSite> = pending
+            .iter()
+            .map(|&index| &all_call_sites[index])
+            .collect();
+        for (index, result) in pending.iter().zip(resolver.resolve_each(&pending_sites)) {
+            results[*index] = result;
+        }
+
+        for (summary, range) in summaries.iter().zip(&call_site_ranges) {
+            if let Err(e) = self
+                .parse_cache
+                .store_resolved_calls(&summary.path, results[range.clone()].to_vec())
+            {
+                eprintln!(
+                    "Warning: Failed to cache call resolution for {}: {}",
+                    summary.path.display(),
+                    e
+                );
+            }
+        }
+        self.parse_cache.set_resolution_scope(scope);
+        if let Err(e) = self.parse_cache.persist() {
+            eprintln!("Warning: Failed to persist parse cache: {}", e);
+        }
 
         // Resolve function calls into edges when call sites are available
+        self.call_resolution = CallResolution::default();
         if !all_call_sites.is_empty() {
-            let call_edges = resolver.resolve_calls(&all_call_sites);
+            let (call_edges, call_resolution) =
+                FunctionResolver::merge_resolved(&all_call_sites, results);
+            self.call_resolution = call_resolution;
             let mut added = 0usize;
             for edge in call_edges {
                 if graph_builder.add_edge(edge).is_some() {
                     added += 1;
                 }
             }
-            println!("Resolved {} call edges", added);
+            self.log(&format!(
+                "Resolved {} call edges ({} of {} call sites re-resolved)",
+                added,
+                pending.len(),
+                all_call_sites.len()
+            ));
         } else {
-            println!("No call sites detected; skipping call resolution");
+            self.log("No call sites detected; skipping call resolution");
+        }
+
+        let mut graph = graph_builder.build();
+        let overrides = Self::link_overrides(&mut graph);
+        if overrides > 0 {
+            self.log(&format!("Linked {} overriding methods", overrides));
+        }
+        if self.composition_edges {
+            let linked = Self::link_compositions(&mut graph, compositions);
+            self.log(&format!("Linked {} field and property types", linked));
+        }
+        if self.import_edges {
+            let imports = super::imports::link_imports(&mut graph, files);
+            self.log(&format!("Linked {} imports", imports));
+        }
+        self.timings.resolve = resolve_start.elapsed();
+        // Outputs follow index order; make it independent of parse and resolution order
+        Ok(graph.sorted())
+    }
+
+    /// Names whose definitions may differ from those the stored call resolution saw:
+    /// whatever the reparsed files define now or defined before. `None` when stored
+    /// results cannot be trusted at all, because there are none or because files joined
+    /// or left the index without being reparsed.
+    fn changed_definitions(
+        &self,
+        files: &[FileSummary],
+        scope: &HashSet<PathBuf>,
+    ) -> Option<HashSet<String>> {
+        let previous_scope = self.parse_cache.resolution_scope()?;
+        let left = previous_scope.iter().any(|path| !scope.contains(path));
+        let joined = files
+            .iter()
+            .any(|file| file.from_cache && !previous_scope.contains(&file.path));
+        if left || joined {
+            return None;
+        }
+
+        Some(
+            files
+                .iter()
+                .flat_map(|file| file.changed_names.iter().cloned())
+                .collect(),
+        )
+    }
+
+    /// Add an `Overrides` edge from each method to the same-named method of its nearest
+    /// ancestor, following `Inheritance` edges. Only parent classes present in the graph
+    /// count; external placeholders have no methods to override.
+    fn link_overrides(graph: &mut DependencyGraph) -> usize {
+        let children = |graph: &DependencyGraph, idx: NodeIndex, edge_type: EdgeType| {
+            graph
+                .edges(idx)
+                .filter(move |e| e.weight().edge_type == edge_type)
+                .map(|e| e.target())
+                .collect::<Vec<_>>()
+        };
+        let methods_of = |graph: &DependencyGraph, class: NodeIndex| {
+            children(graph, class, EdgeType::Contains)
+                .into_iter()
+                .filter(|&idx| graph[idx].node_type == NodeType::Function)
+                .collect::<Vec<_>>()
+        };
+
+        let mut links = Vec::new();
+        for class in graph.node_indices() {
+            if graph[class].node_type != NodeType::Class {
+                continue;
+            }
+            let parents = children(graph, class, EdgeType::Inheritance);
+            if parents.is_empty() {
+                continue;
+            }
+
+            for method in methods_of(graph, class) {
+                // Breadth-first, so the nearest ancestor's definition wins
+                let mut visited: HashSet<NodeIndex> = [class].into_iter().collect();
+                let mut queue: VecDeque<NodeIndex> = parents.iter().copied().collect();
+                while let Some(ancestor) = queue.pop_front() {
+                    if !visited.insert(ancestor) {
+                        continue;
+                    }
+                    let overridden = methods_of(graph, ancestor)
+                        .into_iter()
+                        .find(|&parent_method| graph[parent_method].name == graph[method].name);
+                    if let Some(parent_method) = overridden {
+                        links.push((method, parent_method));
+                        break;
+                    }
+                    queue.extend(children(graph, ancestor, EdgeType::Inheritance));
+                }
+            }
+        }
+
+        for &(method, parent_method) in &links {
+            let edge = Edge::new(
+                EdgeType::Overrides,
+                graph[method].id.clone(),
+                graph[parent_method].id.clone(),
+            )
+            .with_line(graph[method].line_number);
+            graph.add_edge(method, parent_method, edge);
         }
+        links.len()
+    }
 
-        Ok(graph_builder.build())
+    /// Add the `Composes` edges parsers emitted, once per class and held type. A target still
+    /// pointing at an `external:class:Name:0` placeholder goes to the class, interface or
+    /// enum of that name, preferring one in the holding class's directory; a name shared by
+    /// several elsewhere, or found nowhere, is dropped, as is a class holding its own type.
+    fn link_compositions(graph: &mut DependencyGraph, compositions: Vec<Edge>) -> usize {
+        let mut by_id: HashMap<&str, NodeIndex> = HashMap::new();
+        let mut types_by_name: HashMap<&str, Vec<NodeIndex>> = HashMap::new();
+        for idx in graph.node_indices() {
+            let node = &graph[idx];
+            by_id.insert(node.id.as_str(), idx);
+            let is_type = matches!(
+                node.node_type,
+                NodeType::Class | NodeType::Interface | NodeType::Enum
+            );
+            if is_type && node.visibility.as_deref() != Some("external") {
+                types_by_name
+                    .entry(node.name.as_str())
+                    .or_default()
+                    .push(idx);
+            }
+        }
+
+        let mut links: Vec<(NodeIndex, NodeIndex, Edge)> = Vec::new();
+        let mut seen: HashSet<(NodeIndex, NodeIndex)> = HashSet::new();
+        for mut edge in compositions {
+            let Some(&holder) = by_id.get(edge.source_id.as_str()) else {
+                continue;
+            };
+            let placeholder_name = edge
+                .target_id
+                .strip_prefix("external:class:")
+                .and_then(|rest| rest.strip_suffix(":0"));
+            let held = match placeholder_name {
+                Some(name) => {
+                    let candidates = types_by_name.get(name).map(Vec::as_slice).unwrap_or(&[]);
+                    let holder_dir = graph[holder].file_path.parent();
+                    candidates
+                        .iter()
+                        .copied()
+                        .find(|&idx| graph[idx].file_path.parent() == holder_dir)
+                        .or_else(|| (candidates.len() == 1).then(|| candidates[0]))
+                }
+                None => by_id.get(edge.target_id.as_str()).copied(),
+            };
+            let Some(held) = held else {
+                continue;
+            };
+            if held != holder && seen.insert((holder, held)) {
+                edge.target_id = graph[held].id.clone();
+                links.push((holder, held, edge));
+            }
+        }
+
+        let linked = links.len();
+        for (holder, held, edge) in links {
+            graph.add_edge(holder, held, edge);
+        }
+        linked
     }
 }
@@ -0,0 +1,277 @@
+//! Pseudonymous copies of a graph for sharing.
+//!
+//! Every name, path component and signature identifier is replaced with a pseudonym
+//! (`Class_7`, `fn_42`, `dir_3/file_9.py`) while node types, lines, edges and weights stay
+//! as they are, so the structure of a proprietary codebase can be shown without its names.
+//! The same original always gets the same pseudonym, and [`Pseudonyms`] records the way
+//! back for the owner.
+
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+use super::graph::{DependencyGraph, NodeType};
+use crate::parsers::common::generate_node_id;
+
+/// Signature words that are language syntax or builtin types rather than names from the
+/// codebase; they are kept so signatures stay readable
+const KEPT_WORDS: &[&str] = &[
+    "self",
+    "Self",
+    "this",
+    "cls",
+    "fn",
+    "def",
+    "func",
+    "function",
+    "pub",
+    "async",
+    "await",
+    "const",
+    "mut",
+    "static",
+    "impl",
+    "dyn",
+    "ref",
+    "var",
+    "let",
+    "val",
+    "final",
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "virtual",
+    "override",
+    "abstract",
+    "unsigned",
+    "void",
+    "int",
+    "long",
+    "short",
+    "float",
+    "double",
+    "bool",
+    "boolean",
+    "char",
+    "byte",
+    "str",
+    "string",
+    "String",
+    "usize",
+    "isize",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "f32",
+    "f64",
+    "None",
+    "null",
+    "nil",
+    "true",
+    "false",
+    "any",
+    "number",
+    "unknown",
+    "object",
+    "Object",
+    "Vec",
+    "Option",
+    "Result",
+    "Box",
+    "List",
+    "Dict",
+    "Map",
+    "Optional",
+    "Any",
+    "Promise",
+    "Array",
+    "error",
+];
+
+/// Original text behind each pseudonym of an anonymized graph
+#[derive(Debug, Default, Clone)]
+pub struct Pseudonyms {
+    /// Pseudonym to original, e.g. `Class_7` -> `UserService`, `dir_3` -> `billing`
+    originals: BTreeMap<String, String>,
+    /// Original to pseudonym, per pseudonym prefix
+    assigned: HashMap<(&'static str, String), String>,
+    counters: HashMap<&'static str, usize>,
+}
+
+impl Pseudonyms {
+    /// The pseudonym for `original` under `prefix`, assigned on first use
+    fn get(&mut self, prefix: &'static str, original: &str) -> String {
+        if let Some(pseudonym) = self.assigned.get(&(prefix, original.to_string())) {
+            return pseudonym.clone();
+        }
+        let counter = self.counters.entry(prefix).or_insert(0);
+        *counter += 1;
+        let pseudonym = format!("{}_{}", prefix, counter);
+        self.assigned
+            .insert((prefix, original.to_string()), pseudonym.clone());
+        self.originals
+            .insert(pseudonym.clone(), original.to_string());
+        pseudonym
+    }
+
+    /// Number of pseudonyms handed out
+    pub fn len(&self) -> usize {
+        self.originals.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.originals.is_empty()
+    }
+
+    /// Original name of `pseudonym`, if it was assigned
+    #[allow(dead_code)]
+    pub fn original(&self, pseudonym: &str) -> Option<&str> {
+        self.originals.get(pseudonym).map(String::as_str)
+    }
+
+    /// JSON object from pseudonym to original, sorted by pseudonym
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&self.originals)?)
+    }
+}
+
+/// Replace names, paths, ids, signatures and decorators of every node with pseudonyms and
+/// drop docstrings, which are free text. Edge endpoint ids are rewritten to match.
+pub fn anonymize(graph: &mut DependencyGraph) -> Pseudonyms {
+    let mut pseudonyms = Pseudonyms::default();
+
+    // Names first, so a signature mentioning a type gets that type's pseudonym
+    let mut type_names: HashMap<String, String> = HashMap::new();
+    for idx in graph.node_indices() {
+        let node = &graph[idx];
+        let pseudonym = pseudonyms.get(name_prefix(&node.node_type), &node.name);
+        if matches!(
+            node.node_type,
+            NodeType::Class | NodeType::Interface | NodeType::Enum
+        ) {
+            type_names.entry(node.name.clone()).or_insert(pseudonym);
+        }
+    }
+
+    let mut paths: HashMap<PathBuf, PathBuf> = HashMap::new();
+    for idx in graph.node_indices() {
+        let node = &graph[idx];
+        let name = pseudonyms.get(name_prefix(&node.node_type), &node.name);
+        let file_path = match paths.get(&node.file_path) {
+            Some(path) => path.clone(),
+            None => {
+                let path = anonymize_path(&node.file_path, &mut pseudonyms);
+                paths.insert(node.file_path.clone(), path.clone());
+                path
+            }
+        };
+        let signature = node.signature.as_deref().map(|signature| {
+            let own_name = (node.name.as_str(), name.as_str());
+            anonymize_words(signature, Some(own_name), &type_names, &mut pseudonyms)
+        });
+        let decorators: Vec<String> = node
+            .decorators
+            .iter()
+            .map(|decorator| anonymize_words(decorator, None, &type_names, &mut pseudonyms))
+            .collect();
+        let id = generate_node_id(
+            &file_path,
+            &format!("{:?}", node.node_type).to_lowercase(),
+            &name,
+            node.line_number,
+        );
+
+        let node = &mut graph[idx];
+        node.id = id;
+        node.name = name;
+        node.file_path = file_path;
+        node.signature = signature;
+        node.decorators = decorators;
+        node.docstring = None;
+    }
+
+    for edge in graph.edge_indices() {
+        let (source, target) = graph.edge_endpoints(edge).unwrap();
+        let (source_id, target_id) = (graph[source].id.clone(), graph[target].id.clone());
+        let weight = &mut graph[edge];
+        weight.source_id = source_id;
+        weight.target_id = target_id;
+    }
+
+    pseudonyms
+}
+
+fn name_prefix(node_type: &NodeType) -> &'static str {
+    match node_type {
+        NodeType::Module => "mod",
+        NodeType::Class => "Class",
+        NodeType::Function => "fn",
+        NodeType::Variable => "var",
+        NodeType::Interface => "Iface",
+        NodeType::Enum => "Enum",
+    }
+}
+
+/// `src/billing/invoice.py` -> `dir_1/dir_2/file_1.py`; the root and `..` are kept, as is
+/// the extension, which only tells the language
+fn anonymize_path(path: &Path, pseudonyms: &mut Pseudonyms) -> PathBuf {
+    let components: Vec<Component> = path.components().collect();
+    let mut anonymized = PathBuf::new();
+    for (position, component) in components.iter().enumerate() {
+        let Component::Normal(part) = component else {
+            anonymized.push(component.as_os_str());
+            continue;
+        };
+        let part = Path::new(part);
+        if position + 1 < components.len() {
+            anonymized.push(pseudonyms.get("dir", &part.to_string_lossy()));
+            continue;
+        }
+        let stem = part.file_stem().unwrap_or_default().to_string_lossy();
+        let mut file = pseudonyms.get("file", &stem);
+        if let Some(extension) = part.extension() {
+            file = format!("{}.{}", file, extension.to_string_lossy());
+        }
+        anonymized.push(file);
+    }
+    anonymized
+}
+
+/// Rewrite each identifier in `text`: the entity's own name and type names become their
+/// pseudonyms, other words `id_N`, except [`KEPT_WORDS`]. Punctuation and spacing are kept.
+fn anonymize_words(
+    text: &str,
+    own_name: Option<(&str, &str)>,
+    type_names: &HashMap<String, String>,
+    pseudonyms: &mut Pseudonyms,
+) -> String {
+    let mut anonymized = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(|c: char| c.is_alphabetic() || c == '_') {
+        anonymized.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        let word = &rest[..end];
+        if KEPT_WORDS.contains(&word) {
+            anonymized.push_str(word);
+        } else if let Some((_, pseudonym)) = own_name.filter(|(original, _)| *original == word) {
+            anonymized.push_str(pseudonym);
+        } else if let Some(pseudonym) = type_names.get(word) {
+            anonymized.push_str(pseudonym);
+        } else {
+            anonymized.push_str(&pseudonyms.get("id", word));
+        }
+        rest = &rest[end..];
+    }
+    anonymized.push_str(rest);
+    anonymized
+}
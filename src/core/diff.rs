@@ -0,0 +1,160 @@
+//! Differences between two saved graphs.
+//!
+//! Nodes are matched by id and edges by type and endpoint ids, so graphs saved with
+//! stable ids (`--stable-ids`) compare cleanly across commits: code that only moved keeps
+//! its id and does not show up as removed and re-added.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::graph::{DependencyGraph, Edge, EdgeType, Node, NodeType};
+
+/// What one graph gained and lost relative to another
+#[derive(Debug, Default)]
+pub struct GraphDiff {
+    pub added_nodes: Vec<Node>,
+    pub removed_nodes: Vec<Node>,
+    /// Added edges with the names of their endpoints in the new graph
+    pub added_edges: Vec<EdgeChange>,
+    /// Removed edges with the names of their endpoints in the old graph
+    pub removed_edges: Vec<EdgeChange>,
+}
+
+/// An added or removed edge, described by its endpoints' names since their ids are long
+#[derive(Debug, Clone)]
+pub struct EdgeChange {
+    pub edge_type: EdgeType,
+    pub source: String,
+    pub target: String,
+    /// File of the source node, which the change is listed under
+    pub file_path: PathBuf,
+    /// Line the edge was derived from, else the source node's line
+    pub line_number: usize,
+}
+
+impl GraphDiff {
+    /// Compare `old` with `new`; weight changes of an edge present in both are ignored
+    pub fn between(old: &DependencyGraph, new: &DependencyGraph) -> Self {
+        let old_ids: HashSet<&str> = old.node_weights().map(|n| n.id.as_str()).collect();
+        let new_ids: HashSet<&str> = new.node_weights().map(|n| n.id.as_str()).collect();
+        let old_edges: HashSet<(EdgeType, &str, &str)> = old.edge_weights().map(edge_key).collect();
+        let new_edges: HashSet<(EdgeType, &str, &str)> = new.edge_weights().map(edge_key).collect();
+
+        Self {
+            added_nodes: new
+                .node_weights()
+                .filter(|n| !old_ids.contains(n.id.as_str()))
+                .cloned()
+                .collect(),
+            removed_nodes: old
+                .node_weights()
+                .filter(|n| !new_ids.contains(n.id.as_str()))
+                .cloned()
+                .collect(),
+            added_edges: edge_changes(new, &old_edges),
+            removed_edges: edge_changes(old, &new_edges),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+
+    /// One line per change under a header per file, ordered by line, then a summary:
+    ///
+    /// ```text
+    /// src/app.rs
+    ///   + fn load (src/app.rs:12)
+    ///   - edge Call main→parse
+    /// ```
+    pub fn render(&self) -> String {
+        let mut by_file: BTreeMap<&Path, Vec<(usize, String)>> = BTreeMap::new();
+        for (sign, nodes) in [('-', &self.removed_nodes), ('+', &self.added_nodes)] {
+            for node in nodes {
+                by_file.entry(&node.file_path).or_default().push((
+                    node.line_number,
+                    format!(
+                        "{} {} {} ({}:{})",
+                        sign,
+                        kind_label(node.node_type),
+                        node.name,
+                        node.file_path.display(),
+                        node.line_number
+                    ),
+                ));
+            }
+        }
+        for (sign, edges) in [('-', &self.removed_edges), ('+', &self.added_edges)] {
+            for edge in edges {
+                by_file.entry(&edge.file_path).or_default().push((
+                    edge.line_number,
+                    format!(
+                        "{} edge {:?} {}→{}",
+                        sign, edge.edge_type, edge.source, edge.target
+                    ),
+                ));
+            }
+        }
+
+        let mut output = String::new();
+        for (file_path, mut changes) in by_file {
+            // Stable sort: at the same line, removals stay ahead of additions
+            changes.sort_by_key(|(line, _)| *line);
+            output.push_str(&format!("{}\n", file_path.display()));
+            for (_, change) in changes {
+                output.push_str(&format!("  {}\n", change));
+            }
+        }
+        if self.is_empty() {
+            output.push_str("No differences\n");
+        } else {
+            output.push_str(&format!(
+                "\nNodes: +{} -{}, edges: +{} -{}\n",
+                self.added_nodes.len(),
+                self.removed_nodes.len(),
+                self.added_edges.len(),
+                self.removed_edges.len()
+            ));
+        }
+        output
+    }
+}
+
+fn edge_key(edge: &Edge) -> (EdgeType, &str, &str) {
+    (edge.edge_type, &edge.source_id, &edge.target_id)
+}
+
+/// Edges of `graph` whose key is not in `other`
+fn edge_changes(
+    graph: &DependencyGraph,
+    other: &HashSet<(EdgeType, &str, &str)>,
+) -> Vec<EdgeChange> {
+    graph
+        .edge_indices()
+        .filter(|&edge| !other.contains(&edge_key(&graph[edge])))
+        .map(|edge| {
+            let (source, target) = graph.edge_endpoints(edge).unwrap();
+            EdgeChange {
+                edge_type: graph[edge].edge_type,
+                source: graph[source].name.clone(),
+                target: graph[target].name.clone(),
+                file_path: graph[source].file_path.clone(),
+                line_number: graph[edge].line.unwrap_or(graph[source].line_number),
+            }
+        })
+        .collect()
+}
+
+fn kind_label(node_type: NodeType) -> &'static str {
+    match node_type {
+        NodeType::Module => "module",
+        NodeType::Class => "class",
+        NodeType::Function => "fn",
+        NodeType::Variable => "var",
+        NodeType::Interface => "interface",
+        NodeType::Enum => "enum",
+    }
+}
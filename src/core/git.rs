@@ -0,0 +1,32 @@
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Files changed between `since` and the working tree, as paths under `dir`.
+///
+/// Runs `git diff --name-only --relative <since>` inside `dir`, so only files below it
+/// are reported. Deleted files are dropped since there is nothing left to parse.
+pub fn changed_files(dir: &Path, since: &str) -> Result<Vec<PathBuf>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["diff", "--name-only", "--relative", since, "--"])
+        .output()
+        .context("Failed to run git; is it installed?")?;
+
+    if !output.status.success() {
+        bail!(
+            "git diff against '{}' failed: {}",
+            since,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| dir.join(line))
+        .filter(|path| path.is_file())
+        .collect())
+}
@@ -2,10 +2,14 @@
 //!
 //! This module defines the core types for representing code entities and their relationships.
 
-use petgraph::{graph::NodeIndex, Directed, Graph};
+use anyhow::{bail, Result};
+use petgraph::algo::{astar, tarjan_scc};
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::{EdgeFiltered, EdgeRef};
+use petgraph::{Directed, Direction, Graph};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 
 /// Type of code entity in the dependency graph.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Copy)]
@@ -25,7 +29,7 @@ pub enum NodeType {
 }
 
 /// Type of relationship between code entities.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Copy)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Copy)]
 pub enum EdgeType {
     /// Module import relationship
     Import,
@@ -35,10 +39,50 @@ pub enum EdgeType {
     Inheritance,
     /// Interface implementation
     Implements,
-    /// General usage relationship
+    /// General usage relationship (decorators, types named in a function signature)
     Uses,
     /// Containment (e.g., class contains method)
     Contains,
+    /// Method redefining a same-named method of an ancestor class
+    Overrides,
+    /// Function throwing or declaring an exception type
+    Throws,
+    /// Class holding a field or property of another type
+    Composes,
+}
+
+/// Common ordering of the per-language `visibility` labels, least visible first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VisibilityLevel {
+    /// `private`, and Python functions nested inside another function
+    Private,
+    /// `internal` (C#, Kotlin) and Java package-private (`package`)
+    Internal,
+    /// `protected`, including Python's `_name` convention
+    Protected,
+    /// `public`, Python dunder methods, external placeholders and unlabelled nodes
+    Public,
+}
+
+impl VisibilityLevel {
+    /// Level of a node's `visibility` label; nodes without one count as public.
+    pub fn of(node: &Node) -> Self {
+        match node.visibility.as_deref() {
+            Some("private") | Some("nested") => Self::Private,
+            Some("internal") | Some("package") => Self::Internal,
+            Some("protected") => Self::Protected,
+            _ => Self::Public,
+        }
+    }
+}
+
+/// Part a special member function plays in an object's lifecycle.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Lifecycle {
+    /// Constructor: `Widget(int size)`
+    Constructor,
+    /// Destructor: `~Widget()`
+    Destructor,
 }
 
 /// A node representing a code entity in the dependency graph.
@@ -54,6 +98,9 @@ pub struct Node {
     pub file_path: PathBuf,
     /// Line number where entity is defined
     pub line_number: usize,
+    /// Last line of the definition; equals `line_number` for one-line entities
+    #[serde(default)]
+    pub end_line: usize,
     /// Programming language
     pub language: String,
     /// Function/method signature with parameters and types
@@ -62,6 +109,22 @@ pub struct Node {
     pub docstring: Option<String>,
     /// Visibility modifier (public, private, etc.)
     pub visibility: Option<String>,
+    /// Declared `async` (Python, TS/JS, Rust, C#)
+    #[serde(default)]
+    pub is_async: bool,
+    /// Branch points (if/for/while/match arms/switch cases) in a function body
+    #[serde(default)]
+    pub complexity: usize,
+    /// Decorators applied to the entity, without `@` or arguments (e.g. `app.route`)
+    #[serde(default)]
+    pub decorators: Vec<String>,
+    /// A test case: Rust `#[test]`, Java `@Test`, Python `test_*` in a test file, or the
+    /// callback of a JS/TS `it()`/`test()` call
+    #[serde(default)]
+    pub is_test: bool,
+    /// Set on C++ constructors and destructors
+    #[serde(default)]
+    pub lifecycle: Option<Lifecycle>,
 }
 
 /// An edge representing a relationship between two code entities.
@@ -75,11 +138,29 @@ pub struct Edge {
     pub target_id: String,
     /// Additional context about the relationship
     pub context: Option<String>,
+    /// How many times the relationship occurs, e.g. call sites of the same callee
+    #[serde(default = "default_edge_weight")]
+    pub weight: usize,
+    /// Source line the relationship comes from: the call, the `extends` clause, the member
+    /// declaration. The first occurrence when `weight` counts several.
+    #[serde(default)]
+    pub line: Option<usize>,
+}
+
+fn default_edge_weight() -> usize {
+    1
 }
 
 /// Directed graph of code dependencies using petgraph.
 pub type DependencyGraph = Graph<Node, Edge, Directed>;
 
+/// Lossless on-disk form of a [`DependencyGraph`]; edges reference nodes by `id`.
+#[derive(Serialize, Deserialize)]
+struct GraphDocument {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
 impl Node {
     pub fn new(
         id: String,
@@ -95,13 +176,25 @@ impl Node {
             node_type,
             file_path,
             line_number,
+            end_line: line_number,
             language,
             signature: None,
             docstring: None,
             visibility: None,
+            is_async: false,
+            complexity: 0,
+            decorators: Vec::new(),
+            is_test: false,
+            lifecycle: None,
         }
     }
 
+    /// Record where the definition ends, e.g. the closing brace of a function body
+    pub fn with_end_line(mut self, end_line: usize) -> Self {
+        self.end_line = end_line;
+        self
+    }
+
     pub fn with_signature(mut self, signature: String) -> Self {
         self.signature = Some(signature);
         self
@@ -116,6 +209,31 @@ impl Node {
         self.visibility = Some(visibility);
         self
     }
+
+    pub fn with_async(mut self, is_async: bool) -> Self {
+        self.is_async = is_async;
+        self
+    }
+
+    pub fn with_complexity(mut self, complexity: usize) -> Self {
+        self.complexity = complexity;
+        self
+    }
+
+    pub fn with_decorators(mut self, decorators: Vec<String>) -> Self {
+        self.decorators = decorators;
+        self
+    }
+
+    pub fn with_test(mut self, is_test: bool) -> Self {
+        self.is_test = is_test;
+        self
+    }
+
+    pub fn with_lifecycle(mut self, lifecycle: Option<Lifecycle>) -> Self {
+        self.lifecycle = lifecycle;
+        self
+    }
 }
 
 impl Edge {
@@ -125,6 +243,8 @@ impl Edge {
             source_id,
             target_id,
             context: None,
+            weight: 1,
+            line: None,
         }
     }
 
@@ -132,6 +252,16 @@ impl Edge {
         self.context = Some(context);
         self
     }
+
+    pub fn with_weight(mut self, weight: usize) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
 }
 
 /// Builder for constructing dependency graphs incrementally.
@@ -166,8 +296,455 @@ impl GraphBuilder {
         self.graph
     }
 
-    #[allow(dead_code)]
+    /// Nodes added so far, in insertion order
+    pub fn nodes(&self) -> impl Iterator<Item = &Node> {
+        self.graph.node_weights()
+    }
+
+    /// Edges added so far, in insertion order
+    pub fn edges(&self) -> impl Iterator<Item = &Edge> {
+        self.graph.edge_weights()
+    }
+
     pub fn get_node_index(&self, id: &str) -> Option<NodeIndex> {
         self.node_map.get(id).copied()
     }
 }
+
+/// Module name [`DependencyGraphExt::top_level_modules`] gives files outside any subdirectory
+pub const ROOT_MODULE: &str = "_root";
+
+/// Graph-level queries over a [`DependencyGraph`].
+///
+/// `DependencyGraph` is a petgraph alias, so analyses live on this extension trait.
+pub trait DependencyGraphExt {
+    /// Find dependency cycles among edges of the given types.
+    ///
+    /// Returns each strongly connected component with more than one node, plus nodes
+    /// with a self-loop. Members are ordered by walking edges from the lowest index,
+    /// so a simple cycle `a→b→c→a` comes back as `[a, b, c]`.
+    fn find_cycles(&self, edge_types: &[EdgeType]) -> Vec<Vec<NodeIndex>>;
+
+    /// Every node reachable from the node whose `id` is `start`, following only
+    /// edges of the given types. The start node comes first, then breadth-first
+    /// order; an unknown `start` yields an empty list.
+    fn reachable_from(&self, start: &str, edge_types: &[EdgeType]) -> Vec<NodeIndex>;
+
+    /// Fewest-hop path from `from` to `to` following only edges of the given types.
+    /// Both ends are included; `None` when `to` cannot be reached.
+    fn shortest_path(
+        &self,
+        from: NodeIndex,
+        to: NodeIndex,
+        edge_types: &[EdgeType],
+    ) -> Option<Vec<NodeIndex>>;
+
+    /// Copy of the graph restricted to `keep` and the edges between them.
+    /// Surviving nodes and edges keep their relative order.
+    fn induced_subgraph(&self, keep: &[NodeIndex]) -> Self
+    where
+        Self: Sized;
+
+    /// Copy of the graph with nodes ordered by file path, line, then name, and edges by
+    /// source id, target id, then type. Index order, and so the order of every output, then
+    /// no longer depends on the order files were parsed in.
+    fn sorted(&self) -> Self
+    where
+        Self: Sized;
+
+    /// Copy of the graph without nodes below `min_level`, the members they contain, and
+    /// any edge touching them.
+    fn retain_visible(&self, min_level: VisibilityLevel) -> Self
+    where
+        Self: Sized;
+
+    /// Group nodes by top-level directory below the deepest directory all files share,
+    /// e.g. `core` and `parsers` for a tree rooted at `src/`. Files directly in that
+    /// directory form the [`ROOT_MODULE`]. Modules come out sorted by name.
+    fn top_level_modules(&self) -> BTreeMap<String, Vec<NodeIndex>>;
+
+    /// Replace line-based ids with `path:type:Qualified.name`, so an edit that only shifts
+    /// lines leaves ids unchanged. Same-named overloads get `#2`, `#3`, ... in line order;
+    /// external placeholders keep their ids. Edge endpoint ids are rewritten to match.
+    fn stabilize_ids(&mut self);
+
+    /// Make file paths relative to `root`, e.g. `/home/me/app/src/db.rs` -> `src/db.rs` for
+    /// root `/home/me/app`, so saved graphs do not depend on where the code was checked out.
+    /// Ids built from a path follow it, as do edge endpoint ids; paths outside `root` and
+    /// external placeholders are left as they are.
+    fn relativize_paths(&mut self, root: &Path);
+
+    /// Functions and classes nothing calls or uses: candidates for dead code.
+    ///
+    /// Likely entry points are skipped: `main`, tests, dunder methods, public or
+    /// external symbols, and interface members. A class counts as used when it is
+    /// subclassed, implemented, or when any of its members is called.
+    fn unreferenced_nodes(&self) -> Vec<NodeIndex>;
+
+    /// Whether a single node would be reported by [`DependencyGraphExt::unreferenced_nodes`].
+    fn is_unreferenced(&self, idx: NodeIndex) -> bool;
+
+    /// Serialize every node and edge field to JSON, in graph index order.
+    fn to_json(&self) -> Result<String>;
+
+    /// Rebuild a graph saved with [`DependencyGraphExt::to_json`] without re-parsing.
+    ///
+    /// Node and edge order are preserved, so indices match the original graph.
+    fn from_json(json: &str) -> Result<Self>
+    where
+        Self: Sized;
+}
+
+/// Point every edge's `source_id`/`target_id` at its endpoints' current ids
+fn sync_edge_ids(graph: &mut DependencyGraph) {
+    for edge in graph.edge_indices() {
+        let (source, target) = graph.edge_endpoints(edge).unwrap();
+        let (source_id, target_id) = (graph[source].id.clone(), graph[target].id.clone());
+        let weight = &mut graph[edge];
+        weight.source_id = source_id;
+        weight.target_id = target_id;
+    }
+}
+
+impl DependencyGraphExt for DependencyGraph {
+    fn find_cycles(&self, edge_types: &[EdgeType]) -> Vec<Vec<NodeIndex>> {
+        // Mirror the graph with only the requested edge types; node indices are preserved
+        let mut filtered: Graph<(), (), Directed> = Graph::with_capacity(self.node_count(), 0);
+        for _ in self.node_indices() {
+            filtered.add_node(());
+        }
+        for edge_ref in self.edge_references() {
+            if edge_types.contains(&edge_ref.weight().edge_type) {
+                filtered.update_edge(edge_ref.source(), edge_ref.target(), ());
+            }
+        }
+
+        let mut cycles = Vec::new();
+        for component in tarjan_scc(&filtered) {
+            let is_cycle =
+                component.len() > 1 || filtered.contains_edge(component[0], component[0]);
+            if !is_cycle {
+                continue;
+            }
+
+            let members: HashSet<NodeIndex> = component.iter().copied().collect();
+            let start = *component.iter().min().unwrap();
+            let mut ordered = vec![start];
+            let mut visited: HashSet<NodeIndex> = [start].into_iter().collect();
+            let mut stack = vec![start];
+            while let Some(&current) = stack.last() {
+                let mut next_nodes: Vec<NodeIndex> = filtered
+                    .neighbors(current)
+                    .filter(|n| members.contains(n) && !visited.contains(n))
+                    .collect();
+                next_nodes.sort();
+                match next_nodes.first() {
+                    Some(&next) => {
+                        visited.insert(next);
+                        ordered.push(next);
+                        stack.push(next);
+                    }
+                    None => {
+                        stack.pop();
+                    }
+                }
+            }
+            cycles.push(ordered);
+        }
+
+        cycles.sort();
+        cycles
+    }
+    fn reachable_from(&self, start: &str, edge_types: &[EdgeType]) -> Vec<NodeIndex> {
+        let Some(start_idx) = self.node_indices().find(|&idx| self[idx].id == start) else {
+            return Vec::new();
+        };
+
+        let mut reached = vec![start_idx];
+        let mut visited: HashSet<NodeIndex> = [start_idx].into_iter().collect();
+        let mut queue = VecDeque::from([start_idx]);
+        while let Some(current) = queue.pop_front() {
+            for edge_ref in self.edges(current) {
+                if edge_types.contains(&edge_ref.weight().edge_type)
+                    && visited.insert(edge_ref.target())
+                {
+                    reached.push(edge_ref.target());
+                    queue.push_back(edge_ref.target());
+                }
+            }
+        }
+        reached
+    }
+
+    fn shortest_path(
+        &self,
+        from: NodeIndex,
+        to: NodeIndex,
+        edge_types: &[EdgeType],
+    ) -> Option<Vec<NodeIndex>> {
+        let filtered = EdgeFiltered::from_fn(self, |edge_ref| {
+            edge_types.contains(&edge_ref.weight().edge_type)
+        });
+        astar(&filtered, from, |idx| idx == to, |_| 1usize, |_| 0).map(|(_, path)| path)
+    }
+
+    fn induced_subgraph(&self, keep: &[NodeIndex]) -> Self {
+        let keep: HashSet<NodeIndex> = keep.iter().copied().collect();
+        self.filter_map(
+            |idx, node| keep.contains(&idx).then(|| node.clone()),
+            |_, edge| Some(edge.clone()),
+        )
+    }
+
+    fn sorted(&self) -> Self {
+        let mut nodes: Vec<NodeIndex> = self.node_indices().collect();
+        nodes.sort_by(|&a, &b| {
+            let (a, b) = (&self[a], &self[b]);
+            (&a.file_path, a.line_number, &a.name, &a.id).cmp(&(
+                &b.file_path,
+                b.line_number,
+                &b.name,
+                &b.id,
+            ))
+        });
+        let mut edges: Vec<EdgeIndex> = self.edge_indices().collect();
+        edges.sort_by(|&a, &b| {
+            let (a, b) = (&self[a], &self[b]);
+            (&a.source_id, &a.target_id, a.edge_type, a.line).cmp(&(
+                &b.source_id,
+                &b.target_id,
+                b.edge_type,
+                b.line,
+            ))
+        });
+
+        let mut sorted = DependencyGraph::with_capacity(nodes.len(), edges.len());
+        let mut new_index: HashMap<NodeIndex, NodeIndex> = HashMap::with_capacity(nodes.len());
+        for idx in nodes {
+            new_index.insert(idx, sorted.add_node(self[idx].clone()));
+        }
+        for edge in edges {
+            let (source, target) = self.edge_endpoints(edge).unwrap();
+            sorted.add_edge(new_index[&source], new_index[&target], self[edge].clone());
+        }
+        sorted
+    }
+
+    fn retain_visible(&self, min_level: VisibilityLevel) -> Self {
+        // A public method of a private class is not part of the API either
+        let mut dropped: HashSet<NodeIndex> = HashSet::new();
+        let mut stack: Vec<NodeIndex> = self
+            .node_indices()
+            .filter(|&idx| VisibilityLevel::of(&self[idx]) < min_level)
+            .collect();
+        while let Some(idx) = stack.pop() {
+            if dropped.insert(idx) {
+                stack.extend(
+                    self.edges(idx)
+                        .filter(|e| e.weight().edge_type == EdgeType::Contains)
+                        .map(|e| e.target()),
+                );
+            }
+        }
+
+        let keep: Vec<NodeIndex> = self
+            .node_indices()
+            .filter(|idx| !dropped.contains(idx))
+            .collect();
+        self.induced_subgraph(&keep)
+    }
+
+    fn top_level_modules(&self) -> BTreeMap<String, Vec<NodeIndex>> {
+        let mut root: Option<PathBuf> = None;
+        for node in self.node_weights() {
+            let dir = node.file_path.parent().unwrap_or(Path::new(""));
+            root = Some(match root {
+                None => dir.to_path_buf(),
+                Some(root) => root
+                    .components()
+                    .zip(dir.components())
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a)
+                    .collect(),
+            });
+        }
+        let root = root.unwrap_or_default();
+
+        let mut modules: BTreeMap<String, Vec<NodeIndex>> = BTreeMap::new();
+        for idx in self.node_indices() {
+            let file_path = &self[idx].file_path;
+            let mut components = file_path
+                .strip_prefix(&root)
+                .unwrap_or(file_path)
+                .components();
+            let module = match (components.next(), components.next()) {
+                (Some(dir), Some(_)) => dir.as_os_str().to_string_lossy().into_owned(),
+                _ => ROOT_MODULE.to_string(),
+            };
+            modules.entry(module).or_default().push(idx);
+        }
+        modules
+    }
+
+    fn stabilize_ids(&mut self) {
+        let mut by_line: Vec<NodeIndex> = self.node_indices().collect();
+        by_line.sort_by_key(|&idx| self[idx].line_number);
+
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        let mut new_ids = Vec::with_capacity(by_line.len());
+        for idx in by_line {
+            let node = &self[idx];
+            if node.visibility.as_deref() == Some("external") {
+                continue;
+            }
+            let base = format!(
+                "{}:{}:{}",
+                node.file_path.to_string_lossy().replace(['/', '\\'], "_"),
+                format!("{:?}", node.node_type).to_lowercase(),
+                qualified_name(self, idx)
+            );
+            let count = occurrences.entry(base.clone()).or_insert(0);
+            *count += 1;
+            let id = if *count == 1 {
+                base
+            } else {
+                format!("{}#{}", base, count)
+            };
+            new_ids.push((idx, id));
+        }
+        for (idx, id) in new_ids {
+            self[idx].id = id;
+        }
+        sync_edge_ids(self);
+    }
+
+    fn relativize_paths(&mut self, root: &Path) {
+        let id_prefix = |path: &Path| path.to_string_lossy().replace(['/', '\\'], "_");
+        for idx in self.node_indices() {
+            let node = &mut self[idx];
+            if node.visibility.as_deref() == Some("external") {
+                continue;
+            }
+            let Ok(relative) = node.file_path.strip_prefix(root) else {
+                continue;
+            };
+            let relative = relative.to_path_buf();
+            if let Some(rest) = node.id.strip_prefix(&id_prefix(&node.file_path)) {
+                node.id = format!("{}{}", id_prefix(&relative), rest);
+            }
+            node.file_path = relative;
+        }
+        sync_edge_ids(self);
+    }
+
+    fn unreferenced_nodes(&self) -> Vec<NodeIndex> {
+        self.node_indices()
+            .filter(|&idx| self.is_unreferenced(idx))
+            .collect()
+    }
+
+    fn is_unreferenced(&self, idx: NodeIndex) -> bool {
+        let node = &self[idx];
+        if !matches!(node.node_type, NodeType::Function | NodeType::Class) {
+            return false;
+        }
+        if matches!(
+            node.visibility.as_deref(),
+            Some("public") | Some("external")
+        ) {
+            return false;
+        }
+        let name = node.name.as_str();
+        let is_entry_point =
+            name == "main" || node.is_test || (name.starts_with("__") && name.ends_with("__"));
+        if is_entry_point {
+            return false;
+        }
+
+        let has_incoming = |target: NodeIndex, edge_types: &[EdgeType]| {
+            self.edges_directed(target, Direction::Incoming)
+                .any(|e| edge_types.contains(&e.weight().edge_type))
+        };
+
+        // Interface members are called through implementations
+        let in_interface = self.edges_directed(idx, Direction::Incoming).any(|e| {
+            e.weight().edge_type == EdgeType::Contains
+                && self[e.source()].node_type == NodeType::Interface
+        });
+        // Overrides are reached through calls on the parent method
+        let overrides = self
+            .edges(idx)
+            .any(|e| e.weight().edge_type == EdgeType::Overrides);
+        if in_interface || overrides || has_incoming(idx, &[EdgeType::Call, EdgeType::Uses]) {
+            return false;
+        }
+
+        if node.node_type == NodeType::Class {
+            let extended_or_held = [
+                EdgeType::Inheritance,
+                EdgeType::Implements,
+                EdgeType::Composes,
+            ];
+            if has_incoming(idx, &extended_or_held) {
+                return false;
+            }
+            let member_used = self
+                .edges(idx)
+                .filter(|e| e.weight().edge_type == EdgeType::Contains)
+                .any(|e| has_incoming(e.target(), &[EdgeType::Call, EdgeType::Uses]));
+            if member_used {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn to_json(&self) -> Result<String> {
+        let document = GraphDocument {
+            nodes: self.node_weights().cloned().collect(),
+            edges: self.edge_weights().cloned().collect(),
+        };
+        Ok(serde_json::to_string_pretty(&document)?)
+    }
+
+    fn from_json(json: &str) -> Result<Self> {
+        let document: GraphDocument = serde_json::from_str(json)?;
+
+        let mut builder = GraphBuilder::new();
+        for node in document.nodes {
+            builder.add_node(node);
+        }
+        for edge in document.edges {
+            let (source_id, target_id) = (edge.source_id.clone(), edge.target_id.clone());
+            if builder.add_edge(edge).is_none() {
+                bail!(
+                    "edge {} -> {} references an unknown node",
+                    source_id,
+                    target_id
+                );
+            }
+        }
+        Ok(builder.build())
+    }
+}
+
+/// Names of the node's containers and the node itself, outermost first, e.g. `Cache.get`
+fn qualified_name(graph: &DependencyGraph, idx: NodeIndex) -> String {
+    let mut parts = vec![graph[idx].name.as_str()];
+    let mut visited: HashSet<NodeIndex> = [idx].into_iter().collect();
+    let mut current = idx;
+    while let Some(parent) = graph
+        .edges_directed(current, Direction::Incoming)
+        .find(|e| e.weight().edge_type == EdgeType::Contains)
+        .map(|e| e.source())
+    {
+        if !visited.insert(parent) {
+            break;
+        }
+        parts.push(graph[parent].name.as_str());
+        current = parent;
+    }
+    parts.reverse();
+    parts.join(".")
+}
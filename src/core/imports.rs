@@ -0,0 +1,364 @@
+//! Import edges between files.
+//!
+//! Parsers record each import as a `Module` node named after the statement text. This
+//! pass gives every importing file a node of its own and links it with `Import` edges to
+//! the files it imports, or to the import node itself when the import points outside the
+//! codebase, turning the import statements into a file-level dependency graph.
+
+use petgraph::graph::NodeIndex;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use super::graph::{DependencyGraph, Edge, EdgeType, Node, NodeType};
+use super::scanner::FileInfo;
+use crate::parsers::common::generate_node_id;
+
+/// File names that stand for their directory: `pkg/__init__.py` is imported as `pkg`
+const PACKAGE_FILES: &[&str] = &["__init__", "index", "mod", "init"];
+
+/// What an import statement points at
+enum ImportPath {
+    /// Relative to the importing file's directory, e.g. `./util` or `../models/user`
+    Relative(String),
+    /// Module path segments, matched against the end of file paths: `["pkg", "models"]`
+    /// finds `src/pkg/models.py`
+    Named(Vec<String>),
+}
+
+/// Add `Import` edges from a node per importing file to the file each import resolves
+/// to, or to the import node when it resolves to none. `files` are all analyzed files,
+/// including those without nodes such as an empty `__init__.py`. Repeated imports of the
+/// same target add to the edge's weight. Returns the number of edges added.
+pub fn link_imports(graph: &mut DependencyGraph, files: &[FileInfo]) -> usize {
+    let files: HashMap<PathBuf, String> = files
+        .iter()
+        .map(|file| (file.path.clone(), file.language.clone()))
+        .collect();
+    let index = FileIndex::new(&files);
+
+    let imports: Vec<NodeIndex> = graph
+        .node_indices()
+        .filter(|&idx| {
+            let node = &graph[idx];
+            node.node_type == NodeType::Module && node.id.contains(":import:")
+        })
+        .collect();
+
+    let mut file_nodes: HashMap<PathBuf, NodeIndex> = HashMap::new();
+    // Import count and first import line per link
+    let mut links: HashMap<(NodeIndex, NodeIndex), (usize, usize)> = HashMap::new();
+    for import in imports {
+        let (importer, language, text, line) = {
+            let node = &graph[import];
+            (
+                node.file_path.clone(),
+                node.language.clone(),
+                node.name.clone(),
+                node.line_number,
+            )
+        };
+        let source = file_node(graph, &mut file_nodes, &importer, &language);
+        let target_file = import_paths(&language, &text)
+            .iter()
+            .find_map(|path| index.resolve(path, &importer));
+
+        let target = match target_file {
+            Some(target_file) => {
+                file_node(graph, &mut file_nodes, &target_file, &files[&target_file])
+            }
+            None => import,
+        };
+        let (count, first_line) = links.entry((source, target)).or_insert((0, line));
+        *count += 1;
+        *first_line = (*first_line).min(line);
+    }
+
+    let mut links: Vec<_> = links.into_iter().collect();
+    links.sort();
+    for &((source, target), (weight, line)) in &links {
+        let edge = Edge::new(
+            EdgeType::Import,
+            graph[source].id.clone(),
+            graph[target].id.clone(),
+        )
+        .with_weight(weight)
+        .with_line(line);
+        graph.add_edge(source, target, edge);
+    }
+    links.len()
+}
+
+/// The `Module` node standing for `path`, created on first use
+fn file_node(
+    graph: &mut DependencyGraph,
+    file_nodes: &mut HashMap<PathBuf, NodeIndex>,
+    path: &Path,
+    language: &str,
+) -> NodeIndex {
+    if let Some(&idx) = file_nodes.get(path) {
+        return idx;
+    }
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let node = Node::new(
+        generate_node_id(path, "file", &name, 0),
+        name,
+        NodeType::Module,
+        path.to_path_buf(),
+        0,
+        language.to_string(),
+    );
+    let idx = graph.add_node(node);
+    file_nodes.insert(path.to_path_buf(), idx);
+    idx
+}
+
+/// Files keyed by every trailing run of their path components, lowercased and without
+/// the extension: `src/pkg/models.py` is found as `models`, `pkg/models` and
+/// `src/pkg/models`
+struct FileIndex {
+    by_suffix: HashMap<String, Vec<PathBuf>>,
+}
+
+impl FileIndex {
+    fn new(files: &HashMap<PathBuf, String>) -> Self {
+        let mut by_suffix: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        for (path, language) in files {
+            let segments = path_segments(&strip_extension(path));
+            let mut keys = vec![segments.clone()];
+            // Packages are imported by their directory; Go imports whole directories
+            let stands_for_dir = segments
+                .last()
+                .is_some_and(|last| PACKAGE_FILES.contains(&last.as_str()));
+            if stands_for_dir || language == "go" {
+                keys.push(segments[..segments.len().saturating_sub(1)].to_vec());
+            }
+            for key in keys {
+                for start in 0..key.len() {
+                    by_suffix
+                        .entry(key[start..].join("/"))
+                        .or_default()
+                        .push(path.clone());
+                }
+            }
+        }
+        for candidates in by_suffix.values_mut() {
+            candidates.sort();
+            candidates.dedup();
+        }
+        Self { by_suffix }
+    }
+
+    /// The file `path` refers to from `importer`. Several matches are narrowed to the one
+    /// sharing the most directories with the importer.
+    fn resolve(&self, path: &ImportPath, importer: &Path) -> Option<PathBuf> {
+        let keys: Vec<String> = match path {
+            ImportPath::Relative(relative) => {
+                let joined = importer
+                    .parent()
+                    .unwrap_or_else(|| Path::new(""))
+                    .join(relative);
+                let normalized = normalize(&joined);
+                // `./foo.service` names foo.service.ts, `./foo.js` names foo.js or foo.ts
+                vec![
+                    path_segments(&normalized).join("/"),
+                    path_segments(&strip_extension(&normalized)).join("/"),
+                ]
+            }
+            // Trailing segments may name items rather than modules: `a.b.Item` tries
+            // `a/b/item`, then `a/b`, then `a`
+            ImportPath::Named(segments) => (1..=segments.len())
+                .rev()
+                .map(|len| segments[..len].join("/").to_lowercase())
+                .collect(),
+        };
+
+        keys.iter().find_map(|key| {
+            let candidates = self.by_suffix.get(key)?;
+            candidates
+                .iter()
+                .filter(|candidate| candidate.as_path() != importer)
+                .max_by_key(|candidate| {
+                    let shared = candidate
+                        .components()
+                        .zip(importer.components())
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    // Ties go to the first path in sorted order
+                    (shared, std::cmp::Reverse(candidate.as_path()))
+                })
+                .cloned()
+        })
+    }
+}
+
+/// Lowercased normal components of `path`
+fn path_segments(path: &Path) -> Vec<String> {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().to_lowercase()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn strip_extension(path: &Path) -> PathBuf {
+    match path.file_stem() {
+        Some(stem) => path.with_file_name(stem),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Resolve `.` and `..` without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+/// First string literal in `text`, without its quotes
+fn quoted(text: &str) -> Option<&str> {
+    let start = text.find(['"', '\'', '`'])?;
+    let quote = text[start..].chars().next()?;
+    let rest = &text[start + 1..];
+    rest.find(quote).map(|end| &rest[..end])
+}
+
+/// Candidate targets of an import statement, most specific first
+fn import_paths(language: &str, text: &str) -> Vec<ImportPath> {
+    let text = text.trim().trim_end_matches(';').trim();
+    let segments = |path: &str, separator: &str| -> Vec<String> {
+        path.split(separator)
+            .map(str::trim)
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    // `name as alias` -> `name`
+    let unaliased = |name: &str| name.split(" as ").next().unwrap_or(name).trim().to_string();
+
+    match language {
+        "python" => {
+            if let Some((module, names)) = text
+                .strip_prefix("from ")
+                .and_then(|rest| rest.split_once(" import "))
+            {
+                let module = module.trim();
+                let names: Vec<String> = names
+                    .trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace())
+                    .split(',')
+                    .map(unaliased)
+                    .filter(|name| !name.is_empty() && name != "*")
+                    .collect();
+                let dots = module.chars().take_while(|&c| c == '.').count();
+                let module_path = module[dots..].replace('.', "/");
+                if dots == 0 {
+                    let base = segments(module, ".");
+                    let mut paths: Vec<ImportPath> = names
+                        .iter()
+                        .map(|name| ImportPath::Named([base.clone(), vec![name.clone()]].concat()))
+                        .collect();
+                    paths.push(ImportPath::Named(base));
+                    return paths;
+                }
+                // `from ..pkg import x`: one dot is the importer's package, each extra
+                // dot a parent
+                let mut base = vec!["."];
+                base.extend(std::iter::repeat_n("..", dots - 1));
+                if !module_path.is_empty() {
+                    base.push(&module_path);
+                }
+                let base = base.join("/");
+                let mut paths: Vec<ImportPath> = names
+                    .iter()
+                    .map(|name| ImportPath::Relative(format!("{}/{}", base, name)))
+                    .collect();
+                if !module_path.is_empty() {
+                    paths.push(ImportPath::Relative(base));
+                }
+                paths
+            } else if let Some(modules) = text.strip_prefix("import ") {
+                modules
+                    .split(',')
+                    .map(|module| ImportPath::Named(segments(&unaliased(module), ".")))
+                    .collect()
+            } else {
+                Vec::new()
+            }
+        }
+        // Bare specifiers (`react`, `@scope/pkg`) are packages, not files in the tree
+        "javascript" | "typescript" => quoted(text)
+            .filter(|spec| spec.starts_with('.'))
+            .map(|spec| vec![ImportPath::Relative(spec.to_string())])
+            .unwrap_or_default(),
+        "java" | "kotlin" => {
+            let path = text
+                .trim_start_matches("import ")
+                .trim_start_matches("static ");
+            let path = unaliased(path);
+            vec![ImportPath::Named(segments(
+                path.trim_end_matches(".*"),
+                ".",
+            ))]
+        }
+        "go" => quoted(text)
+            .map(|path| vec![ImportPath::Named(segments(path, "/"))])
+            .unwrap_or_default(),
+        "rust" => {
+            let path = text
+                .trim_start_matches("pub ")
+                .trim_start_matches("use ")
+                .trim();
+            let (prefix, group) = match path.split_once('{') {
+                Some((prefix, group)) => (prefix, Some(group.trim_end_matches('}'))),
+                None => (path, None),
+            };
+            let prefix: Vec<String> = segments(prefix, "::")
+                .into_iter()
+                .skip_while(|segment| matches!(segment.as_str(), "crate" | "self" | "super"))
+                .collect();
+            let mut paths: Vec<ImportPath> = group
+                .into_iter()
+                .flat_map(|group| group.split(','))
+                .filter_map(|item| {
+                    let item = unaliased(item);
+                    let first = item.split("::").next()?.trim().to_string();
+                    (!first.is_empty() && first != "self")
+                        .then(|| ImportPath::Named([prefix.clone(), vec![first]].concat()))
+                })
+                .collect();
+            if !prefix.is_empty() {
+                paths.push(ImportPath::Named(prefix));
+            }
+            paths
+        }
+        "ruby" => match quoted(text) {
+            Some(path) if text.starts_with("require_relative") => {
+                vec![ImportPath::Relative(format!("./{}", path))]
+            }
+            Some(path) => vec![ImportPath::Named(segments(path, "/"))],
+            None => Vec::new(),
+        },
+        "php" => {
+            let path = text
+                .trim_start_matches("use ")
+                .trim_start_matches("function ")
+                .trim_start_matches("const ");
+            let path = unaliased(path.split(',').next().unwrap_or(path));
+            vec![ImportPath::Named(segments(&path, "\\"))]
+        }
+        // The Lua parser names import nodes after the module: `plenary.async`
+        "lua" => vec![ImportPath::Named(segments(text, "."))],
+        _ => Vec::new(),
+    }
+}
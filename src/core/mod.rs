@@ -1,9 +1,17 @@
 pub mod analyzer;
+pub mod anonymize;
+pub mod diff;
+pub mod git;
 pub mod graph;
+pub mod imports;
 pub mod resolver;
 pub mod scanner;
+pub mod stats;
 
 pub use analyzer::CodebaseAnalyzer;
-pub use graph::{DependencyGraph, Edge, EdgeType, Node, NodeType};
-pub use resolver::{CallSite, CallSiteExtractor, FunctionResolver};
-pub use scanner::FileScanner;
+pub use graph::{
+    DependencyGraph, DependencyGraphExt, Edge, EdgeType, Lifecycle, Node, NodeType, VisibilityLevel,
+};
+pub use resolver::{CallResolution, CallSite, CallSiteExtractor, FunctionResolver};
+pub use scanner::{FileScanner, TestFiles};
+pub use stats::{GraphStats, Hotspot};
@@ -4,10 +4,17 @@
 
 use anyhow::Result;
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use crate::core::{Edge, EdgeType, Node, NodeType};
+use crate::parsers::common::{generate_node_id, test_case_name};
+
+/// Qualifier segments that refer to the surrounding code rather than naming a module or
+/// class: Rust's `crate::`, `self::`, `super::`, `Self::` and PHP's `self::`, `static::`,
+/// `parent::`
+const RELATIVE_QUALIFIERS: &[&str] = &["crate", "self", "super", "Self", "static", "parent"];
 
 /// Fast hash-based function call resolver.
 ///
@@ -23,6 +30,23 @@ pub struct FunctionResolver {
 
     /// Import mapping for qualified names (module.function)
     import_mapping: HashMap<String, String>,
+
+    /// Enclosing class of each method, keyed by function node ID (from Contains edges)
+    class_membership: HashMap<String, String>,
+
+    /// Namespace of each method's class, keyed by function node ID (C# and C++ namespaces
+    /// containing the class)
+    namespace_membership: HashMap<String, String>,
+
+    /// File of every indexed function, keyed by node ID, for caller lookups
+    function_files: HashMap<String, PathBuf>,
+
+    /// Identifiers named in each file's imports, e.g. `billing` and `payments` for
+    /// `from billing.payments import charge`
+    file_imports: HashMap<PathBuf, HashSet<String>>,
+
+    /// Namespaces each file brings into scope with C# `using` directives
+    file_namespaces: HashMap<PathBuf, HashSet<String>>,
 }
 
 #[derive(Debug, Clone)]
@@ -41,14 +65,24 @@ pub struct FunctionEntry {
     pub module_context: String,
 }
 
+/// What a method call's receiver says about the class being called into
+enum ReceiverClass {
+    /// The receiver is named after this class, or is `self`/`this` inside it
+    Known(String),
+    /// No receiver was recorded, e.g. for languages whose calls don't expose one
+    Unknown,
+    /// A receiver no candidate class matches, like a local list or a parameter
+    Unrelated,
+}
+
 #[derive(Debug, Clone)]
 pub struct MethodEntry {
     #[allow(dead_code)]
     pub node_id: String,
     pub name: String,
-    #[allow(dead_code)]
     pub class_name: String,
-    #[allow(dead_code)]
+    /// Namespace the class is declared in, when the language has them
+    pub namespace: Option<String>,
     pub file_path: PathBuf,
     #[allow(dead_code)]
     pub line_number: usize,
@@ -69,6 +103,13 @@ pub struct CallSite {
     pub context: Option<String>,
     /// Line number of the call
     pub line_number: usize,
+    /// Class enclosing the calling function, so `self.save()` resolves to that class's `save`
+    #[serde(default)]
+    pub caller_class: Option<String>,
+    /// Number of arguments passed, to pick among overloads; `None` when unknown, e.g.
+    /// when a spread (`*args`, `...xs`) hides it
+    #[serde(default)]
+    pub arg_count: Option<usize>,
 }
 
 /// Type of function call.
@@ -89,17 +130,101 @@ pub enum CallType {
     ConstructorCall,
 }
 
+/// How many call sites resolution matched to a definition; the rest vanish from the graph.
+#[derive(Debug, Clone, Default)]
+pub struct CallResolution {
+    /// Call sites submitted for resolution
+    pub total: usize,
+    /// Called names that matched no definition, with their call site counts, most
+    /// frequent first
+    pub unresolved: Vec<(String, usize)>,
+}
+
+impl CallResolution {
+    pub fn unresolved_count(&self) -> usize {
+        self.unresolved.iter().map(|(_, count)| count).sum()
+    }
+
+    pub fn resolved_count(&self) -> usize {
+        self.total - self.unresolved_count()
+    }
+
+    /// Share of call sites resolved, 1.0 when there were none
+    pub fn resolved_ratio(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.resolved_count() as f64 / self.total as f64
+        }
+    }
+}
+
 impl FunctionResolver {
     pub fn new() -> Self {
         Self {
             function_index: HashMap::new(),
             method_index: HashMap::new(),
             import_mapping: HashMap::new(),
+            class_membership: HashMap::new(),
+            namespace_membership: HashMap::new(),
+            function_files: HashMap::new(),
+            file_imports: HashMap::new(),
+            file_namespaces: HashMap::new(),
+        }
+    }
+
+    /// Build indexes, using `Contains` edges from classes to functions to tell methods
+    /// apart from free functions. Nodes and edges may be borrowed, e.g. from a graph.
+    pub fn build_indexes_with_edges<N: Borrow<Node> + Sync, E: Borrow<Edge>>(
+        &mut self,
+        nodes: &[N],
+        edges: &[E],
+    ) -> Result<()> {
+        let nodes_by_id: HashMap<&str, &Node> = nodes
+            .iter()
+            .map(|n| (n.borrow().id.as_str(), n.borrow()))
+            .collect();
+
+        let contains: Vec<(&Node, &Node)> = edges
+            .iter()
+            .map(Borrow::borrow)
+            .filter(|edge| edge.edge_type == EdgeType::Contains)
+            .filter_map(|edge| {
+                Some((
+                    *nodes_by_id.get(edge.source_id.as_str())?,
+                    *nodes_by_id.get(edge.target_id.as_str())?,
+                ))
+            })
+            .collect();
+        let class_namespaces: HashMap<&str, &str> = contains
+            .iter()
+            .filter(|(container, member)| {
+                container.node_type == NodeType::Module
+                    && matches!(member.node_type, NodeType::Class | NodeType::Interface)
+            })
+            .map(|(namespace, class)| (class.id.as_str(), namespace.name.as_str()))
+            .collect();
+
+        self.class_membership.clear();
+        self.namespace_membership.clear();
+        for (container, member) in contains {
+            if matches!(container.node_type, NodeType::Class | NodeType::Interface)
+                && member.node_type == NodeType::Function
+            {
+                self.class_membership
+                    .insert(member.id.clone(), container.name.clone());
+                if let Some(namespace) = class_namespaces.get(container.id.as_str()) {
+                    self.namespace_membership
+                        .insert(member.id.clone(), namespace.to_string());
+                }
+            }
         }
+
+        self.build_indexes(nodes)
     }
 
     /// Build indexes from all parsed nodes for fast lookup
-    pub fn build_indexes(&mut self, nodes: &[Node]) -> Result<()> {
+    pub fn build_indexes<N: Borrow<Node> + Sync>(&mut self, nodes: &[N]) -> Result<()> {
         // Pre-calculate capacity to avoid rehashing
         let estimated_functions = nodes.len() / 4; // Rough estimate
 
@@ -111,9 +236,10 @@ impl FunctionResolver {
         self.import_mapping.clear();
 
         // Build function and method indexes in parallel with better allocation
-        let function_nodes: Vec<_> = nodes
+        let function_nodes: Vec<&Node> = nodes
             .par_iter()
-            .filter(|node| matches!(node.node_type, NodeType::Function))
+            .map(Borrow::borrow)
+            .filter(|node: &&Node| matches!(node.node_type, NodeType::Function))
             .collect();
 
         let (functions, methods): (Vec<_>, Vec<_>) = function_nodes
@@ -132,10 +258,24 @@ impl FunctionResolver {
             }
         }
 
-        // Build method index
+        // Build method index. Methods stay reachable by simple calls too, since
+        // implicit-receiver calls (`helper()` inside a class) are common.
         for entry in methods {
             if let FunctionOrMethod::Method(method) = entry {
                 let hash = Self::compute_hash(&method.name);
+                let module_context = self.extract_module_from_path(&method.file_path);
+                self.function_index
+                    .entry(hash)
+                    .or_default()
+                    .push(FunctionEntry {
+                        node_id: method.node_id.clone(),
+                        name: method.name.clone(),
+                        file_path: method.file_path.clone(),
+                        line_number: method.line_number,
+                        signature: method.signature.clone(),
+                        class_context: Some(method.class_name.clone()),
+                        module_context,
+                    });
                 self.method_index
                     .entry(hash)
                     .or_insert_with(Vec::new)
@@ -143,6 +283,11 @@ impl FunctionResolver {
             }
         }
 
+        self.function_files = function_nodes
+            .iter()
+            .map(|node| (node.id.clone(), node.file_path.clone()))
+            .collect();
+
         // Build import mapping
         self.build_import_mapping(nodes)?;
 
@@ -150,13 +295,113 @@ impl FunctionResolver {
     }
 
     /// Resolve function calls to their definitions and create edges
+    ///
+    /// Repeated calls from one caller to the same callee collapse into a single edge
+    /// whose `weight` is the number of call sites; the first call site's context is kept.
+    #[allow(dead_code)]
     pub fn resolve_calls(&self, call_sites: &[CallSite]) -> Vec<Edge> {
+        self.resolve_calls_with_report(call_sites).0
+    }
+
+    /// [`FunctionResolver::resolve_calls`], also reporting which called names matched nothing
+    pub fn resolve_calls_with_report(
+        &self,
+        call_sites: &[CallSite],
+    ) -> (Vec<Edge>, CallResolution) {
+        Self::merge_resolved(call_sites, self.resolve_each(call_sites))
+    }
+
+    /// Resolve every call site on its own, in order: one `Call` edge per site, `None`
+    /// where nothing matched. [`FunctionResolver::merge_resolved`] turns the results into
+    /// graph edges; keeping them per site lets a caller reuse results across runs.
+    pub fn resolve_each<C: Borrow<CallSite> + Sync>(&self, call_sites: &[C]) -> Vec<Option<Edge>> {
         call_sites
             .par_iter()
-            .filter_map(|call_site| self.resolve_single_call(call_site))
+            .map(|call_site| self.resolve_single_call(call_site.borrow()))
             .collect()
     }
 
+    /// Collapse per-site results from [`FunctionResolver::resolve_each`] into weighted
+    /// edges and a report, exactly as [`FunctionResolver::resolve_calls_with_report`] does
+    pub fn merge_resolved(
+        call_sites: &[CallSite],
+        results: Vec<Option<Edge>>,
+    ) -> (Vec<Edge>, CallResolution) {
+        let mut unresolved: HashMap<&str, usize> = HashMap::new();
+        for (call_site, result) in call_sites.iter().zip(&results) {
+            if result.is_none() {
+                *unresolved
+                    .entry(call_site.called_name.as_str())
+                    .or_insert(0) += 1;
+            }
+        }
+        let mut unresolved: Vec<(String, usize)> = unresolved
+            .into_iter()
+            .map(|(name, count)| (name.to_string(), count))
+            .collect();
+        unresolved.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let report = CallResolution {
+            total: call_sites.len(),
+            unresolved,
+        };
+
+        // Sum weights onto each pair's first result, keyed by borrowed ids so the merge
+        // does not copy every id of a large graph
+        let mut weights: Vec<Option<usize>> = vec![None; results.len()];
+        {
+            let mut first: HashMap<(&str, &str), usize> = HashMap::new();
+            for (index, edge) in results.iter().enumerate() {
+                let Some(edge) = edge else { continue };
+                let key = (edge.source_id.as_str(), edge.target_id.as_str());
+                let first_index = *first.entry(key).or_insert(index);
+                *weights[first_index].get_or_insert(0) += edge.weight;
+            }
+        }
+        let edges = results
+            .into_iter()
+            .zip(weights)
+            .filter_map(|(edge, weight)| Some(edge?.with_weight(weight?)))
+            .collect();
+        (edges, report)
+    }
+
+    /// Whether `call_site`, which resolved to `previous` against an earlier index, could
+    /// resolve differently now that the definitions named in `changed_names` were added,
+    /// removed or moved. An exact match only depends on definitions sharing a name with
+    /// one of the call's segments (`repo.save` → `repo`, `save`); an unresolved or fuzzy
+    /// match also depends on every name within fuzzy-matching distance.
+    pub fn may_resolve_differently(
+        &self,
+        call_site: &CallSite,
+        previous: &Option<Edge>,
+        changed_names: &HashSet<String>,
+    ) -> bool {
+        if changed_names.is_empty() {
+            return false;
+        }
+        let shares_a_name = call_site
+            .called_name
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .any(|segment| changed_names.contains(segment));
+        if shares_a_name {
+            return true;
+        }
+
+        let matched_exactly = previous.as_ref().is_some_and(|edge| {
+            !edge
+                .context
+                .as_deref()
+                .is_some_and(|context| context.starts_with("fuzzy_match"))
+        });
+        if matched_exactly {
+            return false;
+        }
+        let target = call_site.called_name.to_lowercase();
+        changed_names.iter().any(|name| {
+            name.len() > 3 && self.levenshtein_distance(&target, &name.to_lowercase()) <= 2
+        })
+    }
+
     /// Resolve a single function call with multiple strategies
     #[allow(dead_code)]
     fn resolve_single_call(&self, call_site: &CallSite) -> Option<Edge> {
@@ -185,7 +430,8 @@ impl FunctionResolver {
                     call_site.caller_id.clone(),
                     best_candidate.node_id.clone(),
                 )
-                .with_context(format!("line:{}", call_site.line_number)),
+                .with_context(format!("line:{}", call_site.line_number))
+                .with_line(call_site.line_number),
             );
         }
 
@@ -200,8 +446,9 @@ impl FunctionResolver {
 
         if let Some(candidates) = self.method_index.get(&hash) {
             // Try to determine the class context from the call site
-            let class_context = self.infer_class_context(call_site);
-            let best_candidate = self.select_best_method_candidate(candidates, &class_context)?;
+            let class_context = self.infer_class_context(call_site, candidates);
+            let best_candidate =
+                self.select_best_method_candidate(candidates, &class_context, call_site)?;
 
             return Some(
                 Edge::new(
@@ -209,7 +456,8 @@ impl FunctionResolver {
                     call_site.caller_id.clone(),
                     best_candidate.node_id.clone(),
                 )
-                .with_context(format!("method_call:line:{}", call_site.line_number)),
+                .with_context(format!("method_call:line:{}", call_site.line_number))
+                .with_line(call_site.line_number),
             );
         }
 
@@ -218,22 +466,46 @@ impl FunctionResolver {
 
     #[allow(dead_code)]
     fn resolve_qualified_call(&self, call_site: &CallSite) -> Option<Edge> {
-        let parts: Vec<&str> = call_site.called_name.split('.').collect();
-        if parts.len() < 2 {
+        let parts = Self::qualified_name_parts(&call_site.called_name);
+        let (&function_name, qualifier) = parts.split_last()?;
+        if qualifier.is_empty() {
             return self.resolve_simple_call(call_site);
         }
 
-        let module_name = parts[..parts.len() - 1].join(".");
-        let function_name = parts[parts.len() - 1];
+        let module_name = qualifier.join(".");
 
-        // Check import mapping first
+        // Check import mapping first: C# `using Pay = Acme.Payments.Gateway;` makes
+        // `Pay.Refund()` a call into `Gateway`
         if let Some(resolved_module) = self.import_mapping.get(&module_name) {
             let full_name = format!("{}.{}", resolved_module, function_name);
-            return self.resolve_by_full_name(&full_name, call_site);
+            if let Some(edge) = self.resolve_by_full_name(&full_name, call_site) {
+                return Some(edge);
+            }
+        }
+        if let Some(edge) = self.resolve_by_full_name(&call_site.called_name, call_site) {
+            return Some(edge);
         }
 
         // Try direct module resolution
-        self.resolve_by_module_and_function(&module_name, function_name, call_site)
+        self.resolve_by_module_and_function(qualifier, function_name, call_site)
+    }
+
+    /// Segments of a qualified name: `.` for Python/JS, `::` for C++/Rust/PHP scopes and
+    /// `\` for PHP namespaces. Generic arguments (`Vec::<i32>::new`) and the empty
+    /// segment of a global `::func` are dropped.
+    fn qualified_name_parts(called_name: &str) -> Vec<&str> {
+        let separator = if called_name.contains("::") {
+            "::"
+        } else if called_name.contains('\\') {
+            "\\"
+        } else {
+            "."
+        };
+        called_name
+            .split(separator)
+            .map(|segment| segment.split('<').next().unwrap_or(segment).trim())
+            .filter(|segment| !segment.is_empty())
+            .collect()
     }
 
     #[allow(dead_code)]
@@ -258,7 +530,8 @@ impl FunctionResolver {
                     call_site.caller_id.clone(),
                     best_candidate.node_id.clone(),
                 )
-                .with_context(format!("attribute_call:line:{}", call_site.line_number)),
+                .with_context(format!("attribute_call:line:{}", call_site.line_number))
+                .with_line(call_site.line_number),
             );
         }
 
@@ -306,21 +579,27 @@ impl FunctionResolver {
                         .map(|c| c == class_name)
                         .unwrap_or(false)
                 {
-                    return Some(Edge::new(
-                        EdgeType::Call,
-                        call_site.caller_id.clone(),
-                        candidate.node_id.clone(),
-                    ));
+                    return Some(
+                        Edge::new(
+                            EdgeType::Call,
+                            call_site.caller_id.clone(),
+                            candidate.node_id.clone(),
+                        )
+                        .with_line(call_site.line_number),
+                    );
                 }
             }
         }
 
         // If no specific constructor found, create an external class reference
-        Some(Edge::new(
-            EdgeType::Call,
-            call_site.caller_id.clone(),
-            format!("external:class:{}:0", class_name),
-        ))
+        Some(
+            Edge::new(
+                EdgeType::Call,
+                call_site.caller_id.clone(),
+                format!("external:class:{}:0", class_name),
+            )
+            .with_line(call_site.line_number),
+        )
     }
 
     /// Compute stable hash for function names with optimized hashing
@@ -338,29 +617,31 @@ impl FunctionResolver {
         candidates: &'a [FunctionEntry],
         call_site: &CallSite,
     ) -> Option<&'a FunctionEntry> {
+        let candidates: Vec<&'a FunctionEntry> = candidates
+            .iter()
+            .filter(|candidate| self.is_reachable_without_receiver(candidate, call_site))
+            .collect();
         if candidates.is_empty() {
             return None;
         }
 
         if candidates.len() == 1 {
-            return Some(&candidates[0]);
+            return Some(candidates[0]);
         }
 
         // Fast scoring system for candidate selection - avoid string operations
-        let context_ref = call_site.context.as_ref();
-        let mut best_candidate = &candidates[0];
+        let caller_file = self.function_files.get(&call_site.caller_id);
+        let mut best_candidate = candidates[0];
         let mut best_score = 0;
 
         for candidate in candidates {
             let mut score = 0;
 
-            // Prefer same file (fast path check)
-            if let Some(ctx) = context_ref {
-                if ctx.contains(&*candidate.file_path.to_string_lossy()) {
+            // Prefer the caller's own file, then modules the caller's file imports
+            if let Some(caller_file) = caller_file {
+                if candidate.file_path == *caller_file {
                     score += 100;
-                }
-                // Prefer same module
-                if ctx.contains(&candidate.module_context) {
+                } else if self.is_imported_by(caller_file, candidate) {
                     score += 50;
                 }
             }
@@ -370,6 +651,12 @@ impl FunctionResolver {
                 score += 25;
             }
 
+            // Overloads differ by arity: one taking this many arguments beats another in
+            // the caller's own file
+            if accepts_arg_count(candidate.signature.as_deref(), call_site.arg_count) {
+                score += 120;
+            }
+
             // Prefer exact name matches (in case of hash collisions)
             if candidate.name == call_site.called_name {
                 score += 200;
@@ -384,6 +671,44 @@ impl FunctionResolver {
         Some(best_candidate)
     }
 
+    /// Class enclosing the caller, from the call site or from Contains edges
+    fn caller_class<'a>(&'a self, call_site: &'a CallSite) -> Option<&'a str> {
+        call_site.caller_class.as_deref().or_else(|| {
+            self.class_membership
+                .get(&call_site.caller_id)
+                .map(String::as_str)
+        })
+    }
+
+    /// A method called without a receiver (`helper()`) can only be one of the caller's
+    /// own class; free functions are reachable from anywhere
+    fn is_reachable_without_receiver(
+        &self,
+        candidate: &FunctionEntry,
+        call_site: &CallSite,
+    ) -> bool {
+        candidate
+            .class_context
+            .as_deref()
+            .is_none_or(|class_name| self.caller_class(call_site) == Some(class_name))
+    }
+
+    /// Whether `caller_file` imports the module defining `candidate`: its file stem
+    /// (`payments` for `payments.py`) or, for package-per-directory languages like Go, its
+    /// directory name appears in one of the file's imports
+    fn is_imported_by(&self, caller_file: &Path, candidate: &FunctionEntry) -> bool {
+        let Some(imports) = self.file_imports.get(caller_file) else {
+            return false;
+        };
+        imports.contains(&candidate.module_context)
+            || candidate
+                .file_path
+                .parent()
+                .and_then(|dir| dir.file_name())
+                .and_then(|dir| dir.to_str())
+                .is_some_and(|dir| imports.contains(dir))
+    }
+
     /// Fuzzy matching for function names (handles typos, case differences)
     #[allow(dead_code)]
     fn fuzzy_resolve_function(&self, call_site: &CallSite) -> Option<Edge> {
@@ -393,6 +718,9 @@ impl FunctionResolver {
         // Only check if the name is reasonably similar (Levenshtein distance)
         for candidates in self.function_index.values() {
             for candidate in candidates {
+                if !self.is_reachable_without_receiver(candidate, call_site) {
+                    continue;
+                }
                 let distance = self.levenshtein_distance(&target, &candidate.name.to_lowercase());
 
                 // Only consider matches with distance <= 2 for reasonable-length names
@@ -411,18 +739,21 @@ impl FunctionResolver {
                     call_site.caller_id.clone(),
                     candidate.node_id.clone(),
                 )
-                .with_context(format!("fuzzy_match:line:{}", call_site.line_number)),
+                .with_context(format!("fuzzy_match:line:{}", call_site.line_number))
+                .with_line(call_site.line_number),
             );
         }
 
         None
     }
 
-    /// Simple Levenshtein distance calculation
+    /// Simple Levenshtein distance calculation, counted in characters rather than bytes
     #[allow(dead_code)]
     fn levenshtein_distance(&self, s1: &str, s2: &str) -> usize {
-        let len1 = s1.len();
-        let len2 = s2.len();
+        let s1_chars: Vec<char> = s1.chars().collect();
+        let s2_chars: Vec<char> = s2.chars().collect();
+        let len1 = s1_chars.len();
+        let len2 = s2_chars.len();
 
         if len1 == 0 {
             return len2;
@@ -441,9 +772,6 @@ impl FunctionResolver {
             matrix[0][j] = j;
         }
 
-        let s1_chars: Vec<char> = s1.chars().collect();
-        let s2_chars: Vec<char> = s2.chars().collect();
-
         for i in 1..=len1 {
             for j in 1..=len2 {
                 let cost = if s1_chars[i - 1] == s2_chars[j - 1] {
@@ -468,13 +796,18 @@ impl FunctionResolver {
     // Helper methods for different resolution strategies
     fn create_function_entry(&self, node: &Node) -> FunctionOrMethod {
         // Determine if this is a method (has class context) or function
-        let class_context = self.extract_class_from_id(&node.id);
+        let class_context = self
+            .class_membership
+            .get(&node.id)
+            .cloned()
+            .or_else(|| self.extract_class_from_id(&node.id));
 
         if let Some(class_name) = class_context {
             FunctionOrMethod::Method(MethodEntry {
                 node_id: node.id.clone(),
                 name: node.name.clone(),
                 class_name,
+                namespace: self.namespace_membership.get(&node.id).cloned(),
                 file_path: node.file_path.clone(),
                 line_number: node.line_number,
                 signature: node.signature.clone(),
@@ -510,9 +843,26 @@ impl FunctionResolver {
             .to_string()
     }
 
-    fn build_import_mapping(&mut self, nodes: &[Node]) -> Result<()> {
-        for node in nodes {
+    fn build_import_mapping<N: Borrow<Node>>(&mut self, nodes: &[N]) -> Result<()> {
+        self.file_imports.clear();
+        self.file_namespaces.clear();
+        for node in nodes.iter().map(Borrow::borrow) {
+            if node.node_type == NodeType::Module && node.language == "csharp" {
+                self.parse_using_directive(node);
+            }
             if node.node_type == NodeType::Module {
+                // Import nodes are named after their statement text; every identifier in it
+                // is a module or name the file may refer to
+                let identifiers = node
+                    .name
+                    .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .filter(|word| !word.is_empty())
+                    .map(str::to_string);
+                self.file_imports
+                    .entry(node.file_path.clone())
+                    .or_default()
+                    .extend(identifiers);
+
                 // Parse import statements to build module mapping
                 // This is language-specific and would need refinement
                 if node.name.contains("import") {
@@ -535,6 +885,28 @@ impl FunctionResolver {
         }
     }
 
+    /// `using Acme.Payments;` puts a namespace in the file's scope; `using Pay =
+    /// Acme.Payments.Gateway;` maps an alias the way Python's `import ... as` would
+    fn parse_using_directive(&mut self, node: &Node) {
+        let Some(directive) = node.name.strip_prefix("using ") else {
+            return;
+        };
+        let directive = directive.trim_end_matches(';').trim();
+        let directive = directive.strip_prefix("static ").unwrap_or(directive);
+        match directive.split_once('=') {
+            Some((alias, target)) => {
+                self.import_mapping
+                    .insert(alias.trim().to_string(), target.trim().to_string());
+            }
+            None => {
+                self.file_namespaces
+                    .entry(node.file_path.clone())
+                    .or_default()
+                    .insert(directive.to_string());
+            }
+        }
+    }
+
     fn parse_from_import(&self, _stmt: &str) -> Option<Vec<(String, String)>> {
         // Simplified parsing - production code would use proper AST analysis
         None // TODO: Implement proper import parsing
@@ -546,34 +918,209 @@ impl FunctionResolver {
         called_name.split('.').last().map(|s| s.to_string())
     }
 
-    #[allow(dead_code)]
-    fn infer_class_context(&self, _call_site: &CallSite) -> Option<String> {
-        // Would analyze the calling context to determine likely class
-        None // TODO: Implement class inference
+    /// Guess the receiver's class: `self`/`this` means the caller's own class, otherwise
+    /// a receiver named after a candidate class (`user.save()` -> `User`) wins. The caller's
+    /// class comes from the call site, or from Contains edges for call sites without one.
+    fn infer_class_context(
+        &self,
+        call_site: &CallSite,
+        candidates: &[MethodEntry],
+    ) -> ReceiverClass {
+        let caller_class = || match self.caller_class(call_site) {
+            Some(class_name) => ReceiverClass::Known(class_name.to_string()),
+            None => ReceiverClass::Unknown,
+        };
+
+        let receiver = call_site.context.as_deref().and_then(|ctx| {
+            ctx.split(';')
+                .find_map(|part| part.strip_prefix("receiver:"))
+        });
+
+        let Some(receiver) = receiver else {
+            return caller_class();
+        };
+        if matches!(receiver, "self" | "this" | "$this" | "cls" | "super()") {
+            return caller_class();
+        }
+
+        // Compare ignoring case, `$`/`@` sigils and underscores: `$order_item` ~ `OrderItem`
+        let normalize = |name: &str| -> String {
+            name.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect()
+        };
+        let receiver_key = normalize(receiver.rsplit(['.', ':', '>']).next().unwrap_or(receiver));
+        candidates
+            .iter()
+            .find(|candidate| normalize(&candidate.class_name) == receiver_key)
+            .map_or(ReceiverClass::Unrelated, |candidate| {
+                ReceiverClass::Known(candidate.class_name.clone())
+            })
     }
 
-    #[allow(dead_code)]
+    /// A method of the receiver's class; without a recorded receiver, one in the caller's
+    /// file, then the caller's directory. A receiver of an unrelated type (a local list's
+    /// `append`) matches nothing rather than a same-named method elsewhere.
     fn select_best_method_candidate<'a>(
         &self,
         candidates: &'a [MethodEntry],
-        _class_context: &Option<String>,
+        class_context: &ReceiverClass,
+        call_site: &CallSite,
     ) -> Option<&'a MethodEntry> {
-        candidates.first() // Simplified selection
+        let caller_file = self.function_files.get(&call_site.caller_id);
+        match class_context {
+            ReceiverClass::Known(class_name) => {
+                // Overloads: the one taking this many arguments wins. Classes sharing a
+                // name across files: the one in the caller's file wins
+                let same_class: Vec<&MethodEntry> = candidates
+                    .iter()
+                    .filter(|c| &c.class_name == class_name)
+                    .collect();
+                let arity_matches: Vec<&MethodEntry> = same_class
+                    .iter()
+                    .copied()
+                    .filter(|c| accepts_arg_count(c.signature.as_deref(), call_site.arg_count))
+                    .collect();
+                let preferred = if arity_matches.is_empty() {
+                    same_class
+                } else {
+                    arity_matches
+                };
+                let first = *preferred.first()?;
+                preferred
+                    .iter()
+                    .copied()
+                    .find(|c| Some(&c.file_path) == caller_file)
+                    .or(Some(first))
+            }
+            ReceiverClass::Unknown => {
+                if let Some(caller_file) = caller_file {
+                    if let Some(candidate) = candidates.iter().find(|c| &c.file_path == caller_file)
+                    {
+                        return Some(candidate);
+                    }
+                    let caller_dir = caller_file.parent();
+                    if let Some(candidate) = candidates
+                        .iter()
+                        .find(|c| c.file_path.parent() == caller_dir)
+                    {
+                        return Some(candidate);
+                    }
+                }
+                candidates.first()
+            }
+            ReceiverClass::Unrelated => None,
+        }
     }
 
-    #[allow(dead_code)]
-    fn resolve_by_full_name(&self, _full_name: &str, _call_site: &CallSite) -> Option<Edge> {
-        None // TODO: Implement full name resolution
+    /// Resolve `Namespace.Class.Method` to a method of a class declared in that namespace.
+    /// Without a namespace (`Class.Method`), the class must be in the caller's own
+    /// namespace or one the caller's file is `using`. Only classes with a known namespace
+    /// match; everything else is left to [`Self::resolve_by_module_and_function`].
+    fn resolve_by_full_name(&self, full_name: &str, call_site: &CallSite) -> Option<Edge> {
+        let parts = Self::qualified_name_parts(full_name);
+        let (&function_name, qualifier) = parts.split_last()?;
+        let (&class_name, namespace) = qualifier.split_last()?;
+        let candidates = self.method_index.get(&Self::compute_hash(function_name))?;
+
+        let namespace = namespace.join(".");
+        let caller_namespace = self.namespace_membership.get(&call_site.caller_id);
+        let caller_usings = self
+            .function_files
+            .get(&call_site.caller_id)
+            .and_then(|file| self.file_namespaces.get(file));
+        let in_scope = |candidate_namespace: &String| {
+            if !namespace.is_empty() {
+                return *candidate_namespace == namespace;
+            }
+            caller_namespace == Some(candidate_namespace)
+                || caller_usings.is_some_and(|usings| usings.contains(candidate_namespace))
+        };
+
+        let matches: Vec<&MethodEntry> = candidates
+            .iter()
+            .filter(|c| c.name == function_name && c.class_name == class_name)
+            .filter(|c| c.namespace.as_ref().is_some_and(in_scope))
+            .collect();
+        let best_candidate = matches
+            .iter()
+            .find(|c| accepts_arg_count(c.signature.as_deref(), call_site.arg_count))
+            .or(matches.first())?;
+
+        Some(
+            Edge::new(
+                EdgeType::Call,
+                call_site.caller_id.clone(),
+                best_candidate.node_id.clone(),
+            )
+            .with_context(format!("qualified_call:line:{}", call_site.line_number))
+            .with_line(call_site.line_number),
+        )
     }
 
-    #[allow(dead_code)]
+    /// Resolve `function` among definitions matching `qualifier`: a segment naming the
+    /// candidate's class, its file stem or one of its directories, as `crate::billing::charge`
+    /// names `charge` in `src/billing.rs`. A qualifier matching no candidate still resolves
+    /// to one in the caller's file or in a module it imports, covering inline modules and
+    /// C++ namespaces. Qualifiers made only of `crate`, `self`, `super` and the like pick
+    /// among all candidates, preferring the caller's file.
     fn resolve_by_module_and_function(
         &self,
-        _module: &str,
-        _function: &str,
-        _call_site: &CallSite,
+        qualifier: &[&str],
+        function: &str,
+        call_site: &CallSite,
     ) -> Option<Edge> {
-        None // TODO: Implement module-based resolution
+        let candidates = self.function_index.get(&Self::compute_hash(function))?;
+        let context: Vec<&str> = qualifier
+            .iter()
+            .copied()
+            .filter(|segment| !RELATIVE_QUALIFIERS.contains(segment))
+            .collect();
+        let caller_file = self.function_files.get(&call_site.caller_id);
+
+        let mut best: Option<(&FunctionEntry, usize)> = None;
+        for candidate in candidates.iter().filter(|c| c.name == function) {
+            let mut score = 0;
+            for &segment in &context {
+                if candidate.class_context.as_deref() == Some(segment) {
+                    score += 150;
+                } else if candidate.module_context == segment {
+                    score += 100;
+                } else if candidate
+                    .file_path
+                    .parent()
+                    .is_some_and(|dir| dir.iter().any(|part| part == segment))
+                {
+                    score += 50;
+                }
+            }
+            let matches_context = context.is_empty() || score > 0;
+            if let Some(caller_file) = caller_file {
+                if candidate.file_path == *caller_file {
+                    score += 100;
+                } else if self.is_imported_by(caller_file, candidate) {
+                    score += 50;
+                }
+            }
+            if !matches_context && score == 0 {
+                continue;
+            }
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((candidate, score));
+            }
+        }
+        let (best_candidate, _) = best?;
+
+        Some(
+            Edge::new(
+                EdgeType::Call,
+                call_site.caller_id.clone(),
+                best_candidate.node_id.clone(),
+            )
+            .with_context(format!("qualified_call:line:{}", call_site.line_number))
+            .with_line(call_site.line_number),
+        )
     }
 
     #[allow(dead_code)]
@@ -608,12 +1155,91 @@ impl FunctionOrMethod {
     }
 }
 
+/// First parameters that are the receiver rather than an argument
+const RECEIVER_PARAMETERS: &[&str] = &["self", "&self", "&mut self", "mut self", "cls", "this"];
+
+/// Whether a function with `signature` can be called with `arg_count` arguments; false
+/// when either is unknown
+fn accepts_arg_count(signature: Option<&str>, arg_count: Option<usize>) -> bool {
+    let (Some(signature), Some(arg_count)) = (signature, arg_count) else {
+        return false;
+    };
+    parameter_arity(signature).is_some_and(|(required, max)| {
+        required <= arg_count && max.is_none_or(|max| arg_count <= max)
+    })
+}
+
+/// Required and maximum argument counts of the first parameter list in `signature`, as
+/// in `add((int a, int b))` or `fn add(&self, a: i32) -> i32`. Receivers (`self`, `this`)
+/// do not count, defaults and `b?: T` are optional, and `*args`, `...xs` or `int... xs`
+/// leave the maximum open.
+fn parameter_arity(signature: &str) -> Option<(usize, Option<usize>)> {
+    let start = signature.find('(')?;
+    let mut depth = 0usize;
+    let mut end = None;
+    for (offset, c) in signature[start..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    end = Some(start + offset);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut params = signature[start + 1..end?].trim();
+    // Parsers that keep the parameter list node's text wrap it twice: `add((int a))`
+    if params.starts_with('(') && params.ends_with(')') {
+        params = &params[1..params.len() - 1];
+    }
+
+    let mut pieces = Vec::new();
+    let (mut depth, mut piece_start, mut previous) = (0usize, 0, ' ');
+    for (offset, c) in params.char_indices() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            // `=>` and `->` in function types close nothing
+            '>' if matches!(previous, '=' | '-') => {}
+            ')' | ']' | '}' | '>' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                pieces.push(&params[piece_start..offset]);
+                piece_start = offset + 1;
+            }
+            _ => {}
+        }
+        previous = c;
+    }
+    pieces.push(&params[piece_start..]);
+
+    let (mut required, mut optional, mut variadic) = (0, 0, false);
+    for (position, piece) in pieces.iter().map(|piece| piece.trim()).enumerate() {
+        let is_receiver =
+            position == 0 && (RECEIVER_PARAMETERS.contains(&piece) || piece.starts_with("this:"));
+        // Empty lists, trailing commas, C's `(void)` and Python's `*` and `/` markers
+        if is_receiver || matches!(piece, "" | "void" | "*" | "/") {
+            continue;
+        }
+        if piece.starts_with('*') || piece.contains("...") {
+            variadic = true;
+        } else if piece.contains("?:") || (piece.contains('=') && !piece.contains("==")) {
+            optional += 1;
+        } else {
+            required += 1;
+        }
+    }
+    Some((required, (!variadic).then_some(required + optional)))
+}
+
 /// Optimized call site extractor that identifies function calls during AST traversal
 pub struct CallSiteExtractor {
     call_sites: Vec<CallSite>,
     current_function: Option<String>,
     current_function_line: Option<usize>,
-    current_file: Option<String>,
+    current_class: Option<String>,
+    current_file: Option<PathBuf>,
 }
 
 impl CallSiteExtractor {
@@ -622,6 +1248,7 @@ impl CallSiteExtractor {
             call_sites: Vec::new(),
             current_function: None,
             current_function_line: None,
+            current_class: None,
             current_file: None,
         }
     }
@@ -633,23 +1260,36 @@ impl CallSiteExtractor {
         file_path: &std::path::Path,
     ) -> Vec<CallSite> {
         self.call_sites.clear();
-        self.current_file = Some(
-            file_path
-                .to_string_lossy()
-                .replace('/', "_")
-                .replace('\\', "_"),
-        );
+        self.current_file = Some(file_path.to_path_buf());
         self.traverse_ast(root, source);
         std::mem::take(&mut self.call_sites)
     }
 
     fn traverse_ast(&mut self, node: &tree_sitter::Node, source: &[u8]) {
-        // Track current function context for different languages
-        if self.is_function_node(node) {
-            if let Some((func_name, line_num)) = self.extract_function_info(node, source) {
-                self.current_function = Some(func_name);
-                self.current_function_line = Some(line_num);
-            }
+        let outer_function = (self.current_function.clone(), self.current_function_line);
+
+        // Methods of a class defined inside a function belong to that class; the enclosing
+        // class comes back after it
+        let class_name = self.extract_class_name(node, source);
+        let enters_class = class_name.is_some();
+        let outer_class = if enters_class {
+            std::mem::replace(&mut self.current_class, class_name)
+        } else {
+            None
+        };
+
+        // Track current function context for different languages; JS/TS `it()`/`test()`
+        // callbacks are named after their description, like the parsers' test nodes
+        let test_case = test_case_name(node, source);
+        let enters_function = self.is_function_node(node) || test_case.is_some();
+        let function_info = match test_case {
+            Some(name) => Some((name, node.start_position().row + 1)),
+            None if enters_function => self.extract_function_info(node, source),
+            None => None,
+        };
+        if let Some((func_name, line_num)) = function_info {
+            self.current_function = Some(func_name);
+            self.current_function_line = Some(line_num);
         }
 
         // Extract call sites (including class instantiations)
@@ -665,22 +1305,55 @@ impl CallSiteExtractor {
             self.traverse_ast(&child, source);
         }
 
-        // Clear function context when exiting function
-        if self.is_function_node(node) {
-            self.current_function = None;
-            self.current_function_line = None;
+        // Restore the enclosing function's context when exiting a nested one; anonymous
+        // functions (Lua callbacks, say) never replaced it
+        if enters_function {
+            (self.current_function, self.current_function_line) = outer_function;
+        }
+        if enters_class {
+            self.current_class = outer_class;
         }
     }
 
+    /// Name of the class `node` defines, if it is a class definition
+    fn extract_class_name(&self, node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+        if matches!(
+            node.kind(),
+            "class_implementation" | "category_implementation" // Objective-C
+        ) {
+            return crate::parsers::objc::class_name(node, source).map(str::to_string);
+        }
+        if !matches!(
+            node.kind(),
+            "class_definition" |  // Python
+            "class_declaration" | // TypeScript/JavaScript/Java/C#/PHP/Kotlin
+            "class_specifier" |   // C++
+            "struct_specifier" |  // C++
+            "class" // Ruby
+        ) {
+            return None;
+        }
+        // `struct Point p;` names a type without defining it
+        if node.kind().ends_with("_specifier") {
+            node.child_by_field_name("body")?;
+        }
+        let name = node.child_by_field_name("name")?;
+        Some(self.extract_text(&name, source).to_string())
+    }
+
     fn is_function_node(&self, node: &tree_sitter::Node) -> bool {
         matches!(
             node.kind(),
-            "function_definition" |        // Python/C++
-            "function_declaration" |       // TypeScript/JavaScript
-            "method_definition" |          // TypeScript/JavaScript
+            "function_definition" |        // Python/C++, Lua `function() end`
+            "function_declaration" |       // TypeScript/JavaScript/Lua
+            "local_function_declaration" | // Lua
+            "method_definition" |          // TypeScript/JavaScript/Objective-C
             "constructor_declaration" |    // C++
             "destructor_declaration" |     // C++
-            "function_item" // Rust
+            "function_item" |              // Rust
+            "method" |                     // Ruby
+            "singleton_method" |           // Ruby def self.name
+            "method_declaration" // PHP
         )
     }
 
@@ -692,9 +1365,15 @@ impl CallSiteExtractor {
         let line_num = node.start_position().row + 1;
 
         // Try different ways to extract function name based on node type
-        let func_name = if let Some(name_node) = node.child_by_field_name("name") {
+        let func_name = if Self::is_objc_method(node) {
+            // Objective-C methods are named by their selector, `setName:age:`
+            crate::parsers::objc::selector(node, source)?
+        } else if let Some(name_node) = node.child_by_field_name("name") {
             // Python, TypeScript
-            self.extract_text(&name_node, source).to_string()
+            self.extract_lua_function_name(&name_node, source)?
+        } else if let Some(variable) = Self::lua_assigned_variable(node) {
+            // Lua `local f = function() end`, named after the variable
+            self.extract_lua_function_name(&variable, source)?
         } else {
             // C++ and other patterns - look for identifier nodes
             let mut cursor = node.walk();
@@ -717,8 +1396,24 @@ impl CallSiteExtractor {
                             return Some((self.extract_text(&child, source).to_string(), line_num));
                         }
                     }
-                    "property_identifier" => {
-                        // TypeScript method names
+                    "pointer_declarator" => {
+                        // C/C++ functions returning pointers: `int *make_buf(size_t n)`
+                        let mut declarator = child;
+                        while declarator.kind() == "pointer_declarator" {
+                            declarator = declarator.child_by_field_name("declarator")?;
+                        }
+                        let name_node = declarator.child_by_field_name("declarator")?;
+                        if declarator.kind() == "function_declarator"
+                            && name_node.kind() == "identifier"
+                        {
+                            return Some((
+                                self.extract_text(&name_node, source).to_string(),
+                                line_num,
+                            ));
+                        }
+                    }
+                    "property_identifier" | "simple_identifier" => {
+                        // TypeScript method names, Kotlin function names
                         return Some((self.extract_text(&child, source).to_string(), line_num));
                     }
                     _ => continue,
@@ -734,6 +1429,47 @@ impl CallSiteExtractor {
         }
     }
 
+    /// Objective-C `- (void)reload { ... }`, as opposed to a JS/TS `method_definition`
+    fn is_objc_method(node: &tree_sitter::Node) -> bool {
+        node.kind() == "method_definition"
+            && node
+                .child(0)
+                .is_some_and(|scope| matches!(scope.kind(), "-" | "+"))
+    }
+
+    /// Name of a function declared as `M.foo` / `M:foo` (Lua) is the part after the table;
+    /// any other name node is taken whole
+    fn extract_lua_function_name(
+        &self,
+        name_node: &tree_sitter::Node,
+        source: &[u8],
+    ) -> Option<String> {
+        let name_node = match name_node.kind() {
+            "dot_index_expression" => name_node.child_by_field_name("field")?,
+            "method_index_expression" => name_node.child_by_field_name("method")?,
+            _ => *name_node,
+        };
+        Some(self.extract_text(&name_node, source).to_string())
+    }
+
+    /// Variable a Lua `function() end` expression is assigned to, matched by position in
+    /// `a, b = f, g`
+    fn lua_assigned_variable<'a>(node: &tree_sitter::Node<'a>) -> Option<tree_sitter::Node<'a>> {
+        let values = node
+            .parent()
+            .filter(|parent| parent.kind() == "expression_list")?;
+        let assignment = values
+            .parent()
+            .filter(|parent| parent.kind() == "assignment_statement")?;
+        let index =
+            (0..values.named_child_count()).position(|i| values.named_child(i) == Some(*node))?;
+        let mut cursor = assignment.walk();
+        let variables = assignment
+            .named_children(&mut cursor)
+            .find(|child| child.kind() == "variable_list")?;
+        variables.named_child(index)
+    }
+
     fn is_call_node(&self, node: &tree_sitter::Node) -> bool {
         matches!(
             node.kind(),
@@ -741,7 +1477,15 @@ impl CallSiteExtractor {
             "call_expression" |         // TypeScript/JavaScript/C++/Rust
             "new_expression" |          // C++ class instantiation
             "constructor_call" |        // C++ constructor calls
-            "macro_invocation" // Rust macro calls (like println!)
+            "macro_invocation" |        // Rust macro calls (like println!)
+            "function_call_expression" | // PHP foo()
+            "member_call_expression" |  // PHP $obj->foo()
+            "nullsafe_member_call_expression" | // PHP $obj?->foo()
+            "scoped_call_expression" |  // PHP Foo::bar()
+            "object_creation_expression" | // PHP new Foo()
+            "function_call" |            // Lua
+            "invocation_expression" |    // C# Foo.Bar()
+            "message_expression" // Objective-C [obj doThing]
         )
     }
 
@@ -752,15 +1496,13 @@ impl CallSiteExtractor {
             return None;
         }
 
-        // Build proper caller ID that matches the node ID format used in the parsers
+        // Same id the parsers give the enclosing function node, so call edges join onto it
         let caller_id = if let Some(ref current_func) = self.current_function {
-            // Build a proper node ID that matches the exact format used by generate_node_id
-            // Format: "file_path_with_underscores:type:name:line_number"
-            format!(
-                "{}:function:{}:{}",
-                self.current_file.as_deref().unwrap_or("unknown"),
+            generate_node_id(
+                self.current_file.as_deref().unwrap_or(Path::new("unknown")),
+                "function",
                 current_func,
-                self.current_function_line.unwrap_or(0)
+                self.current_function_line.unwrap_or(0),
             )
         } else {
             "module_level".to_string()
@@ -770,17 +1512,57 @@ impl CallSiteExtractor {
             caller_id,
             called_name,
             call_type,
-            context: Some(format!("ast_node:{}", node.kind())),
+            context: Some(match self.extract_receiver(node, source) {
+                Some(receiver) => format!("ast_node:{};receiver:{}", node.kind(), receiver),
+                None => format!("ast_node:{}", node.kind()),
+            }),
             line_number: node.start_position().row + 1,
+            caller_class: self
+                .current_function
+                .as_ref()
+                .and(self.current_class.clone()),
+            arg_count: Self::count_arguments(node),
         })
     }
 
+    /// Arguments in the call's argument list; keyword arguments count like positional ones
+    fn count_arguments(node: &tree_sitter::Node) -> Option<usize> {
+        // An Objective-C selector already spells out its arity
+        if node.kind() == "message_expression" {
+            return None;
+        }
+        let arguments = node.child_by_field_name("arguments")?;
+        if !matches!(arguments.kind(), "argument_list" | "arguments") {
+            // Python `sum(x for x in xs)` passes the generator itself
+            return Some(1);
+        }
+        let mut count = 0;
+        let mut cursor = arguments.walk();
+        for argument in arguments.named_children(&mut cursor) {
+            match argument.kind() {
+                "comment" => {}
+                "list_splat"
+                | "dictionary_splat"
+                | "spread_element"
+                | "splat_argument"
+                | "hash_splat_argument"
+                | "variadic_unpacking" => return None,
+                _ => count += 1,
+            }
+        }
+        Some(count)
+    }
+
     fn extract_called_function_info(
         &self,
         node: &tree_sitter::Node,
         source: &[u8],
     ) -> Option<(String, CallType)> {
         match node.kind() {
+            "call" if node.child_by_field_name("method").is_some() => {
+                // Ruby calls: receiver.method(args), Foo.new, or a bare method(args)
+                self.extract_ruby_call_info(node, source)
+            }
             "call" | "call_expression" => {
                 // Regular function calls
                 let function_node = node.child(0)?;
@@ -817,6 +1599,18 @@ impl CallSiteExtractor {
                 }
                 None
             }
+            "function_call_expression"
+            | "member_call_expression"
+            | "nullsafe_member_call_expression"
+            | "scoped_call_expression"
+            | "object_creation_expression" => self.extract_php_call_info(node, source),
+            "function_call" => self.extract_lua_call_info(node, source),
+            "invocation_expression" => self.extract_csharp_call_info(node, source),
+            // Objective-C [obj setName:name age:age] sends `setName:age:`
+            "message_expression" => Some((
+                crate::parsers::objc::selector(node, source)?,
+                CallType::MethodCall,
+            )),
             "constructor_call" => {
                 // Direct constructor calls
                 if let Some(name_node) = node.child(0) {
@@ -830,6 +1624,185 @@ impl CallSiteExtractor {
         }
     }
 
+    /// Text of the object a method is called on (`user` in `user.save()`), if any
+    fn extract_receiver<'a>(&self, node: &tree_sitter::Node, source: &'a [u8]) -> Option<&'a str> {
+        let receiver = match node.kind() {
+            // Ruby
+            "call" if node.child_by_field_name("method").is_some() => {
+                node.child_by_field_name("receiver")?
+            }
+            // PHP
+            "member_call_expression" | "nullsafe_member_call_expression" => {
+                node.child_by_field_name("object")?
+            }
+            // C# obj.Method()
+            "invocation_expression" => node
+                .child_by_field_name("function")
+                .filter(|function| function.kind() == "member_access_expression")?
+                .child_by_field_name("expression")?,
+            // Lua M.foo() / obj:method()
+            "function_call" => node
+                .child_by_field_name("name")?
+                .child_by_field_name("table")?,
+            // Objective-C [obj doThing]
+            "message_expression" => node
+                .child_by_field_name("receiver")
+                .or_else(|| node.named_child(0))?,
+            "call" | "call_expression" => {
+                let function_node = node.child(0)?;
+                match function_node.kind() {
+                    // Python obj.method
+                    "attribute" => function_node.child_by_field_name("object")?,
+                    // Rust uses `value`, C++ uses `argument`
+                    "field_expression" => function_node
+                        .child_by_field_name("value")
+                        .or_else(|| function_node.child_by_field_name("argument"))?,
+                    // Kotlin obj.method
+                    "navigation_expression" => function_node.named_child(0)?,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        Some(self.extract_text(&receiver, source))
+    }
+
+    fn extract_ruby_call_info(
+        &self,
+        node: &tree_sitter::Node,
+        source: &[u8],
+    ) -> Option<(String, CallType)> {
+        let method_name = self
+            .extract_text(&node.child_by_field_name("method")?, source)
+            .to_string();
+
+        match node.child_by_field_name("receiver") {
+            Some(receiver) if method_name == "new" => {
+                // Foo.new / Foo::Bar.new instantiate the receiver class
+                let class_name = self.extract_text(&receiver, source).to_string();
+                Some((class_name, CallType::ConstructorCall))
+            }
+            Some(_) => Some((method_name, CallType::MethodCall)),
+            None => Some((method_name, CallType::SimpleCall)),
+        }
+    }
+
+    fn extract_lua_call_info(
+        &self,
+        node: &tree_sitter::Node,
+        source: &[u8],
+    ) -> Option<(String, CallType)> {
+        let name_node = node.child_by_field_name("name")?;
+        match name_node.kind() {
+            "identifier" => Some((
+                self.extract_text(&name_node, source).to_string(),
+                CallType::SimpleCall,
+            )),
+            // M.foo() and obj:method(); the table is kept as the receiver
+            "dot_index_expression" | "method_index_expression" => {
+                let called_name = self.extract_lua_function_name(&name_node, source)?;
+                Some((called_name, CallType::MethodCall))
+            }
+            // (get_handler())(), handlers[name]()
+            _ => Some((
+                self.extract_text(&name_node, source).to_string(),
+                CallType::DynamicCall,
+            )),
+        }
+    }
+
+    /// C# `Helper()`, `this.Helper()` and `user.Save()` call methods by name; a capitalized
+    /// receiver (`Gateway.Charge()`, `Acme.Payments.Gateway.Charge()`) names a class,
+    /// possibly namespace-qualified, so the whole path is kept
+    fn extract_csharp_call_info(
+        &self,
+        node: &tree_sitter::Node,
+        source: &[u8],
+    ) -> Option<(String, CallType)> {
+        let function_node = node.child_by_field_name("function")?;
+        match function_node.kind() {
+            "identifier" => Some((
+                self.extract_text(&function_node, source).to_string(),
+                CallType::SimpleCall,
+            )),
+            // Process<T>()
+            "generic_name" => Some((
+                self.extract_text(&function_node.named_child(0)?, source)
+                    .to_string(),
+                CallType::SimpleCall,
+            )),
+            "member_access_expression" => {
+                let name_node = function_node.child_by_field_name("name")?;
+                let name_node = match name_node.kind() {
+                    "generic_name" => name_node.named_child(0)?,
+                    _ => name_node,
+                };
+                let method_name = self.extract_text(&name_node, source);
+                let receiver = function_node
+                    .child_by_field_name("expression")
+                    .map(|receiver| self.extract_text(&receiver, source))
+                    .unwrap_or("this");
+                let names_class = receiver
+                    .split('.')
+                    .all(|segment| segment.starts_with(|c: char| c.is_ascii_uppercase()));
+                if names_class {
+                    Some((
+                        format!("{}.{}", receiver, method_name),
+                        CallType::QualifiedCall,
+                    ))
+                } else {
+                    Some((method_name.to_string(), CallType::MethodCall))
+                }
+            }
+            _ => Some((
+                self.extract_text(&function_node, source).to_string(),
+                CallType::DynamicCall,
+            )),
+        }
+    }
+
+    fn extract_php_call_info(
+        &self,
+        node: &tree_sitter::Node,
+        source: &[u8],
+    ) -> Option<(String, CallType)> {
+        match node.kind() {
+            "function_call_expression" => {
+                let function_node = node.child_by_field_name("function")?;
+                let called_name = self.extract_text(&function_node, source).to_string();
+                // Namespaced functions: \App\helper()
+                if function_node.kind() == "qualified_name" {
+                    Some((called_name, CallType::QualifiedCall))
+                } else {
+                    Some((called_name, CallType::SimpleCall))
+                }
+            }
+            "member_call_expression" | "nullsafe_member_call_expression" => {
+                // $this->foo() / $obj?->foo()
+                let method_name = self.extract_text(&node.child_by_field_name("name")?, source);
+                Some((method_name.to_string(), CallType::MethodCall))
+            }
+            "scoped_call_expression" => {
+                // Foo::bar(), self::bar(), parent::bar()
+                let scope = self.extract_text(&node.child_by_field_name("scope")?, source);
+                let method_name = self.extract_text(&node.child_by_field_name("name")?, source);
+                Some((
+                    format!("{}::{}", scope, method_name),
+                    CallType::QualifiedCall,
+                ))
+            }
+            "object_creation_expression" => {
+                let mut cursor = node.walk();
+                let class_node = node
+                    .named_children(&mut cursor)
+                    .find(|c| matches!(c.kind(), "name" | "qualified_name"))?;
+                let class_name = self.extract_text(&class_node, source).to_string();
+                Some((class_name, CallType::ConstructorCall))
+            }
+            _ => None,
+        }
+    }
+
     fn extract_function_name_from_node(
         &self,
         function_node: &tree_sitter::Node,
@@ -844,26 +1817,28 @@ impl CallSiteExtractor {
                 // Python attribute access: obj.method() or self.method() or super().method()
                 // Extract the method name (rightmost identifier)
                 let full_text = self.extract_text(function_node, source);
-                
+
                 // Handle special cases
-                if full_text.starts_with("self.") {
+                if let Some(method) = full_text.strip_prefix("self.") {
                     // self.method() - extract method name
-                    return full_text[5..].to_string();
+                    return method.to_string();
                 }
-                if full_text.starts_with("cls.") {
+                if let Some(method) = full_text.strip_prefix("cls.") {
                     // cls.method() - class method call
-                    return full_text[4..].to_string();
+                    return method.to_string();
                 }
-                if full_text.starts_with("super().") {
+                if let Some(method) = full_text.strip_prefix("super().") {
                     // super().method() - parent method call
-                    return full_text[8..].to_string();
+                    return method.to_string();
                 }
-                
+
                 // For other attribute access like module.func or obj.method
                 // Look for the attribute identifier
                 let mut cursor = function_node.walk();
                 for child in function_node.children(&mut cursor) {
-                    if child.kind() == "identifier" && child.start_byte() > function_node.start_byte() {
+                    if child.kind() == "identifier"
+                        && child.start_byte() > function_node.start_byte()
+                    {
                         // This is the attribute name (not the object)
                         let attr = self.extract_text(&child, source);
                         if !attr.is_empty() {
@@ -871,7 +1846,7 @@ impl CallSiteExtractor {
                         }
                     }
                 }
-                
+
                 // Fallback: return the full attribute chain
                 full_text.to_string()
             }
@@ -902,6 +1877,23 @@ impl CallSiteExtractor {
                     self.extract_text(function_node, source).to_string()
                 }
             }
+            "simple_identifier" => {
+                // Kotlin simple call: func() or ClassName()
+                self.extract_text(function_node, source).to_string()
+            }
+            "navigation_expression" => {
+                // Kotlin member call: obj.method() - the method is the last navigation suffix
+                let mut method_name = None;
+                let mut cursor = function_node.walk();
+                for child in function_node.children(&mut cursor) {
+                    if child.kind() == "navigation_suffix" {
+                        if let Some(ident) = child.named_child(0) {
+                            method_name = Some(self.extract_text(&ident, source).to_string());
+                        }
+                    }
+                }
+                method_name.unwrap_or_else(|| self.extract_text(function_node, source).to_string())
+            }
             "subscript_expression" => {
                 // Function pointer calls: func_ptr()
                 self.extract_text(function_node, source).to_string()
@@ -945,10 +1937,11 @@ impl CallSiteExtractor {
                 }
             }
             "field_expression" => CallType::MethodCall, // obj.method()
+            "navigation_expression" => CallType::MethodCall, // Kotlin obj.method()
             "qualified_identifier" => CallType::QualifiedCall, // namespace::func() or Class::method()
             "scoped_identifier" => CallType::QualifiedCall, // Rust std::println, crate::module::function
             "generic_function" => CallType::QualifiedCall,  // Rust Vec::<i32>::new()
-            "identifier" => {
+            "identifier" | "simple_identifier" => {
                 // Check if it looks like a class instantiation (PascalCase)
                 if !called_name.is_empty() {
                     let first_char = called_name.chars().next().unwrap();
@@ -1,7 +1,18 @@
 use anyhow::Result;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
 use rayon::prelude::*;
-use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+/// Files larger than this are skipped unless `FileScanner::with_max_file_size` says otherwise
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// How much of a file is searched for a NUL byte to tell binary files apart
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
 
 #[derive(Debug, Clone)]
 pub struct FileInfo {
@@ -11,26 +22,258 @@ pub struct FileInfo {
     pub extension: String,
 }
 
-pub struct FileScanner;
+/// A file with a source extension that a scan passed over
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedFile {
+    pub path: PathBuf,
+    pub reason: SkipReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Over the size limit, with the file's size in bytes
+    TooLarge(u64),
+    /// Minified bundle such as `app.min.js`
+    Minified,
+    /// Contains a NUL byte, so it is not text
+    Binary,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::TooLarge(size) => write!(f, "{} bytes", size),
+            SkipReason::Minified => write!(f, "minified"),
+            SkipReason::Binary => write!(f, "binary"),
+        }
+    }
+}
+
+/// Which files `FileScanner` keeps with respect to [`FileScanner::is_test_file`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TestFiles {
+    #[default]
+    Include,
+    Exclude,
+    Only,
+}
+
+pub struct FileScanner {
+    /// User-supplied `--exclude` globs, matched against root-relative paths and file names
+    excludes: Option<GlobSet>,
+    /// Whether `.gitignore` files in the tree are honored
+    respect_gitignore: bool,
+    /// Keep, drop, or keep only test files
+    test_files: TestFiles,
+    /// Extra extension → language mappings, for parsers registered with `ParserFactory`
+    language_extensions: std::collections::HashMap<String, String>,
+    /// Size in bytes above which files are skipped; `None` keeps files of any size
+    max_file_size: Option<u64>,
+    /// Files passed over since the last `take_skipped`
+    skipped: Mutex<Vec<SkippedFile>>,
+}
 
 impl FileScanner {
     pub fn new() -> Self {
-        Self
+        Self {
+            excludes: None,
+            respect_gitignore: true,
+            test_files: TestFiles::Include,
+            language_extensions: std::collections::HashMap::new(),
+            max_file_size: Some(DEFAULT_MAX_FILE_SIZE),
+            skipped: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Skip files and directories matching any of the given globs.
+    ///
+    /// A trailing `/**` also excludes the directory itself, so the walk never descends into it.
+    pub fn with_excludes(mut self, patterns: &[String]) -> Result<Self> {
+        if patterns.is_empty() {
+            self.excludes = None;
+            return Ok(self);
+        }
+
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            builder.add(Glob::new(pattern)?);
+            if let Some(dir) = pattern.strip_suffix("/**") {
+                builder.add(Glob::new(dir)?);
+            }
+        }
+        self.excludes = Some(builder.build()?);
+        Ok(self)
+    }
+
+    pub fn with_gitignore(mut self, respect: bool) -> Self {
+        self.respect_gitignore = respect;
+        self
+    }
+
+    pub fn with_test_files(mut self, test_files: TestFiles) -> Self {
+        self.test_files = test_files;
+        self
+    }
+
+    /// Skip files larger than `max_file_size` bytes, such as vendored bundles and generated
+    /// code that would dominate parse time; `None` lifts the limit.
+    pub fn with_max_file_size(mut self, max_file_size: Option<u64>) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Files skipped by the scans since the last call, as too large, minified or binary,
+    /// in path order
+    pub fn take_skipped(&self) -> Vec<SkippedFile> {
+        let mut skipped = std::mem::take(&mut *self.skipped.lock().unwrap());
+        skipped.sort_by(|a, b| a.path.cmp(&b.path));
+        skipped
+    }
+
+    /// Why `path` should not be parsed despite its source extension, if it should not.
+    /// `size` saves a `stat` when the caller already has the metadata.
+    fn skip_reason(&self, path: &Path, size: Option<u64>) -> Option<SkipReason> {
+        let is_minified = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.contains(".min."));
+        if is_minified {
+            return Some(SkipReason::Minified);
+        }
+        if let Some(max_file_size) = self.max_file_size {
+            let size = size.or_else(|| path.metadata().ok().map(|metadata| metadata.len()))?;
+            if size > max_file_size {
+                return Some(SkipReason::TooLarge(size));
+            }
+        }
+
+        let mut head = [0u8; BINARY_SNIFF_BYTES];
+        let read = File::open(path)
+            .and_then(|mut file| file.read(&mut head))
+            .ok()?;
+        head[..read].contains(&0).then_some(SkipReason::Binary)
+    }
+
+    /// Whether to parse `path`, recording it as skipped if not
+    fn admits(&self, path: &Path, size: Option<u64>) -> bool {
+        let Some(reason) = self.skip_reason(path, size) else {
+            return true;
+        };
+        self.skipped.lock().unwrap().push(SkippedFile {
+            path: path.to_path_buf(),
+            reason,
+        });
+        false
+    }
+
+    /// Pick up files with these extensions (without the dot) as `language`, whenever
+    /// `language` is requested. Pairs with a parser added via `ParserFactory::register`.
+    #[allow(dead_code)]
+    pub fn with_language_extensions(mut self, language: &str, extensions: &[&str]) -> Self {
+        for extension in extensions {
+            self.language_extensions
+                .insert(extension.to_string(), language.to_string());
+        }
+        self
+    }
+
+    /// Whether `path` is test code by the naming conventions of `language`: `*_test.go`,
+    /// `test_*.py`, `*.spec.ts`, `FooTest.java`, files under a Rust `tests/` directory, and
+    /// so on. Pass a path relative to the scanned root so directories above it don't count.
+    ///
+    /// Inline Rust `#[cfg(test)]` modules share a file with the code under test and are
+    /// only caught when split out into their own `tests.rs`.
+    pub fn is_test_file(path: &Path, language: &str) -> bool {
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            return false;
+        };
+        let stem = file_name.split('.').next().unwrap_or(file_name);
+        let in_dir = |names: &[&str]| {
+            path.parent().is_some_and(|parent| {
+                parent
+                    .components()
+                    .any(|c| c.as_os_str().to_str().is_some_and(|c| names.contains(&c)))
+            })
+        };
+
+        match language {
+            "go" => stem.ends_with("_test"),
+            "python" => stem.starts_with("test_") || stem.ends_with("_test") || stem == "conftest",
+            "typescript" | "javascript" => {
+                let mut parts = file_name.split('.').skip(1);
+                parts.any(|part| part == "test" || part == "spec") || in_dir(&["__tests__"])
+            }
+            // Maven/Gradle keep tests under src/test/
+            "java" | "kotlin" => {
+                stem.ends_with("Test") || stem.ends_with("Tests") || in_dir(&["test"])
+            }
+            "csharp" => stem.ends_with("Test") || stem.ends_with("Tests"),
+            "php" => stem.ends_with("Test") || in_dir(&["tests"]),
+            "rust" => stem == "tests" || stem.ends_with("_test") || in_dir(&["tests", "benches"]),
+            "ruby" => stem.ends_with("_spec") || stem.ends_with("_test") || in_dir(&["spec"]),
+            "c" | "cpp" => stem.starts_with("test_") || stem.ends_with("_test"),
+            // busted specs
+            "lua" => stem.ends_with("_spec") || in_dir(&["spec"]),
+            // XCTest case classes
+            "objc" => stem.ends_with("Tests") || stem.ends_with("Test"),
+            _ => false,
+        }
+    }
+
+    fn keeps(&self, relative: &Path, language: &str) -> bool {
+        match self.test_files {
+            TestFiles::Include => true,
+            TestFiles::Exclude => !Self::is_test_file(relative, language),
+            TestFiles::Only => Self::is_test_file(relative, language),
+        }
+    }
+
+    fn is_excluded(excludes: &GlobSet, root_path: &Path, path: &Path) -> bool {
+        let relative = path.strip_prefix(root_path).unwrap_or(path);
+        excludes.is_match(relative)
+            || path
+                .file_name()
+                .is_some_and(|name| excludes.is_match(Path::new(name)))
+    }
+
+    /// A listed file has no root to be relative to, so the globs are tried against the
+    /// path from each of its directories down to the file name alone
+    fn is_listed_excluded(excludes: &GlobSet, path: &Path) -> bool {
+        let components: Vec<Component> = path.components().collect();
+        (0..components.len())
+            .any(|start| excludes.is_match(components[start..].iter().collect::<PathBuf>()))
     }
 
     pub fn scan_directory(&self, root_path: &Path, languages: &[&str]) -> Result<Vec<FileInfo>> {
         let supported_extensions = self.get_extensions_for_languages(languages);
 
-        // Collect all entries first for parallel processing
-        let entries: Vec<_> = WalkDir::new(root_path)
+        // Collect all entries first for parallel processing. Excluded directories are
+        // pruned in filter_entry so large trees like node_modules are never walked.
+        let mut walker = WalkBuilder::new(root_path);
+        walker
             .follow_links(false)
-            .into_iter()
+            .hidden(false)
+            .ignore(false)
+            .git_global(false)
+            .git_exclude(false)
+            .git_ignore(self.respect_gitignore)
+            .require_git(false);
+
+        if let Some(excludes) = self.excludes.clone() {
+            let root = root_path.to_path_buf();
+            walker.filter_entry(move |entry| {
+                entry.depth() == 0 || !Self::is_excluded(&excludes, &root, entry.path())
+            });
+        }
+
+        let entries: Vec<_> = walker
+            .build()
             .filter_map(|e| e.ok())
             .filter(|entry| entry.path().is_file())
             .collect();
 
         // Process entries in parallel
-        let files: Vec<FileInfo> = entries
+        let mut files: Vec<FileInfo> = entries
             .par_iter()
             .filter_map(|entry| {
                 let path = entry.path();
@@ -39,6 +282,14 @@ impl FileScanner {
                     .and_then(|extension| {
                         supported_extensions
                             .get(extension)
+                            .filter(|language| {
+                                let relative = path.strip_prefix(root_path).unwrap_or(path);
+                                self.keeps(relative, language)
+                            })
+                            .filter(|_| {
+                                let size = entry.metadata().ok().map(|metadata| metadata.len());
+                                self.admits(path, size)
+                            })
                             .map(|language| FileInfo {
                                 path: path.to_path_buf(),
                                 language: language.clone(),
@@ -47,10 +298,66 @@ impl FileScanner {
                     })
             })
             .collect();
+        // Directory listing order varies by filesystem; parse and merge in a fixed order
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(files)
+    }
 
+    /// Scan several roots into one list; files reachable from more than one root, as with
+    /// nested roots, are kept once
+    pub fn scan_directories(&self, roots: &[PathBuf], languages: &[&str]) -> Result<Vec<FileInfo>> {
+        let mut seen = std::collections::HashSet::new();
+        let mut files = Vec::new();
+        for root in roots {
+            for file in self.scan_directory(root, languages)? {
+                if seen.insert(file.path.clone()) {
+                    files.push(file);
+                }
+            }
+        }
         Ok(files)
     }
 
+    /// Classify an explicit list of files, skipping duplicates, unsupported extensions and
+    /// files matching the exclude globs
+    pub fn scan_files(&self, paths: &[PathBuf], languages: &[&str]) -> Vec<FileInfo> {
+        let supported_extensions = self.get_extensions_for_languages(languages);
+        let mut seen = std::collections::HashSet::new();
+
+        paths
+            .iter()
+            .filter(|path| seen.insert(path.as_path()))
+            .filter(|path| {
+                self.excludes
+                    .as_ref()
+                    .is_none_or(|excludes| !Self::is_listed_excluded(excludes, path))
+            })
+            .filter_map(|path| {
+                let extension = path.extension()?.to_str()?;
+                let language = supported_extensions.get(extension)?;
+                if !self.keeps(path, language) || !self.admits(path, None) {
+                    return None;
+                }
+                Some(FileInfo {
+                    path: path.clone(),
+                    language: language.clone(),
+                    extension: extension.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Distinct languages of the scanned files, sorted; backs `--languages auto`
+    pub fn languages_present(files: &[FileInfo]) -> Vec<String> {
+        files
+            .iter()
+            .map(|file| file.language.clone())
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
     fn get_extensions_for_languages(
         &self,
         languages: &[&str],
@@ -95,10 +402,34 @@ impl FileScanner {
                 "csharp" | "c#" => {
                     extensions.insert("cs", "csharp".to_string());
                 }
+                "kotlin" | "kt" => {
+                    extensions.insert("kt", "kotlin".to_string());
+                    extensions.insert("kts", "kotlin".to_string());
+                }
+                "ruby" | "rb" => {
+                    extensions.insert("rb", "ruby".to_string());
+                }
+                "php" => {
+                    extensions.insert("php", "php".to_string());
+                }
+                "lua" => {
+                    extensions.insert("lua", "lua".to_string());
+                }
+                "objc" | "objective-c" => {
+                    extensions.insert("m", "objc".to_string());
+                    // Headers stay with C when both are requested
+                    extensions.entry("h").or_insert_with(|| "objc".to_string());
+                }
                 _ => {}
             }
         }
 
+        for (extension, language) in &self.language_extensions {
+            if languages.contains(&language.as_str()) {
+                extensions.insert(extension, language.clone());
+            }
+        }
+
         extensions
     }
 }
@@ -0,0 +1,274 @@
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
+
+use super::graph::{DependencyGraph, EdgeType, NodeType};
+use super::resolver::CallResolution;
+use super::scanner::SkippedFile;
+
+/// Aggregate metrics for a dependency graph, printed by `--stats`
+#[derive(Debug, Clone, Default)]
+pub struct GraphStats {
+    pub nodes_by_type: BTreeMap<String, usize>,
+    pub edges_by_type: BTreeMap<String, usize>,
+    pub files_by_language: BTreeMap<String, usize>,
+    /// (function name, incoming call sites summed over edge weights), most called first
+    pub most_called: Vec<(String, usize)>,
+    /// Mean longest call chain over functions nobody calls
+    pub avg_call_depth: f64,
+    pub max_call_depth: usize,
+    /// Call sites the analyzer resolved, when known; a graph alone does not record misses
+    pub call_resolution: Option<CallResolution>,
+    /// Files the scan passed over as too large, minified or binary; they have no nodes
+    pub skipped_files: Vec<SkippedFile>,
+}
+
+impl GraphStats {
+    const MOST_CALLED_LIMIT: usize = 10;
+    const SKIPPED_FILES_LIMIT: usize = 10;
+
+    pub fn compute(graph: &DependencyGraph) -> Self {
+        let mut stats = Self::default();
+
+        let mut files: HashSet<(&str, &std::path::Path)> = HashSet::new();
+        for node in graph.node_weights() {
+            *stats
+                .nodes_by_type
+                .entry(format!("{:?}", node.node_type))
+                .or_insert(0) += 1;
+            // External placeholders carry the referencing file, not a file of their own
+            if node.visibility.as_deref() != Some("external") {
+                files.insert((node.language.as_str(), node.file_path.as_path()));
+            }
+        }
+        for (language, _) in files {
+            *stats
+                .files_by_language
+                .entry(language.to_string())
+                .or_insert(0) += 1;
+        }
+
+        for edge_ref in graph.edge_references() {
+            *stats
+                .edges_by_type
+                .entry(format!("{:?}", edge_ref.weight().edge_type))
+                .or_insert(0) += 1;
+        }
+
+        let mut most_called: Vec<(String, usize)> = graph
+            .node_indices()
+            .filter(|&idx| graph[idx].node_type == NodeType::Function)
+            .map(|idx| (graph[idx].name.clone(), Self::call_in_degree(graph, idx)))
+            .filter(|(_, calls)| *calls > 0)
+            .collect();
+        most_called.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        most_called.truncate(Self::MOST_CALLED_LIMIT);
+        stats.most_called = most_called;
+
+        let (avg, max) = Self::call_depths(graph);
+        stats.avg_call_depth = avg;
+        stats.max_call_depth = max;
+
+        stats
+    }
+
+    /// Attach the analyzer's call resolution counts, reported as a resolved/total ratio
+    pub fn with_call_resolution(mut self, call_resolution: CallResolution) -> Self {
+        self.call_resolution = Some(call_resolution);
+        self
+    }
+
+    /// Attach the files the analyzer's scan skipped, listed by path
+    pub fn with_skipped_files(mut self, skipped_files: Vec<SkippedFile>) -> Self {
+        self.skipped_files = skipped_files;
+        self
+    }
+
+    /// Incoming calls counted per call site, so `a` calling `b` three times counts three
+    fn call_in_degree(graph: &DependencyGraph, idx: NodeIndex) -> usize {
+        graph
+            .edges_directed(idx, Direction::Incoming)
+            .filter(|e| e.weight().edge_type == EdgeType::Call)
+            .map(|e| e.weight().weight)
+            .sum()
+    }
+
+    /// Longest call chain starting at each entry point (a function with no callers).
+    /// Edges that close a cycle are ignored so recursion does not inflate the depth.
+    fn call_depths(graph: &DependencyGraph) -> (f64, usize) {
+        let mut memo: HashMap<NodeIndex, usize> = HashMap::new();
+        let mut depths = Vec::new();
+
+        for idx in graph.node_indices() {
+            if graph[idx].node_type != NodeType::Function || Self::call_in_degree(graph, idx) > 0 {
+                continue;
+            }
+            let mut on_stack = HashSet::new();
+            depths.push(Self::depth_from(graph, idx, &mut memo, &mut on_stack));
+        }
+
+        if depths.is_empty() {
+            return (0.0, 0);
+        }
+        let max = depths.iter().copied().max().unwrap_or(0);
+        let avg = depths.iter().sum::<usize>() as f64 / depths.len() as f64;
+        (avg, max)
+    }
+
+    fn depth_from(
+        graph: &DependencyGraph,
+        idx: NodeIndex,
+        memo: &mut HashMap<NodeIndex, usize>,
+        on_stack: &mut HashSet<NodeIndex>,
+    ) -> usize {
+        if let Some(&depth) = memo.get(&idx) {
+            return depth;
+        }
+        on_stack.insert(idx);
+
+        let mut deepest = 0;
+        for edge_ref in graph.edges(idx) {
+            if edge_ref.weight().edge_type != EdgeType::Call
+                || on_stack.contains(&edge_ref.target())
+            {
+                continue;
+            }
+            deepest = deepest.max(1 + Self::depth_from(graph, edge_ref.target(), memo, on_stack));
+        }
+
+        on_stack.remove(&idx);
+        memo.insert(idx, deepest);
+        deepest
+    }
+}
+
+/// A function ranked by `--top-k`, with the measures behind its score
+#[derive(Debug, Clone)]
+pub struct Hotspot {
+    pub name: String,
+    pub file_path: PathBuf,
+    pub line_number: usize,
+    /// Incoming call sites, as in `most_called`
+    pub callers: usize,
+    pub complexity: usize,
+    /// Distinct functions it calls
+    pub callees: usize,
+    /// Sum of the three measures, each divided by its maximum over the graph, so 0 to 3
+    pub score: f64,
+}
+
+impl Hotspot {
+    /// The `k` functions defined in the analyzed files that score highest, ties broken by
+    /// name: the ones many places depend on, with many branches and calls of their own
+    pub fn top(graph: &DependencyGraph, k: usize) -> Vec<Self> {
+        let mut hotspots: Vec<Self> = graph
+            .node_indices()
+            .filter(|&idx| {
+                graph[idx].node_type == NodeType::Function
+                    && graph[idx].visibility.as_deref() != Some("external")
+            })
+            .map(|idx| {
+                let node = &graph[idx];
+                let callees: HashSet<NodeIndex> = graph
+                    .edges(idx)
+                    .filter(|e| e.weight().edge_type == EdgeType::Call)
+                    .map(|e| e.target())
+                    .collect();
+                Self {
+                    name: node.name.clone(),
+                    file_path: node.file_path.clone(),
+                    line_number: node.line_number,
+                    callers: GraphStats::call_in_degree(graph, idx),
+                    complexity: node.complexity,
+                    callees: callees.len(),
+                    score: 0.0,
+                }
+            })
+            .collect();
+
+        let scale = |max: usize| if max == 0 { 0.0 } else { 1.0 / max as f64 };
+        let callers = scale(hotspots.iter().map(|h| h.callers).max().unwrap_or(0));
+        let complexity = scale(hotspots.iter().map(|h| h.complexity).max().unwrap_or(0));
+        let callees = scale(hotspots.iter().map(|h| h.callees).max().unwrap_or(0));
+        for hotspot in &mut hotspots {
+            hotspot.score = hotspot.callers as f64 * callers
+                + hotspot.complexity as f64 * complexity
+                + hotspot.callees as f64 * callees;
+        }
+
+        hotspots.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        hotspots.truncate(k);
+        hotspots
+    }
+}
+
+impl fmt::Display for Hotspot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:.2} (callers {}, complexity {}, calls {}) {}:{}",
+            self.name,
+            self.score,
+            self.callers,
+            self.complexity,
+            self.callees,
+            self.file_path.display(),
+            self.line_number
+        )
+    }
+}
+
+impl fmt::Display for GraphStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Nodes by type:")?;
+        for (node_type, count) in &self.nodes_by_type {
+            writeln!(f, "  {}: {}", node_type, count)?;
+        }
+        writeln!(f, "Edges by type:")?;
+        for (edge_type, count) in &self.edges_by_type {
+            writeln!(f, "  {}: {}", edge_type, count)?;
+        }
+        writeln!(f, "Files by language:")?;
+        for (language, count) in &self.files_by_language {
+            writeln!(f, "  {}: {}", language, count)?;
+        }
+        if !self.skipped_files.is_empty() {
+            writeln!(f, "Skipped files: {}", self.skipped_files.len())?;
+            for skipped in self.skipped_files.iter().take(Self::SKIPPED_FILES_LIMIT) {
+                writeln!(f, "  {} ({})", skipped.path.display(), skipped.reason)?;
+            }
+            if self.skipped_files.len() > Self::SKIPPED_FILES_LIMIT {
+                writeln!(
+                    f,
+                    "  ... and {} more",
+                    self.skipped_files.len() - Self::SKIPPED_FILES_LIMIT
+                )?;
+            }
+        }
+        writeln!(f, "Most called functions:")?;
+        for (name, calls) in &self.most_called {
+            writeln!(f, "  {} ({} calls)", name, calls)?;
+        }
+        if let Some(resolution) = &self.call_resolution {
+            writeln!(
+                f,
+                "Call sites resolved: {}/{} ({:.1}%)",
+                resolution.resolved_count(),
+                resolution.total,
+                resolution.resolved_ratio() * 100.0
+            )?;
+        }
+        write!(
+            f,
+            "Call depth: avg {:.2}, max {}",
+            self.avg_call_depth, self.max_call_depth
+        )
+    }
+}
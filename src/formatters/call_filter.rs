@@ -0,0 +1,75 @@
+use petgraph::graph::EdgeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use std::borrow::Cow;
+
+use crate::core::{DependencyGraph, EdgeType, NodeType};
+
+/// `Call` edges a human-facing formatter leaves out: calls to denylisted functions and
+/// calls made fewer times than a minimum. Machine-readable formats keep every edge.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CallFilter {
+    /// `(owner, name)` of hidden callees; `String::from` is `(Some("String"), "from")`
+    hidden: Vec<(Option<String>, String)>,
+    /// Calls with fewer call sites than this are dropped
+    min_weight: usize,
+}
+
+impl CallFilter {
+    /// Hide calls to these functions, given as `name`, `Type::name` or `Type.name`; a
+    /// trailing `!` is ignored so Rust macros can be written as they are called
+    pub fn hide(&mut self, names: &[String]) {
+        self.hidden = names
+            .iter()
+            .map(|name| {
+                let name = name.trim().trim_end_matches('!');
+                match name.rsplit_once("::").or_else(|| name.rsplit_once('.')) {
+                    Some((owner, name)) => (Some(owner.to_string()), name.to_string()),
+                    None => (None, name.to_string()),
+                }
+            })
+            .collect();
+    }
+
+    pub fn set_min_weight(&mut self, min_weight: usize) {
+        self.min_weight = min_weight;
+    }
+
+    /// `graph` without the filtered calls; borrowed as is when nothing is filtered
+    pub fn apply<'a>(&self, graph: &'a DependencyGraph) -> Cow<'a, DependencyGraph> {
+        if self.hidden.is_empty() && self.min_weight <= 1 {
+            return Cow::Borrowed(graph);
+        }
+        // Every node is kept, so node indices stay the same
+        Cow::Owned(graph.filter_map(
+            |_, node| Some(node.clone()),
+            |edge, weight| self.keeps(graph, edge).then(|| weight.clone()),
+        ))
+    }
+
+    fn keeps(&self, graph: &DependencyGraph, edge: EdgeIndex) -> bool {
+        let weight = &graph[edge];
+        if weight.edge_type != EdgeType::Call {
+            return true;
+        }
+        if weight.weight < self.min_weight {
+            return false;
+        }
+        let Some((_, callee)) = graph.edge_endpoints(edge) else {
+            return true;
+        };
+        let callee_name = graph[callee].name.as_str();
+        !self.hidden.iter().any(|(owner, name)| {
+            name == callee_name
+                && owner.as_deref().is_none_or(|owner| {
+                    graph
+                        .edges_directed(callee, Direction::Incoming)
+                        .filter(|e| e.weight().edge_type == EdgeType::Contains)
+                        .any(|e| {
+                            let container = &graph[e.source()];
+                            container.name == owner && container.node_type != NodeType::Module
+                        })
+                })
+        })
+    }
+}
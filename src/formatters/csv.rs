@@ -0,0 +1,106 @@
+use anyhow::Result;
+use petgraph::visit::EdgeRef;
+use std::borrow::Cow;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::DependencyGraph;
+
+/// CSV edge list (plus an optional node table) for pandas, R or spreadsheets
+pub struct CsvFormatter;
+
+impl CsvFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Write the edge list to `output_path` and the node table next to it.
+    pub fn format_to_file(&self, graph: &DependencyGraph, output_path: &Path) -> Result<()> {
+        fs::write(output_path, self.format_to_string(graph)?)?;
+        fs::write(
+            Self::nodes_path(output_path),
+            self.format_nodes_to_string(graph)?,
+        )?;
+        Ok(())
+    }
+
+    /// `graph.csv` → `graph.nodes.csv`
+    pub fn nodes_path(output_path: &Path) -> PathBuf {
+        output_path.with_extension("nodes.csv")
+    }
+
+    /// Edge list: one row per edge, `line` being where the edge was derived from, or where
+    /// the source entity is defined for edges without a line.
+    pub fn format_to_string(&self, graph: &DependencyGraph) -> Result<String> {
+        let mut output = String::with_capacity(64 + graph.edge_count() * 160);
+        output.push_str("source_id,source_name,target_id,target_name,edge_type,context,line\n");
+
+        for edge_ref in graph.edge_references() {
+            let edge = edge_ref.weight();
+            let source = &graph[edge_ref.source()];
+            let target = &graph[edge_ref.target()];
+            Self::push_row(
+                &mut output,
+                &[
+                    &source.id,
+                    &source.name,
+                    &target.id,
+                    &target.name,
+                    &format!("{:?}", edge.edge_type),
+                    edge.context.as_deref().unwrap_or(""),
+                    &edge.line.unwrap_or(source.line_number).to_string(),
+                ],
+            );
+        }
+        Ok(output)
+    }
+
+    /// Node table, joinable with the edge list on `id`.
+    pub fn format_nodes_to_string(&self, graph: &DependencyGraph) -> Result<String> {
+        let mut output = String::with_capacity(64 + graph.node_count() * 160);
+        output.push_str("id,name,node_type,file_path,line_number,language,visibility,signature\n");
+
+        for node in graph.node_weights() {
+            Self::push_row(
+                &mut output,
+                &[
+                    &node.id,
+                    &node.name,
+                    &format!("{:?}", node.node_type),
+                    &node.file_path.to_string_lossy(),
+                    &node.line_number.to_string(),
+                    &node.language,
+                    node.visibility.as_deref().unwrap_or(""),
+                    node.signature.as_deref().unwrap_or(""),
+                ],
+            );
+        }
+        Ok(output)
+    }
+
+    fn push_row(output: &mut String, fields: &[&str]) {
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                output.push(',');
+            }
+            output.push_str(&Self::escape(field));
+        }
+        output.push('\n');
+    }
+
+    /// RFC 4180 quoting: fields with commas, quotes or line breaks are wrapped in quotes,
+    /// with embedded quotes doubled. Signatures routinely need it.
+    fn escape(field: &str) -> Cow<'_, str> {
+        if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\"")).into()
+        } else {
+            field.into()
+        }
+    }
+}
+
+impl Default for CsvFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,121 @@
+use anyhow::Result;
+use petgraph::visit::EdgeRef;
+use std::fs;
+use std::path::Path;
+
+use crate::core::DependencyGraph;
+
+/// GraphML XML formatter for graph tools such as Gephi and yEd
+pub struct GraphMlFormatter;
+
+impl GraphMlFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    #[allow(dead_code)]
+    pub fn format_to_file(&self, graph: &DependencyGraph, output_path: &Path) -> Result<()> {
+        let content = self.format_to_string(graph)?;
+        fs::write(output_path, content)?;
+        Ok(())
+    }
+
+    /// Render the GraphML document as a string.
+    pub fn format_to_string(&self, graph: &DependencyGraph) -> Result<String> {
+        // Roughly 300 bytes per node and 120 per edge once attributes are written out
+        let mut output =
+            String::with_capacity(1024 + graph.node_count() * 300 + graph.edge_count() * 120);
+
+        output.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        output.push_str(
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" \
+             xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+             xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns \
+             http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">\n",
+        );
+
+        let node_keys = [
+            ("name", "string"),
+            ("type", "string"),
+            ("file_path", "string"),
+            ("line_number", "int"),
+            ("language", "string"),
+            ("visibility", "string"),
+        ];
+        for (key, attr_type) in node_keys {
+            output.push_str(&format!(
+                "  <key id=\"{}\" for=\"node\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+                key, key, attr_type
+            ));
+        }
+        for key in ["edge_type", "context"] {
+            output.push_str(&format!(
+                "  <key id=\"{}\" for=\"edge\" attr.name=\"{}\" attr.type=\"string\"/>\n",
+                key, key
+            ));
+        }
+
+        output.push_str("  <graph id=\"embargo\" edgedefault=\"directed\">\n");
+
+        for node in graph.node_weights() {
+            output.push_str(&format!("    <node id=\"{}\">\n", Self::escape(&node.id)));
+            Self::push_data(&mut output, "name", &node.name);
+            Self::push_data(&mut output, "type", &format!("{:?}", node.node_type));
+            Self::push_data(&mut output, "file_path", &node.file_path.to_string_lossy());
+            Self::push_data(&mut output, "line_number", &node.line_number.to_string());
+            Self::push_data(&mut output, "language", &node.language);
+            if let Some(visibility) = &node.visibility {
+                Self::push_data(&mut output, "visibility", visibility);
+            }
+            output.push_str("    </node>\n");
+        }
+
+        for (edge_number, edge_ref) in graph.edge_references().enumerate() {
+            let edge = edge_ref.weight();
+            output.push_str(&format!(
+                "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">\n",
+                edge_number,
+                Self::escape(&graph[edge_ref.source()].id),
+                Self::escape(&graph[edge_ref.target()].id)
+            ));
+            Self::push_data(&mut output, "edge_type", &format!("{:?}", edge.edge_type));
+            if let Some(context) = &edge.context {
+                Self::push_data(&mut output, "context", context);
+            }
+            output.push_str("    </edge>\n");
+        }
+
+        output.push_str("  </graph>\n</graphml>\n");
+        Ok(output)
+    }
+
+    fn push_data(output: &mut String, key: &str, value: &str) {
+        output.push_str(&format!(
+            "      <data key=\"{}\">{}</data>\n",
+            key,
+            Self::escape(value)
+        ));
+    }
+
+    /// Escape XML-special characters; C++ templates and signatures are full of them
+    pub(super) fn escape(value: &str) -> String {
+        let mut escaped = String::with_capacity(value.len());
+        for c in value.chars() {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&apos;"),
+                _ => escaped.push(c),
+            }
+        }
+        escaped
+    }
+}
+
+impl Default for GraphMlFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,223 @@
+use anyhow::Result;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use super::graphml::GraphMlFormatter;
+use super::tree::{DirEntry, Outline};
+use crate::core::{DependencyGraph, EdgeType, Node, NodeType};
+
+const STYLE: &str = r##"
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.4em; margin-bottom: 0.2em; }
+.summary { color: #666; margin-top: 0; }
+#filter { width: 100%; max-width: 32em; padding: 0.4em; margin: 0.5em 0 1em; font-size: 1em; }
+ul { list-style: none; padding-left: 1.2em; margin: 0; }
+#tree { padding-left: 0; }
+summary { cursor: pointer; }
+.dir > details > summary { font-weight: 600; }
+.file > details > summary { font-family: monospace; }
+.entity { font-family: monospace; }
+.kind { font-weight: 600; }
+.class > .label .kind, .class > details > summary .kind { color: #b58900; }
+.interface > .label .kind, .interface > details > summary .kind { color: #2aa198; }
+.enum > .label .kind, .enum > details > summary .kind { color: #d33682; }
+.function > .label .kind, .function > details > summary .kind { color: #859900; }
+.line, .loc { color: #999; }
+.sig { color: #555; padding-left: 1.2em; white-space: pre-wrap; }
+.calls li::before { content: "\2192  "; color: #999; }
+"##;
+
+const SCRIPT: &str = r##"
+const filter = document.getElementById("filter");
+function apply(li, query) {
+  const self = !query || li.dataset.name.includes(query);
+  let childMatch = false;
+  for (const child of li.querySelectorAll(":scope > details > ul.children > li")) {
+    if (apply(child, self ? "" : query)) childMatch = true;
+  }
+  li.hidden = !(self || childMatch);
+  const details = li.querySelector(":scope > details");
+  if (details && query) details.open = childMatch;
+  return !li.hidden;
+}
+filter.addEventListener("input", () => {
+  const query = filter.value.trim().toLowerCase();
+  for (const li of document.querySelectorAll("#tree > li")) apply(li, query);
+});
+"##;
+
+/// Self-contained HTML page: a collapsible directory → file → entity tree, color-coded
+/// by node type, with each function's calls one click away and a filter box on top
+pub struct HtmlFormatter;
+
+impl HtmlFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    #[allow(dead_code)]
+    pub fn format_to_file(&self, graph: &DependencyGraph, output_path: &Path) -> Result<()> {
+        let content = self.format_to_string(graph)?;
+        fs::write(output_path, content)?;
+        Ok(())
+    }
+
+    /// Render the page as a string.
+    pub fn format_to_string(&self, graph: &DependencyGraph) -> Result<String> {
+        let outline = Outline::build(graph);
+        let mut output = String::with_capacity(8192 + graph.node_count() * 200);
+
+        output.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        output.push_str(&format!(
+            "<title>EMBARGO - {}</title>\n",
+            GraphMlFormatter::escape(&outline.root_label)
+        ));
+        output.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+        output.push_str(&format!(
+            "<h1>{}</h1>\n<p class=\"summary\">{} nodes, {} edges</p>\n",
+            GraphMlFormatter::escape(&outline.root_label),
+            graph.node_count(),
+            graph.edge_count()
+        ));
+        output.push_str(
+            "<input id=\"filter\" type=\"search\" placeholder=\"Filter by name\" autofocus>\n",
+        );
+        output.push_str("<ul id=\"tree\">\n");
+        self.format_dir(&outline.root, graph, &outline, &mut output);
+        output.push_str("</ul>\n");
+        output.push_str(&format!("<script>{}</script>\n</body>\n</html>\n", SCRIPT));
+        Ok(output)
+    }
+
+    fn format_dir(
+        &self,
+        dir: &DirEntry,
+        graph: &DependencyGraph,
+        outline: &Outline,
+        output: &mut String,
+    ) {
+        for (name, child) in &dir.dirs {
+            Self::open_item(output, "dir", name, &format!("{}/", name));
+            self.format_dir(child, graph, outline, output);
+            output.push_str("</ul></details></li>\n");
+        }
+
+        for (name, entities) in &dir.files {
+            Self::open_item(output, "file", name, name);
+            let mut visited = HashSet::new();
+            self.format_entities(entities, graph, outline, &mut visited, output);
+            output.push_str("</ul></details></li>\n");
+        }
+    }
+
+    fn format_entities(
+        &self,
+        entities: &[NodeIndex],
+        graph: &DependencyGraph,
+        outline: &Outline,
+        visited: &mut HashSet<NodeIndex>,
+        output: &mut String,
+    ) {
+        for idx in Outline::ordered(graph, entities, visited) {
+            visited.insert(idx);
+            let node = &graph[idx];
+            let class = Self::css_class(node);
+            let label = self.label(node);
+
+            let members = outline.members(graph, idx);
+            let calls: Vec<NodeIndex> = graph
+                .edges(idx)
+                .filter(|e| e.weight().edge_type == EdgeType::Call)
+                .map(|e| e.target())
+                .collect();
+            if members.is_empty() && calls.is_empty() && node.signature.is_none() {
+                output.push_str(&format!(
+                    "<li class=\"entity {}\" data-name=\"{}\"><span class=\"label\">{}</span></li>\n",
+                    class,
+                    GraphMlFormatter::escape(&node.name.to_lowercase()),
+                    label
+                ));
+                continue;
+            }
+
+            output.push_str(&format!(
+                "<li class=\"entity {}\" data-name=\"{}\"><details><summary>{}</summary>\n",
+                class,
+                GraphMlFormatter::escape(&node.name.to_lowercase()),
+                label
+            ));
+            if let Some(signature) = &node.signature {
+                output.push_str(&format!(
+                    "<div class=\"sig\">{}</div>\n",
+                    GraphMlFormatter::escape(signature)
+                ));
+            }
+            if !calls.is_empty() {
+                output.push_str("<ul class=\"calls\">\n");
+                for target in calls {
+                    let callee = &graph[target];
+                    output.push_str(&format!(
+                        "<li>{} <span class=\"loc\">{}:{}</span></li>\n",
+                        GraphMlFormatter::escape(&callee.name),
+                        GraphMlFormatter::escape(&callee.file_path.to_string_lossy()),
+                        callee.line_number
+                    ));
+                }
+                output.push_str("</ul>\n");
+            }
+            output.push_str("<ul class=\"children\">\n");
+            self.format_entities(&members, graph, outline, visited, output);
+            output.push_str("</ul></details></li>\n");
+        }
+    }
+
+    /// `<li>` with an expanded `<details>` whose children follow in a `ul.children`
+    fn open_item(output: &mut String, class: &str, name: &str, summary: &str) {
+        output.push_str(&format!(
+            "<li class=\"{}\" data-name=\"{}\"><details open><summary>{}</summary>\
+             <ul class=\"children\">\n",
+            class,
+            GraphMlFormatter::escape(&name.to_lowercase()),
+            GraphMlFormatter::escape(summary)
+        ));
+    }
+
+    fn css_class(node: &Node) -> &'static str {
+        match node.node_type {
+            NodeType::Class => "class",
+            NodeType::Interface => "interface",
+            NodeType::Enum => "enum",
+            NodeType::Function => "function",
+            NodeType::Module => "module",
+            NodeType::Variable => "variable",
+        }
+    }
+
+    fn label(&self, node: &Node) -> String {
+        let kind = match node.node_type {
+            NodeType::Function => "fn",
+            _ => Self::css_class(node),
+        };
+        let suffix = if node.node_type == NodeType::Function {
+            "()"
+        } else {
+            ""
+        };
+        format!(
+            "<span class=\"kind\">{}</span> {}{} <span class=\"line\">:{}</span>",
+            kind,
+            GraphMlFormatter::escape(&node.name),
+            suffix,
+            node.line_number
+        )
+    }
+}
+
+impl Default for HtmlFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,8 +1,9 @@
 use anyhow::Result;
-use petgraph::graph::NodeIndex;
+use petgraph::algo::tarjan_scc;
+use petgraph::graph::{Graph, NodeIndex};
 use petgraph::visit::EdgeRef;
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::path::Path;
 
@@ -12,20 +13,36 @@ use crate::core::{DependencyGraph, EdgeType, NodeType};
 pub struct JsonCompactFormatter {
     /// Include full metadata or just essential information
     minimal: bool,
+    /// Add per-file aggregates under `file_metrics`
+    file_metrics: bool,
 }
 
 impl JsonCompactFormatter {
     pub fn new() -> Self {
-        Self { minimal: true }
+        Self {
+            minimal: true,
+            file_metrics: false,
+        }
+    }
+
+    /// Add a `file_metrics` array parallel to `files`: per file, its node counts by type,
+    /// the edges leaving its nodes (not counting `Contains`), the longest call chain
+    /// starting in it and its languages, e.g.
+    /// `{"nodes":{"function":3},"edges_out":5,"max_call_depth":2,"languages":["rust"]}`
+    pub fn with_file_metrics(mut self, include: bool) -> Self {
+        self.file_metrics = include;
+        self
     }
 
+    #[allow(dead_code)]
     pub fn format_to_file(&self, graph: &DependencyGraph, output_path: &Path) -> Result<()> {
-        let json_content = self.format_graph(graph)?;
+        let json_content = self.format_to_string(graph)?;
         fs::write(output_path, json_content)?;
         Ok(())
     }
 
-    fn format_graph(&self, graph: &DependencyGraph) -> Result<String> {
+    /// Serialize the graph to compact JSON in memory.
+    pub fn format_to_string(&self, graph: &DependencyGraph) -> Result<String> {
         let node_indices: Vec<NodeIndex> = graph.node_indices().collect();
 
         // Build compressed node mappings
@@ -53,12 +70,27 @@ impl JsonCompactFormatter {
                 let file_id = file_map[&node.file_path.to_string_lossy().to_string()];
 
                 let node_json = if self.minimal {
-                    json!({
+                    let mut node_obj = json!({
                         "n": node.name,
                         "t": self.type_code(node.node_type),
                         "f": file_id,
                         "l": node.line_number
-                    })
+                    });
+                    // Only async nodes carry the flag, keeping the common case small
+                    // Spans past the first line only; `e` is the definition's last line
+                    if node.end_line > node.line_number {
+                        node_obj["e"] = json!(node.end_line);
+                    }
+                    if node.is_async {
+                        node_obj["a"] = json!(1);
+                    }
+                    if node.is_test {
+                        node_obj["ts"] = json!(1);
+                    }
+                    if node.complexity > 0 {
+                        node_obj["x"] = json!(node.complexity);
+                    }
+                    node_obj
                 } else {
                     let mut node_obj = json!({
                         "id": node.id,
@@ -69,12 +101,25 @@ impl JsonCompactFormatter {
                         "lang": node.language
                     });
 
+                    if node.end_line > node.line_number {
+                        node_obj["end_line"] = json!(node.end_line);
+                    }
+
                     if let Some(ref sig) = node.signature {
                         node_obj["sig"] = json!(sig);
                     }
                     if let Some(ref vis) = node.visibility {
                         node_obj["vis"] = json!(vis);
                     }
+                    if node.is_async {
+                        node_obj["async"] = json!(true);
+                    }
+                    if node.is_test {
+                        node_obj["test"] = json!(true);
+                    }
+                    if node.complexity > 0 {
+                        node_obj["complexity"] = json!(node.complexity);
+                    }
 
                     node_obj
                 };
@@ -92,14 +137,21 @@ impl JsonCompactFormatter {
             if let (Some(&src_id), Some(&tgt_id)) =
                 (node_id_map.get(&source_idx), node_id_map.get(&target_idx))
             {
+                let edge = edge_ref.weight();
                 let edge_json = if self.minimal {
-                    json!([src_id, tgt_id, self.edge_code(edge_ref.weight().edge_type)])
+                    // `[src, tgt, type]`, plus the source line when known
+                    let mut edge_json = json!([src_id, tgt_id, self.edge_code(edge.edge_type)]);
+                    if let Some(line) = edge.line {
+                        edge_json.as_array_mut().unwrap().push(json!(line));
+                    }
+                    edge_json
                 } else {
                     json!({
                         "src": src_id,
                         "tgt": tgt_id,
-                        "type": self.edge_code(edge_ref.weight().edge_type),
-                        "ctx": edge_ref.weight().context
+                        "type": self.edge_code(edge.edge_type),
+                        "ctx": edge.context,
+                        "line": edge.line
                     })
                 };
                 edges.push(edge_json);
@@ -115,7 +167,7 @@ impl JsonCompactFormatter {
             file_vec
         };
 
-        let output = json!({
+        let mut output = json!({
             "meta": {
                 "nodes": graph.node_count(),
                 "edges": graph.edge_count(),
@@ -125,10 +177,101 @@ impl JsonCompactFormatter {
             "nodes": nodes,
             "edges": edges
         });
+        if self.file_metrics {
+            output["file_metrics"] = json!(self.file_metrics(graph, &files));
+        }
 
         Ok(serde_json::to_string(&output)?)
     }
 
+    /// One metrics object per entry of `files`, in the same order
+    fn file_metrics(&self, graph: &DependencyGraph, files: &[String]) -> Vec<serde_json::Value> {
+        let call_depths = Self::call_depths(graph);
+
+        #[derive(Default)]
+        struct FileMetrics {
+            nodes: BTreeMap<&'static str, usize>,
+            edges_out: usize,
+            max_call_depth: usize,
+            languages: BTreeSet<String>,
+        }
+        let mut metrics: HashMap<String, FileMetrics> = HashMap::new();
+        for idx in graph.node_indices() {
+            let node = &graph[idx];
+            let entry = metrics
+                .entry(node.file_path.to_string_lossy().to_string())
+                .or_default();
+            *entry
+                .nodes
+                .entry(self.type_name(node.node_type))
+                .or_insert(0) += 1;
+            entry.edges_out += graph
+                .edges(idx)
+                .filter(|edge| edge.weight().edge_type != EdgeType::Contains)
+                .count();
+            if node.node_type == NodeType::Function {
+                entry.max_call_depth = entry.max_call_depth.max(call_depths[&idx]);
+            }
+            entry.languages.insert(node.language.clone());
+        }
+
+        files
+            .iter()
+            .map(|path| {
+                let file = metrics.remove(path).unwrap_or_default();
+                json!({
+                    "nodes": file.nodes,
+                    "edges_out": file.edges_out,
+                    "max_call_depth": file.max_call_depth,
+                    "languages": file.languages
+                })
+            })
+            .collect()
+    }
+
+    /// Length of the longest call chain starting at each node, counting the node itself.
+    /// Functions calling each other in a cycle count as one step.
+    fn call_depths(graph: &DependencyGraph) -> HashMap<NodeIndex, usize> {
+        // Mirror the graph with only call edges; node indices are preserved
+        let mut calls: Graph<(), (), petgraph::Directed> =
+            Graph::with_capacity(graph.node_count(), 0);
+        for _ in graph.node_indices() {
+            calls.add_node(());
+        }
+        for edge_ref in graph.edge_references() {
+            if edge_ref.weight().edge_type == EdgeType::Call {
+                calls.update_edge(edge_ref.source(), edge_ref.target(), ());
+            }
+        }
+
+        // Tarjan yields components callees first, so every callee's depth is known
+        // by the time its callers are reached
+        let mut depths = HashMap::with_capacity(graph.node_count());
+        for component in tarjan_scc(&calls) {
+            let deepest_callee = component
+                .iter()
+                .flat_map(|&member| calls.neighbors(member))
+                .filter_map(|callee| depths.get(&callee).copied())
+                .max()
+                .unwrap_or(0);
+            for member in component {
+                depths.insert(member, deepest_callee + 1);
+            }
+        }
+        depths
+    }
+
+    fn type_name(&self, node_type: NodeType) -> &'static str {
+        match node_type {
+            NodeType::Module => "module",
+            NodeType::Class => "class",
+            NodeType::Function => "function",
+            NodeType::Variable => "variable",
+            NodeType::Interface => "interface",
+            NodeType::Enum => "enum",
+        }
+    }
+
     fn type_code(&self, node_type: NodeType) -> u8 {
         match node_type {
             NodeType::Module => 0,
@@ -148,6 +291,9 @@ impl JsonCompactFormatter {
             EdgeType::Implements => 3,
             EdgeType::Uses => 4,
             EdgeType::Contains => 5,
+            EdgeType::Overrides => 6,
+            EdgeType::Throws => 7,
+            EdgeType::Composes => 8,
         }
     }
 }
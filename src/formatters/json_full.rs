@@ -0,0 +1,34 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+use crate::core::{DependencyGraph, DependencyGraphExt};
+
+/// Lossless JSON dump of the whole graph, for tooling rather than LLM prompts.
+///
+/// The output loads back with `DependencyGraph::from_json`.
+pub struct JsonFullFormatter;
+
+impl JsonFullFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    #[allow(dead_code)]
+    pub fn format_to_file(&self, graph: &DependencyGraph, output_path: &Path) -> Result<()> {
+        let content = self.format_to_string(graph)?;
+        fs::write(output_path, content)?;
+        Ok(())
+    }
+
+    /// Serialize every node and edge field.
+    pub fn format_to_string(&self, graph: &DependencyGraph) -> Result<String> {
+        graph.to_json()
+    }
+}
+
+impl Default for JsonFullFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -57,8 +57,15 @@ pub trait LlmLanguageAdapter {
     }
 
     /// Additional language-specific function annotations (merged with generic ones)
-    fn language_specific_annotations(&self, _node: &Node) -> Vec<String> {
-        Vec::new()
+    ///
+    /// By default each node is handed to the adapter for its own language, so a
+    /// repository mixing languages keeps their tags whichever adapter was picked
+    fn language_specific_annotations(&self, node: &Node) -> Vec<String> {
+        match node.language.as_str() {
+            "kotlin" => KotlinLanguageAdapter.language_specific_annotations(node),
+            "php" => PhpLanguageAdapter.language_specific_annotations(node),
+            _ => Vec::new(),
+        }
     }
 
     /// Optional display override for a called target (e.g., Python __init__ -> ClassName())
@@ -97,6 +104,64 @@ impl DefaultLanguageAdapter {
 
 impl LlmLanguageAdapter for DefaultLanguageAdapter {}
 
+/// Kotlin adapter that flags `data class` declarations
+#[derive(Default)]
+pub struct KotlinLanguageAdapter;
+
+impl KotlinLanguageAdapter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LlmLanguageAdapter for KotlinLanguageAdapter {
+    fn name(&self) -> &'static str {
+        "kotlin"
+    }
+
+    fn language_specific_annotations(&self, node: &Node) -> Vec<String> {
+        let mut ann = Vec::new();
+        if node.node_type == NodeType::Class
+            && node
+                .signature
+                .as_deref()
+                .is_some_and(|sig| sig.starts_with("data class"))
+        {
+            ann.push("DATA".to_string());
+        }
+        ann
+    }
+}
+
+/// PHP adapter that flags traits, which the parser models as classes
+#[derive(Default)]
+pub struct PhpLanguageAdapter;
+
+impl PhpLanguageAdapter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LlmLanguageAdapter for PhpLanguageAdapter {
+    fn name(&self) -> &'static str {
+        "php"
+    }
+
+    fn language_specific_annotations(&self, node: &Node) -> Vec<String> {
+        let mut ann = Vec::new();
+        if node.node_type == NodeType::Class
+            && node
+                .signature
+                .as_deref()
+                .is_some_and(|sig| sig.starts_with("trait "))
+        {
+            ann.push("TRAIT".to_string());
+        }
+        ann
+    }
+}
+
 /// Python-specific adapter for richer intra-file and instantiation hints
 pub struct PythonLanguageAdapter;
 
@@ -148,6 +213,14 @@ impl LlmLanguageAdapter for PythonLanguageAdapter {
             1
         } else if call_name.starts_with("fit") || call_name.starts_with("predict") {
             2
+        } else if matches!(call_name, "print" | "pprint")
+            || ["logging", "logger", "log"].iter().any(|module| {
+                call_name.starts_with(&format!("{}.", module))
+                    || call_name.starts_with(&format!("{}::", module))
+            })
+        {
+            // Output and logging carry the least signal; list them after everything else
+            9
         } else {
             LlmLanguageAdapter::get_call_priority(&DefaultLanguageAdapter, call_name)
         }
@@ -158,12 +231,13 @@ impl LlmLanguageAdapter for PythonLanguageAdapter {
         if node.name == "__init__" {
             ann.push("CTOR".to_string());
         }
-        if node.name.starts_with("test_") {
-            ann.push("TEST".to_string());
-        }
         if node.name.starts_with("__") && node.name.ends_with("__") {
             ann.push("DUNDER".to_string());
         }
+        ann.extend(LlmLanguageAdapter::language_specific_annotations(
+            &DefaultLanguageAdapter,
+            node,
+        ));
         ann
     }
 
@@ -7,23 +7,80 @@
 //!
 //! - **DIRECTORY_TREE**: Hierarchical file organization with semantic prefixes
 //! - **ARCHITECTURAL_CLUSTERS**: Code grouped by functional purpose
-//! - **DEPENDENCY_PATTERNS**: Cross-module relationship analysis
+//! - **DEPENDENCY_PATTERNS**: Cross-module relationship analysis, per-cluster cohesion and
+//!   the most coupled cluster pairs
+//! - **LAYERS**: Files by runtime layer along Call edges (opt-in via `with_layered`)
+//! - **CYCLES**: Call/Uses cycles (Verbose only, omitted when the graph is acyclic)
+//! - **POSSIBLY_UNUSED**: Unreferenced functions/classes (Verbose with `with_unused`)
 //!
 //! ## Behavioral Notation
 //!
-//! - `function()[ENTRY]` - Public API entry point
-//! - `function()[HOT]` - Performance-critical function
+//! - `function()[ENTRY]` - Public API entry point, a `with_entry_patterns` match, or an
+//!   HTTP handler (`@app.route`, `@GetMapping`, ...)
+//! - `function()[HOT]` - Called from more places than most functions (see `with_hot_threshold`)
+//! - `function()[ASYNC]` - Declared `async`
+//! - `function()[TEST]` - Test case (`#[test]`, `@Test`, pytest `test_*`, JS `it()`/`test()`)
+//! - `Widget()[CTOR]`, `~Widget()[DTOR]` - C++ constructor and destructor
+//! - `function()[RECURSIVE]` - Calls itself, directly or through other functions
+//! - `macro_rules! name[MACRO]` - Rust macro definition
+//! - `method()[OVERRIDE]` - Redefines a parent class method
+//! - `function()[CX:n]` - n branch points, above the complexity threshold
+//! - `function()[DEAD?]` - Nothing calls or uses it (opt-in via `with_unused`)
+//! - `Type[STRUCT]` / `Type[ALIAS]` - Go/C# struct, Go type alias
+//! - `Type[RECORD]` - C# record
+//! - `Type[TYPE]` - TypeScript type alias
+//! - `function()!{IOException}` - Exception types thrown or declared (Verbose)
 //! - `function()->{calls}` - Immediate function calls
+//! - `function()->{callee×N}` - Callee invoked from N call sites
+//! - `function()// "summary"` - First docstring line (opt-in via `with_docstrings`)
+//! - `x{get,set}` - Getter and setter of property `x` (opt-in via `with_collapse_accessors`)
 
 use anyhow::Result;
-use petgraph::graph::NodeIndex;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use petgraph::algo::{condensation, toposort};
+use petgraph::graph::{Graph, NodeIndex};
 use petgraph::visit::EdgeRef;
+use petgraph::Direction;
 use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use super::call_filter::CallFilter;
 use super::llm_language::{DefaultLanguageAdapter, LlmLanguageAdapter};
-use crate::core::{DependencyGraph, Edge, Node, NodeType};
+use crate::core::{DependencyGraph, DependencyGraphExt, Edge, EdgeType, Lifecycle, Node, NodeType};
+
+/// Cluster of files no `with_cluster_patterns` glob matches
+const UNCLASSIFIED_CLUSTER: &str = "UNCLASSIFIED";
+
+/// Cluster pairs listed under `MOST_COUPLED_PAIRS`, most entangled first
+const COUPLED_PAIRS_SHOWN: usize = 5;
+
+/// Abbreviations `compact_signature` applies, first match wins at each position. A
+/// pattern only matches whole tokens: `String` becomes `str`, `MyStringThing` stays.
+const SIGNATURE_ABBREVIATIONS: &[(&str, &str)] = &[
+    // The receiver says nothing the entity's placement does not
+    ("&mut self, ", ""),
+    ("&self, ", ""),
+    ("&self", ""),
+    // Graph-building parameters and return types
+    ("&mut Vec<Node>", "nodes"),
+    ("&mut Vec<Edge>", "edges"),
+    ("Vec<Node>", "nodes"),
+    ("Vec<Edge>", "edges"),
+    // Borrowed and owned forms of common types
+    ("&Path", "Path"),
+    ("&str", "str"),
+    ("&[u8]", "bytes"),
+    ("&TSNode", "Node"),
+    ("PathBuf", "Path"),
+    ("String", "str"),
+    ("usize", "int"),
+    // Wrappers become sigils: `Option<T>` → `?T>`, `Result<T>` → `!T>`
+    ("Option<", "?"),
+    ("Result<", "!"),
+    // The unit type; `run()` follows an identifier, so it is a call and stays
+    ("()", "void"),
+];
 
 /// Output verbosity level for LLM-optimized format.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -45,6 +102,8 @@ pub struct LLMOptimizedFormatter {
     use_hierarchical: bool,
     /// Whether to compress identifiers (true for token efficiency)
     compress_ids: bool,
+    /// Whether to abbreviate common types in signatures (`String` → `str`, `Option<` → `?`)
+    compress_signatures: bool,
     /// Whether to use semantic clustering (groups by architectural domains)
     use_semantic_clustering: bool,
     /// Whether to use advanced DAG compression (pattern-based edge compression)
@@ -53,6 +112,38 @@ pub struct LLMOptimizedFormatter {
     language_adapter: Box<dyn LlmLanguageAdapter>,
     /// Output verbosity level
     verbosity: OutputVerbosity,
+    /// How many levels of calls to expand under each function (1 = immediate calls only)
+    max_call_depth: usize,
+    /// Append a one-line docstring summary to functions and types
+    include_docstrings: bool,
+    /// Tag functions and classes nothing references as `DEAD?`
+    flag_unused: bool,
+    /// Functions with more branch points than this are tagged `CX:n`
+    complexity_threshold: usize,
+    /// Function names always tagged `ENTRY`, e.g. `handle_*`
+    entry_patterns: Option<GlobSet>,
+    /// Call name patterns and the priority calls matching them sort by in `→{...}`,
+    /// overriding the language adapter's; the first matching pattern wins
+    call_priorities: Option<(GlobSet, Vec<u8>)>,
+    /// Functions with more callers than this are tagged `HOT`; `None` derives it from the graph
+    hot_threshold: Option<usize>,
+    /// Clusters with fewer nodes than this are folded into a trailing `MISC` cluster
+    min_cluster_size: usize,
+    /// Path globs and the cluster name of files matching them, replacing the language
+    /// adapter's clustering; the first matching pattern wins
+    cluster_patterns: Option<(GlobSet, Vec<String>)>,
+    /// `(path pattern, prefix)` pairs replacing the built-in semantic prefix taxonomy
+    semantic_prefixes: Option<Vec<(String, String)>>,
+    /// Collapse single-child directory chains in the tree, e.g. `com/example/app/`
+    flatten_namespaces: bool,
+    /// Prefix methods with their enclosing types, e.g. `User.process`
+    qualified_names: bool,
+    /// Show getter/setter pairs as one `x{get,set}` entity
+    collapse_accessors: bool,
+    /// Add a `## LAYERS` section grouping files by call depth from the entry layer
+    layered: bool,
+    /// Calls left out of the output, e.g. one-off utility calls
+    call_filter: CallFilter,
 }
 
 impl LLMOptimizedFormatter {
@@ -62,10 +153,26 @@ impl LLMOptimizedFormatter {
             include_metadata: true,
             use_hierarchical: true,
             compress_ids: true,
+            compress_signatures: true,
             use_semantic_clustering: true,
             use_advanced_dag: true,
             language_adapter: Box::new(DefaultLanguageAdapter::new()),
             verbosity: OutputVerbosity::default(),
+            max_call_depth: 1,
+            include_docstrings: false,
+            flag_unused: false,
+            complexity_threshold: 10,
+            entry_patterns: None,
+            call_priorities: None,
+            hot_threshold: None,
+            min_cluster_size: 1,
+            cluster_patterns: None,
+            semantic_prefixes: None,
+            flatten_namespaces: false,
+            qualified_names: false,
+            collapse_accessors: false,
+            layered: false,
+            call_filter: CallFilter::default(),
         }
     }
 
@@ -75,6 +182,143 @@ impl LLMOptimizedFormatter {
         self
     }
 
+    /// Expand nested calls up to `depth` levels, e.g. `a→{b→{c}}` at depth 2. Calls past the
+    /// limit are summarized as `+N more`. Values below 1 are treated as 1.
+    pub fn with_max_call_depth(mut self, depth: usize) -> Self {
+        self.max_call_depth = depth.max(1);
+        self
+    }
+
+    /// Append the first docstring line to each entity, e.g. `load()// "Read the config"`.
+    /// Summaries are cut to 60 characters.
+    pub fn with_docstrings(mut self, include: bool) -> Self {
+        self.include_docstrings = include;
+        self
+    }
+
+    /// Tag unreferenced functions and classes `[DEAD?]`; Verbose output also lists them
+    /// under `## POSSIBLY_UNUSED`. Off by default since dynamic dispatch and reflection
+    /// make this a heuristic.
+    pub fn with_unused(mut self, flag: bool) -> Self {
+        self.flag_unused = flag;
+        self
+    }
+
+    /// Tag functions with more than `threshold` branch points `[CX:n]` (default 10)
+    pub fn with_complexity_threshold(mut self, threshold: usize) -> Self {
+        self.complexity_threshold = threshold;
+        self
+    }
+
+    /// Tag functions called from more than `threshold` distinct callers `[HOT]`. By default the
+    /// threshold puts the top decile of the graph's functions by caller count above it, and
+    /// is never below 2.
+    pub fn with_hot_threshold(mut self, threshold: usize) -> Self {
+        self.hot_threshold = Some(threshold);
+        self
+    }
+
+    /// List clusters with fewer than `size` nodes under one `MISC` cluster at the end of
+    /// `ARCHITECTURAL_CLUSTERS` instead of individually (default 1, which folds nothing)
+    pub fn with_min_cluster_size(mut self, size: usize) -> Self {
+        self.min_cluster_size = size;
+        self
+    }
+
+    /// Replace the built-in directory prefixes (`services/` → `S`, `widgets/` → `W`, ...) with
+    /// `(pattern, prefix)` pairs such as `("handlers/", "H")`. A file takes the prefix of the
+    /// first pattern its path contains, else `U`.
+    pub fn with_semantic_prefixes(mut self, prefixes: Vec<(String, String)>) -> Self {
+        self.semantic_prefixes = Some(prefixes);
+        self
+    }
+
+    /// Print directory chains without files of their own on one line in `DIRECTORY_TREE`,
+    /// so Java-style `com/ → example/ → app/` nesting becomes `com/example/app/`
+    pub fn with_flatten_namespaces(mut self, flatten: bool) -> Self {
+        self.flatten_namespaces = flatten;
+        self
+    }
+
+    /// Show methods and the calls to them as `User.process` rather than `process`, so
+    /// same-named methods of different classes can be told apart. The owner comes from
+    /// `Contains` edges, or from a qualified node id such as `lib.rs:method:User::process:4`.
+    pub fn with_qualified_names(mut self, qualified: bool) -> Self {
+        self.qualified_names = qualified;
+        self
+    }
+
+    /// Merge a property's accessors into a single entity such as `total{get,set}`. Recognizes
+    /// `get x()`/`set x()` in TypeScript and JavaScript, `getX`/`isX`/`setX` in Java and
+    /// Kotlin, and `get_X`/`set_X`/`init_X` in C#. A lone getter is left as is.
+    pub fn with_collapse_accessors(mut self, collapse: bool) -> Self {
+        self.collapse_accessors = collapse;
+        self
+    }
+
+    /// Drop calls to these functions (`len`, `String::from`, `println!`) from every section.
+    /// Dropped calls no longer count as callers, for `HOT` and `DEAD?` tags alike.
+    pub fn with_hide_calls(mut self, names: Vec<String>) -> Self {
+        self.call_filter.hide(&names);
+        self
+    }
+
+    /// Drop calls made from fewer than `min_weight` call sites
+    pub fn with_min_edge_weight(mut self, min_weight: usize) -> Self {
+        self.call_filter.set_min_weight(min_weight);
+        self
+    }
+
+    /// Tag functions whose name matches any of these globs `[ENTRY]`, even when something in
+    /// the same file calls them. Adds to the built-in heuristics rather than replacing them.
+    pub fn with_entry_patterns(mut self, patterns: Vec<String>) -> Result<Self> {
+        if patterns.is_empty() {
+            self.entry_patterns = None;
+            return Ok(self);
+        }
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &patterns {
+            builder.add(Glob::new(pattern)?);
+        }
+        self.entry_patterns = Some(builder.build()?);
+        Ok(self)
+    }
+
+    /// Group files matching each path glob into the named cluster, e.g.
+    /// `("src/billing/**", "BILLING")`, instead of by the language adapter's directory
+    /// heuristics. Globs match any trailing part of a path; files matching none are listed
+    /// under `UNCLASSIFIED`.
+    pub fn with_cluster_patterns(mut self, clusters: Vec<(String, String)>) -> Result<Self> {
+        if clusters.is_empty() {
+            self.cluster_patterns = None;
+            return Ok(self);
+        }
+        let mut builder = GlobSetBuilder::new();
+        for (pattern, _) in &clusters {
+            builder.add(Glob::new(pattern)?);
+        }
+        let names = clusters.into_iter().map(|(_, name)| name).collect();
+        self.cluster_patterns = Some((builder.build()?, names));
+        Ok(self)
+    }
+
+    /// Sort calls matching each glob by its priority in `→{...}`, lower first, e.g.
+    /// `("log_*", 9)` to list logging last or `("*_service", 0)` to list services first.
+    /// Calls matching no pattern keep the language adapter's priority (1-5).
+    pub fn with_call_priorities(mut self, priorities: Vec<(String, u8)>) -> Result<Self> {
+        if priorities.is_empty() {
+            self.call_priorities = None;
+            return Ok(self);
+        }
+        let mut builder = GlobSetBuilder::new();
+        for (pattern, _) in &priorities {
+            builder.add(Glob::new(pattern)?);
+        }
+        let values = priorities.iter().map(|(_, priority)| *priority).collect();
+        self.call_priorities = Some((builder.build()?, values));
+        Ok(self)
+    }
+
     #[allow(dead_code)]
     pub fn with_metadata(mut self, include: bool) -> Self {
         self.include_metadata = include;
@@ -91,6 +335,13 @@ impl LLMOptimizedFormatter {
         self
     }
 
+    /// Abbreviate signatures with [`SIGNATURE_ABBREVIATIONS`] (default); when off they are
+    /// shown as written, on one line
+    pub fn with_signature_compression(mut self, compress: bool) -> Self {
+        self.compress_signatures = compress;
+        self
+    }
+
     #[allow(dead_code)]
     pub fn with_semantic_clustering(mut self, cluster: bool) -> Self {
         self.use_semantic_clustering = cluster;
@@ -103,6 +354,13 @@ impl LLMOptimizedFormatter {
         self
     }
 
+    /// List files by runtime layer in a `## LAYERS` section: `L0` holds the files no other
+    /// file calls, and every file sits one layer below its deepest caller
+    pub fn with_layered(mut self, layered: bool) -> Self {
+        self.layered = layered;
+        self
+    }
+
     /// Set a custom language adapter
     pub fn with_language_adapter(mut self, adapter: Box<dyn LlmLanguageAdapter>) -> Self {
         self.language_adapter = adapter;
@@ -115,13 +373,28 @@ impl LLMOptimizedFormatter {
         Self::new().with_language_adapter(adapter)
     }
 
+    /// Convenience: Kotlin-tuned formatter
+    pub fn for_kotlin() -> Self {
+        let adapter = Box::new(crate::formatters::KotlinLanguageAdapter::new());
+        Self::new().with_language_adapter(adapter)
+    }
+
+    /// Convenience: PHP-tuned formatter
+    pub fn for_php() -> Self {
+        let adapter = Box::new(crate::formatters::PhpLanguageAdapter::new());
+        Self::new().with_language_adapter(adapter)
+    }
+
+    #[allow(dead_code)]
     pub fn format_to_file(&self, graph: &DependencyGraph, output_path: &Path) -> Result<()> {
-        let formatted_content = self.format_graph(graph)?;
+        let formatted_content = self.format_to_string(graph)?;
         fs::write(output_path, formatted_content)?;
         Ok(())
     }
 
-    fn format_graph(&self, graph: &DependencyGraph) -> Result<String> {
+    /// Render the LLM-optimized view as a string. Pure: no filesystem access.
+    pub fn format_to_string(&self, graph: &DependencyGraph) -> Result<String> {
+        let graph = &*self.call_filter.apply(graph);
         let mut output = String::with_capacity(8192);
 
         // Interpretation key only for Standard and Verbose modes
@@ -157,13 +430,27 @@ impl LLMOptimizedFormatter {
         let file_map = self.build_enhanced_file_map(&directory_tree);
 
         if self.use_semantic_clustering && !semantic_clusters.is_empty() {
-            self.format_with_clusters(&mut output, &semantic_clusters, &directory_tree, graph)?;
+            let hot_threshold = self.hot_threshold(graph);
+            let recursive = Self::recursive_functions(graph);
+            self.format_with_clusters(
+                &mut output,
+                &semantic_clusters,
+                &directory_tree,
+                graph,
+                hot_threshold,
+                &recursive,
+            )?;
         } else if self.use_hierarchical {
             self.format_hierarchical(&mut output, &by_type, &file_map, graph)?;
         } else {
             self.format_flat(&mut output, &by_type, &file_map, graph)?;
         }
 
+        if self.layered {
+            output.push('\n');
+            self.format_layers(&mut output, graph, &directory_tree.common_prefix);
+        }
+
         // Dependency patterns only for Verbose mode
         if self.verbosity == OutputVerbosity::Verbose {
             if self.use_advanced_dag {
@@ -172,11 +459,116 @@ impl LLMOptimizedFormatter {
             } else {
                 self.format_dependency_summary(&mut output, graph);
             }
+            self.format_cycles(&mut output, graph);
+            if self.flag_unused {
+                self.format_possibly_unused(&mut output, graph);
+            }
         }
 
         Ok(output)
     }
 
+    /// List Call/Uses cycles as `a→b→c→a`
+    fn format_cycles(&self, output: &mut String, graph: &DependencyGraph) {
+        let cycles = graph.find_cycles(&[EdgeType::Call, EdgeType::Uses]);
+        if cycles.is_empty() {
+            return;
+        }
+
+        output.push_str("## CYCLES\n");
+        for cycle in cycles {
+            let mut names: Vec<&str> = cycle.iter().map(|&idx| graph[idx].name.as_str()).collect();
+            names.push(names[0]);
+            output.push_str(&names.join("→"));
+            output.push('\n');
+        }
+        output.push('\n');
+    }
+
+    /// List files by layer as `L1: api/routes.py,api/users.py`, paths relative to `root`.
+    ///
+    /// Files calling each other in a cycle are condensed into one unit and share a layer.
+    /// Files that neither call nor are called from another file are left out: they have
+    /// no place in the runtime layering.
+    fn format_layers(&self, output: &mut String, graph: &DependencyGraph, root: &str) {
+        let mut file_graph: Graph<&Path, ()> = Graph::new();
+        let mut file_indices: HashMap<&Path, NodeIndex> = HashMap::new();
+        for edge_ref in graph.edge_references() {
+            if edge_ref.weight().edge_type != EdgeType::Call {
+                continue;
+            }
+            let (source, target) = (&graph[edge_ref.source()], &graph[edge_ref.target()]);
+            let external = |node: &Node| node.visibility.as_deref() == Some("external");
+            if source.file_path == target.file_path || external(source) || external(target) {
+                continue;
+            }
+            let [source, target] = [source, target].map(|node| {
+                *file_indices
+                    .entry(node.file_path.as_path())
+                    .or_insert_with(|| file_graph.add_node(node.file_path.as_path()))
+            });
+            file_graph.update_edge(source, target, ());
+        }
+        if file_graph.node_count() == 0 {
+            return;
+        }
+
+        // Longest path from the entry layer, over the acyclic graph of call cycles
+        let units = condensation(file_graph, true);
+        let order = toposort(&units, None).unwrap_or_default();
+        let mut depth = vec![0usize; units.node_count()];
+        for &unit in &order {
+            for next in units.neighbors(unit) {
+                depth[next.index()] = depth[next.index()].max(depth[unit.index()] + 1);
+            }
+        }
+
+        let mut layers: BTreeMap<usize, Vec<PathBuf>> = BTreeMap::new();
+        for unit in units.node_indices() {
+            layers
+                .entry(depth[unit.index()])
+                .or_default()
+                .extend(units[unit].iter().map(|path| path.to_path_buf()));
+        }
+
+        output.push_str("## LAYERS\n");
+        for (depth, mut files) in layers {
+            files.sort();
+            let names: Vec<String> = files
+                .iter()
+                .map(|path| {
+                    let path = path.to_string_lossy();
+                    path.strip_prefix(root)
+                        .unwrap_or(&path)
+                        .trim_start_matches(['/', '\\'])
+                        .to_string()
+                })
+                .collect();
+            output.push_str(&format!("L{}: {}\n", depth, names.join(",")));
+        }
+        output.push('\n');
+    }
+
+    /// List unreferenced functions and classes as `name (file:line)`
+    fn format_possibly_unused(&self, output: &mut String, graph: &DependencyGraph) {
+        let unused = graph.unreferenced_nodes();
+        if unused.is_empty() {
+            return;
+        }
+
+        output.push_str("## POSSIBLY_UNUSED\n");
+        for idx in unused {
+            let node = &graph[idx];
+            output.push_str(&format!(
+                "{} ({}:{})\n",
+                node.name,
+                self.extract_filename(&node.file_path.to_string_lossy()),
+                node.line_number
+            ));
+        }
+        output.push('\n');
+    }
+
     fn format_hierarchical(
         &self,
         output: &mut String,
@@ -397,6 +789,55 @@ impl LLMOptimizedFormatter {
         }
     }
 
+    /// Distinct functions calling `idx`
+    fn caller_count(graph: &DependencyGraph, idx: NodeIndex) -> usize {
+        graph
+            .edges_directed(idx, Direction::Incoming)
+            .filter(|e| e.weight().edge_type == EdgeType::Call)
+            .count()
+    }
+
+    /// Caller count above which a function is `HOT`: the configured threshold, else the
+    /// highest count outside the top decile of functions, at least 2 so a pair of callers
+    /// is never enough
+    fn hot_threshold(&self, graph: &DependencyGraph) -> usize {
+        if let Some(threshold) = self.hot_threshold {
+            return threshold;
+        }
+        let mut counts: Vec<usize> = graph
+            .node_indices()
+            .filter(|&idx| graph[idx].node_type == NodeType::Function)
+            .map(|idx| Self::caller_count(graph, idx))
+            .collect();
+        counts.sort_unstable();
+        let top_decile = counts.len().div_ceil(10);
+        let below_top = counts
+            .len()
+            .checked_sub(top_decile + 1)
+            .map_or(0, |idx| counts[idx]);
+        below_top.max(2)
+    }
+
+    /// STRUCT/RECORD/ALIAS/TYPE for Go, C# and TypeScript types, which share
+    /// `NodeType::Class` with classes
+    fn type_annotations(node: &Node) -> Vec<String> {
+        let signature = node.signature.as_deref().unwrap_or_default();
+        if signature.starts_with("struct ") {
+            vec!["STRUCT".to_string()]
+        } else if signature.starts_with("record ") {
+            vec!["RECORD".to_string()]
+        } else if signature.starts_with("type ") && signature.contains(" = ") {
+            // TypeScript aliases usually define a type shape rather than rename another type
+            if node.language == "typescript" {
+                vec!["TYPE".to_string()]
+            } else {
+                vec!["ALIAS".to_string()]
+            }
+        } else {
+            Vec::new()
+        }
+    }
+
     fn type_symbol(&self, node_type: NodeType) -> &'static str {
         match node_type {
             NodeType::Module => "MOD",
@@ -440,7 +881,11 @@ impl LLMOptimizedFormatter {
         all_paths.sort();
         all_paths.dedup();
 
-        DirectoryTree::build(all_paths)
+        let mut tree = DirectoryTree::build(all_paths, self.semantic_prefixes.as_deref());
+        if self.flatten_namespaces {
+            tree.flatten();
+        }
+        tree
     }
 
     /// Group nodes into semantic architectural clusters
@@ -452,7 +897,7 @@ impl LLMOptimizedFormatter {
 
         for nodes in by_type.values() {
             for &(idx, node) in nodes {
-                let cluster_name = self.language_adapter.classify_node_cluster(node);
+                let cluster_name = self.classify_node_cluster(node);
                 clusters
                     .entry(cluster_name)
                     .or_insert_with(Vec::new)
@@ -463,32 +908,23 @@ impl LLMOptimizedFormatter {
         clusters
     }
 
-    /// Classify a node into an architectural cluster
-    #[allow(dead_code)]
+    /// Cluster of a node: the first `with_cluster_patterns` glob matching its path, or
+    /// `UNCLASSIFIED` when patterns are configured but none match; otherwise the language
+    /// adapter's path heuristics
     fn classify_node_cluster(&self, node: &Node) -> String {
-        let path = node.file_path.to_string_lossy();
-
-        if path.contains("/services/") {
-            "CORE_SERVICES".to_string()
-        } else if path.contains("/entities/") {
-            "DATA_ENTITIES".to_string()
-        } else if path.contains("/components/") {
-            "UI_COMPONENTS".to_string()
-        } else if path.contains("/widgets/dialogs/") {
-            "DIALOG_SYSTEM".to_string()
-        } else if path.contains("/widgets/ribbon/") {
-            "RIBBON_SYSTEM".to_string()
-        } else if path.contains("/widgets/buttons/") {
-            "BUTTON_SYSTEM".to_string()
-        } else if path.contains("/widgets/view_widgets/") {
-            "VIEW_SYSTEM".to_string()
-        } else if path.contains("/widgets/") {
-            "UI_WIDGETS".to_string()
-        } else if path.contains("/menus/") {
-            "MENU_SYSTEM".to_string()
-        } else {
-            "UTILITY_LAYER".to_string()
-        }
+        let Some((globs, names)) = &self.cluster_patterns else {
+            return self.language_adapter.classify_node_cluster(node);
+        };
+        // Patterns are relative to some project directory, so try every path suffix that
+        // starts at a component: `src/billing/**` matches `/home/me/app/src/billing/a.py`
+        let components: Vec<_> = node.file_path.components().collect();
+        (0..components.len())
+            .find_map(|start| {
+                let suffix: PathBuf = components[start..].iter().collect();
+                globs.matches(&suffix).into_iter().min()
+            })
+            .map(|index| names[index].clone())
+            .unwrap_or_else(|| UNCLASSIFIED_CLUSTER.to_string())
     }
 
     /// Build enhanced file map with semantic prefixes
@@ -524,6 +960,8 @@ impl LLMOptimizedFormatter {
         clusters: &HashMap<String, Vec<(NodeIndex, &Node)>>,
         directory_tree: &DirectoryTree,
         graph: &DependencyGraph,
+        hot_threshold: usize,
+        recursive: &std::collections::HashSet<NodeIndex>,
     ) -> Result<()> {
         // Directory tree header
         output.push_str("## DIRECTORY_TREE\n");
@@ -535,17 +973,28 @@ impl LLMOptimizedFormatter {
 
         let mut cluster_names: Vec<_> = clusters.keys().cloned().collect();
         cluster_names.sort();
+        let mut sections: Vec<(String, Vec<(NodeIndex, &Node)>)> = Vec::new();
+        let mut misc: Vec<(NodeIndex, &Node)> = Vec::new();
         for cluster_name in cluster_names {
-            let nodes = match clusters.get(&cluster_name) {
-                Some(v) => v,
-                None => continue,
-            };
+            let nodes = &clusters[&cluster_name];
+            if nodes.len() < self.min_cluster_size {
+                misc.extend(nodes.iter().copied());
+            } else {
+                sections.push((cluster_name, nodes.clone()));
+            }
+        }
+        if !misc.is_empty() {
+            sections.push(("MISC".to_string(), misc));
+        }
+
+        let call_depths = Self::call_depths(graph);
+        for (cluster_name, nodes) in &sections {
             if nodes.is_empty() {
                 continue;
             }
 
             // Calculate cluster metrics
-            let max_depth = self.calculate_max_call_depth(nodes, graph);
+            let max_depth = Self::calculate_max_call_depth(nodes, &call_depths);
             output.push_str(&format!("### {}\n", cluster_name));
             output.push_str(&format!(
                 "NODES:{} CALL_DEPTH:{}\n\n",
@@ -572,7 +1021,8 @@ impl LLMOptimizedFormatter {
                         .cmp(&nb.line_number)
                         .then_with(|| na.name.cmp(&nb.name))
                 });
-                let behavioral_entities = self.build_behavioral_entities(&file_nodes, graph);
+                let behavioral_entities =
+                    self.build_behavioral_entities(&file_nodes, graph, hot_threshold, recursive);
                 let entity_strings: Vec<String> = behavioral_entities
                     .iter()
                     .map(|entity| self.format_behavioral_entity(entity))
@@ -599,6 +1049,8 @@ impl LLMOptimizedFormatter {
         // Edge type analysis
         let mut edge_patterns: HashMap<String, usize> = HashMap::new();
         let mut cluster_edges: HashMap<(String, String), usize> = HashMap::new();
+        // (intra-cluster edges, edges touching the cluster) per cluster
+        let mut cluster_touch: BTreeMap<String, (usize, usize)> = BTreeMap::new();
 
         for edge_ref in graph.edge_references() {
             let edge_type = format!("{:?}", edge_ref.weight().edge_type);
@@ -609,9 +1061,15 @@ impl LLMOptimizedFormatter {
                 graph.node_weight(edge_ref.source()),
                 graph.node_weight(edge_ref.target()),
             ) {
-                let source_cluster = self.language_adapter.classify_node_cluster(source_node);
-                let target_cluster = self.language_adapter.classify_node_cluster(target_node);
-                if source_cluster != target_cluster {
+                let source_cluster = self.classify_node_cluster(source_node);
+                let target_cluster = self.classify_node_cluster(target_node);
+                if source_cluster == target_cluster {
+                    let counts = cluster_touch.entry(source_cluster).or_default();
+                    counts.0 += 1;
+                    counts.1 += 1;
+                } else {
+                    cluster_touch.entry(source_cluster.clone()).or_default().1 += 1;
+                    cluster_touch.entry(target_cluster.clone()).or_default().1 += 1;
                     *cluster_edges
                         .entry((source_cluster, target_cluster))
                         .or_insert(0) += 1;
@@ -628,9 +1086,41 @@ impl LLMOptimizedFormatter {
         output.push_str("\n### CROSS_CLUSTER_FLOW\n");
         let mut pairs: Vec<_> = cluster_edges.into_iter().collect();
         pairs.sort_by(|a, b| a.0.cmp(&b.0));
-        for ((source, target), count) in pairs {
+        for ((source, target), count) in &pairs {
             output.push_str(&format!("{}→{}: {}\n", source, target, count));
         }
+
+        // Share of each cluster's edges that stay inside it; low values mean a cluster
+        // mostly talks to others
+        output.push_str("\n### CLUSTER_COHESION\n");
+        for (cluster, (intra, touching)) in &cluster_touch {
+            output.push_str(&format!(
+                "{}: {:.2} ({}/{})\n",
+                cluster,
+                *intra as f64 / *touching as f64,
+                intra,
+                touching
+            ));
+        }
+
+        // Both directions summed, so A→B and B→A count towards the same pair
+        let mut coupling: HashMap<(String, String), usize> = HashMap::new();
+        for ((source, target), count) in pairs {
+            let key = if source <= target {
+                (source, target)
+            } else {
+                (target, source)
+            };
+            *coupling.entry(key).or_insert(0) += count;
+        }
+        if !coupling.is_empty() {
+            let mut coupled: Vec<_> = coupling.into_iter().collect();
+            coupled.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            output.push_str("\n### MOST_COUPLED_PAIRS\n");
+            for ((first, second), count) in coupled.into_iter().take(COUPLED_PAIRS_SHOWN) {
+                output.push_str(&format!("{}↔{}: {}\n", first, second, count));
+            }
+        }
         output.push('\n');
     }
 
@@ -659,12 +1149,17 @@ impl LLMOptimizedFormatter {
             "- **DIRECTORY_TREE** = Hierarchical file organization with semantic prefixes\n",
         );
         output.push_str("- **ARCHITECTURAL_CLUSTERS** = Code grouped by functional purpose\n");
-        output.push_str("- **DEPENDENCY_PATTERNS** = Cross-module relationship analysis\n\n");
+        output.push_str("- **DEPENDENCY_PATTERNS** = Cross-module relationship analysis\n");
+        if self.layered {
+            output.push_str("- **LAYERS** = Files by call depth, L0 = entry layer nothing calls\n");
+        }
+        output.push('\n');
 
         output.push_str("### BEHAVIORAL NOTATION\n");
         output.push_str("- **filename.rs→[...]** = File containing list of functions/entities\n");
         output.push_str("- **function()[ENTRY]** = Public API entry point, start analysis here\n");
-        output.push_str("- **function()[HOT]** = Performance-critical, optimization target\n");
+        output.push_str("- **function()[HOT]** = Heavily depended upon, many callers\n");
+        output.push_str("- **function()[RECURSIVE]** = On a call cycle, check termination\n");
         output.push_str("- **function()→{calls}** = Immediate function calls (execution flow)\n");
         output.push_str("- **module::function** = Cross-module dependency\n\n");
 
@@ -678,6 +1173,27 @@ impl LLMOptimizedFormatter {
         output.push_str("5. **Dependencies**: Cross-cluster flows show coupling patterns\n\n");
 
         output.push_str("### SEMANTIC PREFIXES\n");
+        if let Some(prefixes) = &self.semantic_prefixes {
+            for (pattern, prefix) in prefixes {
+                output.push_str(&format!("- **{}[N]** = {}\n", prefix, pattern));
+            }
+            output.push_str("- **U[N]** = Everything else\n\n");
+        } else {
+            Self::add_default_prefix_key(output);
+        }
+
+        output.push_str("### AI REASONING TASKS\n");
+        output.push_str("- **Code Understanding**: Follow [ENTRY]→{calls} chains\n");
+        output.push_str("- **Bug Hunting**: Trace execution flows through clusters\n");
+        output.push_str("- **Refactoring**: Analyze cross-cluster dependencies\n");
+        output.push_str("- **Performance**: Focus on [HOT] functions and call depths\n");
+        output.push_str("- **Architecture**: Understand cluster responsibilities\n\n");
+
+        output.push_str("---\n\n");
+    }
+
+    /// Legend for the built-in prefixes assigned by `DirectoryTree::build_semantic_prefixes`
+    fn add_default_prefix_key(output: &mut String) {
         output.push_str("- **S[N]** = Services (business logic)\n");
         output.push_str("- **E[N]** = Entities (data models)\n");
         output.push_str("- **C[N]** = Components (UI elements)\n");
@@ -689,35 +1205,15 @@ impl LLMOptimizedFormatter {
         output.push_str("- **T[N]** = Type widgets (specialized UI)\n");
         output.push_str("- **W[N]** = General widgets\n");
         output.push_str("- **U[N]** = Utilities (helpers)\n\n");
-
-        output.push_str("### AI REASONING TASKS\n");
-        output.push_str("- **Code Understanding**: Follow [ENTRY]→{calls} chains\n");
-        output.push_str("- **Bug Hunting**: Trace execution flows through clusters\n");
-        output.push_str("- **Refactoring**: Analyze cross-cluster dependencies\n");
-        output.push_str("- **Performance**: Focus on [HOT] functions and call depths\n");
-        output.push_str("- **Architecture**: Understand cluster responsibilities\n\n");
-
-        output.push_str("---\n\n");
     }
 
     /// Calculate maximum call depth in a cluster
-    fn calculate_max_call_depth(
-        &self,
-        nodes: &[(NodeIndex, &Node)],
-        graph: &DependencyGraph,
-    ) -> usize {
-        let mut max_depth = 0;
-
-        for &(node_idx, _) in nodes {
-            let depth = self.calculate_node_call_depth(
-                node_idx,
-                graph,
-                &mut std::collections::HashSet::new(),
-            );
-            max_depth = max_depth.max(depth);
-        }
-
-        max_depth
+    fn calculate_max_call_depth(nodes: &[(NodeIndex, &Node)], call_depths: &[usize]) -> usize {
+        nodes
+            .iter()
+            .map(|&(node_idx, _)| call_depths[node_idx.index()])
+            .max()
+            .unwrap_or(0)
     }
 
     /// Build behavioral entities (compact format with nested calls)
@@ -725,23 +1221,72 @@ impl LLMOptimizedFormatter {
         &self,
         file_nodes: &[(NodeIndex, &Node)],
         graph: &DependencyGraph,
+        hot_threshold: usize,
+        recursive: &std::collections::HashSet<NodeIndex>,
     ) -> Vec<BehavioralEntity> {
         let mut entities = Vec::new();
 
+        let (accessor_groups, accessor_members) = if self.collapse_accessors {
+            Self::accessor_groups(file_nodes, graph)
+        } else {
+            (Vec::new(), HashMap::new())
+        };
+        let mut collapsed: Vec<Option<BehavioralEntity>> =
+            accessor_groups.iter().map(|_| None).collect();
+
         for &(node_idx, node) in file_nodes {
             if matches!(node.node_type, crate::core::NodeType::Function) {
                 let nested_calls = self.extract_immediate_calls(node_idx, graph, file_nodes);
-                let annotations = self.get_compact_annotations(node, graph, file_nodes);
+                let annotations =
+                    self.get_compact_annotations(node, graph, file_nodes, hot_threshold, recursive);
+
+                let (name, signature) = match self.owner_name(node_idx, graph) {
+                    Some(owner) => (
+                        format!("{}.{}", owner, node.name),
+                        node.signature.as_deref().map(|signature| {
+                            Self::qualify_signature(signature, &owner, &node.name)
+                        }),
+                    ),
+                    None => (node.name.clone(), node.signature.clone()),
+                };
 
-                entities.push(BehavioralEntity {
-                    name: node.name.clone(),
-                    signature: node.signature.clone(),
+                let entity = BehavioralEntity {
+                    name,
+                    signature,
                     annotations,
+                    thrown: self.thrown_types(node_idx, graph),
                     nested_calls,
-                });
+                    doc_summary: self.doc_summary(node),
+                };
+                match accessor_members.get(&node_idx) {
+                    Some(&group) => {
+                        Self::merge_accessor(&mut collapsed[group], entity, &accessor_groups[group])
+                    }
+                    None => entities.push(entity),
+                }
+            } else if matches!(node.node_type, crate::core::NodeType::Class) {
+                // Types only surface when there is something to say about them
+                let mut annotations = Self::type_annotations(node);
+                annotations.extend(self.language_adapter.language_specific_annotations(node));
+                if self.flag_unused && graph.is_unreferenced(node_idx) {
+                    annotations.push("DEAD?".to_string());
+                }
+                let doc_summary = self.doc_summary(node);
+                if !annotations.is_empty() || doc_summary.is_some() {
+                    entities.push(BehavioralEntity {
+                        name: node.name.clone(),
+                        signature: Some(node.name.clone()),
+                        annotations,
+                        thrown: Vec::new(),
+                        nested_calls: Vec::new(),
+                        doc_summary,
+                    });
+                }
             }
         }
 
+        entities.extend(collapsed.into_iter().flatten());
+
         // Sort by importance (entry points first, then by call complexity)
         entities.sort_by(|a, b| {
             let a_is_entry = a.annotations.contains(&"ENTRY".to_string());
@@ -757,66 +1302,201 @@ impl LLMOptimizedFormatter {
         entities
     }
 
-    /// Extract immediate function calls (depth 1 only for compactness)
+    /// Accessors of the same property on the same owner, keeping only groups with more
+    /// than one kind, and the group each of their functions belongs to
+    fn accessor_groups(
+        file_nodes: &[(NodeIndex, &Node)],
+        graph: &DependencyGraph,
+    ) -> (Vec<AccessorGroup>, HashMap<NodeIndex, usize>) {
+        const KIND_ORDER: [&str; 3] = ["get", "set", "init"];
+
+        let mut groups: Vec<AccessorGroup> = Vec::new();
+        let mut by_property: HashMap<(Option<NodeIndex>, String), usize> = HashMap::new();
+        for &(node_idx, node) in file_nodes {
+            if node.node_type != NodeType::Function {
+                continue;
+            }
+            let Some((property, kind)) = Self::accessor_kind(node) else {
+                continue;
+            };
+            let owner = graph
+                .edges_directed(node_idx, Direction::Incoming)
+                .find(|edge| edge.weight().edge_type == EdgeType::Contains)
+                .map(|edge| edge.source());
+            let group = *by_property
+                .entry((owner, property.clone()))
+                .or_insert_with(|| {
+                    groups.push(AccessorGroup {
+                        property,
+                        kinds: Vec::new(),
+                        members: Vec::new(),
+                    });
+                    groups.len() - 1
+                });
+            let group = &mut groups[group];
+            if !group.kinds.contains(&kind) {
+                group.kinds.push(kind);
+            }
+            group.members.push(node_idx);
+        }
+
+        groups.retain(|group| group.kinds.len() > 1);
+        for group in &mut groups {
+            group
+                .kinds
+                .sort_by_key(|kind| KIND_ORDER.iter().position(|k| k == kind));
+        }
+        let members = groups
+            .iter()
+            .enumerate()
+            .flat_map(|(i, group)| group.members.iter().map(move |&member| (member, i)))
+            .collect();
+        (groups, members)
+    }
+
+    /// `(property, kind)` when `node` is a getter or setter, going by its language's
+    /// conventions
+    fn accessor_kind(node: &Node) -> Option<(String, &'static str)> {
+        match node.language.as_str() {
+            // The parsers keep the keyword: `get total()`
+            "typescript" | "javascript" => {
+                let signature = node.signature.as_deref()?;
+                ["get", "set"]
+                    .into_iter()
+                    .find(|kind| {
+                        signature
+                            .strip_prefix(kind)
+                            .is_some_and(|rest| rest.starts_with(' '))
+                    })
+                    .map(|kind| (node.name.clone(), kind))
+            }
+            // getName/isActive/setName, for property `name`/`active`
+            "java" | "kotlin" => {
+                let (rest, kind) = [("get", "get"), ("is", "get"), ("set", "set")]
+                    .into_iter()
+                    .find_map(|(prefix, kind)| Some((node.name.strip_prefix(prefix)?, kind)))?;
+                let mut chars = rest.chars();
+                let first = chars.next().filter(|c| c.is_uppercase())?;
+                Some((first.to_lowercase().chain(chars).collect(), kind))
+            }
+            // Property accessors are emitted as get_Name/set_Name/init_Name
+            "csharp" => {
+                let (prefix, property) = node.name.split_once('_')?;
+                let kind = ["get", "set", "init"].into_iter().find(|k| *k == prefix)?;
+                Some((property.to_string(), kind))
+            }
+            _ => None,
+        }
+    }
+
+    /// Fold one accessor's entity into its group's `x{get,set}` entity, combining tags
+    /// and calls
+    fn merge_accessor(
+        merged: &mut Option<BehavioralEntity>,
+        entity: BehavioralEntity,
+        group: &AccessorGroup,
+    ) {
+        let merged = merged.get_or_insert_with(|| {
+            let property = format!("{}{{{}}}", group.property, group.kinds.join(","));
+            // Keep the owner prefix added by `with_qualified_names`
+            let name = match entity.name.rsplit_once('.') {
+                Some((owner, _)) => format!("{}.{}", owner, property),
+                None => property,
+            };
+            BehavioralEntity {
+                signature: Some(name.clone()),
+                name,
+                annotations: Vec::new(),
+                thrown: Vec::new(),
+                nested_calls: Vec::new(),
+                doc_summary: None,
+            }
+        });
+        for annotation in entity.annotations {
+            if !merged.annotations.contains(&annotation) {
+                merged.annotations.push(annotation);
+            }
+        }
+        for exception in entity.thrown {
+            if !merged.thrown.contains(&exception) {
+                merged.thrown.push(exception);
+            }
+        }
+        for call in entity.nested_calls {
+            if !merged.nested_calls.contains(&call) {
+                merged.nested_calls.push(call);
+            }
+        }
+        if merged.doc_summary.is_none() {
+            merged.doc_summary = entity.doc_summary;
+        }
+    }
+
+    /// Extract calls made by a function, expanded up to `max_call_depth` levels
     fn extract_immediate_calls(
         &self,
         node_idx: NodeIndex,
         graph: &DependencyGraph,
         file_nodes: &[(NodeIndex, &Node)],
     ) -> Vec<String> {
-        let mut calls = Vec::new();
         let file_node_indices: std::collections::HashSet<NodeIndex> =
             file_nodes.iter().map(|(idx, _)| *idx).collect();
+        let mut call_path = std::collections::HashSet::from([node_idx]);
+        self.extract_calls_at_depth(node_idx, graph, &file_node_indices, 1, &mut call_path)
+    }
+
+    /// One level of call extraction. `call_path` holds the functions being expanded above
+    /// this level so recursion does not loop.
+    fn extract_calls_at_depth(
+        &self,
+        node_idx: NodeIndex,
+        graph: &DependencyGraph,
+        file_node_indices: &std::collections::HashSet<NodeIndex>,
+        depth: usize,
+        call_path: &mut std::collections::HashSet<NodeIndex>,
+    ) -> Vec<String> {
+        let mut calls = Vec::new();
 
         for edge_ref in graph.edges(node_idx) {
             if matches!(edge_ref.weight().edge_type, crate::core::EdgeType::Call) {
                 let target_idx = edge_ref.target();
                 if let Some(target_node) = graph.node_weight(target_idx) {
-                    if file_node_indices.contains(&target_idx) {
-                        // Internal call - allow language adapter to override display
-                        if let Some(display) = self.language_adapter.format_call_display(
-                            target_idx,
-                            target_node,
-                            graph,
-                        ) {
-                            calls.push(display);
-                        } else {
-                            calls.push(target_node.name.clone());
-                        }
+                    // Let adapter override the callee name if applicable
+                    let name = self
+                        .language_adapter
+                        .format_call_display(target_idx, target_node, graph)
+                        .unwrap_or_else(|| match self.owner_name(target_idx, graph) {
+                            Some(owner) => format!("{}.{}", owner, target_node.name),
+                            None => target_node.name.clone(),
+                        });
+
+                    let display = if file_node_indices.contains(&target_idx) {
+                        name
                     } else {
                         // External call - show with simplified module context
                         let module_name = self
                             .language_adapter
                             .extract_module_from_path(&target_node.file_path.to_string_lossy());
                         if module_name == "unknown" || module_name.is_empty() {
-                            if let Some(display) = self.language_adapter.format_call_display(
-                                target_idx,
-                                target_node,
-                                graph,
-                            ) {
-                                calls.push(display);
-                            } else {
-                                calls.push(target_node.name.clone());
-                            }
+                            name
                         } else {
-                            // Let adapter override the callee name if applicable
-                            let name = if let Some(display) = self
-                                .language_adapter
-                                .format_call_display(target_idx, target_node, graph)
-                            {
-                                display
-                            } else {
-                                target_node.name.clone()
-                            };
-                            calls.push(format!("{}::{}", module_name, name));
+                            format!("{}::{}", module_name, name)
                         }
+                    };
+
+                    // Repeated call sites show as `name×N`
+                    let weight = edge_ref.weight().weight;
+                    if weight > 1 {
+                        calls.push((format!("{}×{}", display, weight), target_idx));
+                    } else {
+                        calls.push((display, target_idx));
                     }
                 }
             }
         }
 
         // Sort calls: internal first, then external, prioritize common patterns
-        calls.sort_by(|a, b| {
+        calls.sort_by(|(a, _), (b, _)| {
             let a_internal = !a.contains("::");
             let b_internal = !b.contains("::");
 
@@ -834,11 +1514,120 @@ impl LLMOptimizedFormatter {
 
         // Limit to first 6 calls for better insight while maintaining compactness
         calls.truncate(6);
+
         calls
+            .into_iter()
+            .map(|(display, target_idx)| {
+                if call_path.contains(&target_idx) {
+                    return display;
+                }
+                if depth < self.max_call_depth {
+                    call_path.insert(target_idx);
+                    let nested = self.extract_calls_at_depth(
+                        target_idx,
+                        graph,
+                        file_node_indices,
+                        depth + 1,
+                        call_path,
+                    );
+                    call_path.remove(&target_idx);
+                    if nested.is_empty() {
+                        display
+                    } else {
+                        format!("{}→{{{}}}", display, nested.join(","))
+                    }
+                } else if self.max_call_depth > 1 {
+                    // Deeper calls were requested but this is the limit: count what is hidden
+                    let hidden = graph
+                        .edges(target_idx)
+                        .filter(|e| e.weight().edge_type == EdgeType::Call)
+                        .count();
+                    if hidden > 0 {
+                        format!("{}→{{+{} more}}", display, hidden)
+                    } else {
+                        display
+                    }
+                } else {
+                    display
+                }
+            })
+            .collect()
+    }
+
+    /// Functions on a call cycle: calling themselves directly or through others
+    fn recursive_functions(graph: &DependencyGraph) -> std::collections::HashSet<NodeIndex> {
+        graph
+            .find_cycles(&[EdgeType::Call])
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Enclosing types of a function, outermost first (`Outer.Inner`), when qualified
+    /// names are on. Namespaces and modules are left out; the file already says where the
+    /// function lives.
+    fn owner_name(&self, node_idx: NodeIndex, graph: &DependencyGraph) -> Option<String> {
+        let node = &graph[node_idx];
+        if !self.qualified_names || node.node_type != NodeType::Function {
+            return None;
+        }
+
+        let mut owners = Vec::new();
+        let mut visited = std::collections::HashSet::from([node_idx]);
+        let mut current = node_idx;
+        while let Some(parent) = graph
+            .edges_directed(current, Direction::Incoming)
+            .find(|e| e.weight().edge_type == EdgeType::Contains)
+            .map(|e| e.source())
+        {
+            let is_type = matches!(
+                graph[parent].node_type,
+                NodeType::Class | NodeType::Interface | NodeType::Enum
+            );
+            if !is_type || !visited.insert(parent) {
+                break;
+            }
+            owners.push(graph[parent].name.as_str());
+            current = parent;
+        }
+        if !owners.is_empty() {
+            owners.reverse();
+            return Some(owners.join("."));
+        }
+
+        // No containment edge: fall back to a qualified name segment in the id
+        let suffix = format!(".{}", node.name);
+        node.id
+            .replace("::", ".")
+            .split(':')
+            .skip(1)
+            .find_map(|segment| {
+                let qualifier = segment.strip_suffix(&suffix)?;
+                qualifier.rsplit('.').next().map(str::to_string)
+            })
+            .filter(|owner| !owner.is_empty())
+    }
+
+    /// Put `owner.` in front of the function name in its signature, unless the parser
+    /// already qualified it (`User::fn process`) or the name is not followed by `(`
+    fn qualify_signature(signature: &str, owner: &str, name: &str) -> String {
+        let already_qualified = signature.contains(&format!("{}::", owner))
+            || signature.contains(&format!("{}.{}", owner, name));
+        if already_qualified {
+            return signature.to_string();
+        }
+        signature.replacen(&format!("{}(", name), &format!("{}.{}(", owner, name), 1)
     }
 
     /// Get priority for call ordering (lower number = higher priority)
     fn get_call_priority(&self, call_name: &str) -> u8 {
+        // `log×3` is a call to `log`
+        let call_name = call_name.split('×').next().unwrap_or(call_name);
+        if let Some((patterns, priorities)) = &self.call_priorities {
+            if let Some(first) = patterns.matches(call_name).into_iter().min() {
+                return priorities[first];
+            }
+        }
         self.language_adapter.get_call_priority(call_name)
     }
 
@@ -848,6 +1637,8 @@ impl LLMOptimizedFormatter {
         node: &Node,
         graph: &DependencyGraph,
         file_nodes: &[(NodeIndex, &Node)],
+        hot_threshold: usize,
+        recursive: &std::collections::HashSet<NodeIndex>,
     ) -> Vec<String> {
         let mut annotations = Vec::new();
 
@@ -883,7 +1674,9 @@ impl LLMOptimizedFormatter {
             }
 
             // More precise entry point detection
-            if !is_called_internally {
+            if self.is_declared_entry(node) {
+                annotations.push("ENTRY".to_string());
+            } else if !is_called_internally {
                 // True entry points: main, new, parse_file, or public methods
                 if node.name == "main"
                     || node.name == "new"
@@ -895,13 +1688,52 @@ impl LLMOptimizedFormatter {
                 }
             }
 
-            // Add performance hints
-            if node.name.contains("resolve")
-                || node.name.contains("compute")
-                || node.name.contains("build")
-            {
+            if Self::caller_count(graph, node_idx) > hot_threshold {
                 annotations.push("HOT".to_string());
             }
+
+            if recursive.contains(&node_idx) {
+                annotations.push("RECURSIVE".to_string());
+            }
+        }
+
+        if node.is_async {
+            annotations.push("ASYNC".to_string());
+        }
+
+        if node.is_test {
+            annotations.push("TEST".to_string());
+        }
+
+        match node.lifecycle {
+            Some(Lifecycle::Constructor) => annotations.push("CTOR".to_string()),
+            Some(Lifecycle::Destructor) => annotations.push("DTOR".to_string()),
+            None => {}
+        }
+
+        if node
+            .signature
+            .as_deref()
+            .is_some_and(|signature| signature.starts_with("macro_rules!"))
+        {
+            annotations.push("MACRO".to_string());
+        }
+
+        let overrides = current_node_idx.is_some_and(|idx| {
+            graph
+                .edges(idx)
+                .any(|e| e.weight().edge_type == EdgeType::Overrides)
+        });
+        if overrides {
+            annotations.push("OVERRIDE".to_string());
+        }
+
+        if node.complexity > self.complexity_threshold {
+            annotations.push(format!("CX:{}", node.complexity));
+        }
+
+        if self.flag_unused && current_node_idx.is_some_and(|idx| graph.is_unreferenced(idx)) {
+            annotations.push("DEAD?".to_string());
         }
 
         // Merge language-specific annotations
@@ -928,61 +1760,78 @@ impl LLMOptimizedFormatter {
             result.push_str(&format!("[{}]", entity.annotations.join(",")));
         }
 
+        if !entity.thrown.is_empty() {
+            result.push_str(&format!("!{{{}}}", entity.thrown.join(",")));
+        }
+
         // Add nested calls if any
         if !entity.nested_calls.is_empty() {
             result.push_str(&format!("→{{{}}}", entity.nested_calls.join(",")));
         }
 
+        if let Some(ref summary) = entity.doc_summary {
+            result.push_str(&format!("// \"{}\"", summary));
+        }
+
         result
     }
 
+    /// Exception types a function throws or declares, along its `Throws` edges; Verbose only
+    fn thrown_types(&self, node_idx: NodeIndex, graph: &DependencyGraph) -> Vec<String> {
+        if self.verbosity != OutputVerbosity::Verbose {
+            return Vec::new();
+        }
+        let mut thrown: Vec<String> = graph
+            .edges_directed(node_idx, Direction::Outgoing)
+            .filter(|edge| edge.weight().edge_type == EdgeType::Throws)
+            .map(|edge| graph[edge.target()].name.clone())
+            .collect();
+        // Edges come out newest first
+        thrown.reverse();
+        thrown
+    }
+
+    /// First non-empty docstring line, without comment markers, cut to 60 characters
+    fn doc_summary(&self, node: &Node) -> Option<String> {
+        const MAX_SUMMARY_CHARS: usize = 60;
+
+        if !self.include_docstrings {
+            return None;
+        }
+        let first_line = node
+            .docstring
+            .as_deref()?
+            .lines()
+            .map(|line| line.trim().trim_start_matches(['/', '*', '#', '!']).trim())
+            .find(|line| !line.is_empty())?;
+
+        let summary = first_line.replace('"', "'");
+        if summary.chars().count() <= MAX_SUMMARY_CHARS {
+            return Some(summary);
+        }
+        let cut: String = summary.chars().take(MAX_SUMMARY_CHARS - 3).collect();
+        Some(format!("{}...", cut.trim_end()))
+    }
+
     /// Convert verbose signature to ultra-compact format for LLM consumption
     fn compact_signature(&self, signature: &str) -> String {
-        let mut compact = signature.to_string();
-
-        // Remove excessive whitespace and newlines
-        compact = compact.replace('\n', " ").replace('\t', " ");
-
-        // Collapse multiple spaces into single space
-        while compact.contains("  ") {
-            compact = compact.replace("  ", " ");
-        }
-
-        // Remove unnecessary tokens for LLM efficiency
-        compact = compact
-            .replace("&mut self, ", "") // Remove common self parameter
-            .replace("&self, ", "") // Remove immutable self parameter
-            .replace("&self", "") // Remove standalone self parameter
-            .replace("&Path", "Path") // Simplify common types
-            .replace("&str", "str") // Simplify string references
-            .replace("&[u8]", "bytes") // Simplify byte slices
-            .replace("&TSNode", "Node") // Simplify tree-sitter nodes
-            .replace("&mut Vec<Node>", "nodes") // Simplify common parameters
-            .replace("&mut Vec<Edge>", "edges") // Simplify common parameters
-            .replace("Vec<Node>", "nodes") // Simplify return types
-            .replace("Vec<Edge>", "edges") // Simplify return types
-            .replace("Option<", "?") // Simplify Option types
-            .replace("Result<", "!") // Simplify Result types
-            .replace("PathBuf", "Path") // Simplify path types
-            .replace("String", "str") // Simplify string types
-            .replace("usize", "int") // Simplify integer types
-            .replace("bool", "bool") // Keep bool as is
-            .replace("()", "void") // Simplify unit type
+        // One line, single spaces
+        let compact = signature
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        if !self.compress_signatures {
+            return compact;
+        }
+
+        let compact = abbreviate_signature(&compact)
             .replace(" -> ", "→") // Use arrow symbol
-            .replace(" ->", "→") // Handle space variations
-            .replace("-> ", "→") // Handle space variations
-            .replace(" ->", "→") // Handle space variations
-            .replace("->", "→") // Handle no spaces
+            .replace("->", "→") // Handle space variations
             .replace("::", ".") // Use dot notation
-            .replace("()", "()") // Keep parentheses
             .replace("( ", "(") // Remove space after opening paren
             .replace(" )", ")") // Remove space before closing paren
             .replace(" ,", ",") // Remove space before comma
-            .replace(", ", ",") // Remove space after comma
-            .replace(" ,", ",") // Handle both cases
-            .replace("  ", " ") // Collapse remaining double spaces
-            .trim()
-            .to_string();
+            .replace(", ", ","); // Remove space after comma
 
         // Final cleanup - remove any remaining excessive whitespace
         compact.split_whitespace().collect::<Vec<&str>>().join(" ")
@@ -994,29 +1843,34 @@ impl LLMOptimizedFormatter {
         self.language_adapter.extract_module_from_path(path)
     }
 
-    /// Calculate call depth for a single node (with cycle detection)
-    fn calculate_node_call_depth(
-        &self,
-        node_idx: NodeIndex,
-        graph: &DependencyGraph,
-        visited: &mut std::collections::HashSet<NodeIndex>,
-    ) -> usize {
-        if visited.contains(&node_idx) {
-            return 0; // Prevent infinite recursion
+    /// Call depth of every node, by index: the most functions along any chain of calls
+    /// starting there, a call cycle counting once. Computed over the acyclic graph of call
+    /// cycles so it stays linear in the size of the graph.
+    fn call_depths(graph: &DependencyGraph) -> Vec<usize> {
+        let calls = graph.filter_map(
+            |node_idx, _| Some(node_idx),
+            |_, edge| (edge.edge_type == EdgeType::Call).then_some(()),
+        );
+        let units = condensation(calls, true);
+        let order = toposort(&units, None).unwrap_or_default();
+
+        // Callees come after their callers in `order`, so walk it backwards
+        let mut unit_depth = vec![0usize; units.node_count()];
+        for &unit in order.iter().rev() {
+            unit_depth[unit.index()] = 1 + units
+                .neighbors(unit)
+                .map(|next| unit_depth[next.index()])
+                .max()
+                .unwrap_or(0);
         }
 
-        visited.insert(node_idx);
-
-        let mut max_child_depth = 0;
-        for edge_ref in graph.edges(node_idx) {
-            if matches!(edge_ref.weight().edge_type, crate::core::EdgeType::Call) {
-                let child_depth = self.calculate_node_call_depth(edge_ref.target(), graph, visited);
-                max_child_depth = max_child_depth.max(child_depth);
+        let mut depths = vec![0usize; graph.node_count()];
+        for unit in units.node_indices() {
+            for node_idx in &units[unit] {
+                depths[node_idx.index()] = unit_depth[unit.index()];
             }
         }
-
-        visited.remove(&node_idx);
-        1 + max_child_depth
+        depths
     }
 
     /// Build call trees for functions in a file
@@ -1128,13 +1982,50 @@ impl LLMOptimizedFormatter {
         }
     }
 
+    /// Entry point by a `with_entry_patterns` glob or an HTTP route decorator
+    fn is_declared_entry(&self, node: &Node) -> bool {
+        /// Python decorators and Java annotations marking HTTP handlers, compared against
+        /// the last dotted segment so `app.get` and `router.get` both match
+        const ROUTE_DECORATORS: &[&str] = &[
+            "route",
+            "api_route",
+            "get",
+            "post",
+            "put",
+            "patch",
+            "delete",
+            "head",
+            "options",
+            "websocket",
+            "RequestMapping",
+            "GetMapping",
+            "PostMapping",
+            "PutMapping",
+            "PatchMapping",
+            "DeleteMapping",
+        ];
+
+        if self
+            .entry_patterns
+            .as_ref()
+            .is_some_and(|patterns| patterns.is_match(&node.name))
+        {
+            return true;
+        }
+        node.decorators.iter().any(|decorator| {
+            let last = decorator.rsplit('.').next().unwrap_or(decorator);
+            ROUTE_DECORATORS.contains(&last)
+        })
+    }
+
     /// Get annotations for a function based on its characteristics
     #[allow(unused_variables)]
     fn get_function_annotations(&self, node: &Node, children: &[CallTreeNode]) -> Vec<String> {
         let mut annotations = Vec::new();
 
         // Entry point detection
-        if node.visibility.as_ref().map_or(false, |v| v == "public") {
+        if node.visibility.as_ref().map_or(false, |v| v == "public") || self.is_declared_entry(node)
+        {
             annotations.push("ENTRY".to_string());
         }
 
@@ -1216,12 +2107,23 @@ struct BehavioralEntity {
     #[allow(dead_code)]
     signature: Option<String>,
     annotations: Vec<String>,
+    /// Exception types from `Throws` edges, shown as `!{..}`
+    thrown: Vec<String>,
     nested_calls: Vec<String>,
+    doc_summary: Option<String>,
+}
+
+/// Getters and setters of one property, shown as a single `x{get,set}` entity
+struct AccessorGroup {
+    property: String,
+    /// Accessor kinds present, `get` first
+    kinds: Vec<&'static str>,
+    members: Vec<NodeIndex>,
 }
 
 /// Directory tree structure for path compression (dynamic)
 #[derive(Debug)]
-struct DirectoryTree {
+pub(super) struct DirectoryTree {
     common_prefix: String,
     semantic_prefixes: HashMap<String, String>,
     root: DirNode,
@@ -1263,6 +2165,25 @@ impl DirNode {
         }
     }
 
+    /// Merge this directory with its only subdirectory while it holds no files itself,
+    /// then do the same below. Counts are already rolled up, so they carry over as is.
+    fn flatten(&mut self) {
+        while self.children.len() == 1
+            && self
+                .children
+                .values()
+                .all(|child| child.file_count == self.file_count)
+        {
+            if let Some((_, child)) = self.children.pop_first() {
+                self.name = format!("{}/{}", self.name, child.name);
+                self.children = child.children;
+            }
+        }
+        for child in self.children.values_mut() {
+            child.flatten();
+        }
+    }
+
     fn finalize_counts(&mut self) {
         let keys: Vec<String> = self.children.keys().cloned().collect();
         for k in keys {
@@ -1278,21 +2199,24 @@ impl DirNode {
 }
 
 impl DirectoryTree {
-    fn build(paths: Vec<String>) -> Self {
+    fn build(paths: Vec<String>, custom_prefixes: Option<&[(String, String)]>) -> Self {
         let common_prefix = Self::find_common_prefix(&paths);
-        let semantic_prefixes = Self::build_semantic_prefixes(&paths, &common_prefix);
+        let semantic_prefixes = match custom_prefixes {
+            Some(custom) => Self::match_custom_prefixes(&paths, &common_prefix, custom),
+            None => Self::build_semantic_prefixes(&paths, &common_prefix),
+        };
 
         let mut root = DirNode::new("".to_string());
         for path in &paths {
             let rel = path
                 .strip_prefix(&common_prefix)
                 .unwrap_or(path)
-                .trim_start_matches('/')
+                .trim_start_matches(['/', '\\'])
                 .to_string();
             if rel.is_empty() {
                 continue;
             }
-            let parts: Vec<&str> = rel.split('/').collect();
+            let parts: Vec<&str> = rel.split(['/', '\\']).collect();
             let prefix = semantic_prefixes
                 .get(path)
                 .cloned()
@@ -1308,31 +2232,60 @@ impl DirectoryTree {
         tree
     }
 
-    fn find_common_prefix(paths: &[String]) -> String {
+    /// Collapse single-child chains below the root, like `find_common_prefix` does above it
+    fn flatten(&mut self) {
+        for child in self.root.children.values_mut() {
+            child.flatten();
+        }
+    }
+
+    pub(super) fn find_common_prefix(paths: &[String]) -> String {
         if paths.is_empty() {
             return String::new();
         }
 
+        // Shortest shared length across every path, not just the last pair compared
         let first = &paths[0];
-        let mut prefix_len = first.len();
-
-        for path in paths.iter().skip(1) {
-            prefix_len = first
-                .chars()
-                .zip(path.chars())
-                .take_while(|(a, b)| a == b)
-                .count();
-        }
-
-        // Trim to last directory separator to avoid partial segments
+        let prefix_len = paths
+            .iter()
+            .skip(1)
+            .map(|path| {
+                first
+                    .chars()
+                    .zip(path.chars())
+                    .take_while(|(a, b)| a == b)
+                    .count()
+            })
+            .fold(first.chars().count(), usize::min);
+
+        // Trim to last directory separator to avoid partial segments; Windows paths use `\`
         let prefix: String = first.chars().take(prefix_len).collect();
-        if let Some(pos) = prefix.rfind('/') {
+        if let Some(pos) = prefix.rfind(['/', '\\']) {
             prefix[..=pos].to_string()
         } else {
             String::new()
         }
     }
 
+    /// First configured pattern contained in each path wins; unmatched files are `U`
+    fn match_custom_prefixes(
+        paths: &[String],
+        common_prefix: &str,
+        custom: &[(String, String)],
+    ) -> HashMap<String, String> {
+        paths
+            .iter()
+            .map(|path| {
+                let relative_path = path.strip_prefix(common_prefix).unwrap_or(path);
+                let prefix = custom
+                    .iter()
+                    .find(|(pattern, _)| relative_path.contains(pattern.as_str()))
+                    .map_or("U", |(_, prefix)| prefix.as_str());
+                (path.clone(), prefix.to_string())
+            })
+            .collect()
+    }
+
     fn build_semantic_prefixes(paths: &[String], common_prefix: &str) -> HashMap<String, String> {
         let mut prefixes = HashMap::new();
 
@@ -1429,3 +2382,31 @@ impl Default for LLMOptimizedFormatter {
         Self::new()
     }
 }
+
+/// Apply [`SIGNATURE_ABBREVIATIONS`] in one left-to-right pass. A match must not follow
+/// an identifier character, nor be followed by one when it ends in one.
+fn abbreviate_signature(signature: &str) -> String {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut abbreviated = String::with_capacity(signature.len());
+    let mut position = 0;
+    while let Some(c) = signature[position..].chars().next() {
+        let rest = &signature[position..];
+        let follows_ident = signature[..position].ends_with(is_ident);
+        let abbreviation = SIGNATURE_ABBREVIATIONS.iter().find(|(pattern, _)| {
+            !follows_ident
+                && rest.starts_with(pattern)
+                && !(pattern.ends_with(is_ident) && rest[pattern.len()..].starts_with(is_ident))
+        });
+        match abbreviation {
+            Some((pattern, replacement)) => {
+                abbreviated.push_str(replacement);
+                position += pattern.len();
+            }
+            None => {
+                abbreviated.push(c);
+                position += c.len_utf8();
+            }
+        }
+    }
+    abbreviated
+}
@@ -0,0 +1,159 @@
+use anyhow::Result;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::core::{DependencyGraph, EdgeType, Node, NodeType};
+
+/// Mermaid flowchart formatter for embedding graphs in Markdown (GitHub, wikis)
+pub struct MermaidFormatter {
+    /// Upper bound on rendered nodes; Mermaid struggles with very large graphs
+    max_nodes: Option<usize>,
+}
+
+impl MermaidFormatter {
+    pub fn new() -> Self {
+        Self { max_nodes: None }
+    }
+
+    /// Render at most `max_nodes` nodes, noting how many were omitted
+    #[allow(dead_code)]
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn format_to_file(&self, graph: &DependencyGraph, output_path: &Path) -> Result<()> {
+        let content = self.format_to_string(graph)?;
+        fs::write(output_path, content)?;
+        Ok(())
+    }
+
+    /// Render the fenced mermaid block as a string.
+    pub fn format_to_string(&self, graph: &DependencyGraph) -> Result<String> {
+        let mut output = String::with_capacity(4096);
+        output.push_str("```mermaid\ngraph LR\n");
+
+        // Deterministic selection: file, then line, then name
+        let mut indices: Vec<NodeIndex> = graph.node_indices().collect();
+        indices.sort_by(|a, b| {
+            let na = &graph[*a];
+            let nb = &graph[*b];
+            na.file_path
+                .cmp(&nb.file_path)
+                .then_with(|| na.line_number.cmp(&nb.line_number))
+                .then_with(|| na.name.cmp(&nb.name))
+        });
+
+        let total = indices.len();
+        if let Some(max) = self.max_nodes {
+            indices.truncate(max);
+        }
+        let ids = Self::node_ids(graph, &indices);
+
+        // One subgraph per source file
+        let mut by_file: BTreeMap<String, Vec<NodeIndex>> = BTreeMap::new();
+        for &idx in &indices {
+            let path = graph[idx].file_path.to_string_lossy().to_string();
+            by_file.entry(path).or_default().push(idx);
+        }
+
+        for (file_number, (path, file_nodes)) in by_file.iter().enumerate() {
+            output.push_str(&format!(
+                "  subgraph file{}[\"{}\"]\n",
+                file_number,
+                Self::escape_label(path)
+            ));
+            for &idx in file_nodes {
+                output.push_str(&format!(
+                    "    {}\n",
+                    self.format_node(&ids[&idx], &graph[idx])
+                ));
+            }
+            output.push_str("  end\n");
+        }
+
+        for edge_ref in graph.edge_references() {
+            let (Some(source), Some(target)) =
+                (ids.get(&edge_ref.source()), ids.get(&edge_ref.target()))
+            else {
+                continue;
+            };
+            let arrow = match edge_ref.weight().edge_type {
+                EdgeType::Call => "-->",
+                EdgeType::Inheritance | EdgeType::Implements => "-.->",
+                EdgeType::Overrides => "-.->|overrides|",
+                EdgeType::Throws => "-.->|throws|",
+                EdgeType::Composes => "-->|has|",
+                EdgeType::Uses | EdgeType::Import => "-->|uses|",
+                // Containment is already conveyed by the file subgraphs
+                EdgeType::Contains => continue,
+            };
+            output.push_str(&format!("  {} {} {}\n", source, arrow, target));
+        }
+
+        if total > indices.len() {
+            output.push_str(&format!(
+                "  omitted[\"{} more nodes omitted\"]\n",
+                total - indices.len()
+            ));
+        }
+
+        output.push_str("```\n");
+        Ok(output)
+    }
+
+    fn format_node(&self, id: &str, node: &Node) -> String {
+        let label = Self::escape_label(&node.name);
+        match node.node_type {
+            NodeType::Function => format!("{}(\"{}()\")", id, label),
+            NodeType::Class => format!("{}[\"{}\"]", id, label),
+            NodeType::Interface => format!("{}{{{{\"{}\"}}}}", id, label),
+            NodeType::Module => format!("{}[/\"{}\"/]", id, label),
+            NodeType::Variable | NodeType::Enum => format!("{}[(\"{}\")]", id, label),
+        }
+    }
+
+    /// Mermaid id of each rendered node. Sanitizing is not injective (`a/b.py` and `a_b.py`
+    /// both become `a_b_py`), so a node whose id is already taken gets a numeric suffix
+    fn node_ids(graph: &DependencyGraph, indices: &[NodeIndex]) -> HashMap<NodeIndex, String> {
+        let mut taken = HashSet::new();
+        indices
+            .iter()
+            .map(|&idx| {
+                let base = Self::sanitize_id(&graph[idx].id);
+                let mut id = base.clone();
+                let mut suffix = 1;
+                while !taken.insert(id.clone()) {
+                    suffix += 1;
+                    id = format!("{}_{}", base, suffix);
+                }
+                (idx, id)
+            })
+            .collect()
+    }
+
+    /// Mermaid ids must be plain identifiers; derive a stable one from the node id
+    fn sanitize_id(id: &str) -> String {
+        let mut sanitized: String = id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        // Ids starting with a digit, or colliding with keywords like `end`, break the parser
+        sanitized.insert_str(0, "n_");
+        sanitized
+    }
+
+    fn escape_label(label: &str) -> String {
+        label.replace('"', "#quot;")
+    }
+}
+
+impl Default for MermaidFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,34 +1,85 @@
 use anyhow::Result;
 use petgraph::graph::NodeIndex;
 use petgraph::visit::EdgeRef;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
 use crate::core::{DependencyGraph, Edge, EdgeType, Node, NodeType};
+use call_filter::CallFilter;
 
+mod call_filter;
+mod csv;
+mod graphml;
+mod html;
 mod json_compact;
+mod json_full;
 mod llm_language;
 mod llm_optimized;
-
+mod mermaid;
+mod sqlite;
+mod symbols;
+mod tree;
+
+pub use csv::CsvFormatter;
+pub use graphml::GraphMlFormatter;
+pub use html::HtmlFormatter;
 pub use json_compact::JsonCompactFormatter;
-pub use llm_language::{LlmLanguageAdapter, PythonLanguageAdapter};
+pub use json_full::JsonFullFormatter;
+pub use llm_language::{
+    KotlinLanguageAdapter, LlmLanguageAdapter, PhpLanguageAdapter, PythonLanguageAdapter,
+};
 pub use llm_optimized::{LLMOptimizedFormatter, OutputVerbosity};
-
-pub struct EmbargoFormatter;
+pub use mermaid::MermaidFormatter;
+pub use sqlite::SqliteFormatter;
+pub use symbols::SymbolsFormatter;
+pub use tree::TreeFormatter;
+
+pub struct EmbargoFormatter {
+    /// Append each entity's span to its heading, e.g. `### foo [L42-L88]`
+    line_ranges: bool,
+    /// Calls left out of the `Calls` lists
+    call_filter: CallFilter,
+}
 
 impl EmbargoFormatter {
     pub fn new() -> Self {
-        Self
+        Self {
+            line_ranges: false,
+            call_filter: CallFilter::default(),
+        }
     }
 
+    /// Show where each definition starts and ends next to its name, for editors that
+    /// jump to and highlight the whole definition
+    pub fn with_line_ranges(mut self, line_ranges: bool) -> Self {
+        self.line_ranges = line_ranges;
+        self
+    }
+
+    /// Leave calls to these functions (`len`, `String::from`, `println!`) out of the
+    /// report, to cut utility noise
+    pub fn with_hide_calls(mut self, names: Vec<String>) -> Self {
+        self.call_filter.hide(&names);
+        self
+    }
+
+    /// Leave out calls made from fewer than `min_weight` call sites
+    pub fn with_min_edge_weight(mut self, min_weight: usize) -> Self {
+        self.call_filter.set_min_weight(min_weight);
+        self
+    }
+
+    #[allow(dead_code)]
     pub fn format_to_file(&self, graph: &DependencyGraph, output_path: &Path) -> Result<()> {
-        let formatted_content = self.format_graph(graph)?;
+        let formatted_content = self.format_to_string(graph)?;
         fs::write(output_path, formatted_content)?;
         Ok(())
     }
 
-    fn format_graph(&self, graph: &DependencyGraph) -> Result<String> {
+    /// Render the markdown report in memory, for callers that want the text itself.
+    pub fn format_to_string(&self, graph: &DependencyGraph) -> Result<String> {
+        let graph = &*self.call_filter.apply(graph);
         let mut output = String::new();
 
         output.push_str("# EMBARGO - Codebase Dependency Analysis\n\n");
@@ -102,7 +153,7 @@ impl EmbargoFormatter {
         output.push_str("## Dependency Graph Summary\n\n");
         output.push_str("### Edge Types\n\n");
 
-        let mut edge_counts = HashMap::new();
+        let mut edge_counts = BTreeMap::new();
         for edge_ref in graph.edge_references() {
             let edge_type = &edge_ref.weight().edge_type;
             *edge_counts.entry(format!("{:?}", edge_type)).or_insert(0) += 1;
@@ -121,7 +172,10 @@ impl EmbargoFormatter {
         output.push_str("- **Inheritance**: Class inheritance relationships\n");
         output.push_str("- **Implements**: Interface implementation relationships\n");
         output.push_str("- **Uses**: General usage relationships\n");
-        output.push_str("- **Contains**: Containment relationships (class contains method)\n\n");
+        output.push_str("- **Contains**: Containment relationships (class contains method)\n");
+        output.push_str("- **Overrides**: Method redefining a parent class method\n");
+        output.push_str("- **Throws**: Function throwing or declaring an exception type\n");
+        output.push_str("- **Composes**: Class holding a field or property of another type\n\n");
 
         output.push_str("---\n\n");
         output.push_str("*Generated by embargo - Optimize for LLM consumption and agentic software development*\n");
@@ -131,7 +185,7 @@ impl EmbargoFormatter {
 
     fn format_module_node(&self, node: &Node, idx: NodeIndex, graph: &DependencyGraph) -> String {
         let mut output = String::new();
-        output.push_str(&format!("### {}\n\n", node.name));
+        output.push_str(&self.heading(node));
         output.push_str(&format!("- **ID**: `{}`\n", node.id));
         output.push_str(&format!("- **File**: `{}`\n", node.file_path.display()));
         output.push_str(&format!("- **Line**: {}\n", node.line_number));
@@ -151,7 +205,7 @@ impl EmbargoFormatter {
 
     fn format_class_node(&self, node: &Node, idx: NodeIndex, graph: &DependencyGraph) -> String {
         let mut output = String::new();
-        output.push_str(&format!("### {}\n\n", node.name));
+        output.push_str(&self.heading(node));
         output.push_str(&format!("- **ID**: `{}`\n", node.id));
         output.push_str(&format!("- **File**: `{}`\n", node.file_path.display()));
         output.push_str(&format!("- **Line**: {}\n", node.line_number));
@@ -202,7 +256,7 @@ impl EmbargoFormatter {
         graph: &DependencyGraph,
     ) -> String {
         let mut output = String::new();
-        output.push_str(&format!("### {}\n\n", node.name));
+        output.push_str(&self.heading(node));
         output.push_str(&format!("- **ID**: `{}`\n", node.id));
         output.push_str(&format!("- **File**: `{}`\n", node.file_path.display()));
         output.push_str(&format!("- **Line**: {}\n", node.line_number));
@@ -224,7 +278,7 @@ impl EmbargoFormatter {
 
     fn format_function_node(&self, node: &Node, idx: NodeIndex, graph: &DependencyGraph) -> String {
         let mut output = String::new();
-        output.push_str(&format!("### {}\n\n", node.name));
+        output.push_str(&self.heading(node));
         output.push_str(&format!("- **ID**: `{}`\n", node.id));
         output.push_str(&format!("- **File**: `{}`\n", node.file_path.display()));
         output.push_str(&format!("- **Line**: {}\n", node.line_number));
@@ -266,7 +320,7 @@ impl EmbargoFormatter {
         _graph: &DependencyGraph,
     ) -> String {
         let mut output = String::new();
-        output.push_str(&format!("### {}\n\n", node.name));
+        output.push_str(&self.heading(node));
         output.push_str(&format!("- **ID**: `{}`\n", node.id));
         output.push_str(&format!("- **File**: `{}`\n", node.file_path.display()));
         output.push_str(&format!("- **Line**: {}\n", node.line_number));
@@ -280,6 +334,17 @@ impl EmbargoFormatter {
         output
     }
 
+    fn heading(&self, node: &Node) -> String {
+        if self.line_ranges {
+            format!(
+                "### {} [L{}-L{}]\n\n",
+                node.name, node.line_number, node.end_line
+            )
+        } else {
+            format!("### {}\n\n", node.name)
+        }
+    }
+
     fn get_outgoing_edges<'a>(
         &self,
         node_idx: NodeIndex,
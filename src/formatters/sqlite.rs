@@ -0,0 +1,111 @@
+use anyhow::Result;
+use petgraph::visit::EdgeRef;
+use rusqlite::{params, Connection};
+use std::fs;
+use std::path::Path;
+
+use crate::core::DependencyGraph;
+
+const SCHEMA: &str = "
+CREATE TABLE nodes (
+    id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    node_type TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    line_number INTEGER NOT NULL,
+    end_line INTEGER NOT NULL,
+    language TEXT NOT NULL,
+    signature TEXT,
+    docstring TEXT,
+    visibility TEXT,
+    is_async INTEGER NOT NULL,
+    complexity INTEGER NOT NULL,
+    decorators TEXT NOT NULL,
+    is_test INTEGER NOT NULL
+);
+CREATE TABLE edges (
+    edge_type TEXT NOT NULL,
+    source_id TEXT NOT NULL,
+    target_id TEXT NOT NULL,
+    context TEXT,
+    weight INTEGER NOT NULL
+);
+CREATE INDEX nodes_id ON nodes(id);
+CREATE INDEX nodes_name ON nodes(name);
+CREATE INDEX nodes_node_type ON nodes(node_type);
+CREATE INDEX nodes_file_path ON nodes(file_path);
+CREATE INDEX edges_edge_type ON edges(edge_type);
+CREATE INDEX edges_source_id ON edges(source_id);
+CREATE INDEX edges_target_id ON edges(target_id);
+";
+
+/// SQLite database with `nodes` and `edges` tables mirroring the graph structs, for SQL
+/// queries over graphs too large to browse as JSON. Edges reference nodes by `id`;
+/// `decorators` is stored as a JSON array.
+pub struct SqliteFormatter;
+
+impl SqliteFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Write the database to `output_path`, replacing any existing file.
+    pub fn format_to_file(&self, graph: &DependencyGraph, output_path: &Path) -> Result<()> {
+        if output_path.exists() {
+            fs::remove_file(output_path)?;
+        }
+        let mut conn = Connection::open(output_path)?;
+        conn.execute_batch(SCHEMA)?;
+
+        // One transaction for all rows; autocommit per insert is orders of magnitude slower
+        let tx = conn.transaction()?;
+        {
+            let mut insert_node = tx.prepare(
+                "INSERT INTO nodes (id, name, node_type, file_path, line_number, end_line, \
+                 language, signature, docstring, visibility, is_async, complexity, decorators, \
+                 is_test) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            )?;
+            for node in graph.node_weights() {
+                insert_node.execute(params![
+                    node.id,
+                    node.name,
+                    format!("{:?}", node.node_type),
+                    node.file_path.to_string_lossy(),
+                    node.line_number as i64,
+                    node.end_line as i64,
+                    node.language,
+                    node.signature,
+                    node.docstring,
+                    node.visibility,
+                    node.is_async,
+                    node.complexity as i64,
+                    serde_json::to_string(&node.decorators)?,
+                    node.is_test,
+                ])?;
+            }
+
+            let mut insert_edge = tx.prepare(
+                "INSERT INTO edges (edge_type, source_id, target_id, context, weight) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            for edge_ref in graph.edge_references() {
+                let edge = edge_ref.weight();
+                insert_edge.execute(params![
+                    format!("{:?}", edge.edge_type),
+                    graph[edge_ref.source()].id,
+                    graph[edge_ref.target()].id,
+                    edge.context,
+                    edge.weight as i64,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+impl Default for SqliteFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
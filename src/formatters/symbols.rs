@@ -0,0 +1,108 @@
+use anyhow::Result;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::core::{DependencyGraph, EdgeType, Node, NodeType};
+
+/// Flat per-file symbol list in the spirit of LSP `textDocument/documentSymbol`, for
+/// outline views and editor shims that do not want the whole graph.
+///
+/// ```json
+/// [{"file":"src/cart.py","symbols":[
+///   {"name":"Cart","kind":"class","line":3,"end_line":12,"container":null},
+///   {"name":"add","kind":"method","line":5,"end_line":7,"container":"Cart"}]}]
+/// ```
+pub struct SymbolsFormatter;
+
+#[derive(Serialize)]
+struct FileSymbols<'a> {
+    file: String,
+    symbols: Vec<Symbol<'a>>,
+}
+
+#[derive(Serialize)]
+struct Symbol<'a> {
+    name: &'a str,
+    kind: &'static str,
+    line: usize,
+    end_line: usize,
+    /// Name of the symbol that `Contains` this one
+    container: Option<&'a str>,
+}
+
+impl SymbolsFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    #[allow(dead_code)]
+    pub fn format_to_file(&self, graph: &DependencyGraph, output_path: &Path) -> Result<()> {
+        let content = self.format_to_string(graph)?;
+        fs::write(output_path, content)?;
+        Ok(())
+    }
+
+    /// Files in path order, each with its symbols ordered by line
+    pub fn format_to_string(&self, graph: &DependencyGraph) -> Result<String> {
+        let mut files: BTreeMap<String, Vec<Symbol>> = BTreeMap::new();
+        for idx in graph.node_indices() {
+            let node = &graph[idx];
+            if !Self::is_symbol(node) {
+                continue;
+            }
+            let container = graph
+                .edges_directed(idx, Direction::Incoming)
+                .filter(|e| e.weight().edge_type == EdgeType::Contains)
+                .map(|e| &graph[e.source()])
+                .find(|owner| Self::is_symbol(owner));
+            files
+                .entry(node.file_path.to_string_lossy().replace('\\', "/"))
+                .or_default()
+                .push(Symbol {
+                    name: &node.name,
+                    kind: Self::kind(node.node_type, container.is_some()),
+                    line: node.line_number,
+                    end_line: node.end_line,
+                    container: container.map(|owner| owner.name.as_str()),
+                });
+        }
+
+        let files: Vec<FileSymbols> = files
+            .into_iter()
+            .map(|(file, mut symbols)| {
+                symbols.sort_by(|a, b| (a.line, a.name).cmp(&(b.line, b.name)));
+                FileSymbols { file, symbols }
+            })
+            .collect();
+        Ok(serde_json::to_string_pretty(&files)?)
+    }
+
+    /// Declarations only; imports and external placeholders are not symbols of the file
+    fn is_symbol(node: &Node) -> bool {
+        node.node_type != NodeType::Module && node.visibility.as_deref() != Some("external")
+    }
+
+    /// LSP `SymbolKind` name, lowercased; members of a type are methods and fields
+    fn kind(node_type: NodeType, contained: bool) -> &'static str {
+        match (node_type, contained) {
+            (NodeType::Module, _) => "module",
+            (NodeType::Class, _) => "class",
+            (NodeType::Interface, _) => "interface",
+            (NodeType::Enum, _) => "enum",
+            (NodeType::Function, false) => "function",
+            (NodeType::Function, true) => "method",
+            (NodeType::Variable, false) => "variable",
+            (NodeType::Variable, true) => "field",
+        }
+    }
+}
+
+impl Default for SymbolsFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
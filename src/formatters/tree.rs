@@ -0,0 +1,249 @@
+use anyhow::Result;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use super::llm_optimized::DirectoryTree;
+use crate::core::{DependencyGraph, EdgeType, Node, NodeType};
+
+/// Indented outline for the terminal: directories → files → types → methods
+pub struct TreeFormatter {
+    /// Wrap entity labels in ANSI colors by node type
+    color: bool,
+}
+
+/// Directory level of the outline; files map to their top-level entities
+#[derive(Default)]
+pub(super) struct DirEntry {
+    pub(super) dirs: BTreeMap<String, DirEntry>,
+    pub(super) files: BTreeMap<String, Vec<NodeIndex>>,
+}
+
+/// Classes, interfaces, enums and functions grouped by directory and file, with members
+/// nested under their owners. Shared by the tree and HTML outputs.
+pub(super) struct Outline {
+    /// Common directory of every shown file, or `.`
+    pub(super) root_label: String,
+    pub(super) root: DirEntry,
+    shown: HashSet<NodeIndex>,
+}
+
+impl Outline {
+    pub(super) fn build(graph: &DependencyGraph) -> Self {
+        let shown: Vec<NodeIndex> = graph
+            .node_indices()
+            .filter(|&idx| Self::is_shown(&graph[idx]))
+            .collect();
+        let shown_set: HashSet<NodeIndex> = shown.iter().copied().collect();
+
+        let paths: Vec<String> = shown
+            .iter()
+            .map(|&idx| Self::display_path(&graph[idx]))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let common_prefix = DirectoryTree::find_common_prefix(&paths);
+
+        let mut root = DirEntry::default();
+        for &idx in &shown {
+            let path = Self::display_path(&graph[idx]);
+            let relative = path.strip_prefix(&common_prefix).unwrap_or(&path);
+            let mut parts: Vec<&str> = relative.split('/').filter(|p| !p.is_empty()).collect();
+            let file_name = parts.pop().unwrap_or_default().to_string();
+
+            let mut dir = &mut root;
+            for part in parts {
+                dir = dir.dirs.entry(part.to_string()).or_default();
+            }
+            let entities = dir.files.entry(file_name).or_default();
+
+            // Members reached through `Contains` are listed under their owner instead
+            let owned = graph.edges_directed(idx, Direction::Incoming).any(|e| {
+                e.weight().edge_type == EdgeType::Contains && shown_set.contains(&e.source())
+            });
+            if !owned {
+                entities.push(idx);
+            }
+        }
+
+        let root_label = if common_prefix.is_empty() {
+            ".".to_string()
+        } else {
+            common_prefix
+        };
+        Self {
+            root_label,
+            root,
+            shown: shown_set,
+        }
+    }
+
+    /// `entities` minus those already listed, ordered by line
+    pub(super) fn ordered(
+        graph: &DependencyGraph,
+        entities: &[NodeIndex],
+        visited: &HashSet<NodeIndex>,
+    ) -> Vec<NodeIndex> {
+        let mut entities: Vec<NodeIndex> = entities
+            .iter()
+            .copied()
+            .filter(|idx| !visited.contains(idx))
+            .collect();
+        entities.sort_by_key(|&idx| (graph[idx].line_number, graph[idx].name.clone()));
+        entities.dedup();
+        entities
+    }
+
+    /// Shown entities `idx` contains
+    pub(super) fn members(&self, graph: &DependencyGraph, idx: NodeIndex) -> Vec<NodeIndex> {
+        graph
+            .edges(idx)
+            .filter(|e| {
+                e.weight().edge_type == EdgeType::Contains && self.shown.contains(&e.target())
+            })
+            .map(|e| e.target())
+            .collect()
+    }
+
+    fn display_path(node: &Node) -> String {
+        node.file_path.to_string_lossy().replace('\\', "/")
+    }
+
+    /// Structural entities only; imports, fields and external placeholders are noise here
+    fn is_shown(node: &Node) -> bool {
+        matches!(
+            node.node_type,
+            NodeType::Class | NodeType::Interface | NodeType::Enum | NodeType::Function
+        ) && node.visibility.as_deref() != Some("external")
+    }
+}
+
+impl TreeFormatter {
+    pub fn new() -> Self {
+        Self { color: false }
+    }
+
+    /// Enable ANSI colors; callers decide based on TTY detection and `NO_COLOR`
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn format_to_file(&self, graph: &DependencyGraph, output_path: &Path) -> Result<()> {
+        let content = self.format_to_string(graph)?;
+        fs::write(output_path, content)?;
+        Ok(())
+    }
+
+    /// Render the outline as a string.
+    pub fn format_to_string(&self, graph: &DependencyGraph) -> Result<String> {
+        let outline = Outline::build(graph);
+
+        let mut output = String::with_capacity(4096);
+        output.push_str(&outline.root_label);
+        output.push('\n');
+        self.format_dir(&outline.root, graph, &outline, "", &mut output);
+        Ok(output)
+    }
+
+    fn format_dir(
+        &self,
+        dir: &DirEntry,
+        graph: &DependencyGraph,
+        outline: &Outline,
+        indent: &str,
+        output: &mut String,
+    ) {
+        let total = dir.dirs.len() + dir.files.len();
+        let mut position = 0;
+
+        for (name, child) in &dir.dirs {
+            position += 1;
+            let (connector, child_indent) = Self::branch(indent, position == total);
+            output.push_str(&format!("{}{}{}/\n", indent, connector, name));
+            self.format_dir(child, graph, outline, &child_indent, output);
+        }
+
+        for (name, entities) in &dir.files {
+            position += 1;
+            let (connector, child_indent) = Self::branch(indent, position == total);
+            output.push_str(&format!("{}{}{}\n", indent, connector, name));
+            let mut visited = HashSet::new();
+            self.format_entities(
+                entities,
+                graph,
+                outline,
+                &child_indent,
+                &mut visited,
+                output,
+            );
+        }
+    }
+
+    fn format_entities(
+        &self,
+        entities: &[NodeIndex],
+        graph: &DependencyGraph,
+        outline: &Outline,
+        indent: &str,
+        visited: &mut HashSet<NodeIndex>,
+        output: &mut String,
+    ) {
+        let entities = Outline::ordered(graph, entities, visited);
+        let total = entities.len();
+        for (i, idx) in entities.into_iter().enumerate() {
+            visited.insert(idx);
+            let (connector, child_indent) = Self::branch(indent, i + 1 == total);
+            output.push_str(&format!(
+                "{}{}{} :{}\n",
+                indent,
+                connector,
+                self.label(&graph[idx]),
+                graph[idx].line_number
+            ));
+
+            let members = outline.members(graph, idx);
+            self.format_entities(&members, graph, outline, &child_indent, visited, output);
+        }
+    }
+
+    fn branch(indent: &str, is_last: bool) -> (&'static str, String) {
+        if is_last {
+            ("└─ ", format!("{}   ", indent))
+        } else {
+            ("├─ ", format!("{}│  ", indent))
+        }
+    }
+
+    fn label(&self, node: &Node) -> String {
+        let (kind, color) = match node.node_type {
+            NodeType::Class => ("class", "33"),
+            NodeType::Interface => ("interface", "36"),
+            NodeType::Enum => ("enum", "35"),
+            NodeType::Function => ("fn", "32"),
+            NodeType::Module => ("mod", "34"),
+            NodeType::Variable => ("var", "2"),
+        };
+        let suffix = if node.node_type == NodeType::Function {
+            "()"
+        } else {
+            ""
+        };
+        let text = format!("{} {}{}", kind, node.name, suffix);
+        if self.color {
+            format!("\x1b[{}m{}\x1b[0m", color, text)
+        } else {
+            text
+        }
+    }
+}
+
+impl Default for TreeFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
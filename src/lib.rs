@@ -10,11 +10,17 @@
 //! - **LLM-Optimized**: Compact format with semantic clustering and behavioral notation
 //! - **Markdown**: Traditional readable format with full details
 //! - **JSON-Compact**: Minimal token format for programmatic consumption
+//! - **Mermaid**: Flowchart block for embedding in Markdown docs
+//! - **GraphML**: XML graph for Gephi, yEd and other graph tools
+//! - **Tree**: Indented files → classes → methods outline for the terminal
+//! - **HTML**: Self-contained page with a collapsible, filterable tree for sharing
 //!
 //! ## Supported Languages
 //!
-//! Python, TypeScript, Rust, C++, JavaScript, Java, C#, Go
+//! Python, TypeScript, Rust, C, C++, JavaScript, Java, C#, Go, Ruby, Kotlin, PHP, Lua,
+//! Objective-C
 
+pub mod config;
 pub mod core;
 pub mod formatters;
 pub mod parsers;
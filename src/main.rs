@@ -1,47 +1,368 @@
 use anyhow::Result;
-use clap::{Parser, ValueEnum};
-use std::path::PathBuf;
-use std::time::Instant;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant};
 
+mod config;
 mod core;
 mod formatters;
 mod parsers;
 
-use crate::core::CodebaseAnalyzer;
+use crate::config::Config;
+use crate::core::scanner::DEFAULT_MAX_FILE_SIZE;
+use crate::core::{
+    CodebaseAnalyzer, DependencyGraph, DependencyGraphExt, EdgeType, FileScanner, GraphStats,
+    Hotspot, TestFiles, VisibilityLevel,
+};
+use crate::parsers::cache::ParseCache;
+
+const DEFAULT_OUTPUT: &str = "EMBARGO.md";
+
+/// Output directory for `--split` when none is given
+const DEFAULT_SPLIT_OUTPUT: &str = "EMBARGO";
+
+/// Names listed by `--report-unresolved`
+const UNRESOLVED_REPORT_LIMIT: usize = 20;
+
+const DEFAULT_LANGUAGES: &[&str] = &[
+    "python",
+    "typescript",
+    "javascript",
+    "c",
+    "cpp",
+    "rust",
+    "java",
+    "go",
+    "csharp",
+    "ruby",
+    "kotlin",
+    "php",
+    "lua",
+    "objc",
+];
 
 #[derive(Debug, Clone, Parser)]
 #[command(
     name = "embargo",
     version = "0.1.0",
     author = "embargo developers",
-    about = "Ultrafast codebase dependency extractor - Sub-1s analysis"
+    about = "Ultrafast codebase dependency extractor - Sub-1s analysis",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
 )]
 struct Cli {
-    /// Input directory to analyze
-    #[arg(short, long, value_name = "PATH")]
-    input: PathBuf,
-
-    /// Output file path
-    #[arg(short, long, value_name = "FILE", default_value = "EMBARGO.md")]
-    output: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    /// Comma-separated list of languages to analyze
+    /// Input directory or single source file to analyze, `-` to read file paths from stdin,
+    /// or `@FILE` to read newline-delimited paths from FILE. Repeat it (or list directories
+    /// separated by commas) to merge several roots into one graph
     #[arg(
         short,
         long,
-        value_name = "LANGS",
+        value_name = "PATH",
         value_delimiter = ',',
-        default_value = "python,typescript,javascript,cpp,rust,java,go,csharp"
+        required = true
     )]
-    languages: Vec<String>,
+    input: Vec<PathBuf>,
 
-    /// Output format: markdown, llm-optimized, json-compact
-    #[arg(short, long, value_name = "FORMAT", value_enum, default_value_t = OutputFormat::LlmOptimized)]
-    format: OutputFormat,
+    /// Output file path, or `-` to write to stdout [default: EMBARGO.md]
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Treat --output as a directory [default: EMBARGO] and write one file per top-level
+    /// directory of the input, plus an index.md listing the edges between them
+    #[arg(long)]
+    split: bool,
+
+    /// Comma-separated list of languages to analyze, or `auto` to enable those found in the
+    /// input (`auto,rust,go` limits detection to the listed ones) [default: all supported]
+    #[arg(short, long, value_name = "LANGS", value_delimiter = ',')]
+    languages: Option<Vec<String>>,
+
+    /// Output format: markdown, llm-optimized, json-compact, json-full, mermaid, graphml, tree,
+    /// html, sqlite, csv, symbols, or `all` to write every format from one analysis, each to
+    /// `<output stem>.<format>.<ext>` next to --output [default: llm-optimized]
+    #[arg(short, long, value_name = "FORMAT", value_enum)]
+    format: Option<OutputFormat>,
+
+    /// Output verbosity for llm-optimized format: compact, standard, verbose [default: standard]
+    #[arg(long, value_name = "LEVEL", value_enum)]
+    verbosity: Option<Verbosity>,
+
+    /// Settings file; defaults to embargo.toml in the input directory when present.
+    /// Command-line flags override its values
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Levels of nested calls to list under each function in llm-optimized output
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    max_depth: usize,
+
+    /// Append a one-line docstring summary to each entity in llm-optimized output
+    #[arg(long)]
+    docstrings: bool,
+
+    /// Show each definition's full line span, e.g. `foo [L42-L88]`, in markdown output
+    #[arg(long)]
+    line_ranges: bool,
+
+    /// Tag functions and classes nothing references as [DEAD?] in llm-optimized output
+    #[arg(long)]
+    unused: bool,
+
+    /// Tag functions with more branch points than N as [CX:n] in llm-optimized output
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    complexity_threshold: usize,
+
+    /// Tag functions called from more than N places as [HOT] in llm-optimized output
+    /// [default: top decile of the graph's functions by caller count, at least 2]
+    #[arg(long, value_name = "N")]
+    hot_threshold: Option<usize>,
+
+    /// Fold clusters with fewer than N nodes into one MISC cluster in llm-optimized output
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    min_nodes: usize,
+
+    /// Collapse directory chains without files of their own (`com/example/app/`) in the
+    /// llm-optimized directory tree
+    #[arg(long)]
+    flatten_namespaces: bool,
+
+    /// Name methods after their class in llm-optimized output (`User.process`), so
+    /// same-named methods of different classes stay distinguishable
+    #[arg(long)]
+    qualified_names: bool,
+
+    /// Show getter/setter pairs as one `x{get,set}` entry in llm-optimized output
+    #[arg(long)]
+    collapse_accessors: bool,
+
+    /// Add a LAYERS section to llm-optimized output grouping files by call depth, from
+    /// the files nothing calls down to the ones everything calls
+    #[arg(long)]
+    layers: bool,
+
+    /// Show signatures as written in llm-optimized output instead of abbreviating common
+    /// types (`String` → `str`, `Option<T>` → `?T`)
+    #[arg(long)]
+    no_signature_compression: bool,
+
+    /// Add per-file node counts, outgoing edges, call depth and languages to json-compact
+    /// output, under `file_metrics`
+    #[arg(long)]
+    file_metrics: bool,
+
+    /// Link each file to the files it imports with Import edges, adding a node per file
+    #[arg(long)]
+    import_edges: bool,
+
+    /// Link each class to the classes its fields and properties hold with Composes edges
+    /// (Java, C#, TypeScript, Go)
+    #[arg(long)]
+    composition_edges: bool,
+
+    /// Directory prefix for llm-optimized output as PATTERN=PREFIX (repeatable, first match
+    /// wins), e.g. 'handlers/=H'. Replaces the built-in services/ → S, widgets/ → W, ... set
+    #[arg(long, value_name = "PATTERN=PREFIX")]
+    prefix: Vec<String>,
+
+    /// Cluster for llm-optimized output as GLOB=NAME (repeatable, first match wins), e.g.
+    /// 'src/billing/**=BILLING'. Replaces the built-in clusters; unmatched files are UNCLASSIFIED
+    #[arg(long, value_name = "GLOB=NAME")]
+    cluster: Vec<String>,
+
+    /// Order calls in llm-optimized `→{...}` lists as PATTERN=N (repeatable, first match
+    /// wins, lower first), e.g. 'log_*=9' to list logging last or '*_service=0' first
+    #[arg(long, value_name = "PATTERN=N")]
+    call_priority: Vec<String>,
+
+    /// Leave calls to these functions out of markdown and llm-optimized output (comma-separated,
+    /// repeatable), e.g. 'len,println!,String::from'. JSON and other formats keep them
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    hide_calls: Vec<String>,
+
+    /// Leave calls made from fewer than N call sites out of markdown and llm-optimized output
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    min_edge_weight: usize,
+
+    /// Glob of function names to tag [ENTRY] in llm-optimized output (repeatable), e.g.
+    /// 'handle_*'. HTTP route handlers (@app.get, @GetMapping, ...) are tagged regardless
+    #[arg(long, value_name = "GLOB")]
+    entry_pattern: Vec<String>,
+
+    /// Glob of files or directories to skip (repeatable), e.g. 'vendor/**' or '*.test.ts'
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<String>,
+
+    /// Do not honor .gitignore files found in the input tree
+    #[arg(long)]
+    no_gitignore: bool,
+
+    /// Skip source files larger than BYTES, e.g. vendored bundles; 0 for no limit
+    #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_MAX_FILE_SIZE)]
+    max_file_size: u64,
+
+    /// Skip test files (`*_test.go`, `test_*.py`, `*.spec.ts`, `FooTest.java`, Rust `tests/`, ...)
+    #[arg(long, conflicts_with = "only_tests")]
+    exclude_tests: bool,
+
+    /// Analyze only test files, to map how the test suite is structured
+    #[arg(long)]
+    only_tests: bool,
 
-    /// Output verbosity for llm-optimized format: compact, standard, verbose
-    #[arg(long, value_name = "LEVEL", value_enum, default_value_t = Verbosity::Standard)]
-    verbosity: Verbosity,
+    /// Reparse every file instead of reusing the .embargo-cache next to the output
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Print node/edge counts, most-called functions and call depth after analysis
+    #[arg(long)]
+    stats: bool,
+
+    /// Print the K functions to look at first, ranked by callers, branch count and calls
+    /// made, each scaled to the graph's maximum, with their scores and file:line
+    #[arg(long, value_name = "K")]
+    top_k: Option<usize>,
+
+    /// Print how long scanning, parsing, index building, call resolution and formatting took
+    #[arg(long)]
+    timing: bool,
+
+    /// List the most frequent call targets that matched no definition and were dropped
+    #[arg(long)]
+    report_unresolved: bool,
+
+    /// List files tree-sitter could not fully parse on stderr, with their error count and
+    /// where the first error starts; their nodes and edges may be incomplete
+    #[arg(long)]
+    parser_errors: bool,
+
+    /// Exit with an error when any file had syntax errors, after writing the output
+    #[arg(long)]
+    fail_on_parse_error: bool,
+
+    /// Print the shortest chain of calls from one function to another, e.g. to see how
+    /// `main` ends up in `write_output`. Every pair of same-named matches is tried. The
+    /// chain is printed on stdout in place of the usual output; no file is written
+    #[arg(long, num_args = 2, value_names = ["FROM", "TO"])]
+    path: Option<Vec<String>>,
+
+    /// Report files parsed so far (and the current language) on stderr during long scans
+    #[arg(long)]
+    progress: bool,
+
+    /// Keep only what this node (matched by id, else by name) transitively depends on
+    #[arg(long, value_name = "NODE_NAME")]
+    entry: Option<String>,
+
+    /// Build node ids from file path and qualified name instead of line number, so ids
+    /// survive edits that only move code up or down (e.g. `src_app.py:function:Cache.get`)
+    #[arg(long)]
+    stable_ids: bool,
+
+    /// Store file paths (and the ids built from them) relative to the input directory, so
+    /// saved graphs are the same on every machine. Also settable in embargo.toml
+    #[arg(long)]
+    root_relative_paths: bool,
+
+    /// Keep file paths as given, overriding `root_relative_paths = true` in embargo.toml
+    #[arg(long, conflicts_with = "root_relative_paths")]
+    absolute_paths: bool,
+
+    /// Only report files changed since this git ref, plus their direct neighbours
+    #[arg(long, value_name = "REF")]
+    since: Option<String>,
+
+    /// Drop symbols less visible than LEVEL, with their members: all, public, public-protected
+    #[arg(long, value_name = "LEVEL", value_enum, default_value_t = VisibilityFilter::All)]
+    visibility: VisibilityFilter,
+
+    /// Replace names, paths and signatures with stable pseudonyms (`Class_7`, `fn_42`,
+    /// `dir_3/file_9.py`), keeping the graph's structure, so the output can be shared
+    #[arg(long)]
+    anonymize: bool,
+
+    /// Write the pseudonym-to-original mapping of --anonymize to FILE as JSON
+    #[arg(long, value_name = "FILE", requires = "anonymize")]
+    anonymize_map: Option<PathBuf>,
+
+    /// Keep running and regenerate the output whenever a source file under the input changes
+    #[arg(long)]
+    watch: bool,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum Command {
+    /// Answer questions about a graph saved with `--format json-full`, without re-parsing
+    Query(QueryArgs),
+    /// List nodes and edges added or removed between two graphs saved with
+    /// `--format json-full`, grouped by file; save both with `--stable-ids` so moved code
+    /// is not reported
+    Diff(DiffArgs),
+    /// Analyze and format a tree repeatedly without the parse cache and report the mean
+    /// and 95th percentile time of each phase
+    Bench(BenchArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+#[command(group(
+    clap::ArgGroup::new("question")
+        .required(true)
+        .multiple(true)
+        .args(["callers", "callees", "path"])
+))]
+struct QueryArgs {
+    /// Graph file written by `--format json-full`
+    #[arg(value_name = "GRAPH")]
+    graph: PathBuf,
+
+    /// List the functions calling NAME
+    #[arg(long, value_name = "NAME")]
+    callers: Option<String>,
+
+    /// List the functions NAME calls
+    #[arg(long, value_name = "NAME")]
+    callees: Option<String>,
+
+    /// Shortest chain of calls leading from one function to another
+    #[arg(long, num_args = 2, value_names = ["FROM", "TO"])]
+    path: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Args)]
+struct DiffArgs {
+    /// Graph before the change
+    #[arg(value_name = "OLD")]
+    old: PathBuf,
+
+    /// Graph after the change
+    #[arg(value_name = "NEW")]
+    new: PathBuf,
+}
+
+#[derive(Debug, Clone, Args)]
+struct BenchArgs {
+    /// Directory to analyze
+    #[arg(value_name = "PATH")]
+    target: PathBuf,
+
+    /// Number of timed runs
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    iterations: u32,
+
+    /// Comma-separated list of languages to analyze [default: all supported]
+    #[arg(short, long, value_name = "LANGS", value_delimiter = ',')]
+    languages: Option<Vec<String>>,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
@@ -50,6 +371,15 @@ enum OutputFormat {
     Markdown,
     LlmOptimized,
     JsonCompact,
+    JsonFull,
+    Mermaid,
+    Graphml,
+    Tree,
+    Html,
+    Sqlite,
+    Csv,
+    Symbols,
+    All,
 }
 
 /// Output verbosity level for llm-optimized format.
@@ -65,100 +395,1161 @@ enum Verbosity {
     Verbose,
 }
 
+/// Minimum visibility kept by `--visibility`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+enum VisibilityFilter {
+    /// Keep everything
+    All,
+    /// Public symbols only
+    Public,
+    /// Public and protected symbols
+    PublicProtected,
+}
+
+impl VisibilityFilter {
+    fn min_level(self) -> Option<VisibilityLevel> {
+        match self {
+            VisibilityFilter::All => None,
+            VisibilityFilter::Public => Some(VisibilityLevel::Public),
+            VisibilityFilter::PublicProtected => Some(VisibilityLevel::Protected),
+        }
+    }
+}
+
 impl OutputFormat {
     fn as_str(self) -> &'static str {
         match self {
             OutputFormat::Markdown => "markdown",
             OutputFormat::LlmOptimized => "llm-optimized",
             OutputFormat::JsonCompact => "json-compact",
+            OutputFormat::JsonFull => "json-full",
+            OutputFormat::Mermaid => "mermaid",
+            OutputFormat::Graphml => "graphml",
+            OutputFormat::Tree => "tree",
+            OutputFormat::Html => "html",
+            OutputFormat::Sqlite => "sqlite",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Symbols => "symbols",
+            OutputFormat::All => "all",
+        }
+    }
+
+    /// File extension for `--split` module files
+    fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Markdown | OutputFormat::LlmOptimized => "md",
+            OutputFormat::JsonCompact | OutputFormat::JsonFull | OutputFormat::Symbols => "json",
+            OutputFormat::Mermaid => "mmd",
+            OutputFormat::Graphml => "graphml",
+            OutputFormat::Tree => "txt",
+            OutputFormat::Html => "html",
+            OutputFormat::Sqlite => "db",
+            OutputFormat::Csv => "csv",
+            OutputFormat::All => unreachable!("--format all is expanded before writing"),
+        }
+    }
+
+    /// Every concrete format, in the order `--format all` writes them
+    fn each() -> impl Iterator<Item = OutputFormat> {
+        OutputFormat::value_variants()
+            .iter()
+            .copied()
+            .filter(|format| *format != OutputFormat::All)
+    }
+
+    /// Where `--format all` writes this format: `EMBARGO.md` becomes `EMBARGO.mermaid.mmd`
+    fn sibling_path(self, output: &Path) -> PathBuf {
+        let stem = output
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        output.with_file_name(format!("{}.{}.{}", stem, self.as_str(), self.extension()))
+    }
+}
+
+/// Deepest directory holding every path, a file counting as its parent directory: the
+/// root `--root-relative-paths` makes paths relative to
+fn common_directory(paths: &[PathBuf]) -> PathBuf {
+    let mut dirs = paths.iter().map(|path| {
+        if path.is_dir() {
+            path.as_path()
+        } else {
+            path.parent().unwrap_or_else(|| Path::new(""))
+        }
+    });
+    let Some(first) = dirs.next() else {
+        return PathBuf::new();
+    };
+    let mut common: Vec<Component> = first.components().collect();
+    for dir in dirs {
+        let shared = common
+            .iter()
+            .zip(dir.components())
+            .take_while(|(a, b)| **a == *b)
+            .count();
+        common.truncate(shared);
+    }
+    common.iter().collect()
+}
+
+/// Whether `input` is `-` or `@FILE` rather than a directory
+fn is_file_list_input(input: &Path) -> bool {
+    let input_str = input.to_string_lossy();
+    input_str == "-" || input_str.starts_with('@')
+}
+
+/// Resolve `-` (stdin) and `@FILE` inputs to an explicit file list; `None` means a directory.
+fn read_input_file_list(input: &Path) -> Result<Option<Vec<PathBuf>>> {
+    let input_str = input.to_string_lossy();
+    let contents = if input_str == "-" {
+        let mut buffer = String::new();
+        std::io::stdin().read_to_string(&mut buffer)?;
+        buffer
+    } else if let Some(list_path) = input_str.strip_prefix('@') {
+        std::fs::read_to_string(list_path)
+            .map_err(|e| anyhow::anyhow!("Failed to read file list {}: {}", list_path, e))?
+    } else {
+        return Ok(None);
+    };
+
+    let paths = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect();
+    Ok(Some(paths))
+}
+
+/// Split a `--call-priority` value such as `log_*=9` into its pattern and priority.
+fn parse_call_priority(spec: &str) -> Result<(String, u8)> {
+    match spec.split_once('=') {
+        Some((pattern, priority)) if !pattern.is_empty() => match priority.parse() {
+            Ok(priority) => Ok((pattern.to_string(), priority)),
+            Err(_) => anyhow::bail!("Invalid call priority '{}': N must be 0-255", spec),
+        },
+        _ => anyhow::bail!(
+            "Invalid call priority '{}': expected PATTERN=N, e.g. log_*=9",
+            spec
+        ),
+    }
+}
+
+/// Split a `--prefix` value such as `handlers/=H` into its pattern and prefix.
+fn parse_semantic_prefix(spec: &str) -> Result<(String, String)> {
+    match spec.split_once('=') {
+        Some((pattern, prefix)) if !pattern.is_empty() && !prefix.is_empty() => {
+            Ok((pattern.to_string(), prefix.to_string()))
+        }
+        _ => anyhow::bail!(
+            "Invalid prefix '{}': expected PATTERN=PREFIX, e.g. handlers/=H",
+            spec
+        ),
+    }
+}
+
+/// Split a `--cluster` value such as `src/billing/**=BILLING` into its glob and name.
+fn parse_cluster(spec: &str) -> Result<(String, String)> {
+    match spec.split_once('=') {
+        Some((pattern, name)) if !pattern.is_empty() && !name.is_empty() => {
+            Ok((pattern.to_string(), name.to_string()))
+        }
+        _ => anyhow::bail!(
+            "Invalid cluster '{}': expected GLOB=NAME, e.g. src/billing/**=BILLING",
+            spec
+        ),
+    }
+}
+
+/// Restrict the graph to everything reachable from `entry`. An exact node id wins;
+/// otherwise every node with that name is used as a starting point.
+fn prune_to_entry(graph: &DependencyGraph, entry: &str) -> Result<DependencyGraph> {
+    let mut start_ids: Vec<&str> = graph
+        .node_weights()
+        .filter(|node| node.id == entry)
+        .map(|node| node.id.as_str())
+        .collect();
+    if start_ids.is_empty() {
+        start_ids = graph
+            .node_weights()
+            .filter(|node| node.name == entry)
+            .map(|node| node.id.as_str())
+            .collect();
+    }
+    if start_ids.is_empty() {
+        anyhow::bail!("No node named or identified by '{}' in the graph", entry);
+    }
+
+    let all_edge_types = [
+        EdgeType::Import,
+        EdgeType::Call,
+        EdgeType::Inheritance,
+        EdgeType::Implements,
+        EdgeType::Uses,
+        EdgeType::Contains,
+        EdgeType::Overrides,
+        EdgeType::Throws,
+        EdgeType::Composes,
+    ];
+    let reachable: Vec<_> = start_ids
+        .iter()
+        .flat_map(|id| graph.reachable_from(id, &all_edge_types))
+        .collect();
+    Ok(graph.induced_subgraph(&reachable))
+}
+
+/// Keep nodes defined in `files` and every node one edge away from them, so calls
+/// into and out of the changed code survive.
+fn scope_to_files(graph: &DependencyGraph, files: &[PathBuf]) -> DependencyGraph {
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let changed: HashSet<PathBuf> = files.iter().map(|path| canonical(path)).collect();
+
+    let mut path_cache: HashMap<&Path, bool> = HashMap::new();
+    let mut keep: HashSet<NodeIndex> = HashSet::new();
+    for idx in graph.node_indices() {
+        let file_path = graph[idx].file_path.as_path();
+        let in_diff = *path_cache
+            .entry(file_path)
+            .or_insert_with(|| changed.contains(&canonical(file_path)));
+        if in_diff {
+            keep.insert(idx);
+            keep.extend(graph.neighbors_undirected(idx));
+        }
+    }
+
+    let mut keep: Vec<_> = keep.into_iter().collect();
+    keep.sort();
+    graph.induced_subgraph(&keep)
+}
+
+/// Write one file per top-level module into `dir` (see `top_level_modules`) and an
+/// `index.md` linking them. Each module gets the subgraph of its own nodes; edges to
+/// other modules are counted in the index and, when `summarize_in_modules`, appended to
+/// the module file too. Returns the index path.
+fn write_split_output(
+    graph: &DependencyGraph,
+    dir: &Path,
+    format: OutputFormat,
+    summarize_in_modules: bool,
+    write_module: impl Fn(&DependencyGraph, &Path) -> Result<()>,
+) -> Result<PathBuf> {
+    use std::collections::BTreeMap;
+    use std::io::Write;
+
+    std::fs::create_dir_all(dir)?;
+    let modules = graph.top_level_modules();
+    let module_of: HashMap<NodeIndex, &str> = modules
+        .iter()
+        .flat_map(|(name, nodes)| nodes.iter().map(move |&idx| (idx, name.as_str())))
+        .collect();
+
+    // (from, to) → edge type → count
+    let mut crossing: BTreeMap<(&str, &str), BTreeMap<String, usize>> = BTreeMap::new();
+    for edge_ref in graph.edge_references() {
+        let from = module_of[&edge_ref.source()];
+        let to = module_of[&edge_ref.target()];
+        if from != to {
+            *crossing
+                .entry((from, to))
+                .or_default()
+                .entry(format!("{:?}", edge_ref.weight().edge_type))
+                .or_insert(0) += 1;
+        }
+    }
+    let describe = |arrow: &str, other: &str, counts: &BTreeMap<String, usize>| {
+        let by_type: Vec<String> = counts
+            .iter()
+            .map(|(edge_type, count)| format!("{} {}", edge_type, count))
+            .collect();
+        format!(
+            "{} {}: {} ({})",
+            arrow,
+            other,
+            counts.values().sum::<usize>(),
+            by_type.join(", ")
+        )
+    };
+
+    let mut index = String::from("# EMBARGO modules\n\n");
+    for (name, nodes) in &modules {
+        let file_name = format!("{}.{}", name, format.extension());
+        let path = dir.join(&file_name);
+        write_module(&graph.induced_subgraph(nodes), &path)?;
+
+        // Outgoing first, then incoming
+        let outgoing = crossing
+            .iter()
+            .filter(|((from, _), _)| from == name)
+            .map(|((_, to), counts)| describe("→", to, counts));
+        let incoming = crossing
+            .iter()
+            .filter(|((_, to), _)| to == name)
+            .map(|((from, _), counts)| describe("←", from, counts));
+        let summary: Vec<String> = outgoing.chain(incoming).collect();
+        if summarize_in_modules && !summary.is_empty() {
+            let mut file = std::fs::OpenOptions::new().append(true).open(&path)?;
+            write!(file, "\n## CROSS_MODULE_EDGES\n{}\n", summary.join("\n"))?;
+        }
+
+        index.push_str(&format!(
+            "- [{}]({}): {} nodes\n",
+            name,
+            file_name,
+            nodes.len()
+        ));
+        for line in summary {
+            index.push_str(&format!("  {}\n", line));
+        }
+    }
+
+    let index_path = dir.join("index.md");
+    std::fs::write(&index_path, index)?;
+    Ok(index_path)
+}
+
+/// `name (file:line)` for query output
+fn describe_node(graph: &DependencyGraph, idx: NodeIndex) -> String {
+    let node = &graph[idx];
+    format!(
+        "{} ({}:{})",
+        node.name,
+        node.file_path.display(),
+        node.line_number
+    )
+}
+
+/// Indices of every node named `name`; an error when there are none
+fn nodes_named(graph: &DependencyGraph, name: &str) -> Result<Vec<NodeIndex>> {
+    let matches: Vec<NodeIndex> = graph
+        .node_indices()
+        .filter(|&idx| graph[idx].name == name)
+        .collect();
+    if matches.is_empty() {
+        anyhow::bail!("No node named '{}' in the graph", name);
+    }
+    Ok(matches)
+}
+
+/// Shortest call chain between each pair of nodes named `from` and `to`, rendered as
+/// `a (file:line) → b (file:line) → ...`, shortest first. Several nodes may share a
+/// name, so each pair that is connected gets its own line.
+fn describe_call_paths(graph: &DependencyGraph, from: &str, to: &str) -> Result<Vec<String>> {
+    let targets = nodes_named(graph, to)?;
+    let mut paths: Vec<Vec<NodeIndex>> = nodes_named(graph, from)?
+        .into_iter()
+        .flat_map(|start| {
+            targets
+                .iter()
+                .filter_map(move |&end| graph.shortest_path(start, end, &[EdgeType::Call]))
+        })
+        .collect();
+    if paths.is_empty() {
+        return Ok(vec![format!("No call path from {} to {}", from, to)]);
+    }
+    paths.sort_by_key(Vec::len);
+
+    let mut lines = vec![format!("Path from {} to {}:", from, to)];
+    for path in paths {
+        let hops: Vec<String> = path.iter().map(|&idx| describe_node(graph, idx)).collect();
+        lines.push(format!("  {}", hops.join(" → ")));
+    }
+    Ok(lines)
+}
+
+/// Load a graph saved with `--format json-full`
+fn read_graph(path: &Path) -> Result<DependencyGraph> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read graph {}: {}", path.display(), e))?;
+    DependencyGraph::from_json(&json)
+}
+
+/// Load a saved graph and print the answers to each question asked
+fn run_query(args: QueryArgs) -> Result<()> {
+    let graph = read_graph(&args.graph)?;
+
+    let neighbours = |name: &str, direction: Direction| -> Result<Vec<NodeIndex>> {
+        let mut found = Vec::new();
+        for idx in nodes_named(&graph, name)? {
+            for edge_ref in graph.edges_directed(idx, direction) {
+                if edge_ref.weight().edge_type != EdgeType::Call {
+                    continue;
+                }
+                let other = match direction {
+                    Direction::Incoming => edge_ref.source(),
+                    Direction::Outgoing => edge_ref.target(),
+                };
+                if !found.contains(&other) {
+                    found.push(other);
+                }
+            }
+        }
+        Ok(found)
+    };
+
+    if let Some(name) = &args.callers {
+        let found = neighbours(name, Direction::Incoming)?;
+        println!("Callers of {}:", name);
+        for idx in found {
+            println!("  {}", describe_node(&graph, idx));
+        }
+    }
+    if let Some(name) = &args.callees {
+        let found = neighbours(name, Direction::Outgoing)?;
+        println!("Callees of {}:", name);
+        for idx in found {
+            println!("  {}", describe_node(&graph, idx));
+        }
+    }
+    if let Some([from, to]) = args.path.as_deref() {
+        for line in describe_call_paths(&graph, from, to)? {
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
+
+fn run_diff(args: DiffArgs) -> Result<()> {
+    let old = read_graph(&args.old)?;
+    let new = read_graph(&args.new)?;
+    let diff = crate::core::diff::GraphDiff::between(&old, &new);
+    print!("{}", diff.render());
+    Ok(())
+}
+
+/// Milliseconds with a fractional part, for phase timings well under a second
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Mean and nearest-rank 95th percentile of `samples`, which must not be empty
+fn mean_and_p95(samples: &[Duration]) -> (Duration, Duration) {
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+    let mean = sorted.iter().sum::<Duration>() / sorted.len() as u32;
+    let rank = (sorted.len() * 95).div_ceil(100);
+    (mean, sorted[rank - 1])
+}
+
+fn run_bench(args: BenchArgs) -> Result<()> {
+    let languages: Vec<String> = args.languages.unwrap_or_else(|| {
+        DEFAULT_LANGUAGES
+            .iter()
+            .map(|lang| lang.to_string())
+            .collect()
+    });
+    let language_refs: Vec<&str> = languages.iter().map(String::as_str).collect();
+
+    let phases = ["scan", "parse", "index", "resolve", "format", "total"];
+    let mut samples: Vec<Vec<Duration>> = vec![Vec::new(); phases.len()];
+    let mut node_count = 0;
+    for _ in 0..args.iterations {
+        let run_start = Instant::now();
+        // A fresh in-memory cache per run, so every run parses every file
+        let mut analyzer = CodebaseAnalyzer::new()
+            .with_parse_cache(ParseCache::in_memory_only())
+            .with_quiet(true);
+        let graph = analyzer.analyze(&args.target, &language_refs)?;
+        let format_start = Instant::now();
+        crate::formatters::LLMOptimizedFormatter::new().format_to_string(&graph)?;
+        let format = format_start.elapsed();
+
+        let timings = analyzer.timings();
+        let run = [
+            timings.scan,
+            timings.parse,
+            timings.index,
+            timings.resolve,
+            format,
+            run_start.elapsed(),
+        ];
+        for (phase, duration) in samples.iter_mut().zip(run) {
+            phase.push(duration);
+        }
+        node_count = graph.node_count();
+    }
+
+    println!(
+        "{}: {} runs, {} nodes",
+        args.target.display(),
+        args.iterations,
+        node_count
+    );
+    println!("{:<8} {:>10} {:>10}", "phase", "mean", "p95");
+    for (phase, durations) in phases.iter().zip(&samples) {
+        let (mean, p95) = mean_and_p95(durations);
+        println!(
+            "{:<8} {:>8.1}ms {:>8.1}ms",
+            phase,
+            millis(mean),
+            millis(p95)
+        );
+    }
+    Ok(())
+}
+
+/// Quiet period after the last change before regenerating, so an editor's burst of
+/// writes (or a branch checkout) costs one analysis rather than dozens
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Run once, then rerun after every batch of source changes. The on-disk parse cache
+/// carries over between runs, so only the edited files are reparsed.
+fn watch(cli: Cli) -> Result<()> {
+    use notify::{RecursiveMode, Watcher};
+
+    if let Some(list) = cli.input.iter().find(|path| is_file_list_input(path)) {
+        anyhow::bail!(
+            "--watch needs directories to watch, not the file list {}",
+            list.display()
+        );
+    }
+    if cli.output == Some(PathBuf::from("-")) {
+        anyhow::bail!("--watch writes to a file; pass --output FILE instead of -");
+    }
+
+    run(cli.clone())?;
+
+    let (sender, events) = std::sync::mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    for input in &cli.input {
+        watcher.watch(input, RecursiveMode::Recursive)?;
+    }
+    let all_languages: Vec<&str> = DEFAULT_LANGUAGES.to_vec();
+    // Our own output, cache and editor swap files land in the tree too; only react to
+    // files some parser would pick up
+    let touches_sources = |event: notify::Result<notify::Event>| match event {
+        Ok(event) => !FileScanner::new()
+            .scan_files(&event.paths, &all_languages)
+            .is_empty(),
+        Err(e) => {
+            eprintln!("Watch error: {}", e);
+            false
+        }
+    };
+
+    println!("Watching for changes (Ctrl-C to stop)");
+    loop {
+        let event = events.recv()?;
+        if !touches_sources(event) {
+            continue;
+        }
+        while events.recv_timeout(WATCH_DEBOUNCE).is_ok() {}
+
+        let cycle_start = Instant::now();
+        match run(cli.clone()) {
+            Ok(()) => println!("Regenerated in {}ms", cycle_start.elapsed().as_millis()),
+            // A half-saved file should not end the session
+            Err(e) => eprintln!("Regeneration failed: {}", e),
         }
     }
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
-    run(cli)
+    match cli.command {
+        Some(Command::Query(args)) => run_query(args),
+        Some(Command::Diff(args)) => run_diff(args),
+        Some(Command::Bench(args)) => run_bench(args),
+        None if cli.watch => watch(cli),
+        None => run(cli),
+    }
 }
 
 fn run(cli: Cli) -> Result<()> {
     let Cli {
-        input,
+        command: _,
+        input: inputs,
         output,
+        split,
         languages,
         format,
         verbosity,
+        config,
+        max_depth,
+        docstrings,
+        line_ranges,
+        unused,
+        complexity_threshold,
+        hot_threshold,
+        min_nodes,
+        flatten_namespaces,
+        qualified_names,
+        collapse_accessors,
+        layers,
+        no_signature_compression,
+        file_metrics,
+        import_edges,
+        composition_edges,
+        prefix,
+        cluster,
+        call_priority,
+        hide_calls,
+        min_edge_weight,
+        entry_pattern,
+        exclude,
+        no_gitignore,
+        max_file_size,
+        exclude_tests,
+        only_tests,
+        no_cache,
+        stats,
+        top_k,
+        timing,
+        report_unresolved,
+        parser_errors,
+        fail_on_parse_error,
+        path,
+        progress,
+        entry,
+        stable_ids,
+        root_relative_paths,
+        absolute_paths,
+        since,
+        visibility,
+        anonymize,
+        anonymize_map,
+        watch: _,
     } = cli;
+    // Config discovery and `--since` look at the first root
+    let Some(input) = inputs.first().cloned() else {
+        anyhow::bail!("--input is required");
+    };
 
     let start_time = Instant::now();
 
-    let normalized_languages: Vec<String> = languages
+    let config = match &config {
+        Some(path) => Some((path.clone(), Config::from_file(path)?)),
+        None if input.is_dir() => Config::discover(&input)?,
+        None => None,
+    };
+    let (config_path, config) = match config {
+        Some((path, config)) => (Some(path), config),
+        None => (None, Config::default()),
+    };
+
+    let default_output = if split {
+        DEFAULT_SPLIT_OUTPUT
+    } else {
+        DEFAULT_OUTPUT
+    };
+    let output = output
+        .or(config.output)
+        .unwrap_or_else(|| PathBuf::from(default_output));
+    let languages = languages.or(config.languages).unwrap_or_else(|| {
+        DEFAULT_LANGUAGES
+            .iter()
+            .map(|lang| lang.to_string())
+            .collect()
+    });
+    let format = match (format, &config.format) {
+        (Some(format), _) => format,
+        (None, Some(name)) => OutputFormat::from_str(name, true)
+            .map_err(|e| anyhow::anyhow!("Invalid format in config: {}", e))?,
+        (None, None) => OutputFormat::LlmOptimized,
+    };
+    let verbosity = match (verbosity, &config.verbosity) {
+        (Some(verbosity), _) => verbosity,
+        (None, Some(name)) => Verbosity::from_str(name, true)
+            .map_err(|e| anyhow::anyhow!("Invalid verbosity in config: {}", e))?,
+        (None, None) => Verbosity::default(),
+    };
+    let semantic_prefixes = if prefix.is_empty() {
+        config.semantic_prefixes.unwrap_or_default()
+    } else {
+        prefix
+    };
+    let semantic_prefixes = semantic_prefixes
+        .iter()
+        .map(|spec| parse_semantic_prefix(spec))
+        .collect::<Result<Vec<_>>>()?;
+    let clusters = if cluster.is_empty() {
+        config.clusters.unwrap_or_default()
+    } else {
+        cluster
+    };
+    let clusters = clusters
+        .iter()
+        .map(|spec| parse_cluster(spec))
+        .collect::<Result<Vec<_>>>()?;
+    let call_priorities = if call_priority.is_empty() {
+        config.call_priorities.unwrap_or_default()
+    } else {
+        call_priority
+    };
+    let call_priorities = call_priorities
+        .iter()
+        .map(|spec| parse_call_priority(spec))
+        .collect::<Result<Vec<_>>>()?;
+    let root_relative_paths =
+        !absolute_paths && (root_relative_paths || config.root_relative_paths.unwrap_or(false));
+    // Config excludes are project-wide; flags add to them rather than replace them
+    let exclude: Vec<String> = config.exclude.into_iter().chain(exclude).collect();
+
+    // With `-o -` the graph owns stdout, so status lines move to stderr
+    let to_stdout = output == Path::new("-");
+    if to_stdout && format == OutputFormat::Sqlite {
+        anyhow::bail!("SQLite output is a database file; pass --output FILE instead of -");
+    }
+    if to_stdout && split {
+        anyhow::bail!("--split writes a directory of files; pass --output DIR instead of -");
+    }
+    if format == OutputFormat::All && (to_stdout || split) {
+        anyhow::bail!(
+            "--format all writes one file per format; pass --output FILE without --split"
+        );
+    }
+    // `--path` answers a question instead of writing output: the chain is all stdout gets
+    let status_to_stderr = to_stdout || path.is_some();
+    macro_rules! status {
+        ($($arg:tt)*) => {
+            if status_to_stderr {
+                eprintln!($($arg)*);
+            } else {
+                println!($($arg)*);
+            }
+        };
+    }
+
+    let mut normalized_languages: Vec<String> = languages
         .into_iter()
         .map(|lang| lang.trim().to_string())
         .filter(|lang| !lang.is_empty())
         .collect();
+
+    let file_scanner = FileScanner::new()
+        .with_excludes(&exclude)?
+        .with_gitignore(!no_gitignore)
+        .with_max_file_size((max_file_size > 0).then_some(max_file_size))
+        .with_test_files(if exclude_tests {
+            TestFiles::Exclude
+        } else if only_tests {
+            TestFiles::Only
+        } else {
+            TestFiles::Include
+        });
+    let input_file_list = if inputs.len() == 1 {
+        match read_input_file_list(&input)? {
+            Some(paths) => Some(paths),
+            // A lone source file goes straight to its parser instead of a directory walk
+            None if input.is_file() => Some(vec![input.clone()]),
+            None => None,
+        }
+    } else {
+        if let Some(list) = inputs.iter().find(|path| is_file_list_input(path)) {
+            anyhow::bail!(
+                "{} reads a file list and cannot be combined with other inputs",
+                list.display()
+            );
+        }
+        None
+    };
+
+    // `auto` enables the parsers for languages actually present; any languages listed
+    // next to it narrow what detection may pick
+    let auto_detect = normalized_languages.iter().any(|lang| lang == "auto");
+    if auto_detect {
+        normalized_languages.retain(|lang| lang != "auto");
+        let candidates: Vec<&str> = if normalized_languages.is_empty() {
+            DEFAULT_LANGUAGES.to_vec()
+        } else {
+            normalized_languages.iter().map(String::as_str).collect()
+        };
+        let files = match &input_file_list {
+            Some(paths) => file_scanner.scan_files(paths, &candidates),
+            None => file_scanner.scan_directories(&inputs, &candidates)?,
+        };
+        normalized_languages = FileScanner::languages_present(&files);
+    }
     let language_refs: Vec<&str> = normalized_languages.iter().map(String::as_str).collect();
 
-    println!("EMBARGO - Ultrafast Codebase Analysis");
-    println!("Input: {} (targeting <1s)", input.display());
-    println!("Output: {}", output.display());
-    println!("Format: {}", format.as_str());
-    println!("Languages: {:?}", normalized_languages);
+    status!("EMBARGO - Ultrafast Codebase Analysis");
+    let input_label: Vec<String> = inputs
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect();
+    status!("Input: {} (targeting <1s)", input_label.join(", "));
+    status!("Output: {}", output.display());
+    status!("Format: {}", format.as_str());
+    if auto_detect {
+        status!("Languages: {:?} (auto-detected)", normalized_languages);
+    } else {
+        status!("Languages: {:?}", normalized_languages);
+    }
+    if let Some(config_path) = &config_path {
+        status!("Config: {}", config_path.display());
+    }
 
     let analysis_start = Instant::now();
 
-    let mut analyzer = CodebaseAnalyzer::new();
-    let dependency_graph = analyzer.analyze(&input, &language_refs)?;
+    let parse_cache = if no_cache {
+        ParseCache::in_memory_only()
+    } else {
+        let cache_file = output
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(".embargo-cache");
+        ParseCache::from_file(cache_file)
+    };
+    let mut analyzer = CodebaseAnalyzer::new()
+        .with_file_scanner(file_scanner)
+        .with_parse_cache(parse_cache)
+        .with_log_to_stderr(status_to_stderr)
+        .with_progress(progress)
+        .with_import_edges(import_edges)
+        .with_composition_edges(composition_edges);
+
+    // Resolve the diff before parsing so an empty change set skips the analysis entirely
+    let changed_files = match &since {
+        Some(since) => {
+            let repo_dir = if input.is_file() {
+                input
+                    .parent()
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .unwrap_or_else(|| Path::new("."))
+            } else if input_file_list.is_some() {
+                Path::new(".")
+            } else {
+                input.as_path()
+            };
+            let changed = crate::core::git::changed_files(repo_dir, since)?;
+            status!("Files changed since {}: {}", since, changed.len());
+            Some(changed)
+        }
+        None => None,
+    };
+
+    let path_root = match &input_file_list {
+        Some(paths) => common_directory(paths),
+        None => common_directory(&inputs),
+    };
+
+    let mut dependency_graph = match (&changed_files, input_file_list) {
+        (Some(changed), _) if changed.is_empty() => {
+            status!("No files changed; the graph is empty");
+            DependencyGraph::new()
+        }
+        (_, Some(paths)) => analyzer.analyze_files(&paths, &language_refs)?,
+        (_, None) => analyzer.analyze_roots(&inputs, &language_refs)?,
+    };
+
+    if parser_errors || fail_on_parse_error {
+        for (file, errors) in analyzer.syntax_errors() {
+            eprintln!(
+                "Syntax errors in {}: {}, first at line {} column {}",
+                file.display(),
+                errors.len(),
+                errors[0].line,
+                errors[0].column
+            );
+        }
+    }
+
+    if stable_ids {
+        dependency_graph.stabilize_ids();
+    }
+
+    // Cross-file edges were resolved against the full index; now narrow to the diff
+    if let Some(changed) = changed_files.filter(|changed| !changed.is_empty()) {
+        dependency_graph = scope_to_files(&dependency_graph, &changed);
+        status!(
+            "Scoped to {} nodes in or next to changed files",
+            dependency_graph.node_count()
+        );
+    }
+
+    if let Some(entry) = entry {
+        dependency_graph = prune_to_entry(&dependency_graph, &entry)?;
+        status!(
+            "Pruned to {} nodes reachable from {}",
+            dependency_graph.node_count(),
+            entry
+        );
+    }
+
+    if let Some(min_level) = visibility.min_level() {
+        dependency_graph = dependency_graph.retain_visible(min_level);
+        status!(
+            "Filtered to {} nodes at {:?} visibility or above",
+            dependency_graph.node_count(),
+            min_level
+        );
+    }
+
+    if root_relative_paths {
+        dependency_graph.relativize_paths(&path_root);
+    }
+
+    if anonymize {
+        let pseudonyms = crate::core::anonymize::anonymize(&mut dependency_graph);
+        status!("Anonymized with {} pseudonyms", pseudonyms.len());
+        if let Some(map_path) = &anonymize_map {
+            std::fs::write(map_path, pseudonyms.to_json()?)?;
+            status!("Pseudonym mapping: {}", map_path.display());
+        }
+    }
 
     let analysis_time = analysis_start.elapsed();
-    println!(
-        "Analysis completed in {:.2}s",
-        analysis_time.as_secs_f64()
-    );
+    status!("Analysis completed in {:.2}s", analysis_time.as_secs_f64());
 
-    let mut generated_output = output.clone();
+    if let Some([from, to]) = path.as_deref() {
+        for line in describe_call_paths(&dependency_graph, from, to)? {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
 
-    match format {
-        OutputFormat::Markdown => {
-            use crate::formatters::EmbargoFormatter;
-            EmbargoFormatter::new().format_to_file(&dependency_graph, &output)?;
+    let format_start = Instant::now();
+    let mut generated_outputs = Vec::new();
+
+    // Every format but SQLite renders to a string; `--split` renders once per module
+    let render = |graph: &DependencyGraph, format: OutputFormat| -> Result<String> {
+        match format {
+            OutputFormat::Markdown => {
+                use crate::formatters::EmbargoFormatter;
+                EmbargoFormatter::new()
+                    .with_line_ranges(line_ranges)
+                    .with_hide_calls(hide_calls.clone())
+                    .with_min_edge_weight(min_edge_weight)
+                    .format_to_string(graph)
+            }
+            OutputFormat::LlmOptimized => {
+                use crate::formatters::{LLMOptimizedFormatter, OutputVerbosity};
+                let output_verbosity = match verbosity {
+                    Verbosity::Compact => OutputVerbosity::Compact,
+                    Verbosity::Standard => OutputVerbosity::Standard,
+                    Verbosity::Verbose => OutputVerbosity::Verbose,
+                };
+                let mut formatter = if language_refs.iter().any(|lang| *lang == "python") {
+                    LLMOptimizedFormatter::for_python()
+                } else if language_refs.contains(&"kotlin") {
+                    LLMOptimizedFormatter::for_kotlin()
+                } else if language_refs.contains(&"php") {
+                    LLMOptimizedFormatter::for_php()
+                } else {
+                    LLMOptimizedFormatter::new()
+                }
+                .with_verbosity(output_verbosity)
+                .with_max_call_depth(max_depth)
+                .with_docstrings(docstrings)
+                .with_unused(unused)
+                .with_complexity_threshold(complexity_threshold)
+                .with_min_cluster_size(min_nodes)
+                .with_flatten_namespaces(flatten_namespaces)
+                .with_qualified_names(qualified_names)
+                .with_collapse_accessors(collapse_accessors)
+                .with_layered(layers)
+                .with_signature_compression(!no_signature_compression)
+                .with_entry_patterns(entry_pattern.clone())?
+                .with_call_priorities(call_priorities.clone())?
+                .with_hide_calls(hide_calls.clone())
+                .with_min_edge_weight(min_edge_weight)
+                .with_cluster_patterns(clusters.clone())?
+                .with_hierarchical(true)
+                .with_compressed_ids(true);
+                if !semantic_prefixes.is_empty() {
+                    formatter = formatter.with_semantic_prefixes(semantic_prefixes.clone());
+                }
+                if let Some(threshold) = hot_threshold {
+                    formatter = formatter.with_hot_threshold(threshold);
+                }
+                formatter.format_to_string(graph)
+            }
+            OutputFormat::JsonCompact => {
+                use crate::formatters::JsonCompactFormatter;
+                JsonCompactFormatter::new()
+                    .with_file_metrics(file_metrics)
+                    .format_to_string(graph)
+            }
+            OutputFormat::JsonFull => {
+                use crate::formatters::JsonFullFormatter;
+                JsonFullFormatter::new().format_to_string(graph)
+            }
+            OutputFormat::Mermaid => {
+                use crate::formatters::MermaidFormatter;
+                MermaidFormatter::new().format_to_string(graph)
+            }
+            OutputFormat::Graphml => {
+                use crate::formatters::GraphMlFormatter;
+                GraphMlFormatter::new().format_to_string(graph)
+            }
+            OutputFormat::Tree => {
+                use crate::formatters::TreeFormatter;
+                use std::io::IsTerminal;
+                let color = to_stdout
+                    && std::io::stdout().is_terminal()
+                    && std::env::var_os("NO_COLOR").is_none();
+                TreeFormatter::new()
+                    .with_color(color)
+                    .format_to_string(graph)
+            }
+            OutputFormat::Html => {
+                use crate::formatters::HtmlFormatter;
+                HtmlFormatter::new().format_to_string(graph)
+            }
+            OutputFormat::Csv => {
+                use crate::formatters::CsvFormatter;
+                CsvFormatter::new().format_to_string(graph)
+            }
+            OutputFormat::Symbols => {
+                use crate::formatters::SymbolsFormatter;
+                SymbolsFormatter::new().format_to_string(graph)
+            }
+            OutputFormat::Sqlite => {
+                anyhow::bail!("SQLite output is written straight to a database file")
+            }
+            OutputFormat::All => unreachable!("--format all is expanded before rendering"),
         }
-        OutputFormat::LlmOptimized => {
-            use crate::formatters::{LLMOptimizedFormatter, OutputVerbosity};
-            let output_verbosity = match verbosity {
-                Verbosity::Compact => OutputVerbosity::Compact,
-                Verbosity::Standard => OutputVerbosity::Standard,
-                Verbosity::Verbose => OutputVerbosity::Verbose,
-            };
-            let formatter = if language_refs.iter().any(|lang| *lang == "python") {
-                LLMOptimizedFormatter::for_python()
+    };
+
+    if split {
+        let write_module = |graph: &DependencyGraph, path: &Path| -> Result<()> {
+            match format {
+                OutputFormat::Sqlite => {
+                    crate::formatters::SqliteFormatter::new().format_to_file(graph, path)
+                }
+                OutputFormat::Csv => {
+                    crate::formatters::CsvFormatter::new().format_to_file(graph, path)
+                }
+                _ => Ok(std::fs::write(path, render(graph, format)?)?),
+            }
+        };
+        // Only the markdown formats can take an extra section without breaking their syntax
+        let summarize_in_modules =
+            matches!(format, OutputFormat::Markdown | OutputFormat::LlmOptimized);
+        generated_outputs.push(write_split_output(
+            &dependency_graph,
+            &output,
+            format,
+            summarize_in_modules,
+            write_module,
+        )?);
+    } else {
+        // The analysis ran once; `--format all` hands the same graph to every formatter
+        let write_all = format == OutputFormat::All;
+        let formats: Vec<OutputFormat> = if write_all {
+            OutputFormat::each().collect()
+        } else {
+            vec![format]
+        };
+        for format in formats {
+            let output = if write_all {
+                format.sibling_path(&output)
             } else {
-                LLMOptimizedFormatter::new()
+                output.clone()
+            };
+            let mut generated_output = output.clone();
+            let rendered = match format {
+                OutputFormat::JsonCompact | OutputFormat::JsonFull | OutputFormat::Symbols => {
+                    if !to_stdout {
+                        generated_output = output.with_extension("json");
+                        status!("JSON output: {}", generated_output.display());
+                    }
+                    render(&dependency_graph, format)?
+                }
+                OutputFormat::Sqlite => {
+                    use crate::formatters::SqliteFormatter;
+                    generated_output = output.with_extension("db");
+                    status!("SQLite output: {}", generated_output.display());
+                    SqliteFormatter::new().format_to_file(&dependency_graph, &generated_output)?;
+                    String::new()
+                }
+                OutputFormat::Csv => {
+                    use crate::formatters::CsvFormatter;
+                    if !to_stdout {
+                        // The node table only goes to disk; stdout gets the edge list alone
+                        generated_output = output.with_extension("csv");
+                        let nodes_path = CsvFormatter::nodes_path(&generated_output);
+                        std::fs::write(
+                            &nodes_path,
+                            CsvFormatter::new().format_nodes_to_string(&dependency_graph)?,
+                        )?;
+                        status!(
+                            "CSV output: {} (nodes: {})",
+                            generated_output.display(),
+                            nodes_path.display()
+                        );
+                    }
+                    render(&dependency_graph, format)?
+                }
+                _ => render(&dependency_graph, format)?,
+            };
+
+            if to_stdout {
+                use std::io::Write;
+                let mut stdout = std::io::stdout().lock();
+                stdout.write_all(rendered.as_bytes())?;
+                stdout.flush()?;
+            } else if format != OutputFormat::Sqlite {
+                // The SQLite formatter writes its database itself
+                std::fs::write(&generated_output, rendered)?;
             }
-            .with_verbosity(output_verbosity)
-            .with_hierarchical(true)
-            .with_compressed_ids(true);
-            formatter.format_to_file(&dependency_graph, &output)?;
+            generated_outputs.push(generated_output);
         }
-        OutputFormat::JsonCompact => {
-            use crate::formatters::JsonCompactFormatter;
-            let formatter = JsonCompactFormatter::new();
-            generated_output = output.with_extension("json");
-            formatter.format_to_file(&dependency_graph, &generated_output)?;
-            println!("JSON output: {}", generated_output.display());
+    }
+
+    let format_time = format_start.elapsed();
+
+    if stats {
+        status!("Graph statistics:");
+        status!(
+            "{}",
+            GraphStats::compute(&dependency_graph)
+                .with_call_resolution(analyzer.call_resolution().clone())
+                .with_skipped_files(analyzer.skipped_files().to_vec())
+        );
+    }
+
+    if let Some(k) = top_k {
+        status!("Hotspots (callers + complexity + calls, each scaled to 1):");
+        for (rank, hotspot) in Hotspot::top(&dependency_graph, k).iter().enumerate() {
+            status!("  {}. {}", rank + 1, hotspot);
+        }
+    }
+
+    if timing {
+        let timings = analyzer.timings();
+        status!(
+            "Timing: scan {:.1}ms, parse {:.1}ms, index {:.1}ms, resolve {:.1}ms, format {:.1}ms",
+            millis(timings.scan),
+            millis(timings.parse),
+            millis(timings.index),
+            millis(timings.resolve),
+            millis(format_time)
+        );
+    }
+
+    if report_unresolved {
+        let resolution = analyzer.call_resolution();
+        status!(
+            "Unresolved calls: {} of {} call sites",
+            resolution.unresolved_count(),
+            resolution.total
+        );
+        for (name, calls) in resolution.unresolved.iter().take(UNRESOLVED_REPORT_LIMIT) {
+            status!("  {} ({} calls)", name, calls);
         }
     }
 
+    let unparsed_files = analyzer.syntax_errors().len();
+    if fail_on_parse_error && unparsed_files > 0 {
+        anyhow::bail!("Files with syntax errors: {}", unparsed_files);
+    }
+
     let total_time = start_time.elapsed();
-    println!(
+    status!(
         "Analysis complete. Generated {}",
-        generated_output.display()
+        generated_outputs
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
     );
-    println!("Total execution time: {:.2}s", total_time.as_secs_f64());
+    status!("Total execution time: {:.2}s", total_time.as_secs_f64());
 
     if total_time.as_secs_f64() < 1.0 {
-        println!("Sub-1 second execution achieved.");
+        status!("Sub-1 second execution achieved.");
     } else {
-        println!(
+        status!(
             "Execution time: {:.2}s (optimizations in progress)",
             total_time.as_secs_f64()
         );
@@ -0,0 +1,369 @@
+use anyhow::Result;
+use std::path::Path;
+use tree_sitter::Node as TSNode;
+
+use super::common::{
+    assign_edge_lines, count_branches, extract_text, generate_node_id, syntax_errors,
+    TreeSitterParser,
+};
+use super::{LanguageParser, ParseResult};
+use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, Node, NodeType};
+
+/// Branch points counted toward a function's `complexity`
+const BRANCH_KINDS: &[&str] = &[
+    "if_statement",
+    "for_statement",
+    "while_statement",
+    "do_statement",
+    "case_statement",
+    "conditional_expression",
+];
+
+/// Parser for C sources and headers.
+///
+/// The C++ grammar accepts every construct extracted here, so C reuses it, but keeps its own
+/// node shapes: no namespaces or classes, `static` functions are private, and typedefs
+/// (function-pointer typedefs included) become named type nodes.
+pub struct CParser {
+    #[allow(dead_code)]
+    parser: TreeSitterParser,
+}
+
+impl CParser {
+    pub fn new() -> Result<Self> {
+        let language = tree_sitter_cpp::language();
+        let parser = TreeSitterParser::new(language)?;
+        Ok(Self { parser })
+    }
+
+    /// Walk top-level declarations, descending into include guards, `#if` blocks and
+    /// `extern "C" { ... }`
+    fn process_scope(
+        &self,
+        scope: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        let mut cursor = scope.walk();
+        for child in scope.children(&mut cursor) {
+            match child.kind() {
+                "preproc_include" => self.process_include(&child, source, file_path, nodes),
+                "function_definition" => self.process_function(&child, source, file_path, nodes),
+                "type_definition" => self.process_typedef(&child, source, file_path, nodes, edges),
+                "struct_specifier" | "union_specifier" | "enum_specifier" => {
+                    self.process_record(&child, None, source, file_path, nodes, edges);
+                }
+                "declaration" => {
+                    // `struct point { ... } origin;` declares the type alongside a variable
+                    if let Some(type_node) = child.child_by_field_name("type") {
+                        self.process_record(&type_node, None, source, file_path, nodes, edges);
+                    }
+                }
+                "preproc_ifdef" | "preproc_if" | "preproc_else" | "preproc_elif"
+                | "declaration_list" => {
+                    self.process_scope(&child, source, file_path, nodes, edges);
+                }
+                "linkage_specification" => {
+                    if let Some(body) = child.child_by_field_name("body") {
+                        self.process_scope(&body, source, file_path, nodes, edges);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// `#include <stdio.h>` becomes a module named `stdio.h`
+    fn process_include(
+        &self,
+        include_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+    ) {
+        let Some(path_node) = include_node.child_by_field_name("path") else {
+            return;
+        };
+        let header =
+            extract_text(&path_node, source).trim_matches(|c| matches!(c, '<' | '>' | '"'));
+        let line_number = include_node.start_position().row + 1;
+
+        nodes.push(
+            Node::new(
+                generate_node_id(file_path, "include", header, line_number),
+                header.to_string(),
+                NodeType::Module,
+                file_path.to_path_buf(),
+                line_number,
+                "c".to_string(),
+            )
+            .with_end_line(include_node.end_position().row + 1)
+            .with_signature(Self::collapse_whitespace(extract_text(
+                include_node,
+                source,
+            ))),
+        );
+    }
+
+    fn process_function(
+        &self,
+        func_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+    ) {
+        let Some(declarator) = Self::function_declarator(func_node) else {
+            return;
+        };
+        let Some(name_node) = declarator.child_by_field_name("declarator") else {
+            return;
+        };
+        if name_node.kind() != "identifier" {
+            return;
+        }
+        let func_name = extract_text(&name_node, source);
+        let line_number = func_node.start_position().row + 1;
+
+        // Everything before the body: return type, name and parameters
+        let header_end = func_node
+            .child_by_field_name("body")
+            .map_or(func_node.end_byte(), |body| body.start_byte());
+        let header = std::str::from_utf8(&source[func_node.start_byte()..header_end]).unwrap_or("");
+
+        // `static` functions are only visible inside their translation unit
+        let is_static = func_node.children(&mut func_node.walk()).any(|child| {
+            child.kind() == "storage_class_specifier" && extract_text(&child, source) == "static"
+        });
+
+        nodes.push(
+            Node::new(
+                generate_node_id(file_path, "function", func_name, line_number),
+                func_name.to_string(),
+                NodeType::Function,
+                file_path.to_path_buf(),
+                line_number,
+                "c".to_string(),
+            )
+            .with_end_line(func_node.end_position().row + 1)
+            .with_complexity(count_branches(func_node, BRANCH_KINDS))
+            .with_signature(Self::collapse_whitespace(header))
+            .with_visibility(if is_static { "private" } else { "public" }.to_string()),
+        );
+    }
+
+    /// Struct, union or enum with a body; bare references like `struct node *next` are skipped.
+    /// Anonymous records take the typedef name passed as `alias`. Returns the new node's ID.
+    fn process_record(
+        &self,
+        spec: &TSNode,
+        alias: Option<&str>,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) -> Option<String> {
+        let keyword = match spec.kind() {
+            "struct_specifier" => "struct",
+            "union_specifier" => "union",
+            "enum_specifier" => "enum",
+            _ => return None,
+        };
+        let body = spec.child_by_field_name("body")?;
+        let name = spec
+            .child_by_field_name("name")
+            .map(|name| extract_text(&name, source))
+            .or(alias)?;
+        let line_number = spec.start_position().row + 1;
+        let record_id = generate_node_id(file_path, keyword, name, line_number);
+
+        let node_type = if keyword == "enum" {
+            NodeType::Enum
+        } else {
+            NodeType::Class
+        };
+        nodes.push(
+            Node::new(
+                record_id.clone(),
+                name.to_string(),
+                node_type,
+                file_path.to_path_buf(),
+                line_number,
+                "c".to_string(),
+            )
+            .with_end_line(spec.end_position().row + 1)
+            .with_signature(format!("{} {}", keyword, name)),
+        );
+
+        if keyword != "enum" {
+            let mut cursor = body.walk();
+            for field in body.children(&mut cursor) {
+                if field.kind() != "field_declaration" {
+                    continue;
+                }
+                let Some(field_name) = field
+                    .child_by_field_name("declarator")
+                    .and_then(|declarator| Self::declared_name(&declarator))
+                else {
+                    continue;
+                };
+                let field_name = extract_text(&field_name, source);
+                let field_line = field.start_position().row + 1;
+                let field_id = generate_node_id(file_path, "field", field_name, field_line);
+
+                nodes.push(
+                    Node::new(
+                        field_id.clone(),
+                        field_name.to_string(),
+                        NodeType::Variable,
+                        file_path.to_path_buf(),
+                        field_line,
+                        "c".to_string(),
+                    )
+                    .with_end_line(field.end_position().row + 1),
+                );
+                edges.push(Edge::new(EdgeType::Contains, record_id.clone(), field_id));
+            }
+        }
+
+        Some(record_id)
+    }
+
+    /// `typedef` becomes a type node named after the alias. A typedef that only names an
+    /// anonymous record is folded into the record itself.
+    fn process_typedef(
+        &self,
+        typedef_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        let Some(alias_node) = typedef_node
+            .child_by_field_name("declarator")
+            .and_then(|declarator| Self::declared_name(&declarator))
+        else {
+            return;
+        };
+        let alias = extract_text(&alias_node, source);
+
+        // `typedef struct point { ... } point_t;` names the record; the alias points at it
+        let mut record = None;
+        if let Some(type_node) = typedef_node.child_by_field_name("type") {
+            let tag = type_node
+                .child_by_field_name("name")
+                .map(|name| extract_text(&name, source));
+            let record_id =
+                self.process_record(&type_node, Some(alias), source, file_path, nodes, edges);
+            match (record_id, tag) {
+                (Some(_), None) => return,
+                (Some(_), Some(tag)) if tag == alias => return,
+                (Some(record_id), Some(tag)) => {
+                    let keyword = type_node.kind().trim_end_matches("_specifier");
+                    record = Some((record_id, format!("typedef {} {} {}", keyword, tag, alias)));
+                }
+                (None, _) => {}
+            }
+        }
+
+        let line_number = typedef_node.start_position().row + 1;
+        let alias_id = generate_node_id(file_path, "typedef", alias, line_number);
+        let signature = match &record {
+            Some((_, signature)) => signature.clone(),
+            None => Self::collapse_whitespace(extract_text(typedef_node, source))
+                .trim_end_matches(';')
+                .trim_end()
+                .to_string(),
+        };
+
+        nodes.push(
+            Node::new(
+                alias_id.clone(),
+                alias.to_string(),
+                NodeType::Class,
+                file_path.to_path_buf(),
+                line_number,
+                "c".to_string(),
+            )
+            .with_end_line(typedef_node.end_position().row + 1)
+            .with_signature(signature),
+        );
+
+        if let Some((record_id, _)) = record {
+            edges.push(Edge::new(EdgeType::Uses, alias_id, record_id));
+        }
+    }
+
+    /// The `function_declarator` of a definition, looking through the pointer and
+    /// parenthesized declarators of functions returning pointers
+    fn function_declarator<'a>(func_node: &TSNode<'a>) -> Option<TSNode<'a>> {
+        let mut current = func_node.child_by_field_name("declarator")?;
+        loop {
+            match current.kind() {
+                "function_declarator" => return Some(current),
+                "pointer_declarator" | "parenthesized_declarator" | "attributed_declarator" => {
+                    current = current
+                        .child_by_field_name("declarator")
+                        .or_else(|| current.named_child(0))?;
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    /// Name introduced by a declarator: `*next`, `buf[16]` and `(*handler_fn)(int)` all
+    /// declare a single identifier nested somewhere inside
+    fn declared_name<'a>(declarator: &TSNode<'a>) -> Option<TSNode<'a>> {
+        let mut current = *declarator;
+        loop {
+            match current.kind() {
+                "identifier" | "field_identifier" | "type_identifier" => return Some(current),
+                _ => {
+                    current = current
+                        .child_by_field_name("declarator")
+                        .or_else(|| current.named_child(0))?;
+                }
+            }
+        }
+    }
+
+    fn collapse_whitespace(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn extract_call_sites(&self, root: &TSNode, source: &[u8], file_path: &Path) -> Vec<CallSite> {
+        let mut extractor = CallSiteExtractor::new();
+        extractor.extract_from_ast(root, source, file_path)
+    }
+}
+
+impl LanguageParser for CParser {
+    fn parse_file(&self, file_path: &Path) -> Result<ParseResult> {
+        let mut parser = TreeSitterParser::new(tree_sitter_cpp::language())?;
+        let tree = parser.parse_file(file_path)?;
+        let source = parser.get_source(file_path)?;
+        let source_bytes = source.as_bytes();
+
+        let root = tree.root_node();
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+
+        self.process_scope(&root, source_bytes, file_path, &mut nodes, &mut edges);
+
+        let call_sites = self.extract_call_sites(&root, source_bytes, file_path);
+
+        assign_edge_lines(&nodes, &mut edges);
+
+        Ok(ParseResult {
+            nodes,
+            edges,
+            call_sites: Some(call_sites),
+            syntax_errors: syntax_errors(&root),
+        })
+    }
+
+    fn language_name(&self) -> &str {
+        "c"
+    }
+}
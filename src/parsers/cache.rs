@@ -2,31 +2,57 @@ use anyhow::Result;
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::time::UNIX_EPOCH;
 
-use super::ParseResult;
+use super::{ParseResult, SyntaxError};
 use crate::core::{CallSite, Edge, Node};
 
 const DEFAULT_MAX_MEMORY_ENTRIES: usize = 1000;
 
+/// Bump whenever `Node`, `Edge` or `CallSite` change shape, or parsers emit new edges,
+/// so stale caches are discarded
+const CACHE_FORMAT_REVISION: u32 = 26;
+
+fn cache_version() -> String {
+    format!("{}+{}", env!("CARGO_PKG_VERSION"), CACHE_FORMAT_REVISION)
+}
+
 /// Fast cache for parsed results using file modification timestamps
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParsedFileEntry {
     pub nodes: Vec<Node>,
     pub edges: Vec<Edge>,
     pub call_sites: Option<Vec<CallSite>>,
+    /// How each of `call_sites` resolved in the last analysis, in the same order; `None`
+    /// until the file has been through call resolution
+    pub resolved_calls: Option<Vec<Option<Edge>>>,
+    pub syntax_errors: Vec<SyntaxError>,
     pub timestamp: u64,
     pub file_size: u64,
 }
 
+/// On-disk layout of a single-file cache; entries from another embargo version are discarded
+#[derive(Serialize, Deserialize)]
+struct PersistedCache {
+    version: String,
+    entries: HashMap<PathBuf, ParsedFileEntry>,
+    resolution_scope: Option<HashSet<PathBuf>>,
+}
+
 /// High-performance thread-safe cache with memory and (best-effort) disk storage
 pub struct ParseCache {
     memory_cache: DashMap<PathBuf, ParsedFileEntry>,
     cache_dir: Option<PathBuf>,
+    /// Single file holding every entry, written back by `persist`
+    cache_file: Option<PathBuf>,
     max_memory_entries: usize,
+    /// Files whose definitions formed the index the stored `resolved_calls` were resolved
+    /// against; see `resolution_scope`
+    resolution_scope: Option<HashSet<PathBuf>>,
 }
 
 impl ParseCache {
@@ -46,16 +72,81 @@ impl ParseCache {
         Ok(Self {
             memory_cache: DashMap::with_capacity(DEFAULT_MAX_MEMORY_ENTRIES),
             cache_dir,
+            cache_file: None,
             max_memory_entries: DEFAULT_MAX_MEMORY_ENTRIES,
+            resolution_scope: None,
         })
     }
 
+    /// Load a cache persisted to a single file (e.g. `.embargo-cache` next to the output).
+    ///
+    /// A missing, unreadable, or version-mismatched file yields an empty cache. Every entry
+    /// is held in memory so `persist` can write the complete set back.
+    pub fn from_file(cache_file: PathBuf) -> Self {
+        let memory_cache = DashMap::new();
+        let mut resolution_scope = None;
+
+        if let Ok(data) = fs::read(&cache_file) {
+            match bincode::deserialize::<PersistedCache>(&data) {
+                Ok(persisted) if persisted.version == cache_version() => {
+                    for (path, entry) in persisted.entries {
+                        memory_cache.insert(path, entry);
+                    }
+                    resolution_scope = persisted.resolution_scope;
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    eprintln!(
+                        "Warning: Ignoring unreadable cache {}: {err}",
+                        cache_file.display()
+                    );
+                }
+            }
+        }
+
+        Self {
+            memory_cache,
+            cache_dir: None,
+            cache_file: Some(cache_file),
+            max_memory_entries: usize::MAX,
+            resolution_scope,
+        }
+    }
+
+    /// Drop entries for files that are no longer part of the scan, so deleted files do not
+    /// leave stale nodes behind.
+    pub fn retain_files(&self, live_files: &HashSet<PathBuf>) {
+        self.memory_cache
+            .retain(|path, _| live_files.contains(path));
+    }
+
+    /// Write the cache back to its file, if it was created with `from_file`
+    pub fn persist(&self) -> Result<()> {
+        let Some(cache_file) = &self.cache_file else {
+            return Ok(());
+        };
+
+        let persisted = PersistedCache {
+            version: cache_version(),
+            entries: self
+                .memory_cache
+                .iter()
+                .map(|entry| (entry.key().clone(), entry.value().clone()))
+                .collect(),
+            resolution_scope: self.resolution_scope.clone(),
+        };
+        fs::write(cache_file, bincode::serialize(&persisted)?)?;
+        Ok(())
+    }
+
     /// Build an in-memory-only cache without touching the filesystem
     pub fn in_memory_only() -> Self {
         Self {
             memory_cache: DashMap::with_capacity(DEFAULT_MAX_MEMORY_ENTRIES),
             cache_dir: None,
+            cache_file: None,
             max_memory_entries: DEFAULT_MAX_MEMORY_ENTRIES,
+            resolution_scope: None,
         }
     }
 
@@ -93,6 +184,7 @@ impl ParseCache {
                 nodes: entry.nodes.clone(),
                 edges: entry.edges.clone(),
                 call_sites: entry.call_sites.clone(),
+                syntax_errors: entry.syntax_errors.clone(),
             });
         }
 
@@ -102,6 +194,7 @@ impl ParseCache {
                     nodes: entry.nodes.clone(),
                     edges: entry.edges.clone(),
                     call_sites: entry.call_sites.clone(),
+                    syntax_errors: entry.syntax_errors.clone(),
                 };
 
                 if self.memory_cache.len() < self.max_memory_entries {
@@ -129,14 +222,20 @@ impl ParseCache {
             nodes: result.nodes.clone(),
             edges: result.edges.clone(),
             call_sites: result.call_sites.clone(),
+            resolved_calls: None,
+            syntax_errors: result.syntax_errors.clone(),
             timestamp,
             file_size,
         };
 
         if self.memory_cache.len() >= self.max_memory_entries {
-            if let Some(entry) = self.memory_cache.iter().next() {
-                let key = entry.key().clone();
-                drop(entry);
+            // The iterator read-locks its shard; it must be gone before `remove` write-locks it
+            let evicted = self
+                .memory_cache
+                .iter()
+                .next()
+                .map(|entry| entry.key().clone());
+            if let Some(key) = evicted {
                 self.memory_cache.remove(&key);
             }
         }
@@ -150,6 +249,38 @@ impl ParseCache {
         Ok(())
     }
 
+    /// Call resolution recorded for a file by the last analysis, one result per call site
+    pub fn resolved_calls(&self, file_path: &Path) -> Option<Vec<Option<Edge>>> {
+        self.memory_cache.get(file_path)?.resolved_calls.clone()
+    }
+
+    /// Record how a file's call sites resolved, in `call_sites` order. Files without an
+    /// entry (or evicted from memory) are skipped; they are simply resolved again.
+    pub fn store_resolved_calls(
+        &self,
+        file_path: &Path,
+        resolved_calls: Vec<Option<Edge>>,
+    ) -> Result<()> {
+        let Some(mut entry) = self.memory_cache.get_mut(file_path) else {
+            return Ok(());
+        };
+        entry.resolved_calls = Some(resolved_calls);
+        if let Some(cache_path) = self.cache_path(file_path) {
+            self.store_to_disk(&cache_path, &entry)?;
+        }
+        Ok(())
+    }
+
+    /// Files indexed when the stored `resolved_calls` were computed. Those results only
+    /// hold while the index is built from the same files; `None` when unknown.
+    pub fn resolution_scope(&self) -> Option<&HashSet<PathBuf>> {
+        self.resolution_scope.as_ref()
+    }
+
+    pub fn set_resolution_scope(&mut self, scope: HashSet<PathBuf>) {
+        self.resolution_scope = Some(scope);
+    }
+
     /// Clear all caches
     #[allow(dead_code)]
     pub fn clear(&self) -> Result<()> {
@@ -177,6 +308,7 @@ impl ParseCache {
 
         let mut hasher = DefaultHasher::new();
         file_path.hash(&mut hasher);
+        CACHE_FORMAT_REVISION.hash(&mut hasher);
         let hash = hasher.finish();
 
         Some(cache_dir.join(format!("cache_{:x}.bincode", hash)))
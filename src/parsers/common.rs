@@ -1,9 +1,13 @@
 use anyhow::Result;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufReader, Read};
 use std::path::Path;
 use tree_sitter::{Language, Node as TSNode, Parser, Tree};
 
+use super::SyntaxError;
+use crate::core::{Edge, EdgeType, Node, NodeType};
+
 pub struct TreeSitterParser {
     parser: Parser,
     #[allow(dead_code)]
@@ -51,6 +55,9 @@ pub fn extract_text<'a>(node: &TSNode, source: &'a [u8]) -> &'a str {
     std::str::from_utf8(&source[node.byte_range()]).unwrap_or("")
 }
 
+/// Node id `path_with_underscores:kind:name:line`. The single source of truth for ids:
+/// `CallSiteExtractor` builds caller ids with it too, so calls join onto their function.
+/// Declarations sharing a line stay distinct as long as their kinds or names differ.
 pub fn generate_node_id(file_path: &Path, node_type: &str, name: &str, line: usize) -> String {
     format!(
         "{}:{}:{}:{}",
@@ -68,7 +75,7 @@ pub fn extract_docstring(node: &TSNode, source: &[u8]) -> Option<String> {
     // For Python, docstrings can be:
     // 1. Direct child of function_definition (for functions)
     // 2. Inside the "block" child (for classes and functions in some cases)
-    
+
     // First try direct children
     for child in node.children(&mut node.walk()) {
         if child.kind() == "expression_statement" {
@@ -85,7 +92,7 @@ pub fn extract_docstring(node: &TSNode, source: &[u8]) -> Option<String> {
                 }
             }
         }
-        
+
         // Check inside block (for classes)
         if child.kind() == "block" {
             // Look for expression_statement as first non-comment statement
@@ -105,7 +112,8 @@ pub fn extract_docstring(node: &TSNode, source: &[u8]) -> Option<String> {
                     }
                     // Only check first expression_statement
                     break;
-                } else if block_child.kind() != "comment" && block_child.kind() != "pass_statement" {
+                } else if block_child.kind() != "comment" && block_child.kind() != "pass_statement"
+                {
                     // If we hit something other than comment or pass before finding docstring, stop
                     // (docstrings must be first)
                     break;
@@ -116,6 +124,144 @@ pub fn extract_docstring(node: &TSNode, source: &[u8]) -> Option<String> {
     None
 }
 
+/// Whether a function carries the `async` keyword, either directly (Python, TS/JS) or
+/// inside its modifier list (Rust `function_modifiers`, C# `modifier`)
+pub fn is_async_function(node: &TSNode) -> bool {
+    node.children(&mut node.walk())
+        .any(|child| match child.kind() {
+            "async" => true,
+            "function_modifiers" | "modifier" => child
+                .children(&mut child.walk())
+                .any(|modifier| modifier.kind() == "async"),
+            _ => false,
+        })
+}
+
+/// Description of the test case `node` implements, when it is the callback passed to a
+/// Jest/Mocha/Vitest `it("adds numbers", () => ...)` or `test(...)` (also `it.only`,
+/// `test.skip`)
+pub fn test_case_name(node: &TSNode, source: &[u8]) -> Option<String> {
+    if !matches!(
+        node.kind(),
+        "arrow_function" | "function" | "function_expression"
+    ) {
+        return None;
+    }
+    let arguments = node
+        .parent()
+        .filter(|parent| parent.kind() == "arguments")?;
+    let call = arguments
+        .parent()
+        .filter(|parent| parent.kind() == "call_expression")?;
+    let callee = call.child_by_field_name("function")?;
+    let runner = match callee.kind() {
+        "member_expression" => callee.child_by_field_name("object")?,
+        _ => callee,
+    };
+    if runner.kind() != "identifier" || !matches!(extract_text(&runner, source), "it" | "test") {
+        return None;
+    }
+
+    let description = arguments.named_child(0)?;
+    if !matches!(description.kind(), "string" | "template_string") {
+        return None;
+    }
+    let name = extract_text(&description, source).trim_matches(['"', '\'', '`']);
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Push a test function node for every `it()`/`test()` callback under `node` (see
+/// [`test_case_name`]), anchored at the callback like the call sites made inside it
+pub fn push_test_cases(
+    node: &TSNode,
+    source: &[u8],
+    file_path: &Path,
+    language: &str,
+    branch_kinds: &[&str],
+    nodes: &mut Vec<Node>,
+) {
+    if let Some(name) = test_case_name(node, source) {
+        let line_number = node.start_position().row + 1;
+        nodes.push(
+            Node::new(
+                generate_node_id(file_path, "function", &name, line_number),
+                name,
+                NodeType::Function,
+                file_path.to_path_buf(),
+                line_number,
+                language.to_string(),
+            )
+            .with_end_line(node.end_position().row + 1)
+            .with_complexity(count_branches(node, branch_kinds))
+            .with_async(is_async_function(node))
+            .with_test(true),
+        );
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        push_test_cases(&child, source, file_path, language, branch_kinds, nodes);
+    }
+}
+
+/// Number of `kinds` nodes (ifs, loops, match arms, switch cases) under `node`. Nested
+/// closures count toward the enclosing function.
+pub fn count_branches(node: &TSNode, kinds: &[&str]) -> usize {
+    let mut count = 0;
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if kinds.contains(&child.kind()) {
+            count += 1;
+        }
+        count += count_branches(&child, kinds);
+    }
+    count
+}
+
+/// Give every edge the parser left without a line one: a `Contains` edge the line its
+/// member is declared on, any other edge the line of the entity it starts from. Run last,
+/// once `nodes` is complete.
+pub fn assign_edge_lines(nodes: &[Node], edges: &mut [Edge]) {
+    let lines: HashMap<&str, usize> = nodes
+        .iter()
+        .map(|node| (node.id.as_str(), node.line_number))
+        .collect();
+    for edge in edges.iter_mut().filter(|edge| edge.line.is_none()) {
+        let anchor = if edge.edge_type == EdgeType::Contains {
+            &edge.target_id
+        } else {
+            &edge.source_id
+        };
+        edge.line = lines.get(anchor.as_str()).copied();
+    }
+}
+
+/// Places tree-sitter recovered from a syntax error under `node`, in source order. Each
+/// `ERROR` subtree counts once, as does each token the parser had to assume was missing.
+pub fn syntax_errors(node: &TSNode) -> Vec<SyntaxError> {
+    let mut errors = Vec::new();
+    collect_syntax_errors(node, &mut errors);
+    errors
+}
+
+fn collect_syntax_errors(node: &TSNode, errors: &mut Vec<SyntaxError>) {
+    if node.is_error() || node.is_missing() {
+        let position = node.start_position();
+        errors.push(SyntaxError {
+            line: position.row + 1,
+            column: position.column + 1,
+        });
+        return;
+    }
+    if !node.has_error() {
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_syntax_errors(&child, errors);
+    }
+}
+
 pub fn find_child_by_kind<'a>(node: &'a TSNode, kind: &str) -> Option<TSNode<'a>> {
     for child in node.children(&mut node.walk()) {
         if child.kind() == kind {
@@ -135,3 +281,229 @@ pub fn find_children_by_kind<'a>(node: &'a TSNode<'a>, kind: &str) -> Vec<TSNode
     }
     results
 }
+
+/// Type names referenced under `type_nodes` (parameter lists, return types) of `func_node`,
+/// deduplicated in source order. `kinds` are the grammar's type-name node kinds; qualified
+/// names such as `io::Result` or `pkg.Thing` contribute only their last segment. The
+/// function's own generic parameters and `Self` are left out.
+pub fn signature_type_names(
+    func_node: &TSNode,
+    type_nodes: &[TSNode],
+    source: &[u8],
+    kinds: &[&str],
+) -> Vec<String> {
+    fn walk(node: &TSNode, source: &[u8], kinds: &[&str], names: &mut Vec<String>) {
+        if kinds.contains(&node.kind()) {
+            let name = extract_text(node, source);
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+            return;
+        }
+        if matches!(
+            node.kind(),
+            "scoped_type_identifier"
+                | "qualified_type"
+                | "qualified_name"
+                | "nested_type_identifier"
+                | "qualified_identifier"
+        ) {
+            let last = node
+                .child_by_field_name("name")
+                .or_else(|| node.named_child(node.named_child_count().saturating_sub(1)));
+            if let Some(last) = last {
+                walk(&last, source, kinds, names);
+            }
+            return;
+        }
+        for child in node.named_children(&mut node.walk()) {
+            walk(&child, source, kinds, names);
+        }
+    }
+
+    let generics: Vec<&str> = func_node
+        .child_by_field_name("type_parameters")
+        .or_else(|| find_child_by_kind(func_node, "type_parameter_list"))
+        .map(|list| {
+            list.named_children(&mut list.walk())
+                .filter_map(|param| {
+                    if kinds.contains(&param.kind()) {
+                        return Some(param);
+                    }
+                    param
+                        .child_by_field_name("name")
+                        .or_else(|| param.child_by_field_name("left"))
+                        .or_else(|| param.named_child(0))
+                })
+                .map(|name| extract_text(&name, source))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut names = Vec::new();
+    for node in type_nodes {
+        walk(node, source, kinds, &mut names);
+    }
+    names.retain(|name| name != "Self" && !generics.contains(&name.as_str()));
+    names
+}
+
+/// `Uses` edges from a function to each type in its signature. Targets start out as
+/// `external:class:Name:0`; [`resolve_local_types`] repoints the ones declared in the file.
+pub fn push_type_uses(func_id: &str, type_names: &[String], edges: &mut Vec<Edge>) {
+    for type_name in type_names {
+        edges.push(Edge::new(
+            EdgeType::Uses,
+            func_id.to_string(),
+            format!("external:class:{}:0", type_name),
+        ));
+    }
+}
+
+/// `Composes` edges from a class to the types of a field or property declared on `line`,
+/// pointing at `external:class:Name:0` placeholders like [`push_type_uses`]. The analyzer
+/// links them across files when composition edges are enabled.
+pub fn push_composition(class_id: &str, type_names: &[String], line: usize, edges: &mut Vec<Edge>) {
+    for type_name in type_names {
+        edges.push(
+            Edge::new(
+                EdgeType::Composes,
+                class_id.to_string(),
+                format!("external:class:{}:0", type_name),
+            )
+            .with_line(line),
+        );
+    }
+}
+
+/// Exception types `func_node` declares (a Java `throws` clause) or throws itself (`throw`,
+/// `raise`), deduplicated in source order. Nested functions, lambdas and classes are not
+/// searched; they get their own edges. Only capitalized names count, so re-raising a caught
+/// `e` adds nothing.
+pub fn thrown_type_names(func_node: &TSNode, source: &[u8]) -> Vec<String> {
+    fn push_name(node: &TSNode, source: &[u8], names: &mut Vec<String>) {
+        let text = extract_text(node, source);
+        let text = text.split('<').next().unwrap_or(text);
+        let name = text.rsplit('.').next().unwrap_or(text).trim();
+        if name.starts_with(|c: char| c.is_uppercase()) && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+
+    fn walk(node: &TSNode, source: &[u8], names: &mut Vec<String>) {
+        for child in node.named_children(&mut node.walk()) {
+            match child.kind() {
+                "function_definition"
+                | "lambda"
+                | "class_definition"
+                | "lambda_expression"
+                | "class_declaration"
+                | "class_body"
+                | "method_declaration"
+                | "local_function_statement"
+                | "anonymous_method_expression" => {}
+                "throws" => {
+                    for exception in child.named_children(&mut child.walk()) {
+                        push_name(&exception, source, names);
+                    }
+                }
+                "throw_statement" | "throw_expression" | "raise_statement" => {
+                    // `throw new E(..)`, `raise E(..)` or `raise E`
+                    if let Some(thrown) = child.named_child(0) {
+                        let type_node = match thrown.kind() {
+                            "object_creation_expression" => thrown.child_by_field_name("type"),
+                            "call" => thrown.child_by_field_name("function"),
+                            _ => Some(thrown),
+                        };
+                        if let Some(type_node) = type_node {
+                            push_name(&type_node, source, names);
+                        }
+                    }
+                    walk(&child, source, names);
+                }
+                _ => walk(&child, source, names),
+            }
+        }
+    }
+
+    let mut names = Vec::new();
+    walk(func_node, source, &mut names);
+    names
+}
+
+/// `Throws` edges from a function to each exception type, targeting `external:class:Name:0`
+/// like [`push_type_uses`]
+pub fn push_throws(func_id: &str, exception_names: &[String], edges: &mut Vec<Edge>) {
+    for exception_name in exception_names {
+        edges.push(Edge::new(
+            EdgeType::Throws,
+            func_id.to_string(),
+            format!("external:class:{}:0", exception_name),
+        ));
+    }
+}
+
+/// Placeholder class nodes for the exception types of `Throws` edges left external by
+/// [`resolve_local_types`], so the edges are kept in the graph (most exceptions come from the
+/// standard library)
+pub fn push_external_exception_nodes(
+    nodes: &mut Vec<Node>,
+    edges: &[Edge],
+    file_path: &Path,
+    language: &str,
+) {
+    for edge in edges.iter().filter(|e| e.edge_type == EdgeType::Throws) {
+        let Some(name) = edge
+            .target_id
+            .strip_prefix("external:class:")
+            .and_then(|rest| rest.strip_suffix(":0"))
+        else {
+            continue;
+        };
+        if nodes.iter().any(|n| n.id == edge.target_id) {
+            continue;
+        }
+        nodes.push(
+            Node::new(
+                edge.target_id.clone(),
+                name.to_string(),
+                NodeType::Class,
+                file_path.to_path_buf(),
+                0,
+                language.to_string(),
+            )
+            .with_visibility("external".to_string()),
+        );
+    }
+}
+
+/// Point `Uses`, `Throws` and `Composes` edges at `external:class:Name:0` to a class, interface or enum
+/// of the same name declared in this file
+pub fn resolve_local_types(nodes: &[Node], edges: &mut [Edge]) {
+    let mut local_types: HashMap<&str, &str> = HashMap::new();
+    for node in nodes {
+        if matches!(
+            node.node_type,
+            NodeType::Class | NodeType::Interface | NodeType::Enum
+        ) {
+            local_types
+                .entry(node.name.as_str())
+                .or_insert(node.id.as_str());
+        }
+    }
+
+    for edge in edges.iter_mut().filter(|e| {
+        matches!(
+            e.edge_type,
+            EdgeType::Uses | EdgeType::Throws | EdgeType::Composes
+        )
+    }) {
+        let type_name = edge
+            .target_id
+            .strip_prefix("external:class:")
+            .and_then(|rest| rest.strip_suffix(":0"));
+        if let Some(local_id) = type_name.and_then(|name| local_types.get(name)) {
+            edge.target_id = local_id.to_string();
+        }
+    }
+}
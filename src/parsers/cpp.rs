@@ -2,9 +2,23 @@ use anyhow::Result;
 use std::path::Path;
 use tree_sitter::Node as TSNode;
 
-use super::common::{extract_text, find_child_by_kind, generate_node_id, TreeSitterParser};
+use super::common::{
+    assign_edge_lines, count_branches, extract_text, find_child_by_kind, generate_node_id,
+    push_type_uses, resolve_local_types, signature_type_names, syntax_errors, TreeSitterParser,
+};
 use super::{LanguageParser, ParseResult};
-use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, Node, NodeType};
+use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, Lifecycle, Node, NodeType};
+
+/// Branch points counted toward a function's `complexity`
+const BRANCH_KINDS: &[&str] = &[
+    "if_statement",
+    "for_statement",
+    "for_range_loop",
+    "while_statement",
+    "do_statement",
+    "case_statement",
+    "conditional_expression",
+];
 
 pub struct CppParser {
     #[allow(dead_code)]
@@ -54,28 +68,12 @@ impl CppParser {
             file_path.to_path_buf(),
             line_number,
             "cpp".to_string(),
-        );
+        )
+        .with_end_line(include_node.end_position().row + 1);
 
         nodes.push(include_node_obj);
     }
 
-    fn extract_namespaces(
-        &self,
-        root: &TSNode,
-        source: &[u8],
-        file_path: &Path,
-        nodes: &mut Vec<Node>,
-        edges: &mut Vec<Edge>,
-    ) {
-        let mut cursor = root.walk();
-
-        for child in root.children(&mut cursor) {
-            if child.kind() == "namespace_definition" {
-                self.process_namespace(&child, source, file_path, nodes, edges);
-            }
-        }
-    }
-
     fn process_namespace(
         &self,
         namespace_node: &TSNode,
@@ -97,7 +95,8 @@ impl CppParser {
                 file_path.to_path_buf(),
                 line_number,
                 "cpp".to_string(),
-            );
+            )
+            .with_end_line(namespace_node.end_position().row + 1);
 
             nodes.push(namespace_node_obj);
 
@@ -115,15 +114,38 @@ impl CppParser {
         }
     }
 
-    fn extract_classes(
-        &self,
-        root: &TSNode,
-        source: &[u8],
-        file_path: &Path,
-        nodes: &mut Vec<Node>,
-        edges: &mut Vec<Edge>,
-    ) {
-        self.extract_from_declaration_list(root, source, file_path, "", nodes, edges);
+    /// Name a function declarator declares: plain, in-class (`field_identifier`), a
+    /// destructor (`~Widget`) or an operator. Out-of-line definitions like
+    /// `Widget::Widget` also give the class they belong to.
+    fn declarator_name(declarator: &TSNode, source: &[u8]) -> Option<(String, Option<String>)> {
+        let mut name_node = declarator.child_by_field_name("declarator")?;
+        let mut class_name = None;
+        // `ns::Widget::release` nests; the innermost scope is the class
+        while name_node.kind() == "qualified_identifier" {
+            if let Some(scope) = name_node.child_by_field_name("scope") {
+                // `Stack<T>::push` belongs to `Stack`
+                let scope = extract_text(&scope, source);
+                class_name = Some(scope.split('<').next().unwrap_or(scope).to_string());
+            }
+            name_node = name_node.child_by_field_name("name")?;
+        }
+        match name_node.kind() {
+            "identifier" | "field_identifier" | "destructor_name" | "operator_name" => {
+                Some((extract_text(&name_node, source).to_string(), class_name))
+            }
+            _ => None,
+        }
+    }
+
+    /// Constructors are named after their class, destructors `~` and the class name
+    fn lifecycle(member_name: &str, class_name: &str) -> Option<Lifecycle> {
+        if member_name == class_name {
+            Some(Lifecycle::Constructor)
+        } else if member_name.strip_prefix('~').map(str::trim) == Some(class_name) {
+            Some(Lifecycle::Destructor)
+        } else {
+            None
+        }
     }
 
     fn extract_from_declaration_list(
@@ -181,7 +203,8 @@ impl CppParser {
                 file_path.to_path_buf(),
                 line_number,
                 "cpp".to_string(),
-            );
+            )
+            .with_end_line(class_node.end_position().row + 1);
 
             // Handle inheritance
             if let Some(base_class_clause) = find_child_by_kind(class_node, "base_class_clause") {
@@ -193,7 +216,8 @@ impl CppParser {
                             let parent_class = extract_text(&base_name_node, source);
                             let parent_id = format!("external:class:{}:0", parent_class);
                             let inheritance_edge =
-                                Edge::new(EdgeType::Inheritance, class_id.clone(), parent_id);
+                                Edge::new(EdgeType::Inheritance, class_id.clone(), parent_id)
+                                    .with_line(base_class.start_position().row + 1);
                             edges.push(inheritance_edge);
                         }
                     }
@@ -217,7 +241,7 @@ impl CppParser {
                     &field_declaration_list,
                     source,
                     file_path,
-                    &class_id,
+                    (&class_id, class_name),
                     nodes,
                     edges,
                 );
@@ -230,16 +254,24 @@ impl CppParser {
         field_list: &TSNode,
         source: &[u8],
         file_path: &Path,
-        class_id: &str,
+        (class_id, class_name): (&str, &str),
         nodes: &mut Vec<Node>,
         edges: &mut Vec<Edge>,
     ) {
         let mut cursor = field_list.walk();
+        let mut field_types: Vec<String> = Vec::new();
 
         for child in field_list.children(&mut cursor) {
             match child.kind() {
                 "function_definition" => {
-                    self.process_method(&child, source, file_path, class_id, nodes, edges);
+                    self.process_method(
+                        &child,
+                        source,
+                        file_path,
+                        (class_id, class_name),
+                        nodes,
+                        edges,
+                    );
                 }
                 "declaration" => {
                     // Handle method declarations, constructors, destructors
@@ -249,14 +281,32 @@ impl CppParser {
                             &declarator,
                             source,
                             file_path,
-                            class_id,
+                            (class_id, class_name),
                             nodes,
                             edges,
                         );
                     }
                 }
                 "field_declaration" => {
+                    // `void add(const User& user);` parses as a field with a function declarator
+                    if let Some(declarator) = find_child_by_kind(&child, "function_declarator") {
+                        self.process_method_declaration(
+                            &child,
+                            &declarator,
+                            source,
+                            file_path,
+                            (class_id, class_name),
+                            nodes,
+                            edges,
+                        );
+                        continue;
+                    }
                     self.process_field(&child, source, file_path, class_id, nodes, edges);
+                    for type_name in Self::signature_types(&child, None, source) {
+                        if type_name != class_name && !field_types.contains(&type_name) {
+                            field_types.push(type_name);
+                        }
+                    }
                 }
                 "template_declaration" => {
                     self.process_template(&child, source, file_path, class_id, nodes, edges);
@@ -264,6 +314,42 @@ impl CppParser {
                 _ => {}
             }
         }
+
+        // A class depends on the types it holds, template arguments included
+        push_type_uses(class_id, &field_types, edges);
+    }
+
+    /// Types named in a declaration's type and, given a function declarator, its
+    /// parameters: `std::vector<User>` names `vector` and `User`. Parameters of enclosing
+    /// templates are left out.
+    fn signature_types(
+        decl_node: &TSNode,
+        declarator: Option<&TSNode>,
+        source: &[u8],
+    ) -> Vec<String> {
+        let mut parts: Vec<TSNode> = decl_node.child_by_field_name("type").into_iter().collect();
+        parts.extend(declarator.and_then(|node| node.child_by_field_name("parameters")));
+        let mut names = signature_type_names(decl_node, &parts, source, &["type_identifier"]);
+
+        let mut ancestor = decl_node.parent();
+        while let Some(node) = ancestor {
+            if let Some(params) = node
+                .child_by_field_name("parameters")
+                .filter(|_| node.kind() == "template_declaration")
+            {
+                for param in params.named_children(&mut params.walk()) {
+                    let name = param
+                        .child_by_field_name("name")
+                        .or_else(|| find_child_by_kind(&param, "type_identifier"));
+                    if let Some(name) = name {
+                        let name = extract_text(&name, source);
+                        names.retain(|type_name| type_name != name);
+                    }
+                }
+            }
+            ancestor = node.parent();
+        }
+        names
     }
 
     fn process_method(
@@ -271,13 +357,13 @@ impl CppParser {
         method_node: &TSNode,
         source: &[u8],
         file_path: &Path,
-        class_id: &str,
+        (class_id, class_name): (&str, &str),
         nodes: &mut Vec<Node>,
         edges: &mut Vec<Edge>,
     ) {
         if let Some(declarator) = find_child_by_kind(method_node, "function_declarator") {
-            if let Some(name_node) = find_child_by_kind(&declarator, "identifier") {
-                let method_name = extract_text(&name_node, source);
+            if let Some((method_name, _)) = Self::declarator_name(&declarator, source) {
+                let method_name = method_name.as_str();
                 let line_number = method_node.start_position().row + 1;
                 let method_id = generate_node_id(file_path, "method", method_name, line_number);
 
@@ -288,7 +374,10 @@ impl CppParser {
                     file_path.to_path_buf(),
                     line_number,
                     "cpp".to_string(),
-                );
+                )
+                .with_end_line(method_node.end_position().row + 1)
+                .with_complexity(count_branches(method_node, BRANCH_KINDS))
+                .with_lifecycle(Self::lifecycle(method_name, class_name));
 
                 nodes.push(method_node_obj);
 
@@ -296,6 +385,11 @@ impl CppParser {
                 let containment_edge =
                     Edge::new(EdgeType::Contains, class_id.to_string(), method_id.clone());
                 edges.push(containment_edge);
+                push_type_uses(
+                    &method_id,
+                    &Self::signature_types(method_node, Some(&declarator), source),
+                    edges,
+                );
 
                 // Note: Function calls are now extracted separately via extract_call_sites
                 // Legacy function call extraction would go here but is replaced by CallSiteExtractor
@@ -309,12 +403,12 @@ impl CppParser {
         declarator: &TSNode,
         source: &[u8],
         file_path: &Path,
-        class_id: &str,
+        (class_id, class_name): (&str, &str),
         nodes: &mut Vec<Node>,
         edges: &mut Vec<Edge>,
     ) {
-        if let Some(name_node) = find_child_by_kind(declarator, "identifier") {
-            let method_name = extract_text(&name_node, source);
+        if let Some((method_name, _)) = Self::declarator_name(declarator, source) {
+            let method_name = method_name.as_str();
             let line_number = decl_node.start_position().row + 1;
             let method_id = generate_node_id(file_path, "method", method_name, line_number);
 
@@ -325,13 +419,21 @@ impl CppParser {
                 file_path.to_path_buf(),
                 line_number,
                 "cpp".to_string(),
-            );
+            )
+            .with_end_line(decl_node.end_position().row + 1)
+            .with_lifecycle(Self::lifecycle(method_name, class_name));
 
             nodes.push(method_node_obj);
 
             // Add containment edge
-            let containment_edge = Edge::new(EdgeType::Contains, class_id.to_string(), method_id);
+            let containment_edge =
+                Edge::new(EdgeType::Contains, class_id.to_string(), method_id.clone());
             edges.push(containment_edge);
+            push_type_uses(
+                &method_id,
+                &Self::signature_types(decl_node, Some(declarator), source),
+                edges,
+            );
         }
     }
 
@@ -357,7 +459,8 @@ impl CppParser {
                     file_path.to_path_buf(),
                     line_number,
                     "cpp".to_string(),
-                );
+                )
+                .with_end_line(field_node.end_position().row + 1);
 
                 nodes.push(field_node_obj);
 
@@ -379,10 +482,15 @@ impl CppParser {
         edges: &mut Vec<Edge>,
     ) {
         if let Some(declarator) = find_child_by_kind(func_node, "function_declarator") {
-            if let Some(name_node) = find_child_by_kind(&declarator, "identifier") {
-                let func_name = extract_text(&name_node, source);
+            if let Some((func_name, class_name)) = Self::declarator_name(&declarator, source) {
+                let func_name = func_name.as_str();
                 let line_number = func_node.start_position().row + 1;
-                let func_id = generate_node_id(file_path, "function", func_name, line_number);
+                let kind = if class_name.is_some() {
+                    "method"
+                } else {
+                    "function"
+                };
+                let func_id = generate_node_id(file_path, kind, func_name, line_number);
 
                 let func_node_obj = Node::new(
                     func_id.clone(),
@@ -391,16 +499,38 @@ impl CppParser {
                     file_path.to_path_buf(),
                     line_number,
                     "cpp".to_string(),
+                )
+                .with_end_line(func_node.end_position().row + 1)
+                .with_complexity(count_branches(func_node, BRANCH_KINDS))
+                .with_lifecycle(
+                    class_name
+                        .as_deref()
+                        .and_then(|class_name| Self::lifecycle(func_name, class_name)),
                 );
 
+                // `Widget::release() {...}` belongs to Widget when the class is in this file
+                let class_id = class_name.as_deref().and_then(|class_name| {
+                    nodes
+                        .iter()
+                        .find(|node| node.node_type == NodeType::Class && node.name == class_name)
+                        .map(|class| class.id.clone())
+                });
+
                 nodes.push(func_node_obj);
 
-                // Add containment edge if inside namespace
-                if !parent_id.is_empty() {
+                if let Some(class_id) = class_id {
+                    edges.push(Edge::new(EdgeType::Contains, class_id, func_id.clone()));
+                } else if !parent_id.is_empty() {
+                    // Add containment edge if inside namespace
                     let containment_edge =
                         Edge::new(EdgeType::Contains, parent_id.to_string(), func_id.clone());
                     edges.push(containment_edge);
                 }
+                push_type_uses(
+                    &func_id,
+                    &Self::signature_types(func_node, Some(&declarator), source),
+                    edges,
+                );
 
                 // Note: Function calls are now extracted separately via extract_call_sites
                 // Legacy function call extraction would go here but is replaced by CallSiteExtractor
@@ -538,7 +668,8 @@ impl CppParser {
                     file_path.to_path_buf(),
                     line_number,
                     "cpp".to_string(),
-                );
+                )
+                .with_end_line(child.end_position().row + 1);
 
                 nodes.push(using_node);
             }
@@ -561,10 +692,9 @@ impl LanguageParser for CppParser {
         // Extract different types of C++ constructs
         self.extract_includes(&root, source_bytes, file_path, &mut nodes, &mut edges);
         self.extract_using_declarations(&root, source_bytes, file_path, &mut nodes, &mut edges);
-        self.extract_namespaces(&root, source_bytes, file_path, &mut nodes, &mut edges);
-        self.extract_classes(&root, source_bytes, file_path, &mut nodes, &mut edges);
 
-        // Extract global functions and templates
+        // Namespaces, classes, global functions and templates, in one pass so each is
+        // extracted once
         self.extract_from_declaration_list(
             &root,
             source_bytes,
@@ -577,10 +707,14 @@ impl LanguageParser for CppParser {
         // Extract call sites using the new system
         let call_sites = self.extract_call_sites(&root, source_bytes, file_path);
 
+        resolve_local_types(&nodes, &mut edges);
+        assign_edge_lines(&nodes, &mut edges);
+
         Ok(ParseResult {
             nodes,
             edges,
             call_sites: Some(call_sites),
+            syntax_errors: syntax_errors(&root),
         })
     }
 
@@ -3,12 +3,27 @@ use std::path::Path;
 use tree_sitter::Node as TSNode;
 
 use super::common::{
-    extract_docstring, extract_text, find_child_by_kind, find_children_by_kind, generate_node_id,
-    TreeSitterParser,
+    assign_edge_lines, count_branches, extract_docstring, extract_text, find_child_by_kind,
+    find_children_by_kind, generate_node_id, is_async_function, push_composition,
+    push_external_exception_nodes, push_throws, push_type_uses, resolve_local_types,
+    signature_type_names, syntax_errors, thrown_type_names, TreeSitterParser,
 };
 use super::{LanguageParser, ParseResult};
 use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, Node, NodeType};
 
+/// Branch points counted toward a function's `complexity`
+const BRANCH_KINDS: &[&str] = &[
+    "if_statement",
+    "for_statement",
+    "for_each_statement",
+    "foreach_statement",
+    "while_statement",
+    "do_statement",
+    "switch_section",
+    "switch_expression_arm",
+    "conditional_expression",
+];
+
 pub struct CSharpParser {
     #[allow(dead_code)]
     parser: TreeSitterParser,
@@ -43,7 +58,8 @@ impl CSharpParser {
                     file_path.to_path_buf(),
                     line_number,
                     "csharp".to_string(),
-                );
+                )
+                .with_end_line(child.end_position().row + 1);
 
                 nodes.push(using_node);
             }
@@ -75,7 +91,11 @@ impl CSharpParser {
         nodes: &mut Vec<Node>,
         edges: &mut Vec<Edge>,
     ) {
-        if let Some(name_node) = find_child_by_kind(namespace_node, "identifier") {
+        // `namespace Acme.Billing` is named by a qualified_name
+        let name_node = namespace_node
+            .child_by_field_name("name")
+            .or_else(|| find_child_by_kind(namespace_node, "identifier"));
+        if let Some(name_node) = name_node {
             let namespace_name = extract_text(&name_node, source);
             let line_number = namespace_node.start_position().row + 1;
             let namespace_id =
@@ -88,7 +108,8 @@ impl CSharpParser {
                 file_path.to_path_buf(),
                 line_number,
                 "csharp".to_string(),
-            );
+            )
+            .with_end_line(namespace_node.end_position().row + 1);
 
             nodes.push(namespace_node_obj);
 
@@ -117,7 +138,7 @@ impl CSharpParser {
     ) {
         for child in declaration_list.children(&mut declaration_list.walk()) {
             match child.kind() {
-                "class_declaration" => {
+                "class_declaration" | "record_declaration" => {
                     self.process_class(&child, source, file_path, Some(namespace_id), nodes, edges);
                 }
                 "interface_declaration" => {
@@ -159,7 +180,7 @@ impl CSharpParser {
         let mut cursor = root.walk();
 
         for child in root.children(&mut cursor) {
-            if child.kind() == "class_declaration" {
+            if matches!(child.kind(), "class_declaration" | "record_declaration") {
                 self.process_class(&child, source, file_path, None, nodes, edges);
             }
         }
@@ -186,7 +207,18 @@ impl CSharpParser {
                 file_path.to_path_buf(),
                 line_number,
                 "csharp".to_string(),
-            );
+            )
+            .with_end_line(class_node.end_position().row + 1);
+
+            // Records are classes to the graph; the signature keeps the positional
+            // parameters, e.g. `record Person(string Name, int Age)`
+            if class_node.kind() == "record_declaration" {
+                let parameters = find_child_by_kind(class_node, "parameter_list")
+                    .map(|params| extract_text(&params, source))
+                    .unwrap_or_default();
+                class_node_obj =
+                    class_node_obj.with_signature(format!("record {}{}", class_name, parameters));
+            }
 
             // Extract docstring/comments
             if let Some(docstring) = extract_docstring(class_node, source) {
@@ -203,7 +235,8 @@ impl CSharpParser {
                         // For simplicity, treating all base types as inheritance
                         // In a more sophisticated parser, we'd distinguish between classes and interfaces
                         let inheritance_edge =
-                            Edge::new(EdgeType::Inheritance, class_id.clone(), base_id);
+                            Edge::new(EdgeType::Inheritance, class_id.clone(), base_id)
+                                .with_line(base_type.start_position().row + 1);
                         edges.push(inheritance_edge);
                     }
                 }
@@ -246,7 +279,9 @@ impl CSharpParser {
                 file_path.to_path_buf(),
                 line_number,
                 "csharp".to_string(),
-            );
+            )
+            .with_end_line(struct_node.end_position().row + 1)
+            .with_signature(format!("struct {}", struct_name));
 
             nodes.push(struct_node_obj);
 
@@ -281,11 +316,12 @@ impl CSharpParser {
             let enum_node_obj = Node::new(
                 enum_id.clone(),
                 enum_name.to_string(),
-                NodeType::Class, // Using Class type for enums
+                NodeType::Enum,
                 file_path.to_path_buf(),
                 line_number,
                 "csharp".to_string(),
-            );
+            )
+            .with_end_line(enum_node.end_position().row + 1);
 
             nodes.push(enum_node_obj);
 
@@ -320,6 +356,7 @@ impl CSharpParser {
                                 member_line,
                                 "csharp".to_string(),
                             )
+                            .with_end_line(enum_member.end_position().row + 1)
                             .with_visibility("public".to_string());
 
                             nodes.push(member_node);
@@ -390,7 +427,11 @@ impl CSharpParser {
         nodes: &mut Vec<Node>,
         edges: &mut Vec<Edge>,
     ) {
-        if let Some(name_node) = find_child_by_kind(method_node, "identifier") {
+        // A user-defined return type is an identifier too, so prefer the `name` field
+        let name_node = method_node
+            .child_by_field_name("name")
+            .or_else(|| find_child_by_kind(method_node, "identifier"));
+        if let Some(name_node) = name_node {
             let method_name = extract_text(&name_node, source);
             let line_number = method_node.start_position().row + 1;
             let method_id = generate_node_id(file_path, "function", &method_name, line_number);
@@ -410,6 +451,9 @@ impl CSharpParser {
                 line_number,
                 "csharp".to_string(),
             )
+            .with_end_line(method_node.end_position().row + 1)
+            .with_complexity(count_branches(method_node, BRANCH_KINDS))
+            .with_async(is_async_function(method_node))
             .with_signature(signature)
             .with_visibility(visibility);
 
@@ -418,6 +462,12 @@ impl CSharpParser {
             }
 
             nodes.push(method_node_obj);
+            push_type_uses(
+                &method_id,
+                &Self::signature_types(method_node, source),
+                edges,
+            );
+            push_throws(&method_id, &thrown_type_names(method_node, source), edges);
 
             if let Some(class_id) = class_id {
                 let contains_edge = Edge::new(EdgeType::Contains, class_id.to_string(), method_id);
@@ -426,6 +476,24 @@ impl CSharpParser {
         }
     }
 
+    /// Return and parameter types. Parameter names are identifiers as well, so only the
+    /// `type` field of each parameter is walked.
+    fn signature_types(func_node: &TSNode, source: &[u8]) -> Vec<String> {
+        let mut type_nodes: Vec<TSNode> = func_node
+            .child_by_field_name("returns")
+            .or_else(|| func_node.child_by_field_name("type"))
+            .into_iter()
+            .collect();
+        if let Some(params) = func_node.child_by_field_name("parameters") {
+            type_nodes.extend(
+                params
+                    .named_children(&mut params.walk())
+                    .filter_map(|param| param.child_by_field_name("type")),
+            );
+        }
+        signature_type_names(func_node, &type_nodes, source, &["identifier"])
+    }
+
     fn process_constructor(
         &self,
         constructor_node: &TSNode,
@@ -460,10 +528,22 @@ impl CSharpParser {
                 line_number,
                 "csharp".to_string(),
             )
+            .with_end_line(constructor_node.end_position().row + 1)
+            .with_complexity(count_branches(constructor_node, BRANCH_KINDS))
             .with_signature(signature)
             .with_visibility(visibility);
 
             nodes.push(constructor_node_obj);
+            push_type_uses(
+                &constructor_id,
+                &Self::signature_types(constructor_node, source),
+                edges,
+            );
+            push_throws(
+                &constructor_id,
+                &thrown_type_names(constructor_node, source),
+                edges,
+            );
 
             if let Some(class_id) = class_id {
                 let contains_edge =
@@ -502,6 +582,7 @@ impl CSharpParser {
                         line_number,
                         "csharp".to_string(),
                     )
+                    .with_end_line(field_node.end_position().row + 1)
                     .with_visibility(visibility);
 
                     nodes.push(field_node_obj);
@@ -511,6 +592,12 @@ impl CSharpParser {
                     edges.push(contains_edge);
                 }
             }
+            push_composition(
+                class_id,
+                &Self::member_types(field_node, &variable_declaration, source),
+                field_node.start_position().row + 1,
+                edges,
+            );
         }
     }
 
@@ -523,13 +610,64 @@ impl CSharpParser {
         nodes: &mut Vec<Node>,
         edges: &mut Vec<Edge>,
     ) {
-        if let Some(identifier) = find_child_by_kind(property_node, "identifier") {
+        let name_node = property_node
+            .child_by_field_name("name")
+            .or_else(|| find_child_by_kind(property_node, "identifier"));
+        if let Some(identifier) = name_node {
             let property_name = extract_text(&identifier, source);
             let line_number = property_node.start_position().row + 1;
             let property_id = generate_node_id(file_path, "property", &property_name, line_number);
 
             let visibility = self.extract_visibility_modifier(property_node, source);
 
+            // Computed properties run code on access, so they are functions rather than state
+            let accessors = Self::accessors_with_bodies(property_node);
+            if !accessors.is_empty() {
+                let property_type = property_node
+                    .child_by_field_name("type")
+                    .map(|type_node| extract_text(&type_node, source))
+                    .unwrap_or_default();
+                let type_names = Self::signature_types(property_node, source);
+                for (keyword, accessor) in accessors {
+                    let accessor_name = format!("{}_{}", keyword, property_name);
+                    let accessor_line = accessor.start_position().row + 1;
+                    let accessor_id =
+                        generate_node_id(file_path, "function", &accessor_name, accessor_line);
+                    // `private set` narrows the property's own visibility
+                    let accessor_visibility = if find_child_by_kind(&accessor, "modifier").is_some()
+                    {
+                        self.extract_visibility_modifier(&accessor, source)
+                    } else {
+                        visibility.clone()
+                    };
+
+                    nodes.push(
+                        Node::new(
+                            accessor_id.clone(),
+                            accessor_name,
+                            NodeType::Function,
+                            file_path.to_path_buf(),
+                            accessor_line,
+                            "csharp".to_string(),
+                        )
+                        .with_end_line(accessor.end_position().row + 1)
+                        .with_complexity(count_branches(&accessor, BRANCH_KINDS))
+                        .with_signature(format!(
+                            "{} {} {{ {}; }}",
+                            property_type, property_name, keyword
+                        ))
+                        .with_visibility(accessor_visibility),
+                    );
+                    push_type_uses(&accessor_id, &type_names, edges);
+                    edges.push(Edge::new(
+                        EdgeType::Contains,
+                        class_id.to_string(),
+                        accessor_id,
+                    ));
+                }
+                return;
+            }
+
             let property_node_obj = Node::new(
                 property_id.clone(),
                 property_name.to_string(),
@@ -538,15 +676,62 @@ impl CSharpParser {
                 line_number,
                 "csharp".to_string(),
             )
+            .with_end_line(property_node.end_position().row + 1)
             .with_visibility(visibility);
 
             nodes.push(property_node_obj);
 
             let contains_edge = Edge::new(EdgeType::Contains, class_id.to_string(), property_id);
             edges.push(contains_edge);
+            push_composition(
+                class_id,
+                &Self::member_types(property_node, property_node, source),
+                line_number,
+                edges,
+            );
+        }
+    }
+
+    /// Types named in the `type` of a field's declaration or of a property, less the
+    /// enclosing class's generic parameters
+    fn member_types(member_node: &TSNode, declaration: &TSNode, source: &[u8]) -> Vec<String> {
+        let class_node = member_node.parent().and_then(|body| body.parent());
+        match (class_node, declaration.child_by_field_name("type")) {
+            (Some(class_node), Some(type_node)) => {
+                signature_type_names(&class_node, &[type_node], source, &["identifier"])
+            }
+            _ => Vec::new(),
         }
     }
 
+    /// `get`/`set`/`init` accessors that have a body, paired with their keyword. An
+    /// expression-bodied property (`int Total => a + b;`) is a getter on its own.
+    fn accessors_with_bodies<'a>(property_node: &TSNode<'a>) -> Vec<(&'static str, TSNode<'a>)> {
+        if let Some(value) = property_node.child_by_field_name("value") {
+            if value.kind() == "arrow_expression_clause" {
+                return vec![("get", value)];
+            }
+        }
+        let Some(accessor_list) = property_node.child_by_field_name("accessors") else {
+            return Vec::new();
+        };
+        accessor_list
+            .named_children(&mut accessor_list.walk())
+            .filter(|accessor| {
+                accessor.kind() == "accessor_declaration"
+                    && accessor.child_by_field_name("body").is_some()
+            })
+            .filter_map(|accessor| {
+                let keyword = ["get", "set", "init"].into_iter().find(|keyword| {
+                    accessor
+                        .children(&mut accessor.walk())
+                        .any(|child| child.kind() == *keyword)
+                })?;
+                Some((keyword, accessor))
+            })
+            .collect()
+    }
+
     fn process_event(
         &self,
         event_node: &TSNode,
@@ -575,6 +760,7 @@ impl CSharpParser {
                         line_number,
                         "csharp".to_string(),
                     )
+                    .with_end_line(event_node.end_position().row + 1)
                     .with_visibility(visibility);
 
                     nodes.push(event_node_obj);
@@ -626,7 +812,8 @@ impl CSharpParser {
                 file_path.to_path_buf(),
                 line_number,
                 "csharp".to_string(),
-            );
+            )
+            .with_end_line(interface_node.end_position().row + 1);
 
             nodes.push(interface_node_obj);
 
@@ -702,14 +889,19 @@ impl LanguageParser for CSharpParser {
         self.extract_namespaces(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
         self.extract_classes(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
         self.extract_interfaces(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
+        resolve_local_types(&nodes, &mut edges);
+        push_external_exception_nodes(&mut nodes, &edges, file_path, "csharp");
 
         // Extract call sites using the new system
         let call_sites = self.extract_call_sites(&root_node, source_bytes, file_path);
 
+        assign_edge_lines(&nodes, &mut edges);
+
         Ok(ParseResult {
             nodes,
             edges,
             call_sites: Some(call_sites),
+            syntax_errors: syntax_errors(&root_node),
         })
     }
 
@@ -1,14 +1,50 @@
 use anyhow::Result;
+use std::collections::HashSet;
 use std::path::Path;
 use tree_sitter::Node as TSNode;
 
 use super::common::{
-    extract_docstring, extract_text, find_child_by_kind, find_children_by_kind, generate_node_id,
-    TreeSitterParser,
+    assign_edge_lines, count_branches, extract_docstring, extract_text, find_child_by_kind,
+    find_children_by_kind, generate_node_id, push_composition, push_type_uses, resolve_local_types,
+    signature_type_names, syntax_errors, TreeSitterParser,
 };
 use super::{LanguageParser, ParseResult};
 use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, Node, NodeType};
 
+/// Branch points counted toward a function's `complexity`
+const BRANCH_KINDS: &[&str] = &[
+    "if_statement",
+    "for_statement",
+    "expression_case",
+    "type_case",
+    "communication_case",
+];
+
+/// Predeclared types, which never name a type of the codebase
+const BUILTIN_TYPES: &[&str] = &[
+    "any",
+    "bool",
+    "byte",
+    "complex64",
+    "complex128",
+    "error",
+    "float32",
+    "float64",
+    "int",
+    "int8",
+    "int16",
+    "int32",
+    "int64",
+    "rune",
+    "string",
+    "uint",
+    "uint8",
+    "uint16",
+    "uint32",
+    "uint64",
+    "uintptr",
+];
+
 pub struct GoParser {
     #[allow(dead_code)]
     parser: TreeSitterParser,
@@ -45,7 +81,8 @@ impl GoParser {
                         file_path.to_path_buf(),
                         line_number,
                         "go".to_string(),
-                    );
+                    )
+                    .with_end_line(child.end_position().row + 1);
 
                     nodes.push(package_node);
                 }
@@ -112,7 +149,8 @@ impl GoParser {
             file_path.to_path_buf(),
             line_number,
             "go".to_string(),
-        );
+        )
+        .with_end_line(import_spec.end_position().row + 1);
 
         nodes.push(import_node_obj);
     }
@@ -125,35 +163,66 @@ impl GoParser {
         nodes: &mut Vec<Node>,
         edges: &mut Vec<Edge>,
     ) {
+        let enum_types = self.collect_enum_types(root, source);
         let mut cursor = root.walk();
 
         for child in root.children(&mut cursor) {
             match child.kind() {
                 "type_declaration" => {
-                    self.process_type_declaration(&child, source, file_path, nodes, edges);
+                    self.process_type_declaration(
+                        &child,
+                        source,
+                        file_path,
+                        &enum_types,
+                        nodes,
+                        edges,
+                    );
                 }
                 _ => {}
             }
         }
     }
 
+    /// Go has no enum keyword; a named type with typed constants (`Red Color = iota`)
+    /// is the idiom, so those type names are treated as enums.
+    fn collect_enum_types(&self, root: &TSNode, source: &[u8]) -> HashSet<String> {
+        let mut enum_types = HashSet::new();
+        let mut cursor = root.walk();
+
+        for child in root.children(&mut cursor) {
+            if child.kind() != "const_declaration" {
+                continue;
+            }
+            for const_spec in find_children_by_kind(&child, "const_spec") {
+                if let Some(type_node) = const_spec.child_by_field_name("type") {
+                    enum_types.insert(extract_text(&type_node, source).to_string());
+                }
+            }
+        }
+
+        enum_types
+    }
+
     fn process_type_declaration(
         &self,
         type_decl: &TSNode,
         source: &[u8],
         file_path: &Path,
+        enum_types: &HashSet<String>,
         nodes: &mut Vec<Node>,
         edges: &mut Vec<Edge>,
     ) {
-        // Handle type specs within type declaration
-        if let Some(type_spec_list) = find_child_by_kind(type_decl, "type_spec_list") {
-            for type_spec in type_spec_list.children(&mut type_spec_list.walk()) {
-                if type_spec.kind() == "type_spec" {
-                    self.process_type_spec(&type_spec, source, file_path, nodes, edges);
+        // A grouped `type ( ... )` block lists its specs directly under the declaration
+        for type_spec in type_decl.children(&mut type_decl.walk()) {
+            match type_spec.kind() {
+                "type_spec" => {
+                    self.process_type_spec(&type_spec, source, file_path, enum_types, nodes, edges);
                 }
+                "type_alias" => {
+                    self.process_type_alias(&type_spec, source, file_path, nodes);
+                }
+                _ => {}
             }
-        } else if let Some(type_spec) = find_child_by_kind(type_decl, "type_spec") {
-            self.process_type_spec(&type_spec, source, file_path, nodes, edges);
         }
     }
 
@@ -162,6 +231,7 @@ impl GoParser {
         type_spec: &TSNode,
         source: &[u8],
         file_path: &Path,
+        enum_types: &HashSet<String>,
         nodes: &mut Vec<Node>,
         edges: &mut Vec<Edge>,
     ) {
@@ -170,8 +240,7 @@ impl GoParser {
             let line_number = type_spec.start_position().row + 1;
 
             // Determine what kind of type this is
-            if let Some(type_node) = type_spec.child(2) {
-                // Third child is the type definition
+            if let Some(type_node) = type_spec.child_by_field_name("type") {
                 match type_node.kind() {
                     "struct_type" => {
                         self.process_struct_type(
@@ -193,8 +262,26 @@ impl GoParser {
                             edges,
                         );
                     }
+                    _ if enum_types.contains(type_name) => {
+                        let type_id = generate_node_id(file_path, "enum", type_name, line_number);
+                        let type_node_obj = Node::new(
+                            type_id,
+                            type_name.to_string(),
+                            NodeType::Enum,
+                            file_path.to_path_buf(),
+                            line_number,
+                            "go".to_string(),
+                        )
+                        .with_end_line(type_spec.end_position().row + 1)
+                        .with_signature(format!(
+                            "type {} {}",
+                            type_name,
+                            extract_text(&type_node, source)
+                        ));
+                        nodes.push(type_node_obj);
+                    }
                     _ => {
-                        // Generic type alias
+                        // Named type over another type (`type Handler func(...)`)
                         let type_id = generate_node_id(file_path, "type", &type_name, line_number);
                         let type_node_obj = Node::new(
                             type_id,
@@ -203,7 +290,13 @@ impl GoParser {
                             file_path.to_path_buf(),
                             line_number,
                             "go".to_string(),
-                        );
+                        )
+                        .with_end_line(type_spec.end_position().row + 1)
+                        .with_signature(format!(
+                            "type {} {}",
+                            type_name,
+                            extract_text(&type_node, source)
+                        ));
                         nodes.push(type_node_obj);
                     }
                 }
@@ -211,6 +304,42 @@ impl GoParser {
         }
     }
 
+    /// `type Name = Other`
+    fn process_type_alias(
+        &self,
+        alias_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+    ) {
+        let (Some(name_node), Some(target_node)) = (
+            alias_node.child_by_field_name("name"),
+            alias_node.child_by_field_name("type"),
+        ) else {
+            return;
+        };
+        let alias_name = extract_text(&name_node, source);
+        let line_number = alias_node.start_position().row + 1;
+        let alias_id = generate_node_id(file_path, "type", alias_name, line_number);
+
+        nodes.push(
+            Node::new(
+                alias_id,
+                alias_name.to_string(),
+                NodeType::Class,
+                file_path.to_path_buf(),
+                line_number,
+                "go".to_string(),
+            )
+            .with_end_line(alias_node.end_position().row + 1)
+            .with_signature(format!(
+                "type {} = {}",
+                alias_name,
+                extract_text(&target_node, source)
+            )),
+        );
+    }
+
     fn process_struct_type(
         &self,
         name_node: &TSNode,
@@ -231,7 +360,9 @@ impl GoParser {
             file_path.to_path_buf(),
             line_number,
             "go".to_string(),
-        );
+        )
+        .with_end_line(struct_node.end_position().row + 1)
+        .with_signature(format!("struct {}", struct_name));
 
         nodes.push(struct_node_obj);
 
@@ -277,6 +408,7 @@ impl GoParser {
                 line_number,
                 "go".to_string(),
             )
+            .with_end_line(field_decl.end_position().row + 1)
             .with_visibility("public".to_string()); // Go fields are public if capitalized
 
             nodes.push(field_node_obj);
@@ -284,6 +416,26 @@ impl GoParser {
             let contains_edge = Edge::new(EdgeType::Contains, struct_id.to_string(), field_id);
             edges.push(contains_edge);
         }
+
+        // Embedded fields have a type and no name; they are composition all the same.
+        // Generic parameters are declared on the `type_spec` above the struct.
+        let type_spec = field_decl
+            .parent()
+            .and_then(|list| list.parent())
+            .and_then(|struct_type| struct_type.parent());
+        if let (Some(type_spec), Some(type_node)) =
+            (type_spec, field_decl.child_by_field_name("type"))
+        {
+            let mut type_names =
+                signature_type_names(&type_spec, &[type_node], source, &["type_identifier"]);
+            type_names.retain(|name| !BUILTIN_TYPES.contains(&name.as_str()));
+            push_composition(
+                struct_id,
+                &type_names,
+                field_decl.start_position().row + 1,
+                edges,
+            );
+        }
     }
 
     fn process_interface_type(
@@ -306,7 +458,8 @@ impl GoParser {
             file_path.to_path_buf(),
             line_number,
             "go".to_string(),
-        );
+        )
+        .with_end_line(interface_node.end_position().row + 1);
 
         nodes.push(interface_node_obj);
 
@@ -329,7 +482,8 @@ impl GoParser {
                             file_path.to_path_buf(),
                             method_line,
                             "go".to_string(),
-                        );
+                        )
+                        .with_end_line(method_spec.end_position().row + 1);
 
                         nodes.push(method_node_obj);
 
@@ -367,7 +521,7 @@ impl GoParser {
         source: &[u8],
         file_path: &Path,
         nodes: &mut Vec<Node>,
-        _edges: &mut Vec<Edge>,
+        edges: &mut Vec<Edge>,
     ) {
         if let Some(name_node) = find_child_by_kind(func_node, "identifier") {
             let func_name = extract_text(&name_node, source);
@@ -380,13 +534,15 @@ impl GoParser {
             }
 
             let mut func_node_obj = Node::new(
-                func_id,
+                func_id.clone(),
                 func_name.to_string(),
                 NodeType::Function,
                 file_path.to_path_buf(),
                 line_number,
                 "go".to_string(),
             )
+            .with_end_line(func_node.end_position().row + 1)
+            .with_complexity(count_branches(func_node, BRANCH_KINDS))
             .with_signature(signature);
 
             if let Some(docstring) = extract_docstring(func_node, source) {
@@ -394,9 +550,21 @@ impl GoParser {
             }
 
             nodes.push(func_node_obj);
+            push_type_uses(&func_id, &Self::signature_types(func_node, source), edges);
         }
     }
 
+    /// Types named in the parameters and results; the receiver is linked separately
+    fn signature_types(func_node: &TSNode, source: &[u8]) -> Vec<String> {
+        let parts: Vec<TSNode> = ["parameters", "result"]
+            .iter()
+            .filter_map(|field| func_node.child_by_field_name(field))
+            .collect();
+        let mut names = signature_type_names(func_node, &parts, source, &["type_identifier"]);
+        names.retain(|name| !BUILTIN_TYPES.contains(&name.as_str()));
+        names
+    }
+
     fn process_method(
         &self,
         method_node: &TSNode,
@@ -411,7 +579,8 @@ impl GoParser {
             let method_id = generate_node_id(file_path, "function", &method_name, line_number);
 
             let mut signature = method_name.to_string();
-            if let Some(param_list) = find_child_by_kind(method_node, "parameter_list") {
+            // The receiver is also a parameter_list; the method's own parameters follow it
+            if let Some(param_list) = method_node.child_by_field_name("parameters") {
                 signature = format!("{}({})", method_name, extract_text(&param_list, source));
             }
 
@@ -423,6 +592,8 @@ impl GoParser {
                 line_number,
                 "go".to_string(),
             )
+            .with_end_line(method_node.end_position().row + 1)
+            .with_complexity(count_branches(method_node, BRANCH_KINDS))
             .with_signature(signature);
 
             if let Some(docstring) = extract_docstring(method_node, source) {
@@ -430,36 +601,42 @@ impl GoParser {
             }
 
             nodes.push(method_node_obj);
+            push_type_uses(
+                &method_id,
+                &Self::signature_types(method_node, source),
+                edges,
+            );
 
-            // Extract receiver type and create edge
-            if let Some(receiver) = find_child_by_kind(method_node, "parameter_list") {
-                // The first parameter_list is the receiver for methods
-                if let Some(param_decl) = receiver.child(1) {
-                    // Skip opening parenthesis
-                    if param_decl.kind() == "parameter_declaration" {
-                        // Look for type identifier in the receiver
-                        if let Some(type_id) = find_child_by_kind(&param_decl, "type_identifier") {
-                            let receiver_type = extract_text(&type_id, source);
-                            let receiver_type_id = format!("external:struct:{}:0", receiver_type);
-                            let contains_edge =
-                                Edge::new(EdgeType::Contains, receiver_type_id, method_id);
-                            edges.push(contains_edge);
-                        } else if let Some(pointer_type) =
-                            find_child_by_kind(&param_decl, "pointer_type")
-                        {
-                            if let Some(type_id) =
-                                find_child_by_kind(&pointer_type, "type_identifier")
-                            {
-                                let receiver_type = extract_text(&type_id, source);
-                                let receiver_type_id =
-                                    format!("external:struct:{}:0", receiver_type);
-                                let contains_edge =
-                                    Edge::new(EdgeType::Contains, receiver_type_id, method_id);
-                                edges.push(contains_edge);
-                            }
-                        }
-                    }
-                }
+            // Attach the method to its receiver's type, local to this file when possible
+            if let Some(receiver_type) = Self::receiver_type_name(method_node, source) {
+                let receiver_type_id = nodes
+                    .iter()
+                    .find(|node| {
+                        node.name == receiver_type
+                            && matches!(
+                                node.node_type,
+                                NodeType::Class | NodeType::Interface | NodeType::Enum
+                            )
+                    })
+                    .map(|node| node.id.clone())
+                    .unwrap_or_else(|| format!("external:struct:{}:0", receiver_type));
+                edges.push(Edge::new(EdgeType::Contains, receiver_type_id, method_id));
+            }
+        }
+    }
+
+    /// Type named by a method receiver: `User` for `(u User)`, `(u *User)`, `(User)` and
+    /// generic `(l *List[T])`
+    fn receiver_type_name<'a>(method_node: &TSNode, source: &'a [u8]) -> Option<&'a str> {
+        let receiver = method_node.child_by_field_name("receiver")?;
+        let param = find_child_by_kind(&receiver, "parameter_declaration")?;
+        let mut type_node = param.child_by_field_name("type")?;
+        loop {
+            match type_node.kind() {
+                "type_identifier" => return Some(extract_text(&type_node, source)),
+                "pointer_type" | "parenthesized_type" => type_node = type_node.named_child(0)?,
+                "generic_type" => type_node = type_node.child_by_field_name("type")?,
+                _ => return None,
             }
         }
     }
@@ -537,7 +714,8 @@ impl GoParser {
                 file_path.to_path_buf(),
                 line_number,
                 "go".to_string(),
-            );
+            )
+            .with_end_line(var_spec.end_position().row + 1);
 
             nodes.push(var_node_obj);
         }
@@ -563,6 +741,7 @@ impl GoParser {
                 line_number,
                 "go".to_string(),
             )
+            .with_end_line(const_spec.end_position().row + 1)
             .with_visibility("public".to_string()); // Constants are typically public if capitalized
 
             nodes.push(const_node_obj);
@@ -597,14 +776,18 @@ impl LanguageParser for GoParser {
         self.extract_types(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
         self.extract_functions(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
         self.extract_variables(&root_node, source_bytes, file_path, &mut nodes);
+        resolve_local_types(&nodes, &mut edges);
 
         // Extract call sites using the new system
         let call_sites = self.extract_call_sites(&root_node, source_bytes, file_path);
 
+        assign_edge_lines(&nodes, &mut edges);
+
         Ok(ParseResult {
             nodes,
             edges,
             call_sites: Some(call_sites),
+            syntax_errors: syntax_errors(&root_node),
         })
     }
 
@@ -3,11 +3,24 @@ use std::path::Path;
 use tree_sitter::Node as TSNode;
 
 use super::common::{
-    extract_docstring, extract_text, find_child_by_kind, generate_node_id, TreeSitterParser,
+    assign_edge_lines, count_branches, extract_docstring, extract_text, find_child_by_kind,
+    generate_node_id, push_composition, push_external_exception_nodes, push_throws, push_type_uses,
+    resolve_local_types, signature_type_names, syntax_errors, thrown_type_names, TreeSitterParser,
 };
 use super::{LanguageParser, ParseResult};
 use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, Node, NodeType};
 
+/// Branch points counted toward a function's `complexity`
+const BRANCH_KINDS: &[&str] = &[
+    "if_statement",
+    "for_statement",
+    "enhanced_for_statement",
+    "while_statement",
+    "do_statement",
+    "switch_label",
+    "ternary_expression",
+];
+
 pub struct JavaParser {
     #[allow(dead_code)]
     parser: TreeSitterParser,
@@ -44,7 +57,8 @@ impl JavaParser {
                         file_path.to_path_buf(),
                         line_number,
                         "java".to_string(),
-                    );
+                    )
+                    .with_end_line(child.end_position().row + 1);
 
                     nodes.push(package_node);
                 }
@@ -87,7 +101,8 @@ impl JavaParser {
             file_path.to_path_buf(),
             line_number,
             "java".to_string(),
-        );
+        )
+        .with_end_line(import_node.end_position().row + 1);
 
         nodes.push(import_node_obj);
     }
@@ -128,6 +143,7 @@ impl JavaParser {
             let line_number = class_node.start_position().row + 1;
             let class_id = generate_node_id(file_path, "class", &class_name, line_number);
 
+            let annotations = Self::extract_annotations(class_node, source, &class_id, edges);
             let mut class_node_obj = Node::new(
                 class_id.clone(),
                 class_name.to_string(),
@@ -135,7 +151,12 @@ impl JavaParser {
                 file_path.to_path_buf(),
                 line_number,
                 "java".to_string(),
-            );
+            )
+            .with_end_line(class_node.end_position().row + 1)
+            .with_signature(Self::type_signature(
+                "class", class_name, class_node, source,
+            ))
+            .with_decorators(annotations);
 
             // Extract docstring/comments
             if let Some(docstring) = extract_docstring(class_node, source) {
@@ -148,7 +169,8 @@ impl JavaParser {
                     let parent_class = extract_text(&type_node, source);
                     let parent_id = format!("external:class:{}:0", parent_class);
                     let inheritance_edge =
-                        Edge::new(EdgeType::Inheritance, class_id.clone(), parent_id);
+                        Edge::new(EdgeType::Inheritance, class_id.clone(), parent_id)
+                            .with_line(superclass.start_position().row + 1);
                     edges.push(inheritance_edge);
                 }
             }
@@ -156,14 +178,16 @@ impl JavaParser {
             // Handle interfaces (implements)
             if let Some(super_interfaces) = find_child_by_kind(class_node, "super_interfaces") {
                 for interface_list in super_interfaces.children(&mut super_interfaces.walk()) {
-                    if interface_list.kind() == "interface_type_list" {
+                    // `type_list` in current grammars, `interface_type_list` in older ones
+                    if matches!(interface_list.kind(), "type_list" | "interface_type_list") {
                         for interface in interface_list.children(&mut interface_list.walk()) {
                             if interface.kind() == "type_identifier" {
                                 let interface_name = extract_text(&interface, source);
                                 let interface_id =
                                     format!("external:interface:{}:0", interface_name);
                                 let implements_edge =
-                                    Edge::new(EdgeType::Implements, class_id.clone(), interface_id);
+                                    Edge::new(EdgeType::Implements, class_id.clone(), interface_id)
+                                        .with_line(interface.start_position().row + 1);
                                 edges.push(implements_edge);
                             }
                         }
@@ -191,6 +215,7 @@ impl JavaParser {
             let line_number = enum_node.start_position().row + 1;
             let enum_id = generate_node_id(file_path, "enum", &enum_name, line_number);
 
+            let annotations = Self::extract_annotations(enum_node, source, &enum_id, edges);
             let enum_node_obj = Node::new(
                 enum_id.clone(),
                 enum_name.to_string(),
@@ -198,7 +223,9 @@ impl JavaParser {
                 file_path.to_path_buf(),
                 line_number,
                 "java".to_string(),
-            );
+            )
+            .with_end_line(enum_node.end_position().row + 1)
+            .with_decorators(annotations);
 
             nodes.push(enum_node_obj);
 
@@ -224,6 +251,7 @@ impl JavaParser {
                                 constant_line,
                                 "java".to_string(),
                             )
+                            .with_end_line(child.end_position().row + 1)
                             .with_visibility("public".to_string());
 
                             nodes.push(constant_node);
@@ -306,6 +334,7 @@ impl JavaParser {
                     }
                 }
 
+                let annotations = Self::extract_annotations(field_node, source, &field_id, edges);
                 let field_node_obj = Node::new(
                     field_id.clone(),
                     field_name.to_string(),
@@ -314,12 +343,28 @@ impl JavaParser {
                     line_number,
                     "java".to_string(),
                 )
-                .with_visibility(visibility);
+                .with_end_line(field_node.end_position().row + 1)
+                .with_visibility(visibility)
+                .with_decorators(annotations);
 
                 nodes.push(field_node_obj);
 
                 let contains_edge = Edge::new(EdgeType::Contains, class_id.to_string(), field_id);
                 edges.push(contains_edge);
+
+                // Generic parameters are declared on the class, two levels up past the body
+                let class_node = field_node.parent().and_then(|body| body.parent());
+                if let (Some(class_node), Some(type_node)) =
+                    (class_node, field_node.child_by_field_name("type"))
+                {
+                    let type_names = signature_type_names(
+                        &class_node,
+                        &[type_node],
+                        source,
+                        &["type_identifier"],
+                    );
+                    push_composition(class_id, &type_names, line_number, edges);
+                }
             }
         }
     }
@@ -355,6 +400,8 @@ impl JavaParser {
             let interface_id =
                 generate_node_id(file_path, "interface", &interface_name, line_number);
 
+            let annotations =
+                Self::extract_annotations(interface_node, source, &interface_id, edges);
             let interface_node_obj = Node::new(
                 interface_id.clone(),
                 interface_name.to_string(),
@@ -362,7 +409,15 @@ impl JavaParser {
                 file_path.to_path_buf(),
                 line_number,
                 "java".to_string(),
-            );
+            )
+            .with_end_line(interface_node.end_position().row + 1)
+            .with_signature(Self::type_signature(
+                "interface",
+                interface_name,
+                interface_node,
+                source,
+            ))
+            .with_decorators(annotations);
 
             nodes.push(interface_node_obj);
 
@@ -419,6 +474,10 @@ impl JavaParser {
             if let Some(params) = find_child_by_kind(method_node, "formal_parameters") {
                 signature = format!("{}({})", method_name, extract_text(&params, source));
             }
+            // Generic methods keep their type parameters in front, as declared: `<T> find(T key)`
+            if let Some(type_params) = method_node.child_by_field_name("type_parameters") {
+                signature = format!("{} {}", extract_text(&type_params, source), signature);
+            }
 
             let mut visibility = "package".to_string(); // Default visibility
 
@@ -437,6 +496,10 @@ impl JavaParser {
                 }
             }
 
+            let annotations = Self::extract_annotations(method_node, source, &method_id, edges);
+            let is_test = annotations
+                .iter()
+                .any(|name| Self::is_test_annotation(name));
             let mut method_node_obj = Node::new(
                 method_id.clone(),
                 method_name.to_string(),
@@ -445,8 +508,12 @@ impl JavaParser {
                 line_number,
                 "java".to_string(),
             )
+            .with_end_line(method_node.end_position().row + 1)
+            .with_complexity(count_branches(method_node, BRANCH_KINDS))
             .with_signature(signature)
-            .with_visibility(visibility);
+            .with_visibility(visibility)
+            .with_decorators(annotations)
+            .with_test(is_test);
 
             if let Some(docstring) = extract_docstring(method_node, source) {
                 method_node_obj = method_node_obj.with_docstring(docstring);
@@ -454,6 +521,16 @@ impl JavaParser {
 
             nodes.push(method_node_obj);
 
+            // Return and parameter types, e.g. `User findUser(Id id)` uses both `User` and `Id`
+            let type_nodes: Vec<TSNode> = ["type", "parameters"]
+                .iter()
+                .filter_map(|field| method_node.child_by_field_name(field))
+                .collect();
+            let type_names =
+                signature_type_names(method_node, &type_nodes, source, &["type_identifier"]);
+            push_type_uses(&method_id, &type_names, edges);
+            push_throws(&method_id, &thrown_type_names(method_node, source), edges);
+
             if let Some(class_id) = class_id {
                 let contains_edge = Edge::new(EdgeType::Contains, class_id.to_string(), method_id);
                 edges.push(contains_edge);
@@ -461,6 +538,54 @@ impl JavaParser {
         }
     }
 
+    /// `Uses` edges to the annotations in a declaration's modifiers, the way Python
+    /// decorators are linked; returns the annotation names without `@` or arguments
+    fn extract_annotations(
+        declaration: &TSNode,
+        source: &[u8],
+        target_id: &str,
+        edges: &mut Vec<Edge>,
+    ) -> Vec<String> {
+        let Some(modifiers) = find_child_by_kind(declaration, "modifiers") else {
+            return Vec::new();
+        };
+        let mut names = Vec::new();
+        for child in modifiers.children(&mut modifiers.walk()) {
+            if !matches!(child.kind(), "marker_annotation" | "annotation") {
+                continue;
+            }
+            if let Some(name_node) = child.child_by_field_name("name") {
+                let name = extract_text(&name_node, source).to_string();
+                edges.push(Edge::new(
+                    EdgeType::Uses,
+                    target_id.to_string(),
+                    format!("external:annotation:{}:0", name),
+                ));
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// JUnit's test method markers, plain or qualified: `Test`, `org.junit.jupiter.api.Test`
+    fn is_test_annotation(name: &str) -> bool {
+        let simple = name.rsplit('.').next().unwrap_or(name);
+        matches!(
+            simple,
+            "Test" | "ParameterizedTest" | "RepeatedTest" | "TestFactory" | "TestTemplate"
+        )
+    }
+
+    /// `class Repository<T extends Entity>`: keyword, name and any type parameters
+    fn type_signature(keyword: &str, name: &str, declaration: &TSNode, source: &[u8]) -> String {
+        match declaration.child_by_field_name("type_parameters") {
+            Some(type_params) => {
+                format!("{} {}{}", keyword, name, extract_text(&type_params, source))
+            }
+            None => format!("{} {}", keyword, name),
+        }
+    }
+
     /// Extract call sites using the new optimized CallSiteExtractor
     fn extract_call_sites(
         &self,
@@ -489,14 +614,19 @@ impl LanguageParser for JavaParser {
         self.extract_classes(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
         self.extract_interfaces(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
         self.extract_functions(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
+        resolve_local_types(&nodes, &mut edges);
+        push_external_exception_nodes(&mut nodes, &edges, file_path, "java");
 
         // Extract call sites using the new system
         let call_sites = self.extract_call_sites(&root_node, source_bytes, file_path);
 
+        assign_edge_lines(&nodes, &mut edges);
+
         Ok(ParseResult {
             nodes,
             edges,
             call_sites: Some(call_sites),
+            syntax_errors: syntax_errors(&root_node),
         })
     }
 
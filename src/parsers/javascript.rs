@@ -3,11 +3,23 @@ use std::path::Path;
 use tree_sitter::Node as TSNode;
 
 use super::common::{
-    extract_text, find_child_by_kind, find_children_by_kind, generate_node_id, TreeSitterParser,
+    assign_edge_lines, count_branches, extract_text, find_child_by_kind, find_children_by_kind,
+    generate_node_id, is_async_function, push_test_cases, syntax_errors, TreeSitterParser,
 };
 use super::{LanguageParser, ParseResult};
 use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, Node, NodeType};
 
+/// Branch points counted toward a function's `complexity`
+const BRANCH_KINDS: &[&str] = &[
+    "if_statement",
+    "for_statement",
+    "for_in_statement",
+    "while_statement",
+    "do_statement",
+    "switch_case",
+    "ternary_expression",
+];
+
 pub struct JavaScriptParser {
     #[allow(dead_code)]
     parser: TreeSitterParser,
@@ -61,7 +73,8 @@ impl JavaScriptParser {
             file_path.to_path_buf(),
             line_number,
             "javascript".to_string(),
-        );
+        )
+        .with_end_line(import_node.end_position().row + 1);
 
         nodes.push(import_node_obj);
     }
@@ -91,7 +104,8 @@ impl JavaScriptParser {
                             file_path.to_path_buf(),
                             line_number,
                             "javascript".to_string(),
-                        );
+                        )
+                        .with_end_line(var_decl.end_position().row + 1);
 
                         nodes.push(require_node_obj);
                     }
@@ -137,7 +151,8 @@ impl JavaScriptParser {
                 file_path.to_path_buf(),
                 line_number,
                 "javascript".to_string(),
-            );
+            )
+            .with_end_line(class_node.end_position().row + 1);
 
             // Handle inheritance (extends)
             if let Some(class_heritage) = find_child_by_kind(class_node, "class_heritage") {
@@ -145,7 +160,8 @@ impl JavaScriptParser {
                     let parent_class = extract_text(&identifier, source);
                     let parent_id = format!("external:class:{}:0", parent_class);
                     let inheritance_edge =
-                        Edge::new(EdgeType::Inheritance, class_id.clone(), parent_id);
+                        Edge::new(EdgeType::Inheritance, class_id.clone(), parent_id)
+                            .with_line(class_heritage.start_position().row + 1);
                     edges.push(inheritance_edge);
                 }
             }
@@ -194,7 +210,8 @@ impl JavaScriptParser {
                                 file_path.to_path_buf(),
                                 line_number,
                                 "javascript".to_string(),
-                            );
+                            )
+                            .with_end_line(child.end_position().row + 1);
 
                             nodes.push(field_node);
 
@@ -284,6 +301,9 @@ impl JavaScriptParser {
                 line_number,
                 "javascript".to_string(),
             )
+            .with_end_line(func_node.end_position().row + 1)
+            .with_complexity(count_branches(func_node, BRANCH_KINDS))
+            .with_async(is_async_function(func_node))
             .with_signature(signature);
 
             nodes.push(func_node_obj);
@@ -313,6 +333,13 @@ impl JavaScriptParser {
             if let Some(params) = find_child_by_kind(method_node, "formal_parameters") {
                 signature = format!("{}({})", method_name, extract_text(&params, source));
             }
+            // Accessors keep their keyword: `get total()`
+            if let Some(accessor) = ["get", "set"]
+                .into_iter()
+                .find(|kind| find_child_by_kind(method_node, kind).is_some())
+            {
+                signature = format!("{} {}", accessor, signature);
+            }
 
             let method_node_obj = Node::new(
                 method_id.clone(),
@@ -322,6 +349,9 @@ impl JavaScriptParser {
                 line_number,
                 "javascript".to_string(),
             )
+            .with_end_line(method_node.end_position().row + 1)
+            .with_complexity(count_branches(method_node, BRANCH_KINDS))
+            .with_async(is_async_function(method_node))
             .with_signature(signature);
 
             nodes.push(method_node_obj);
@@ -336,7 +366,7 @@ impl JavaScriptParser {
     fn process_arrow_function(
         &self,
         declarator: &TSNode,
-        _arrow_func: &TSNode,
+        arrow_func: &TSNode,
         source: &[u8],
         file_path: &Path,
         nodes: &mut Vec<Node>,
@@ -354,7 +384,10 @@ impl JavaScriptParser {
                 file_path.to_path_buf(),
                 line_number,
                 "javascript".to_string(),
-            );
+            )
+            .with_end_line(declarator.end_position().row + 1)
+            .with_complexity(count_branches(arrow_func, BRANCH_KINDS))
+            .with_async(is_async_function(arrow_func));
 
             nodes.push(func_node_obj);
         }
@@ -363,7 +396,7 @@ impl JavaScriptParser {
     fn process_function_expression(
         &self,
         declarator: &TSNode,
-        _func_expr: &TSNode,
+        func_expr: &TSNode,
         source: &[u8],
         file_path: &Path,
         nodes: &mut Vec<Node>,
@@ -381,7 +414,10 @@ impl JavaScriptParser {
                 file_path.to_path_buf(),
                 line_number,
                 "javascript".to_string(),
-            );
+            )
+            .with_end_line(declarator.end_position().row + 1)
+            .with_complexity(count_branches(func_expr, BRANCH_KINDS))
+            .with_async(is_async_function(func_expr));
 
             nodes.push(func_node_obj);
         }
@@ -420,7 +456,10 @@ impl JavaScriptParser {
                                     file_path.to_path_buf(),
                                     line_number,
                                     "javascript".to_string(),
-                                );
+                                )
+                                .with_end_line(expr_stmt.end_position().row + 1)
+                                .with_complexity(count_branches(&right_side, BRANCH_KINDS))
+                                .with_async(is_async_function(&right_side));
 
                                 nodes.push(method_node_obj);
 
@@ -485,7 +524,10 @@ impl JavaScriptParser {
                                 file_path.to_path_buf(),
                                 line_number,
                                 "javascript".to_string(),
-                            );
+                            )
+                            .with_end_line(child.end_position().row + 1)
+                            .with_complexity(count_branches(&value_node, BRANCH_KINDS))
+                            .with_async(is_async_function(&value_node));
 
                             nodes.push(method_node_obj);
                         }
@@ -521,15 +563,26 @@ impl LanguageParser for JavaScriptParser {
         self.extract_imports(&root_node, source_bytes, file_path, &mut nodes);
         self.extract_classes(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
         self.extract_functions(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
+        push_test_cases(
+            &root_node,
+            source_bytes,
+            file_path,
+            "javascript",
+            BRANCH_KINDS,
+            &mut nodes,
+        );
         self.extract_object_methods(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
 
         // Extract call sites using the new system
         let call_sites = self.extract_call_sites(&root_node, source_bytes, file_path);
 
+        assign_edge_lines(&nodes, &mut edges);
+
         Ok(ParseResult {
             nodes,
             edges,
             call_sites: Some(call_sites),
+            syntax_errors: syntax_errors(&root_node),
         })
     }
 
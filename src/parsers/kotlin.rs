@@ -0,0 +1,450 @@
+use anyhow::Result;
+use std::path::Path;
+use tree_sitter::Node as TSNode;
+
+use super::common::{
+    assign_edge_lines, count_branches, extract_text, find_child_by_kind, generate_node_id,
+    syntax_errors, TreeSitterParser,
+};
+use super::{LanguageParser, ParseResult};
+use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, Node, NodeType};
+
+/// Branch points counted toward a function's `complexity`
+const BRANCH_KINDS: &[&str] = &[
+    "if_expression",
+    "for_statement",
+    "while_statement",
+    "do_while_statement",
+    "when_entry",
+];
+
+pub struct KotlinParser {
+    #[allow(dead_code)]
+    parser: TreeSitterParser,
+}
+
+impl KotlinParser {
+    pub fn new() -> Result<Self> {
+        let language = tree_sitter_kotlin::language();
+        let parser = TreeSitterParser::new(language)?;
+        Ok(Self { parser })
+    }
+
+    fn extract_package_and_imports(
+        &self,
+        root: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+    ) {
+        let mut cursor = root.walk();
+
+        for child in root.children(&mut cursor) {
+            match child.kind() {
+                "package_header" => {
+                    if let Some(name_node) = find_child_by_kind(&child, "identifier") {
+                        let package_name = extract_text(&name_node, source);
+                        let line_number = child.start_position().row + 1;
+                        let package_id =
+                            generate_node_id(file_path, "package", package_name, line_number);
+
+                        nodes.push(
+                            Node::new(
+                                package_id,
+                                package_name.to_string(),
+                                NodeType::Module,
+                                file_path.to_path_buf(),
+                                line_number,
+                                "kotlin".to_string(),
+                            )
+                            .with_end_line(child.end_position().row + 1),
+                        );
+                    }
+                }
+                "import_list" => {
+                    for import in child.children(&mut child.walk()) {
+                        if import.kind() == "import_header" {
+                            self.process_import(&import, source, file_path, nodes);
+                        }
+                    }
+                }
+                "import_header" => {
+                    self.process_import(&child, source, file_path, nodes);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn process_import(
+        &self,
+        import_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+    ) {
+        let import_text = extract_text(import_node, source).trim();
+        let line_number = import_node.start_position().row + 1;
+
+        let module_id = generate_node_id(file_path, "import", import_text, line_number);
+        nodes.push(
+            Node::new(
+                module_id,
+                import_text.to_string(),
+                NodeType::Module,
+                file_path.to_path_buf(),
+                line_number,
+                "kotlin".to_string(),
+            )
+            .with_end_line(import_node.end_position().row + 1),
+        );
+    }
+
+    fn extract_declarations(
+        &self,
+        root: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        let mut cursor = root.walk();
+
+        for child in root.children(&mut cursor) {
+            match child.kind() {
+                "class_declaration" | "interface_declaration" => {
+                    self.process_class(&child, source, file_path, None, nodes, edges);
+                }
+                "object_declaration" => {
+                    self.process_object(&child, source, file_path, None, nodes, edges);
+                }
+                "function_declaration" => {
+                    self.process_function(&child, source, file_path, None, nodes, edges);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Modifiers such as `data`, `sealed`, `private` precede the declaration keyword
+    fn collect_modifiers(&self, decl: &TSNode, source: &[u8]) -> Vec<String> {
+        let mut modifiers = Vec::new();
+        if let Some(modifier_list) = find_child_by_kind(decl, "modifiers") {
+            for modifier in modifier_list.children(&mut modifier_list.walk()) {
+                if modifier.kind() != "annotation" {
+                    modifiers.push(extract_text(&modifier, source).to_string());
+                }
+            }
+        }
+        modifiers
+    }
+
+    fn visibility_from_modifiers(&self, modifiers: &[String]) -> String {
+        modifiers
+            .iter()
+            .find(|m| matches!(m.as_str(), "public" | "private" | "protected" | "internal"))
+            .cloned()
+            .unwrap_or_else(|| "public".to_string())
+    }
+
+    fn process_class(
+        &self,
+        class_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        parent_id: Option<&str>,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        if let Some(name_node) = find_child_by_kind(class_node, "type_identifier") {
+            let class_name = extract_text(&name_node, source);
+            let line_number = class_node.start_position().row + 1;
+
+            // The grammar uses class_declaration for both `class` and `interface`
+            let is_interface = class_node.kind() == "interface_declaration"
+                || class_node
+                    .children(&mut class_node.walk())
+                    .any(|c| c.kind() == "interface");
+            let (kind, node_type) = if is_interface {
+                ("interface", NodeType::Interface)
+            } else {
+                ("class", NodeType::Class)
+            };
+            let class_id = generate_node_id(file_path, kind, class_name, line_number);
+
+            let modifiers = self.collect_modifiers(class_node, source);
+            let mut signature = format!("{} {}", kind, class_name);
+            if let Some(class_modifier) = modifiers
+                .iter()
+                .find(|m| matches!(m.as_str(), "data" | "sealed" | "enum" | "abstract" | "open"))
+            {
+                signature = format!("{} {}", class_modifier, signature);
+            }
+
+            nodes.push(
+                Node::new(
+                    class_id.clone(),
+                    class_name.to_string(),
+                    node_type,
+                    file_path.to_path_buf(),
+                    line_number,
+                    "kotlin".to_string(),
+                )
+                .with_end_line(class_node.end_position().row + 1)
+                .with_signature(signature)
+                .with_visibility(self.visibility_from_modifiers(&modifiers)),
+            );
+
+            if let Some(parent_id) = parent_id {
+                edges.push(Edge::new(
+                    EdgeType::Contains,
+                    parent_id.to_string(),
+                    class_id.clone(),
+                ));
+            }
+
+            self.process_supertypes(class_node, source, &class_id, edges);
+
+            if let Some(body) = find_child_by_kind(class_node, "class_body")
+                .or_else(|| find_child_by_kind(class_node, "enum_class_body"))
+            {
+                self.process_class_body(&body, source, file_path, &class_id, nodes, edges);
+            }
+        }
+    }
+
+    /// `object Foo : Bar` singletons are modelled as classes
+    fn process_object(
+        &self,
+        object_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        parent_id: Option<&str>,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        if let Some(name_node) = find_child_by_kind(object_node, "type_identifier") {
+            let object_name = extract_text(&name_node, source);
+            let line_number = object_node.start_position().row + 1;
+            let object_id = generate_node_id(file_path, "object", object_name, line_number);
+
+            nodes.push(
+                Node::new(
+                    object_id.clone(),
+                    object_name.to_string(),
+                    NodeType::Class,
+                    file_path.to_path_buf(),
+                    line_number,
+                    "kotlin".to_string(),
+                )
+                .with_end_line(object_node.end_position().row + 1)
+                .with_signature(format!("object {}", object_name)),
+            );
+
+            if let Some(parent_id) = parent_id {
+                edges.push(Edge::new(
+                    EdgeType::Contains,
+                    parent_id.to_string(),
+                    object_id.clone(),
+                ));
+            }
+
+            self.process_supertypes(object_node, source, &object_id, edges);
+
+            if let Some(body) = find_child_by_kind(object_node, "class_body") {
+                self.process_class_body(&body, source, file_path, &object_id, nodes, edges);
+            }
+        }
+    }
+
+    /// Supertypes after the `:`. A constructor invocation (`Base()`) marks the superclass,
+    /// a bare type is an implemented interface.
+    fn process_supertypes(
+        &self,
+        decl: &TSNode,
+        source: &[u8],
+        class_id: &str,
+        edges: &mut Vec<Edge>,
+    ) {
+        let mut specifiers = Vec::new();
+        for child in decl.children(&mut decl.walk()) {
+            match child.kind() {
+                "delegation_specifier" => specifiers.push(child),
+                "delegation_specifiers" => {
+                    for spec in child.children(&mut child.walk()) {
+                        if spec.kind() == "delegation_specifier" {
+                            specifiers.push(spec);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for spec in specifiers {
+            let (parent_name, edge_type) = if let Some(invocation) =
+                find_child_by_kind(&spec, "constructor_invocation")
+            {
+                let name =
+                    find_child_by_kind(&invocation, "user_type").map(|t| extract_text(&t, source));
+                (name, EdgeType::Inheritance)
+            } else {
+                let name = find_child_by_kind(&spec, "user_type").map(|t| extract_text(&t, source));
+                (name, EdgeType::Implements)
+            };
+
+            if let Some(parent_name) = parent_name {
+                // Strip generic arguments: Repository<User> -> Repository
+                let parent_name = parent_name.split('<').next().unwrap_or(parent_name).trim();
+                if parent_name.is_empty() {
+                    continue;
+                }
+                let parent_id = if edge_type == EdgeType::Inheritance {
+                    format!("external:class:{}:0", parent_name)
+                } else {
+                    format!("external:interface:{}:0", parent_name)
+                };
+                edges.push(
+                    Edge::new(edge_type, class_id.to_string(), parent_id)
+                        .with_line(spec.start_position().row + 1),
+                );
+            }
+        }
+    }
+
+    fn process_class_body(
+        &self,
+        body: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        class_id: &str,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        for member in body.children(&mut body.walk()) {
+            match member.kind() {
+                "function_declaration" => {
+                    self.process_function(&member, source, file_path, Some(class_id), nodes, edges);
+                }
+                "companion_object" => {
+                    // Companion members are attributed to the enclosing class
+                    if let Some(companion_body) = find_child_by_kind(&member, "class_body") {
+                        self.process_class_body(
+                            &companion_body,
+                            source,
+                            file_path,
+                            class_id,
+                            nodes,
+                            edges,
+                        );
+                    }
+                }
+                "class_declaration" | "interface_declaration" => {
+                    self.process_class(&member, source, file_path, Some(class_id), nodes, edges);
+                }
+                "object_declaration" => {
+                    self.process_object(&member, source, file_path, Some(class_id), nodes, edges);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn process_function(
+        &self,
+        func_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        class_id: Option<&str>,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        if let Some(name_node) = find_child_by_kind(func_node, "simple_identifier") {
+            let func_name = extract_text(&name_node, source);
+            let line_number = func_node.start_position().row + 1;
+            let func_id = generate_node_id(file_path, "function", func_name, line_number);
+
+            let params = find_child_by_kind(func_node, "function_value_parameters")
+                .map(|p| extract_text(&p, source))
+                .unwrap_or("()");
+            let mut signature = format!("{}{}", func_name, params);
+
+            // Return type follows the parameter list: fun foo(): Bar
+            let mut after_params = false;
+            for child in func_node.children(&mut func_node.walk()) {
+                match child.kind() {
+                    "function_value_parameters" => after_params = true,
+                    "function_body" => break,
+                    kind if after_params && kind.ends_with("type") => {
+                        signature = format!("{}: {}", signature, extract_text(&child, source));
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+
+            let modifiers = self.collect_modifiers(func_node, source);
+
+            nodes.push(
+                Node::new(
+                    func_id.clone(),
+                    func_name.to_string(),
+                    NodeType::Function,
+                    file_path.to_path_buf(),
+                    line_number,
+                    "kotlin".to_string(),
+                )
+                .with_end_line(func_node.end_position().row + 1)
+                .with_complexity(count_branches(func_node, BRANCH_KINDS))
+                .with_signature(signature)
+                .with_visibility(self.visibility_from_modifiers(&modifiers)),
+            );
+
+            if let Some(class_id) = class_id {
+                edges.push(Edge::new(EdgeType::Contains, class_id.to_string(), func_id));
+            }
+        }
+    }
+
+    /// Extract call sites using the shared CallSiteExtractor
+    fn extract_call_sites(
+        &self,
+        root_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+    ) -> Vec<CallSite> {
+        let mut extractor = CallSiteExtractor::new();
+        extractor.extract_from_ast(root_node, source, file_path)
+    }
+}
+
+impl LanguageParser for KotlinParser {
+    fn parse_file(&self, file_path: &Path) -> Result<ParseResult> {
+        let mut parser = TreeSitterParser::new(tree_sitter_kotlin::language())?;
+        let tree = parser.parse_file(file_path)?;
+        let source = parser.get_source(file_path)?;
+        let source_bytes = source.as_bytes();
+
+        let root_node = tree.root_node();
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+
+        self.extract_package_and_imports(&root_node, source_bytes, file_path, &mut nodes);
+        self.extract_declarations(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
+
+        let call_sites = self.extract_call_sites(&root_node, source_bytes, file_path);
+
+        assign_edge_lines(&nodes, &mut edges);
+
+        Ok(ParseResult {
+            nodes,
+            edges,
+            call_sites: Some(call_sites),
+            syntax_errors: syntax_errors(&root_node),
+        })
+    }
+
+    fn language_name(&self) -> &str {
+        "kotlin"
+    }
+}
@@ -0,0 +1,320 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+use tree_sitter::Node as TSNode;
+
+use super::common::{
+    assign_edge_lines, count_branches, extract_text, generate_node_id, syntax_errors,
+    TreeSitterParser,
+};
+use super::{LanguageParser, ParseResult};
+use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, Node, NodeType};
+
+/// Branch points counted toward a function's `complexity`
+const BRANCH_KINDS: &[&str] = &[
+    "if_statement",
+    "elseif_statement",
+    "while_statement",
+    "repeat_statement",
+    "for_statement",
+];
+
+pub struct LuaParser {
+    #[allow(dead_code)]
+    parser: TreeSitterParser,
+}
+
+/// Lua has no classes; functions attached to a table (`function M.foo()`) are grouped
+/// under a module node named after the table.
+struct FileContext {
+    /// Line of the `M = {}` that created each table, where the file has one
+    table_lines: HashMap<String, usize>,
+    /// Maps table name to its module node ID, created on first use
+    table_modules: HashMap<String, String>,
+}
+
+impl LuaParser {
+    pub fn new() -> Result<Self> {
+        let language = tree_sitter_lua::language();
+        let parser = TreeSitterParser::new(language)?;
+        Ok(Self { parser })
+    }
+
+    /// `(variable, value)` pairs of an assignment: `a, b = x, y` yields `(a, x)`, `(b, y)`
+    fn assignment_pairs<'a>(assignment: &TSNode<'a>) -> Vec<(TSNode<'a>, TSNode<'a>)> {
+        let mut cursor = assignment.walk();
+        let lists: Vec<TSNode<'a>> = assignment.named_children(&mut cursor).collect();
+        let (Some(variables), Some(values)) = (
+            lists.iter().find(|c| c.kind() == "variable_list"),
+            lists.iter().find(|c| c.kind() == "expression_list"),
+        ) else {
+            return Vec::new();
+        };
+
+        let mut variable_cursor = variables.walk();
+        let mut value_cursor = values.walk();
+        variables
+            .named_children(&mut variable_cursor)
+            .zip(values.named_children(&mut value_cursor))
+            .collect()
+    }
+
+    fn collect_tables(&self, node: &TSNode, source: &[u8], ctx: &mut FileContext) {
+        if node.kind() == "assignment_statement" {
+            for (variable, value) in Self::assignment_pairs(node) {
+                if variable.kind() == "identifier" && value.kind() == "table_constructor" {
+                    ctx.table_lines
+                        .entry(extract_text(&variable, source).to_string())
+                        .or_insert(node.start_position().row + 1);
+                }
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.collect_tables(&child, source, ctx);
+        }
+    }
+
+    /// Walk the whole tree: Lua functions may be declared inside blocks and other functions.
+    fn process_node(
+        &self,
+        node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        ctx: &mut FileContext,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        match node.kind() {
+            "function_declaration" | "local_function_declaration" => {
+                if let Some(name_node) = node.child_by_field_name("name") {
+                    let is_local = node.kind() == "local_function_declaration"
+                        || node.child(0).is_some_and(|first| first.kind() == "local");
+                    self.process_function(
+                        node, &name_node, is_local, source, file_path, ctx, nodes, edges,
+                    );
+                }
+            }
+            "assignment_statement" => {
+                // `local f = function() end` and `M.f = function() end`
+                let is_local = node
+                    .parent()
+                    .is_some_and(|parent| parent.kind() == "variable_declaration");
+                for (variable, value) in Self::assignment_pairs(node) {
+                    if value.kind() == "function_definition" {
+                        self.process_function(
+                            &value, &variable, is_local, source, file_path, ctx, nodes, edges,
+                        );
+                    }
+                }
+            }
+            "function_call" => self.process_require(node, source, file_path, nodes),
+            _ => {}
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.process_node(&child, source, file_path, ctx, nodes, edges);
+        }
+    }
+
+    /// `function_node` holds the parameters and body; `name_node` is the declared name or
+    /// the variable the function is assigned to.
+    #[allow(clippy::too_many_arguments)]
+    fn process_function(
+        &self,
+        function_node: &TSNode,
+        name_node: &TSNode,
+        is_local: bool,
+        source: &[u8],
+        file_path: &Path,
+        ctx: &mut FileContext,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        // `M.foo` / `M:foo` attach the function to table `M`
+        let (table, function_name) = match name_node.kind() {
+            "identifier" => (None, extract_text(name_node, source)),
+            "dot_index_expression" | "method_index_expression" => {
+                let (Some(table), Some(field)) = (
+                    name_node.child_by_field_name("table"),
+                    name_node
+                        .child_by_field_name("field")
+                        .or_else(|| name_node.child_by_field_name("method")),
+                ) else {
+                    return;
+                };
+                (
+                    Some(extract_text(&table, source)),
+                    extract_text(&field, source),
+                )
+            }
+            _ => return,
+        };
+        if function_name.is_empty() {
+            return;
+        }
+
+        let line_number = function_node.start_position().row + 1;
+        let function_id = generate_node_id(file_path, "function", function_name, line_number);
+
+        let params = function_node
+            .child_by_field_name("parameters")
+            .map(|p| {
+                extract_text(&p, source)
+                    .trim_start_matches('(')
+                    .trim_end_matches(')')
+            })
+            .unwrap_or("");
+        let signature = format!("{}({})", extract_text(name_node, source), params);
+
+        nodes.push(
+            Node::new(
+                function_id.clone(),
+                function_name.to_string(),
+                NodeType::Function,
+                file_path.to_path_buf(),
+                line_number,
+                "lua".to_string(),
+            )
+            .with_end_line(function_node.end_position().row + 1)
+            .with_complexity(count_branches(function_node, BRANCH_KINDS))
+            .with_signature(signature)
+            .with_visibility(if is_local { "private" } else { "public" }.to_string()),
+        );
+
+        if let Some(table) = table {
+            let module_id = self.table_module(table, line_number, file_path, ctx, nodes);
+            edges.push(Edge::new(EdgeType::Contains, module_id, function_id));
+        }
+    }
+
+    /// Module node standing in for a table, anchored at the table's constructor when the
+    /// file has one, else at its first function
+    fn table_module(
+        &self,
+        table: &str,
+        line_number: usize,
+        file_path: &Path,
+        ctx: &mut FileContext,
+        nodes: &mut Vec<Node>,
+    ) -> String {
+        if let Some(module_id) = ctx.table_modules.get(table) {
+            return module_id.clone();
+        }
+
+        let line_number = ctx.table_lines.get(table).copied().unwrap_or(line_number);
+        let module_id = generate_node_id(file_path, "module", table, line_number);
+        nodes.push(Node::new(
+            module_id.clone(),
+            table.to_string(),
+            NodeType::Module,
+            file_path.to_path_buf(),
+            line_number,
+            "lua".to_string(),
+        ));
+        ctx.table_modules
+            .insert(table.to_string(), module_id.clone());
+        module_id
+    }
+
+    /// `require("plenary.async")` / `require "json"` imports a module
+    fn process_require(
+        &self,
+        call_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+    ) {
+        let is_require = call_node.child_by_field_name("name").is_some_and(|name| {
+            name.kind() == "identifier" && extract_text(&name, source) == "require"
+        });
+        let Some(arguments) = call_node
+            .child_by_field_name("arguments")
+            .filter(|_| is_require)
+        else {
+            return;
+        };
+
+        let mut cursor = arguments.walk();
+        let Some(string_node) = arguments
+            .named_children(&mut cursor)
+            .find(|arg| arg.kind() == "string")
+        else {
+            return;
+        };
+        let module_name = extract_text(&string_node, source)
+            .trim_matches(|c| matches!(c, '"' | '\'' | '[' | ']' | '='));
+        if module_name.is_empty() {
+            return;
+        }
+
+        let line_number = call_node.start_position().row + 1;
+        let module_id = generate_node_id(file_path, "import", module_name, line_number);
+        nodes.push(
+            Node::new(
+                module_id,
+                module_name.to_string(),
+                NodeType::Module,
+                file_path.to_path_buf(),
+                line_number,
+                "lua".to_string(),
+            )
+            .with_end_line(call_node.end_position().row + 1),
+        );
+    }
+
+    /// Extract call sites using the shared CallSiteExtractor
+    fn extract_call_sites(
+        &self,
+        root_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+    ) -> Vec<CallSite> {
+        let mut extractor = CallSiteExtractor::new();
+        extractor.extract_from_ast(root_node, source, file_path)
+    }
+}
+
+impl LanguageParser for LuaParser {
+    fn parse_file(&self, file_path: &Path) -> Result<ParseResult> {
+        let mut parser = TreeSitterParser::new(tree_sitter_lua::language())?;
+        let tree = parser.parse_file(file_path)?;
+        let source = parser.get_source(file_path)?;
+        let source_bytes = source.as_bytes();
+
+        let root_node = tree.root_node();
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+
+        let mut file_context = FileContext {
+            table_lines: HashMap::new(),
+            table_modules: HashMap::new(),
+        };
+        self.collect_tables(&root_node, source_bytes, &mut file_context);
+        self.process_node(
+            &root_node,
+            source_bytes,
+            file_path,
+            &mut file_context,
+            &mut nodes,
+            &mut edges,
+        );
+
+        let call_sites = self.extract_call_sites(&root_node, source_bytes, file_path);
+
+        assign_edge_lines(&nodes, &mut edges);
+
+        Ok(ParseResult {
+            nodes,
+            edges,
+            call_sites: Some(call_sites),
+            syntax_errors: syntax_errors(&root_node),
+        })
+    }
+
+    fn language_name(&self) -> &str {
+        "lua"
+    }
+}
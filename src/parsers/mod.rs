@@ -1,3 +1,4 @@
+pub mod c;
 pub mod cache;
 pub mod common;
 pub mod cpp;
@@ -5,11 +6,18 @@ pub mod csharp;
 pub mod go;
 pub mod java;
 pub mod javascript;
+pub mod kotlin;
+pub mod lua;
+pub mod objc;
+pub mod php;
 pub mod python;
+pub mod ruby;
 pub mod rust;
 pub mod typescript;
 
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
 use crate::core::{CallSite, Edge, Node};
@@ -19,31 +27,69 @@ pub struct ParseResult {
     pub nodes: Vec<Node>,
     pub edges: Vec<Edge>,
     pub call_sites: Option<Vec<CallSite>>,
+    /// Where the file failed to parse; nodes and edges around these spots may be missing
+    pub syntax_errors: Vec<SyntaxError>,
 }
 
-pub trait LanguageParser {
+/// A spot tree-sitter could not parse and skipped or patched over, 1-based
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Parsers run on rayon workers, so every implementation must be `Send + Sync`.
+pub trait LanguageParser: Send + Sync {
     fn parse_file(&self, file_path: &Path) -> Result<ParseResult>;
     #[allow(dead_code)]
     fn language_name(&self) -> &str;
 }
 
-pub struct ParserFactory;
+/// Builds a parser for a registered language. Called once per worker thread, so it must
+/// be shareable across threads itself.
+pub type ParserConstructor =
+    Box<dyn Fn() -> Result<Box<dyn LanguageParser + Send + Sync>> + Send + Sync>;
+
+pub struct ParserFactory {
+    /// Parsers added with `register`, consulted before the built-in languages
+    registered: HashMap<String, ParserConstructor>,
+}
 
 impl ParserFactory {
     pub fn new() -> Self {
-        Self
+        Self {
+            registered: HashMap::new(),
+        }
+    }
+
+    /// Add a parser for a language EMBARGO doesn't know, or replace a built-in one.
+    ///
+    /// Files only reach it if the scanner maps their extensions to `language`, see
+    /// `FileScanner::with_language_extensions`.
+    #[allow(dead_code)]
+    pub fn register(&mut self, language: &str, constructor: ParserConstructor) {
+        self.registered.insert(language.to_string(), constructor);
     }
 
     pub fn get_parser(&self, language: &str) -> Result<Box<dyn LanguageParser + Send + Sync>> {
+        if let Some(constructor) = self.registered.get(language) {
+            return constructor();
+        }
         match language {
             "python" => Ok(Box::new(python::PythonParser::new()?)),
             "typescript" => Ok(Box::new(typescript::TypeScriptParser::new()?)),
             "javascript" => Ok(Box::new(javascript::JavaScriptParser::new()?)),
+            "c" => Ok(Box::new(c::CParser::new()?)),
             "cpp" | "c++" => Ok(Box::new(cpp::CppParser::new()?)),
             "rust" => Ok(Box::new(rust::RustParser::new()?)),
             "java" => Ok(Box::new(java::JavaParser::new()?)),
             "go" => Ok(Box::new(go::GoParser::new()?)),
             "csharp" | "c#" => Ok(Box::new(csharp::CSharpParser::new()?)),
+            "kotlin" | "kt" => Ok(Box::new(kotlin::KotlinParser::new()?)),
+            "ruby" | "rb" => Ok(Box::new(ruby::RubyParser::new()?)),
+            "php" => Ok(Box::new(php::PhpParser::new()?)),
+            "lua" => Ok(Box::new(lua::LuaParser::new()?)),
+            "objc" | "objective-c" => Ok(Box::new(objc::ObjcParser::new()?)),
             _ => anyhow::bail!("Unsupported language: {}", language),
         }
     }
@@ -0,0 +1,561 @@
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use tree_sitter::Node as TSNode;
+
+use super::common::{
+    assign_edge_lines, count_branches, extract_docstring, extract_text, find_child_by_kind,
+    generate_node_id, syntax_errors, TreeSitterParser,
+};
+use super::{LanguageParser, ParseResult};
+use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, FileScanner, Node, NodeType};
+
+/// Branch points counted toward a function's `complexity`
+const BRANCH_KINDS: &[&str] = &[
+    "if_statement",
+    "for_statement",
+    "for_in_statement",
+    "while_statement",
+    "do_statement",
+    "case_statement",
+    "conditional_expression",
+];
+
+/// `@interface`, `@implementation` and their category forms, all describing one class
+const CLASS_KINDS: &[&str] = &[
+    "class_interface",
+    "class_implementation",
+    "category_interface",
+    "category_implementation",
+];
+
+/// Parser for Objective-C sources and headers.
+///
+/// The `@interface`, class extension and `@implementation` of a class in one file share a
+/// single class node. Methods are named by their full selector (`setName:age:`), which is
+/// also how message sends name them, and keep their `-`/`+` in the signature to tell
+/// instance and class methods apart.
+pub struct ObjcParser {
+    #[allow(dead_code)]
+    parser: TreeSitterParser,
+}
+
+struct FileContext {
+    /// Node ID of each class and protocol declared in the file, at its first declaration
+    type_ids: HashMap<String, String>,
+    /// Type nodes already pushed, since a class can be declared several times
+    emitted: HashSet<String>,
+    /// `(class, selector)` of every method the file's `@implementation`s define; their
+    /// declarations in an `@interface` of the same file are not added again
+    defined: HashSet<(String, String)>,
+}
+
+/// Selector of an Objective-C method definition, declaration or message send:
+/// `setName:age:` for `- (void)setName:(NSString *)name age:(int)age` and
+/// `[user setName:name age:age]`, `reload` for `- (void)reload` and `[view reload]`
+pub fn selector(node: &TSNode, source: &[u8]) -> Option<String> {
+    /// Keywords are the identifiers right before a `:` or a `method_parameter` (which starts
+    /// with one); an identifier standing alone is a unary selector, unless it follows a `:`
+    /// as an argument or parameter name
+    fn collect(
+        node: &TSNode,
+        receiver: Option<usize>,
+        source: &[u8],
+        keywords: &mut Vec<String>,
+        unary: &mut Option<String>,
+    ) {
+        let children: Vec<TSNode> = node.children(&mut node.walk()).collect();
+        for (position, child) in children.iter().enumerate() {
+            if Some(child.id()) == receiver {
+                continue;
+            }
+            match child.kind() {
+                "identifier" | "field_identifier" => {
+                    let name = extract_text(child, source);
+                    let before = position.checked_sub(1).map(|p| children[p].kind());
+                    if children
+                        .get(position + 1)
+                        .is_some_and(|next| matches!(next.kind(), ":" | "method_parameter"))
+                    {
+                        keywords.push(format!("{}:", name));
+                    } else if unary.is_none() && before != Some(":") {
+                        *unary = Some(name.to_string());
+                    }
+                }
+                kind if kind.starts_with("keyword_") || kind == "method_parameter" => {
+                    collect(child, None, source, keywords, unary);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let receiver = match node.kind() {
+        "message_expression" => node
+            .child_by_field_name("receiver")
+            .or_else(|| node.named_child(0))
+            .map(|receiver| receiver.id()),
+        _ => None,
+    };
+    let mut keywords = Vec::new();
+    let mut unary = None;
+    collect(node, receiver, source, &mut keywords, &mut unary);
+    if keywords.is_empty() {
+        unary
+    } else {
+        Some(keywords.concat())
+    }
+}
+
+/// Name of the class an `@interface`/`@implementation` (or a category of it) declares
+pub fn class_name<'a>(node: &TSNode, source: &'a [u8]) -> Option<&'a str> {
+    let name = node.child_by_field_name("name").or_else(|| {
+        node.children(&mut node.walk())
+            .find(|child| matches!(child.kind(), "identifier" | "type_identifier"))
+    })?;
+    Some(extract_text(&name, source))
+}
+
+impl ObjcParser {
+    pub fn new() -> Result<Self> {
+        let language = tree_sitter_objc::language();
+        let parser = TreeSitterParser::new(language)?;
+        Ok(Self { parser })
+    }
+
+    /// Declarations at file level, looking through include guards and `#if` blocks
+    fn top_level<'a>(scope: &TSNode<'a>) -> Vec<TSNode<'a>> {
+        let mut declarations = Vec::new();
+        for child in scope.children(&mut scope.walk()) {
+            match child.kind() {
+                "preproc_ifdef" | "preproc_if" | "preproc_else" | "preproc_elif" => {
+                    declarations.extend(Self::top_level(&child));
+                }
+                _ => declarations.push(child),
+            }
+        }
+        declarations
+    }
+
+    /// Method declarations and definitions of a class or protocol body, without looking
+    /// into method bodies
+    fn members<'a>(container: &TSNode<'a>) -> Vec<TSNode<'a>> {
+        let mut members = Vec::new();
+        for child in container.children(&mut container.walk()) {
+            match child.kind() {
+                "method_declaration" | "method_definition" => members.push(child),
+                "compound_statement" | "protocol_declaration" => {}
+                _ => members.extend(Self::members(&child)),
+            }
+        }
+        members
+    }
+
+    /// First pass: where each type is first declared, and which methods are implemented
+    fn collect_declarations(
+        &self,
+        root: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        ctx: &mut FileContext,
+    ) {
+        for declaration in Self::top_level(root) {
+            let kind = declaration.kind();
+            let (prefix, name) = match kind {
+                "protocol_declaration" => ("interface", class_name(&declaration, source)),
+                _ if CLASS_KINDS.contains(&kind) => ("class", class_name(&declaration, source)),
+                _ => continue,
+            };
+            let Some(name) = name else {
+                continue;
+            };
+            let line_number = declaration.start_position().row + 1;
+            ctx.type_ids
+                .entry(name.to_string())
+                .or_insert_with(|| generate_node_id(file_path, prefix, name, line_number));
+
+            if kind.ends_with("_implementation") {
+                for member in Self::members(&declaration) {
+                    if let Some(selector) = selector(&member, source) {
+                        ctx.defined.insert((name.to_string(), selector));
+                    }
+                }
+            }
+        }
+    }
+
+    fn process_scope(
+        &self,
+        root: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        ctx: &mut FileContext,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        for declaration in Self::top_level(root) {
+            match declaration.kind() {
+                "preproc_include" | "preproc_import" | "module_import" => {
+                    self.process_import(&declaration, source, file_path, nodes);
+                }
+                "protocol_declaration" => {
+                    self.process_type(&declaration, source, file_path, ctx, nodes, edges);
+                }
+                "function_definition" => {
+                    self.process_function(&declaration, source, file_path, nodes)
+                }
+                kind if CLASS_KINDS.contains(&kind) => {
+                    self.process_type(&declaration, source, file_path, ctx, nodes, edges);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// `#import <UIKit/UIKit.h>` becomes a module named `UIKit/UIKit.h`, `@import UIKit;`
+    /// one named `UIKit`
+    fn process_import(
+        &self,
+        import_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+    ) {
+        let Some(path_node) = import_node
+            .child_by_field_name("path")
+            .or_else(|| import_node.child_by_field_name("module"))
+            .or_else(|| import_node.named_child(0))
+        else {
+            return;
+        };
+        let module_name = extract_text(&path_node, source)
+            .trim_matches(|c| matches!(c, '<' | '>' | '"' | ';'))
+            .trim();
+        if module_name.is_empty() {
+            return;
+        }
+        let line_number = import_node.start_position().row + 1;
+
+        nodes.push(
+            Node::new(
+                generate_node_id(file_path, "import", module_name, line_number),
+                module_name.to_string(),
+                NodeType::Module,
+                file_path.to_path_buf(),
+                line_number,
+                "objc".to_string(),
+            )
+            .with_end_line(import_node.end_position().row + 1)
+            .with_signature(Self::collapse_whitespace(extract_text(import_node, source))),
+        );
+    }
+
+    /// A class (any of its `@interface`/`@implementation` blocks) or a `@protocol`, with
+    /// its superclass, adopted protocols and methods
+    fn process_type(
+        &self,
+        type_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        ctx: &mut FileContext,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        let Some(name) = class_name(type_node, source) else {
+            return;
+        };
+        let Some(type_id) = ctx.type_ids.get(name).cloned() else {
+            return;
+        };
+        let is_protocol = type_node.kind() == "protocol_declaration";
+
+        if ctx.emitted.insert(type_id.clone()) {
+            let mut type_node_obj = Node::new(
+                type_id.clone(),
+                name.to_string(),
+                if is_protocol {
+                    NodeType::Interface
+                } else {
+                    NodeType::Class
+                },
+                file_path.to_path_buf(),
+                type_node.start_position().row + 1,
+                "objc".to_string(),
+            )
+            .with_end_line(type_node.end_position().row + 1)
+            .with_signature(Self::header_line(type_node, source));
+            if let Some(docstring) = extract_docstring(type_node, source) {
+                type_node_obj = type_node_obj.with_docstring(docstring);
+            }
+            nodes.push(type_node_obj);
+        }
+
+        // `@interface Admin : User <NSCopying>`; a protocol's own `<NSObject>` is its parent
+        if let Some(superclass) = Self::superclass(type_node, source) {
+            let parent_id = self.type_reference(superclass, NodeType::Class, file_path, ctx, nodes);
+            Self::push_once(edges, EdgeType::Inheritance, &type_id, parent_id);
+        }
+        for protocol in Self::protocols(type_node, source) {
+            let protocol_id =
+                self.type_reference(&protocol, NodeType::Interface, file_path, ctx, nodes);
+            let edge_type = if is_protocol {
+                EdgeType::Inheritance
+            } else {
+                EdgeType::Implements
+            };
+            Self::push_once(edges, edge_type, &type_id, protocol_id);
+        }
+
+        let in_interface = !type_node.kind().ends_with("_implementation");
+        for member in Self::members(type_node) {
+            let Some(selector) = selector(&member, source) else {
+                continue;
+            };
+            if in_interface && ctx.defined.contains(&(name.to_string(), selector.clone())) {
+                continue;
+            }
+            self.process_method(&member, selector, source, file_path, &type_id, nodes, edges);
+        }
+    }
+
+    /// Instance (`-`) or class (`+`) method, named by its selector
+    #[allow(clippy::too_many_arguments)]
+    fn process_method(
+        &self,
+        method_node: &TSNode,
+        selector: String,
+        source: &[u8],
+        file_path: &Path,
+        owner_id: &str,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        let line_number = method_node.start_position().row + 1;
+        let method_id = generate_node_id(file_path, "function", &selector, line_number);
+        // XCTest runs the `test*` instance methods of test case classes
+        let is_test = method_node.kind() == "method_definition"
+            && selector.starts_with("test")
+            && !selector.contains(':')
+            && FileScanner::is_test_file(file_path, "objc");
+
+        let mut method_node_obj = Node::new(
+            method_id.clone(),
+            selector,
+            NodeType::Function,
+            file_path.to_path_buf(),
+            line_number,
+            "objc".to_string(),
+        )
+        .with_end_line(method_node.end_position().row + 1)
+        .with_complexity(count_branches(method_node, BRANCH_KINDS))
+        .with_signature(Self::method_signature(method_node, source))
+        .with_test(is_test);
+        if let Some(docstring) = extract_docstring(method_node, source) {
+            method_node_obj = method_node_obj.with_docstring(docstring);
+        }
+        nodes.push(method_node_obj);
+
+        edges.push(Edge::new(
+            EdgeType::Contains,
+            owner_id.to_string(),
+            method_id,
+        ));
+    }
+
+    /// Plain C function in an Objective-C file
+    fn process_function(
+        &self,
+        func_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+    ) {
+        let mut declarator = func_node.child_by_field_name("declarator");
+        while let Some(current) = declarator.filter(|d| d.kind() == "pointer_declarator") {
+            declarator = current.child_by_field_name("declarator");
+        }
+        let Some(name_node) = declarator
+            .filter(|d| d.kind() == "function_declarator")
+            .and_then(|d| d.child_by_field_name("declarator"))
+            .filter(|name| name.kind() == "identifier")
+        else {
+            return;
+        };
+        let func_name = extract_text(&name_node, source);
+        let line_number = func_node.start_position().row + 1;
+
+        nodes.push(
+            Node::new(
+                generate_node_id(file_path, "function", func_name, line_number),
+                func_name.to_string(),
+                NodeType::Function,
+                file_path.to_path_buf(),
+                line_number,
+                "objc".to_string(),
+            )
+            .with_end_line(func_node.end_position().row + 1)
+            .with_complexity(count_branches(func_node, BRANCH_KINDS))
+            .with_signature(Self::method_signature(func_node, source)),
+        );
+    }
+
+    /// ID of a class or protocol declared in this file, else of an external placeholder
+    /// (added once) so the edge pointing at it is kept
+    fn type_reference(
+        &self,
+        name: &str,
+        node_type: NodeType,
+        file_path: &Path,
+        ctx: &FileContext,
+        nodes: &mut Vec<Node>,
+    ) -> String {
+        if let Some(local_id) = ctx.type_ids.get(name) {
+            return local_id.clone();
+        }
+        let kind = if node_type == NodeType::Interface {
+            "interface"
+        } else {
+            "class"
+        };
+        let external_id = format!("external:{}:{}:0", kind, name);
+        if !nodes.iter().any(|n| n.id == external_id) {
+            nodes.push(
+                Node::new(
+                    external_id.clone(),
+                    name.to_string(),
+                    node_type,
+                    file_path.to_path_buf(),
+                    0,
+                    "objc".to_string(),
+                )
+                .with_visibility("external".to_string()),
+            );
+        }
+        external_id
+    }
+
+    fn push_once(edges: &mut Vec<Edge>, edge_type: EdgeType, source_id: &str, target_id: String) {
+        let exists = edges.iter().any(|e| {
+            e.edge_type == edge_type && e.source_id == source_id && e.target_id == target_id
+        });
+        if !exists {
+            edges.push(Edge::new(edge_type, source_id.to_string(), target_id));
+        }
+    }
+
+    /// The class after `:` in `@interface Admin : User`
+    fn superclass<'a>(type_node: &TSNode, source: &'a [u8]) -> Option<&'a str> {
+        if let Some(superclass) = type_node.child_by_field_name("superclass") {
+            return Some(extract_text(&superclass, source));
+        }
+        let children: Vec<TSNode> = type_node.children(&mut type_node.walk()).collect();
+        let colon = children.iter().position(|child| child.kind() == ":")?;
+        children[colon + 1..]
+            .iter()
+            .find(|child| matches!(child.kind(), "identifier" | "type_identifier"))
+            .map(|child| extract_text(child, source))
+    }
+
+    /// Protocols in the `<NSCopying, NSCoding>` list of a class or protocol. A class
+    /// interface's list parses as type arguments, which before the `:` are instead its own
+    /// generic parameters (`@interface Box<T> : NSObject`)
+    fn protocols(type_node: &TSNode, source: &[u8]) -> Vec<String> {
+        let children: Vec<TSNode> = type_node.children(&mut type_node.walk()).collect();
+        let Some(list) = children
+            .iter()
+            .enumerate()
+            .find(|(position, child)| match child.kind() {
+                "protocol_reference_list" | "protocol_qualifiers" => true,
+                "parameterized_arguments" => children
+                    .get(position + 1)
+                    .is_none_or(|next| next.kind() != ":"),
+                _ => false,
+            })
+            .map(|(_, list)| list)
+        else {
+            return Vec::new();
+        };
+        list.named_children(&mut list.walk())
+            .filter_map(|child| match child.kind() {
+                "identifier" | "type_identifier" => Some(extract_text(&child, source).to_string()),
+                "type_name" => find_child_by_kind(&child, "type_identifier")
+                    .map(|name| extract_text(&name, source).to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// First line of a declaration, e.g. `@interface Admin : User <NSCopying>`
+    fn header_line(node: &TSNode, source: &[u8]) -> String {
+        let text = extract_text(node, source);
+        let line = text.lines().next().unwrap_or(text);
+        Self::collapse_whitespace(line.split('{').next().unwrap_or(line))
+    }
+
+    /// Everything before the body, e.g. `- (void)setName:(NSString *)name age:(int)age`
+    fn method_signature(method_node: &TSNode, source: &[u8]) -> String {
+        let body_start = method_node
+            .child_by_field_name("body")
+            .or_else(|| {
+                method_node
+                    .children(&mut method_node.walk())
+                    .find(|child| child.kind() == "compound_statement")
+            })
+            .map_or(method_node.end_byte(), |body| body.start_byte());
+        let header =
+            std::str::from_utf8(&source[method_node.start_byte()..body_start]).unwrap_or("");
+        Self::collapse_whitespace(header.trim_end().trim_end_matches(';'))
+    }
+
+    fn collapse_whitespace(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn extract_call_sites(&self, root: &TSNode, source: &[u8], file_path: &Path) -> Vec<CallSite> {
+        let mut extractor = CallSiteExtractor::new();
+        extractor.extract_from_ast(root, source, file_path)
+    }
+}
+
+impl LanguageParser for ObjcParser {
+    fn parse_file(&self, file_path: &Path) -> Result<ParseResult> {
+        let mut parser = TreeSitterParser::new(tree_sitter_objc::language())?;
+        let tree = parser.parse_file(file_path)?;
+        let source = parser.get_source(file_path)?;
+        let source_bytes = source.as_bytes();
+
+        let root = tree.root_node();
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+
+        let mut file_context = FileContext {
+            type_ids: HashMap::new(),
+            emitted: HashSet::new(),
+            defined: HashSet::new(),
+        };
+        self.collect_declarations(&root, source_bytes, file_path, &mut file_context);
+        self.process_scope(
+            &root,
+            source_bytes,
+            file_path,
+            &mut file_context,
+            &mut nodes,
+            &mut edges,
+        );
+
+        let call_sites = self.extract_call_sites(&root, source_bytes, file_path);
+
+        assign_edge_lines(&nodes, &mut edges);
+
+        Ok(ParseResult {
+            nodes,
+            edges,
+            call_sites: Some(call_sites),
+            syntax_errors: syntax_errors(&root),
+        })
+    }
+
+    fn language_name(&self) -> &str {
+        "objc"
+    }
+}
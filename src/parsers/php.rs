@@ -0,0 +1,382 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+use tree_sitter::Node as TSNode;
+
+use super::common::{
+    assign_edge_lines, count_branches, extract_text, find_child_by_kind, generate_node_id,
+    syntax_errors, TreeSitterParser,
+};
+use super::{LanguageParser, ParseResult};
+use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, Node, NodeType};
+
+/// Branch points counted toward a function's `complexity`
+const BRANCH_KINDS: &[&str] = &[
+    "if_statement",
+    "else_if_clause",
+    "for_statement",
+    "foreach_statement",
+    "while_statement",
+    "do_statement",
+    "case_statement",
+    "conditional_expression",
+    "match_conditional_expression",
+];
+
+pub struct PhpParser {
+    #[allow(dead_code)]
+    parser: TreeSitterParser,
+}
+
+/// Context for tracking types defined in the current file for extends/implements resolution
+struct FileContext {
+    /// Maps class, interface and trait names to their node IDs
+    type_map: HashMap<String, String>,
+}
+
+impl PhpParser {
+    pub fn new() -> Result<Self> {
+        let language = tree_sitter_php::language();
+        let parser = TreeSitterParser::new(language)?;
+        Ok(Self { parser })
+    }
+
+    fn collect_types(&self, node: &TSNode, source: &[u8], file_path: &Path, ctx: &mut FileContext) {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            let kind = match child.kind() {
+                "class_declaration" => Some("class"),
+                "interface_declaration" => Some("interface"),
+                "trait_declaration" => Some("trait"),
+                _ => None,
+            };
+            if let Some(kind) = kind {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let type_name = extract_text(&name_node, source);
+                    let line_number = child.start_position().row + 1;
+                    let type_id = generate_node_id(file_path, kind, type_name, line_number);
+                    ctx.type_map.insert(type_name.to_string(), type_id);
+                }
+            } else if child.kind() == "namespace_definition" {
+                // Braced namespaces nest their declarations in a body
+                if let Some(body) = child.child_by_field_name("body") {
+                    self.collect_types(&body, source, file_path, ctx);
+                }
+            }
+        }
+    }
+
+    fn process_scope(
+        &self,
+        scope: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        ctx: &FileContext,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        let mut cursor = scope.walk();
+        for child in scope.children(&mut cursor) {
+            match child.kind() {
+                "namespace_definition" => {
+                    self.process_namespace(&child, source, file_path, ctx, nodes, edges);
+                }
+                "namespace_use_declaration" => {
+                    self.process_use(&child, source, file_path, nodes);
+                }
+                "class_declaration" | "interface_declaration" | "trait_declaration" => {
+                    self.process_type(&child, source, file_path, ctx, nodes, edges);
+                }
+                "function_definition" => {
+                    self.process_function(&child, source, file_path, None, nodes, edges);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn process_namespace(
+        &self,
+        namespace_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        ctx: &FileContext,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        if let Some(name_node) = namespace_node.child_by_field_name("name") {
+            let namespace_name = extract_text(&name_node, source);
+            let line_number = namespace_node.start_position().row + 1;
+            let namespace_id =
+                generate_node_id(file_path, "namespace", namespace_name, line_number);
+
+            nodes.push(
+                Node::new(
+                    namespace_id,
+                    namespace_name.to_string(),
+                    NodeType::Module,
+                    file_path.to_path_buf(),
+                    line_number,
+                    "php".to_string(),
+                )
+                .with_end_line(namespace_node.end_position().row + 1),
+            );
+        }
+
+        if let Some(body) = namespace_node.child_by_field_name("body") {
+            self.process_scope(&body, source, file_path, ctx, nodes, edges);
+        }
+    }
+
+    /// `use Foo\Bar;` and grouped `use Foo\{Bar, Baz};` imports
+    fn process_use(
+        &self,
+        use_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+    ) {
+        let use_text = extract_text(use_node, source).trim().trim_end_matches(';');
+        let line_number = use_node.start_position().row + 1;
+
+        let module_id = generate_node_id(file_path, "import", use_text, line_number);
+        nodes.push(
+            Node::new(
+                module_id,
+                use_text.to_string(),
+                NodeType::Module,
+                file_path.to_path_buf(),
+                line_number,
+                "php".to_string(),
+            )
+            .with_end_line(use_node.end_position().row + 1),
+        );
+    }
+
+    /// Resolve a referenced type to a local node or an external placeholder
+    fn resolve_type(&self, type_name: &str, external_kind: &str, ctx: &FileContext) -> String {
+        let short_name = type_name.trim_start_matches('\\');
+        ctx.type_map
+            .get(short_name)
+            .cloned()
+            .unwrap_or_else(|| format!("external:{}:{}:0", external_kind, short_name))
+    }
+
+    /// Names listed in a `base_clause` or `class_interface_clause`
+    fn clause_names<'a>(&self, clause: &TSNode, source: &'a [u8]) -> Vec<&'a str> {
+        let mut names = Vec::new();
+        let mut cursor = clause.walk();
+        for child in clause.children(&mut cursor) {
+            if matches!(child.kind(), "name" | "qualified_name") {
+                names.push(extract_text(&child, source));
+            }
+        }
+        names
+    }
+
+    fn process_type(
+        &self,
+        type_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        ctx: &FileContext,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        let Some(name_node) = type_node.child_by_field_name("name") else {
+            return;
+        };
+        let type_name = extract_text(&name_node, source);
+        let line_number = type_node.start_position().row + 1;
+
+        let (kind, node_type) = match type_node.kind() {
+            "interface_declaration" => ("interface", NodeType::Interface),
+            // Traits are modelled as classes; the signature lets formatters tell them apart
+            "trait_declaration" => ("trait", NodeType::Class),
+            _ => ("class", NodeType::Class),
+        };
+        let type_id = generate_node_id(file_path, kind, type_name, line_number);
+
+        let mut signature = format!("{} {}", kind, type_name);
+        if let Some(modifier) = find_child_by_kind(type_node, "abstract_modifier")
+            .or_else(|| find_child_by_kind(type_node, "final_modifier"))
+        {
+            signature = format!("{} {}", extract_text(&modifier, source), signature);
+        }
+
+        nodes.push(
+            Node::new(
+                type_id.clone(),
+                type_name.to_string(),
+                node_type,
+                file_path.to_path_buf(),
+                line_number,
+                "php".to_string(),
+            )
+            .with_end_line(type_node.end_position().row + 1)
+            .with_signature(signature)
+            .with_visibility("public".to_string()),
+        );
+
+        if let Some(base_clause) = find_child_by_kind(type_node, "base_clause") {
+            // Interfaces may extend several parents; classes extend one
+            for parent in self.clause_names(&base_clause, source) {
+                let parent_kind = if kind == "interface" {
+                    "interface"
+                } else {
+                    "class"
+                };
+                let parent_id = self.resolve_type(parent, parent_kind, ctx);
+                edges.push(
+                    Edge::new(EdgeType::Inheritance, type_id.clone(), parent_id)
+                        .with_line(base_clause.start_position().row + 1),
+                );
+            }
+        }
+
+        if let Some(interface_clause) = find_child_by_kind(type_node, "class_interface_clause") {
+            for interface in self.clause_names(&interface_clause, source) {
+                let interface_id = self.resolve_type(interface, "interface", ctx);
+                edges.push(
+                    Edge::new(EdgeType::Implements, type_id.clone(), interface_id)
+                        .with_line(interface_clause.start_position().row + 1),
+                );
+            }
+        }
+
+        if let Some(body) = type_node.child_by_field_name("body") {
+            self.process_type_body(&body, source, file_path, &type_id, ctx, nodes, edges);
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_type_body(
+        &self,
+        body: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        type_id: &str,
+        ctx: &FileContext,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        let mut cursor = body.walk();
+        for member in body.children(&mut cursor) {
+            match member.kind() {
+                "method_declaration" => {
+                    self.process_function(&member, source, file_path, Some(type_id), nodes, edges);
+                }
+                "use_declaration" => {
+                    // `use SomeTrait;` inside a class body mixes the trait in
+                    for trait_name in self.clause_names(&member, source) {
+                        let trait_id = self.resolve_type(trait_name, "class", ctx);
+                        edges.push(Edge::new(EdgeType::Uses, type_id.to_string(), trait_id));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn process_function(
+        &self,
+        func_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        type_id: Option<&str>,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        let Some(name_node) = func_node.child_by_field_name("name") else {
+            return;
+        };
+        let func_name = extract_text(&name_node, source);
+        let line_number = func_node.start_position().row + 1;
+        let func_id = generate_node_id(file_path, "function", func_name, line_number);
+
+        let params = func_node
+            .child_by_field_name("parameters")
+            .map(|p| extract_text(&p, source))
+            .unwrap_or("()");
+        let mut signature = format!("{}{}", func_name, params);
+        if let Some(return_type) = func_node.child_by_field_name("return_type") {
+            signature = format!("{}: {}", signature, extract_text(&return_type, source));
+        }
+
+        // Methods without a modifier are public
+        let visibility = find_child_by_kind(func_node, "visibility_modifier")
+            .map(|m| extract_text(&m, source))
+            .unwrap_or("public");
+
+        nodes.push(
+            Node::new(
+                func_id.clone(),
+                func_name.to_string(),
+                NodeType::Function,
+                file_path.to_path_buf(),
+                line_number,
+                "php".to_string(),
+            )
+            .with_end_line(func_node.end_position().row + 1)
+            .with_complexity(count_branches(func_node, BRANCH_KINDS))
+            .with_signature(signature)
+            .with_visibility(visibility.to_string()),
+        );
+
+        if let Some(type_id) = type_id {
+            edges.push(Edge::new(EdgeType::Contains, type_id.to_string(), func_id));
+        }
+    }
+
+    /// Extract call sites using the shared CallSiteExtractor
+    fn extract_call_sites(
+        &self,
+        root_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+    ) -> Vec<CallSite> {
+        let mut extractor = CallSiteExtractor::new();
+        extractor.extract_from_ast(root_node, source, file_path)
+    }
+}
+
+impl LanguageParser for PhpParser {
+    fn parse_file(&self, file_path: &Path) -> Result<ParseResult> {
+        let mut parser = TreeSitterParser::new(tree_sitter_php::language())?;
+        let tree = parser.parse_file(file_path)?;
+        let source = parser.get_source(file_path)?;
+        let source_bytes = source.as_bytes();
+
+        let root_node = tree.root_node();
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+
+        let mut file_context = FileContext {
+            type_map: HashMap::new(),
+        };
+        self.collect_types(&root_node, source_bytes, file_path, &mut file_context);
+        self.process_scope(
+            &root_node,
+            source_bytes,
+            file_path,
+            &file_context,
+            &mut nodes,
+            &mut edges,
+        );
+
+        let call_sites = self.extract_call_sites(&root_node, source_bytes, file_path);
+
+        assign_edge_lines(&nodes, &mut edges);
+
+        Ok(ParseResult {
+            nodes,
+            edges,
+            call_sites: Some(call_sites),
+            syntax_errors: syntax_errors(&root_node),
+        })
+    }
+
+    fn language_name(&self) -> &str {
+        "php"
+    }
+}
@@ -4,10 +4,22 @@ use std::path::Path;
 use tree_sitter::Node as TSNode;
 
 use super::common::{
-    extract_docstring, extract_text, find_child_by_kind, generate_node_id, TreeSitterParser,
+    assign_edge_lines, count_branches, extract_docstring, extract_text, find_child_by_kind,
+    generate_node_id, is_async_function, push_external_exception_nodes, push_throws,
+    resolve_local_types, syntax_errors, thrown_type_names, TreeSitterParser,
 };
 use super::{LanguageParser, ParseResult};
-use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, Node, NodeType};
+use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, FileScanner, Node, NodeType};
+
+/// Branch points counted toward a function's `complexity`
+const BRANCH_KINDS: &[&str] = &[
+    "if_statement",
+    "elif_clause",
+    "for_statement",
+    "while_statement",
+    "case_clause",
+    "conditional_expression",
+];
 
 pub struct PythonParser {
     #[allow(dead_code)]
@@ -66,11 +78,21 @@ impl PythonParser {
             file_path.to_path_buf(),
             line_number,
             "python".to_string(),
-        );
+        )
+        .with_end_line(import_node.end_position().row + 1);
 
         nodes.push(import_node);
     }
 
+    /// The function or class a `decorated_definition` wraps; other nodes pass through
+    fn undecorated(node: TSNode) -> TSNode {
+        if node.kind() == "decorated_definition" {
+            node.child_by_field_name("definition").unwrap_or(node)
+        } else {
+            node
+        }
+    }
+
     fn extract_classes(
         &self,
         root: &TSNode,
@@ -83,15 +105,18 @@ impl PythonParser {
         let mut file_context = FileContext {
             class_map: HashMap::new(),
         };
-        
+
         let mut cursor = root.walk();
         for child in root.children(&mut cursor) {
+            let child = Self::undecorated(child);
             if child.kind() == "class_definition" {
                 if let Some(name_node) = find_child_by_kind(&child, "identifier") {
                     let class_name = extract_text(&name_node, source);
                     let line_number = child.start_position().row + 1;
                     let class_id = generate_node_id(file_path, "class", class_name, line_number);
-                    file_context.class_map.insert(class_name.to_string(), class_id);
+                    file_context
+                        .class_map
+                        .insert(class_name.to_string(), class_id);
                 }
             }
         }
@@ -99,6 +124,7 @@ impl PythonParser {
         // Second pass: process classes with context for inheritance resolution
         let mut cursor = root.walk();
         for child in root.children(&mut cursor) {
+            let child = Self::undecorated(child);
             if child.kind() == "class_definition" {
                 self.process_class(&child, source, file_path, nodes, edges, &file_context);
             }
@@ -126,7 +152,8 @@ impl PythonParser {
                 file_path.to_path_buf(),
                 line_number,
                 "python".to_string(),
-            );
+            )
+            .with_end_line(class_node.end_position().row + 1);
 
             if let Some(docstring) = extract_docstring(class_node, source) {
                 class_node_obj = class_node_obj.with_docstring(docstring);
@@ -145,10 +172,9 @@ impl PythonParser {
                 );
             }
 
-            nodes.push(class_node_obj);
-
-            // Extract decorators
-            self.extract_decorators(class_node, source, file_path, &class_id, edges);
+            let decorators =
+                self.extract_decorators(class_node, source, file_path, &class_id, edges);
+            nodes.push(class_node_obj.with_decorators(decorators));
 
             self.extract_class_methods(class_node, source, file_path, &class_id, nodes, edges);
         }
@@ -189,7 +215,7 @@ impl PythonParser {
             } else {
                 // Create external reference and placeholder node
                 let external_id = format!("external:class:{}:0", parent_class);
-                
+
                 // Add placeholder node for external class if not already added
                 let placeholder = Node::new(
                     external_id.clone(),
@@ -198,22 +224,25 @@ impl PythonParser {
                     file_path.to_path_buf(),
                     0,
                     "python".to_string(),
-                ).with_visibility("external".to_string());
-                
+                )
+                .with_visibility("external".to_string());
+
                 // Only add if we haven't seen this external class before
                 if !nodes.iter().any(|n| n.id == external_id) {
                     nodes.push(placeholder);
                 }
-                
+
                 external_id
             };
 
-            let inheritance_edge = Edge::new(EdgeType::Inheritance, class_id.to_string(), parent_id);
+            let inheritance_edge =
+                Edge::new(EdgeType::Inheritance, class_id.to_string(), parent_id)
+                    .with_line(arg.start_position().row + 1);
             edges.push(inheritance_edge);
         }
     }
 
-    /// Extract decorator applications
+    /// Extract decorator applications; returns the decorator names
     fn extract_decorators(
         &self,
         node: &TSNode,
@@ -221,13 +250,14 @@ impl PythonParser {
         file_path: &Path,
         target_id: &str,
         edges: &mut Vec<Edge>,
-    ) {
+    ) -> Vec<String> {
+        let mut names = Vec::new();
         // Look for decorator nodes that are siblings before the function/class
         if let Some(parent) = node.parent() {
             let mut cursor = parent.walk();
             let mut found_target = false;
             let mut decorators = Vec::new();
-            
+
             for child in parent.children(&mut cursor) {
                 if child.kind() == "decorator" {
                     if !found_target {
@@ -237,7 +267,9 @@ impl PythonParser {
                     found_target = true;
                     // Process collected decorators
                     for dec in &decorators {
-                        self.process_decorator(dec, source, file_path, target_id, edges);
+                        names.extend(
+                            self.process_decorator(dec, source, file_path, target_id, edges),
+                        );
                     }
                     decorators.clear();
                 } else if child.kind() != "decorator" {
@@ -246,8 +278,10 @@ impl PythonParser {
                 }
             }
         }
+        names
     }
 
+    /// `Uses` edge to the decorator; returns its name without `@` or arguments
     fn process_decorator(
         &self,
         decorator_node: &TSNode,
@@ -255,11 +289,11 @@ impl PythonParser {
         _file_path: &Path,
         target_id: &str,
         edges: &mut Vec<Edge>,
-    ) {
+    ) -> Option<String> {
         // Extract decorator name (skip the @ symbol)
         let decorator_text = extract_text(decorator_node, source);
         let decorator_name = decorator_text.trim_start_matches('@').trim();
-        
+
         // Handle decorator with arguments: @decorator(args)
         let base_name = if let Some(paren_pos) = decorator_name.find('(') {
             &decorator_name[..paren_pos]
@@ -271,7 +305,9 @@ impl PythonParser {
             let decorator_id = format!("external:decorator:{}:0", base_name);
             let uses_edge = Edge::new(EdgeType::Uses, target_id.to_string(), decorator_id);
             edges.push(uses_edge);
+            return Some(base_name.to_string());
         }
+        None
     }
 
     fn extract_class_methods(
@@ -285,6 +321,7 @@ impl PythonParser {
     ) {
         if let Some(class_body) = find_child_by_kind(class_node, "block") {
             for child in class_body.children(&mut class_body.walk()) {
+                let child = Self::undecorated(child);
                 if child.kind() == "function_definition" {
                     self.process_method(&child, source, file_path, Some(class_id), nodes, edges);
                 }
@@ -303,6 +340,7 @@ impl PythonParser {
         let mut cursor = root.walk();
 
         for child in root.children(&mut cursor) {
+            let child = Self::undecorated(child);
             if child.kind() == "function_definition" {
                 self.process_method(&child, source, file_path, None, nodes, edges);
             }
@@ -327,6 +365,9 @@ impl PythonParser {
             if let Some(params) = find_child_by_kind(func_node, "parameters") {
                 signature = format!("{}({})", func_name, extract_text(&params, source));
             }
+            if let Some(return_type) = func_node.child_by_field_name("return_type") {
+                signature = format!("{} -> {}", signature, extract_text(&return_type, source));
+            }
 
             // Detect visibility based on naming convention
             let visibility = if func_name.starts_with("__") && func_name.ends_with("__") {
@@ -347,7 +388,14 @@ impl PythonParser {
                 line_number,
                 "python".to_string(),
             )
-            .with_signature(signature);
+            .with_end_line(func_node.end_position().row + 1)
+            .with_complexity(count_branches(func_node, BRANCH_KINDS))
+            .with_async(is_async_function(func_node))
+            .with_signature(signature)
+            // pytest collects `test*` functions and methods from test modules only
+            .with_test(
+                func_name.starts_with("test") && FileScanner::is_test_file(file_path, "python"),
+            );
 
             if let Some(vis) = visibility {
                 func_node_obj = func_node_obj.with_visibility(vis);
@@ -357,10 +405,9 @@ impl PythonParser {
                 func_node_obj = func_node_obj.with_docstring(docstring);
             }
 
-            nodes.push(func_node_obj);
-
-            // Extract decorators for this function
-            self.extract_decorators(func_node, source, file_path, &func_id, edges);
+            let decorators = self.extract_decorators(func_node, source, file_path, &func_id, edges);
+            nodes.push(func_node_obj.with_decorators(decorators));
+            push_throws(&func_id, &thrown_type_names(func_node, source), edges);
 
             if let Some(class_id) = class_id {
                 let contains_edge =
@@ -384,7 +431,14 @@ impl PythonParser {
         edges: &mut Vec<Edge>,
     ) {
         if let Some(body) = find_child_by_kind(func_node, "block") {
-            self.traverse_for_nested_functions(&body, source, file_path, parent_func_id, nodes, edges);
+            self.traverse_for_nested_functions(
+                &body,
+                source,
+                file_path,
+                parent_func_id,
+                nodes,
+                edges,
+            );
         }
     }
 
@@ -410,6 +464,10 @@ impl PythonParser {
                     if let Some(params) = find_child_by_kind(&child, "parameters") {
                         signature = format!("{}({})", func_name, extract_text(&params, source));
                     }
+                    if let Some(return_type) = child.child_by_field_name("return_type") {
+                        signature =
+                            format!("{} -> {}", signature, extract_text(&return_type, source));
+                    }
 
                     let mut func_node_obj = Node::new(
                         func_id.clone(),
@@ -419,6 +477,9 @@ impl PythonParser {
                         line_number,
                         "python".to_string(),
                     )
+                    .with_end_line(child.end_position().row + 1)
+                    .with_complexity(count_branches(&child, BRANCH_KINDS))
+                    .with_async(is_async_function(&child))
                     .with_signature(signature)
                     .with_visibility("nested".to_string());
 
@@ -427,18 +488,31 @@ impl PythonParser {
                     }
 
                     nodes.push(func_node_obj);
+                    push_throws(&func_id, &thrown_type_names(&child, source), edges);
 
                     // Create containment edge from parent function
-                    let contains_edge =
-                        Edge::new(EdgeType::Contains, parent_func_id.to_string(), func_id.clone());
+                    let contains_edge = Edge::new(
+                        EdgeType::Contains,
+                        parent_func_id.to_string(),
+                        func_id.clone(),
+                    );
                     edges.push(contains_edge);
 
                     // Recursively check for further nested functions
-                    self.extract_nested_functions(&child, source, file_path, &func_id, nodes, edges);
+                    self.extract_nested_functions(
+                        &child, source, file_path, &func_id, nodes, edges,
+                    );
                 }
             } else if child.kind() != "class_definition" {
                 // Continue traversing but don't go into class definitions
-                self.traverse_for_nested_functions(&child, source, file_path, parent_func_id, nodes, edges);
+                self.traverse_for_nested_functions(
+                    &child,
+                    source,
+                    file_path,
+                    parent_func_id,
+                    nodes,
+                    edges,
+                );
             }
         }
     }
@@ -507,14 +581,19 @@ impl LanguageParser for PythonParser {
         self.extract_imports(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
         self.extract_classes(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
         self.extract_functions(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
+        resolve_local_types(&nodes, &mut edges);
+        push_external_exception_nodes(&mut nodes, &edges, file_path, "python");
 
         // Extract call sites using the new system
         let call_sites = self.extract_call_sites(&root_node, source_bytes, file_path);
 
+        assign_edge_lines(&nodes, &mut edges);
+
         Ok(ParseResult {
             nodes,
             edges,
             call_sites: Some(call_sites),
+            syntax_errors: syntax_errors(&root_node),
         })
     }
 
@@ -0,0 +1,444 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+use tree_sitter::Node as TSNode;
+
+use super::common::{
+    assign_edge_lines, count_branches, extract_text, generate_node_id, syntax_errors,
+    TreeSitterParser,
+};
+use super::{LanguageParser, ParseResult};
+use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, Node, NodeType};
+
+/// Branch points counted toward a function's `complexity`
+const BRANCH_KINDS: &[&str] = &[
+    "if",
+    "unless",
+    "elsif",
+    "if_modifier",
+    "unless_modifier",
+    "while",
+    "until",
+    "while_modifier",
+    "until_modifier",
+    "for",
+    "when",
+    "conditional",
+];
+
+pub struct RubyParser {
+    #[allow(dead_code)]
+    parser: TreeSitterParser,
+}
+
+/// Context for tracking classes defined in the current file for inheritance resolution
+struct FileContext {
+    /// Maps class name to its node ID
+    class_map: HashMap<String, String>,
+}
+
+impl RubyParser {
+    pub fn new() -> Result<Self> {
+        let language = tree_sitter_ruby::language();
+        let parser = TreeSitterParser::new(language)?;
+        Ok(Self { parser })
+    }
+
+    /// Statements of a class/module/method body. Newer grammars wrap them in a
+    /// `body_statement` node, older ones attach them directly.
+    fn body_statements<'a>(node: &TSNode<'a>) -> Vec<TSNode<'a>> {
+        let mut statements = Vec::new();
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() == "body_statement" {
+                let mut inner = child.walk();
+                statements.extend(child.children(&mut inner));
+            } else {
+                statements.push(child);
+            }
+        }
+        statements
+    }
+
+    fn collect_classes(
+        &self,
+        node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        ctx: &mut FileContext,
+    ) {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() == "class" {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let class_name = extract_text(&name_node, source);
+                    let line_number = child.start_position().row + 1;
+                    let class_id = generate_node_id(file_path, "class", class_name, line_number);
+                    ctx.class_map.insert(class_name.to_string(), class_id);
+                }
+            }
+            if matches!(child.kind(), "class" | "module" | "body_statement") {
+                self.collect_classes(&child, source, file_path, ctx);
+            }
+        }
+    }
+
+    /// Walk the statements of a scope, dispatching on definitions. `container_id` is the
+    /// enclosing class or module, if any.
+    #[allow(clippy::too_many_arguments)]
+    fn process_scope(
+        &self,
+        scope: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        container_id: Option<&str>,
+        ctx: &FileContext,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        // Ruby visibility is positional: a bare `private` switches every following def
+        let mut visibility = "public";
+
+        for child in Self::body_statements(scope) {
+            match child.kind() {
+                "module" => {
+                    self.process_module(&child, source, file_path, container_id, ctx, nodes, edges)
+                }
+                "class" => {
+                    self.process_class(&child, source, file_path, container_id, ctx, nodes, edges)
+                }
+                "method" | "singleton_method" => {
+                    self.process_method(
+                        &child,
+                        source,
+                        file_path,
+                        container_id,
+                        visibility,
+                        nodes,
+                        edges,
+                    );
+                }
+                "identifier" => {
+                    let marker = extract_text(&child, source);
+                    if matches!(marker, "private" | "protected" | "public")
+                        && container_id.is_some()
+                    {
+                        visibility = marker;
+                    }
+                }
+                "call" => {
+                    self.process_call_statement(
+                        &child,
+                        source,
+                        file_path,
+                        container_id,
+                        nodes,
+                        edges,
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Handles `require`/`require_relative` and the inline `private def foo` form.
+    fn process_call_statement(
+        &self,
+        call_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        container_id: Option<&str>,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        if call_node.child_by_field_name("receiver").is_some() {
+            return;
+        }
+        let method_name = match call_node.child_by_field_name("method") {
+            Some(method) => extract_text(&method, source),
+            None => return,
+        };
+
+        match method_name {
+            "require" | "require_relative" => {
+                let import_text = extract_text(call_node, source);
+                let line_number = call_node.start_position().row + 1;
+                let module_id = generate_node_id(file_path, "import", import_text, line_number);
+                nodes.push(
+                    Node::new(
+                        module_id,
+                        import_text.to_string(),
+                        NodeType::Module,
+                        file_path.to_path_buf(),
+                        line_number,
+                        "ruby".to_string(),
+                    )
+                    .with_end_line(call_node.end_position().row + 1),
+                );
+            }
+            "private" | "protected" | "public" => {
+                if let Some(arguments) = call_node.child_by_field_name("arguments") {
+                    let mut cursor = arguments.walk();
+                    for arg in arguments.children(&mut cursor) {
+                        if matches!(arg.kind(), "method" | "singleton_method") {
+                            self.process_method(
+                                &arg,
+                                source,
+                                file_path,
+                                container_id,
+                                method_name,
+                                nodes,
+                                edges,
+                            );
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_module(
+        &self,
+        module_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        container_id: Option<&str>,
+        ctx: &FileContext,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        let Some(name_node) = module_node.child_by_field_name("name") else {
+            return;
+        };
+        let module_name = extract_text(&name_node, source);
+        let line_number = module_node.start_position().row + 1;
+        let module_id = generate_node_id(file_path, "module", module_name, line_number);
+
+        nodes.push(
+            Node::new(
+                module_id.clone(),
+                module_name.to_string(),
+                NodeType::Module,
+                file_path.to_path_buf(),
+                line_number,
+                "ruby".to_string(),
+            )
+            .with_end_line(module_node.end_position().row + 1),
+        );
+
+        if let Some(parent_id) = container_id {
+            edges.push(Edge::new(
+                EdgeType::Contains,
+                parent_id.to_string(),
+                module_id.clone(),
+            ));
+        }
+
+        self.process_scope(
+            module_node,
+            source,
+            file_path,
+            Some(&module_id),
+            ctx,
+            nodes,
+            edges,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_class(
+        &self,
+        class_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        container_id: Option<&str>,
+        ctx: &FileContext,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        let Some(name_node) = class_node.child_by_field_name("name") else {
+            return;
+        };
+        let class_name = extract_text(&name_node, source);
+        let line_number = class_node.start_position().row + 1;
+        let class_id = generate_node_id(file_path, "class", class_name, line_number);
+
+        nodes.push(
+            Node::new(
+                class_id.clone(),
+                class_name.to_string(),
+                NodeType::Class,
+                file_path.to_path_buf(),
+                line_number,
+                "ruby".to_string(),
+            )
+            .with_end_line(class_node.end_position().row + 1),
+        );
+
+        if let Some(superclass) = class_node.child_by_field_name("superclass") {
+            // `< Base` - the superclass node wraps the expression after the `<`
+            let parent_class = superclass
+                .named_child(0)
+                .map(|expr| extract_text(&expr, source))
+                .unwrap_or("")
+                .to_string();
+
+            if !parent_class.is_empty() {
+                let parent_id = if let Some(local_id) = ctx.class_map.get(&parent_class) {
+                    local_id.clone()
+                } else {
+                    let external_id = format!("external:class:{}:0", parent_class);
+                    if !nodes.iter().any(|n| n.id == external_id) {
+                        nodes.push(
+                            Node::new(
+                                external_id.clone(),
+                                parent_class.clone(),
+                                NodeType::Class,
+                                file_path.to_path_buf(),
+                                0,
+                                "ruby".to_string(),
+                            )
+                            .with_visibility("external".to_string()),
+                        );
+                    }
+                    external_id
+                };
+                edges.push(
+                    Edge::new(EdgeType::Inheritance, class_id.clone(), parent_id)
+                        .with_line(superclass.start_position().row + 1),
+                );
+            }
+        }
+
+        if let Some(parent_id) = container_id {
+            edges.push(Edge::new(
+                EdgeType::Contains,
+                parent_id.to_string(),
+                class_id.clone(),
+            ));
+        }
+
+        self.process_scope(
+            class_node,
+            source,
+            file_path,
+            Some(&class_id),
+            ctx,
+            nodes,
+            edges,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_method(
+        &self,
+        method_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        container_id: Option<&str>,
+        visibility: &str,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) {
+        let Some(name_node) = method_node.child_by_field_name("name") else {
+            return;
+        };
+        let method_name = extract_text(&name_node, source);
+        let line_number = method_node.start_position().row + 1;
+        let method_id = generate_node_id(file_path, "function", method_name, line_number);
+
+        let params = method_node
+            .child_by_field_name("parameters")
+            .map(|p| {
+                extract_text(&p, source)
+                    .trim_start_matches('(')
+                    .trim_end_matches(')')
+            })
+            .unwrap_or("");
+
+        // `def self.foo` defines a class-level (singleton) method
+        let signature = if method_node.kind() == "singleton_method" {
+            format!("self.{}({})", method_name, params)
+        } else {
+            format!("{}({})", method_name, params)
+        };
+
+        nodes.push(
+            Node::new(
+                method_id.clone(),
+                method_name.to_string(),
+                NodeType::Function,
+                file_path.to_path_buf(),
+                line_number,
+                "ruby".to_string(),
+            )
+            .with_end_line(method_node.end_position().row + 1)
+            .with_complexity(count_branches(method_node, BRANCH_KINDS))
+            .with_signature(signature)
+            .with_visibility(visibility.to_string()),
+        );
+
+        if let Some(parent_id) = container_id {
+            edges.push(Edge::new(
+                EdgeType::Contains,
+                parent_id.to_string(),
+                method_id,
+            ));
+        }
+    }
+
+    /// Extract call sites using the shared CallSiteExtractor
+    fn extract_call_sites(
+        &self,
+        root_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+    ) -> Vec<CallSite> {
+        let mut extractor = CallSiteExtractor::new();
+        extractor.extract_from_ast(root_node, source, file_path)
+    }
+}
+
+impl LanguageParser for RubyParser {
+    fn parse_file(&self, file_path: &Path) -> Result<ParseResult> {
+        let mut parser = TreeSitterParser::new(tree_sitter_ruby::language())?;
+        let tree = parser.parse_file(file_path)?;
+        let source = parser.get_source(file_path)?;
+        let source_bytes = source.as_bytes();
+
+        let root_node = tree.root_node();
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+
+        let mut file_context = FileContext {
+            class_map: HashMap::new(),
+        };
+        self.collect_classes(&root_node, source_bytes, file_path, &mut file_context);
+        self.process_scope(
+            &root_node,
+            source_bytes,
+            file_path,
+            None,
+            &file_context,
+            &mut nodes,
+            &mut edges,
+        );
+
+        let call_sites = self.extract_call_sites(&root_node, source_bytes, file_path);
+
+        assign_edge_lines(&nodes, &mut edges);
+
+        Ok(ParseResult {
+            nodes,
+            edges,
+            call_sites: Some(call_sites),
+            syntax_errors: syntax_errors(&root_node),
+        })
+    }
+
+    fn language_name(&self) -> &str {
+        "ruby"
+    }
+}
@@ -1,14 +1,25 @@
 use anyhow::Result;
+use std::collections::HashMap;
 use std::path::Path;
 use tree_sitter::Node as TSNode;
 
 use super::common::{
-    extract_docstring, extract_text, find_child_by_kind, find_children_by_kind, generate_node_id,
-    TreeSitterParser,
+    assign_edge_lines, count_branches, extract_docstring, extract_text, find_child_by_kind,
+    find_children_by_kind, generate_node_id, is_async_function, push_type_uses,
+    resolve_local_types, signature_type_names, syntax_errors, TreeSitterParser,
 };
 use super::{LanguageParser, ParseResult};
 use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, Node, NodeType};
 
+/// Branch points counted toward a function's `complexity`
+const BRANCH_KINDS: &[&str] = &[
+    "if_expression",
+    "for_expression",
+    "while_expression",
+    "loop_expression",
+    "match_arm",
+];
+
 pub struct RustParser {
     #[allow(dead_code)]
     parser: TreeSitterParser,
@@ -144,7 +155,8 @@ impl RustParser {
                 file_path.to_path_buf(),
                 line_number,
                 "rust".to_string(),
-            );
+            )
+            .with_end_line(mod_node.end_position().row + 1);
 
             nodes.push(module_node);
         }
@@ -169,7 +181,8 @@ impl RustParser {
             file_path.to_path_buf(),
             line_number,
             "rust".to_string(),
-        );
+        )
+        .with_end_line(use_node.end_position().row + 1);
 
         nodes.push(import_node);
     }
@@ -185,10 +198,71 @@ impl RustParser {
         let mut cursor = root.walk();
 
         for child in root.children(&mut cursor) {
-            if child.kind() == "function_item" {
-                self.process_function(&child, source, file_path, nodes, edges);
+            match child.kind() {
+                "function_item" => {
+                    self.process_function(&child, source, file_path, nodes, edges);
+                }
+                "macro_definition" => {
+                    self.process_macro(&child, source, file_path, nodes);
+                }
+                // Inline modules, `#[cfg(test)] mod tests` among them
+                "mod_item" => {
+                    if let Some(body) = child.child_by_field_name("body") {
+                        self.extract_functions(&body, source, file_path, nodes, edges);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Whether an attribute above the item is a test marker: `#[test]`, `#[tokio::test]`
+    fn has_test_attribute(item: &TSNode, source: &[u8]) -> bool {
+        let mut sibling = item.prev_named_sibling();
+        while let Some(attribute) = sibling.filter(|node| node.kind() == "attribute_item") {
+            let text = extract_text(&attribute, source);
+            let path = text
+                .trim_start_matches("#[")
+                .split(['(', ']'])
+                .next()
+                .unwrap_or_default();
+            if path.rsplit("::").next() == Some("test") {
+                return true;
             }
+            sibling = attribute.prev_named_sibling();
         }
+        false
+    }
+
+    /// `macro_rules!` definitions become function nodes, so `name!(...)` invocations resolve
+    /// to them like calls
+    fn process_macro(
+        &self,
+        macro_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+    ) {
+        let Some(name_node) = macro_node.child_by_field_name("name") else {
+            return;
+        };
+        let macro_name = extract_text(&name_node, source);
+        let line_number = macro_node.start_position().row + 1;
+
+        let macro_id = generate_node_id(file_path, "macro", macro_name, line_number);
+        let macro_node_obj = Node::new(
+            macro_id,
+            macro_name.to_string(),
+            NodeType::Function,
+            file_path.to_path_buf(),
+            line_number,
+            "rust".to_string(),
+        )
+        .with_end_line(macro_node.end_position().row + 1)
+        .with_signature(format!("macro_rules! {}", macro_name))
+        .with_docstring(extract_docstring(macro_node, source).unwrap_or_default());
+
+        nodes.push(macro_node_obj);
     }
 
     fn process_function(
@@ -197,7 +271,7 @@ impl RustParser {
         source: &[u8],
         file_path: &Path,
         nodes: &mut Vec<Node>,
-        _edges: &mut Vec<Edge>,
+        edges: &mut Vec<Edge>,
     ) {
         if let Some(name_node) = find_child_by_kind(func_node, "identifier") {
             let func_name = extract_text(&name_node, source);
@@ -218,18 +292,63 @@ impl RustParser {
                 line_number,
                 "rust".to_string(),
             )
+            .with_end_line(func_node.end_position().row + 1)
+            .with_complexity(count_branches(func_node, BRANCH_KINDS))
+            .with_async(is_async_function(func_node))
             .with_signature(signature)
-            .with_docstring(documentation.unwrap_or_default());
+            .with_docstring(documentation.unwrap_or_default())
+            .with_test(Self::has_test_attribute(func_node, source));
 
             nodes.push(func_node_obj);
+            push_type_uses(&func_id, &Self::signature_types(func_node, source), edges);
         }
     }
 
+    /// Types named in a function's parameters and return type
+    fn signature_types(func_node: &TSNode, source: &[u8]) -> Vec<String> {
+        let parts: Vec<TSNode> = ["parameters", "return_type"]
+            .iter()
+            .filter_map(|field| func_node.child_by_field_name(field))
+            .collect();
+        signature_type_names(func_node, &parts, source, &["type_identifier"])
+    }
+
+    /// Map struct, enum and trait names to node IDs so impl blocks can link to them
+    fn collect_types(
+        &self,
+        root: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+    ) -> HashMap<String, String> {
+        let mut type_map = HashMap::new();
+        let mut cursor = root.walk();
+
+        for child in root.children(&mut cursor) {
+            let kind = match child.kind() {
+                "struct_item" => "struct",
+                "enum_item" => "enum",
+                "trait_item" => "trait",
+                _ => continue,
+            };
+            if let Some(name_node) = find_child_by_kind(&child, "type_identifier") {
+                let type_name = extract_text(&name_node, source);
+                let line_number = child.start_position().row + 1;
+                type_map.insert(
+                    type_name.to_string(),
+                    generate_node_id(file_path, kind, type_name, line_number),
+                );
+            }
+        }
+
+        type_map
+    }
+
     fn extract_structs(
         &self,
         root: &TSNode,
         source: &[u8],
         file_path: &Path,
+        type_map: &HashMap<String, String>,
         nodes: &mut Vec<Node>,
         edges: &mut Vec<Edge>,
     ) {
@@ -247,13 +366,108 @@ impl RustParser {
                     self.process_trait(&child, source, file_path, nodes, edges);
                 }
                 "impl_item" => {
-                    self.process_impl(&child, source, file_path, nodes, edges);
+                    self.process_impl(&child, source, file_path, type_map, nodes, edges);
+                }
+                "const_item" | "static_item" => {
+                    self.process_value_item(&child, source, file_path, nodes);
+                }
+                "type_item" => {
+                    self.process_type_alias(&child, source, file_path, nodes);
                 }
                 _ => {}
             }
         }
     }
 
+    /// `visibility` label of an item: `pub` is public, restricted forms such as
+    /// `pub(crate)` are internal, and no modifier is private
+    fn item_visibility(item: &TSNode, source: &[u8]) -> &'static str {
+        match find_child_by_kind(item, "visibility_modifier") {
+            Some(modifier) if extract_text(&modifier, source) == "pub" => "public",
+            Some(_) => "internal",
+            None => "private",
+        }
+    }
+
+    /// Item text from its keyword (after any visibility) up to `end`, on one line
+    fn item_declaration(item: &TSNode, end: usize, source: &[u8]) -> String {
+        let start = find_child_by_kind(item, "visibility_modifier")
+            .and_then(|modifier| modifier.next_sibling())
+            .map_or(item.start_byte(), |keyword| keyword.start_byte());
+        let text = std::str::from_utf8(&source[start..end.max(start)]).unwrap_or("");
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Module-level `const` and `static` items; the signature leaves out the value, which
+    /// can be arbitrarily long: `const MAX_RETRIES: u32`
+    fn process_value_item(
+        &self,
+        item: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+    ) {
+        let Some(name_node) = item.child_by_field_name("name") else {
+            return;
+        };
+        let name = extract_text(&name_node, source);
+        let line_number = item.start_position().row + 1;
+        let kind = if item.kind() == "const_item" {
+            "const"
+        } else {
+            "static"
+        };
+        let declaration_end = item
+            .child_by_field_name("type")
+            .map_or(name_node.end_byte(), |type_node| type_node.end_byte());
+
+        let node = Node::new(
+            generate_node_id(file_path, kind, name, line_number),
+            name.to_string(),
+            NodeType::Variable,
+            file_path.to_path_buf(),
+            line_number,
+            "rust".to_string(),
+        )
+        .with_end_line(item.end_position().row + 1)
+        .with_signature(Self::item_declaration(item, declaration_end, source))
+        .with_visibility(Self::item_visibility(item, source).to_string());
+        nodes.push(node);
+    }
+
+    /// `type Result<T> = std::result::Result<T, Error>;`, as a class whose signature marks
+    /// it as an alias
+    fn process_type_alias(
+        &self,
+        item: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+    ) {
+        let Some(name_node) = item
+            .child_by_field_name("name")
+            .or_else(|| find_child_by_kind(item, "type_identifier"))
+        else {
+            return;
+        };
+        let name = extract_text(&name_node, source);
+        let line_number = item.start_position().row + 1;
+        let declaration = Self::item_declaration(item, item.end_byte(), source);
+
+        let node = Node::new(
+            generate_node_id(file_path, "type", name, line_number),
+            name.to_string(),
+            NodeType::Class,
+            file_path.to_path_buf(),
+            line_number,
+            "rust".to_string(),
+        )
+        .with_end_line(item.end_position().row + 1)
+        .with_signature(declaration.trim_end_matches(';').to_string())
+        .with_visibility(Self::item_visibility(item, source).to_string());
+        nodes.push(node);
+    }
+
     fn process_struct(
         &self,
         struct_node: &TSNode,
@@ -277,6 +491,7 @@ impl RustParser {
                 line_number,
                 "rust".to_string(),
             )
+            .with_end_line(struct_node.end_position().row + 1)
             .with_docstring(documentation.unwrap_or_default());
 
             nodes.push(struct_node_obj);
@@ -319,7 +534,8 @@ impl RustParser {
                     file_path.to_path_buf(),
                     line_number,
                     "rust".to_string(),
-                );
+                )
+                .with_end_line(field_node.end_position().row + 1);
 
                 nodes.push(field_node_obj);
 
@@ -353,6 +569,7 @@ impl RustParser {
                 line_number,
                 "rust".to_string(),
             )
+            .with_end_line(enum_node.end_position().row + 1)
             .with_docstring(documentation.unwrap_or_default());
 
             nodes.push(enum_node_obj);
@@ -382,6 +599,7 @@ impl RustParser {
                 line_number,
                 "rust".to_string(),
             )
+            .with_end_line(trait_node.end_position().row + 1)
             .with_docstring(documentation.unwrap_or_default());
 
             nodes.push(trait_node_obj);
@@ -409,13 +627,21 @@ impl RustParser {
         nodes: &mut Vec<Node>,
         edges: &mut Vec<Edge>,
     ) {
-        let function_nodes = find_children_by_kind(declaration_list, "function_signature_item");
+        // Required methods are bare signatures; provided methods carry a default body
+        let mut cursor = declaration_list.walk();
+        let function_nodes = declaration_list
+            .children(&mut cursor)
+            .filter(|child| matches!(child.kind(), "function_signature_item" | "function_item"));
 
         for func_node in function_nodes {
             if let Some(name_node) = find_child_by_kind(&func_node, "identifier") {
                 let method_name = extract_text(&name_node, source);
                 let line_number = func_node.start_position().row + 1;
 
+                let signature =
+                    self.extract_complete_function_signature(&func_node, source, method_name);
+                let documentation = extract_docstring(&func_node, source);
+
                 let method_id = generate_node_id(file_path, "method", method_name, line_number);
                 let method_node_obj = Node::new(
                     method_id.clone(),
@@ -424,51 +650,112 @@ impl RustParser {
                     file_path.to_path_buf(),
                     line_number,
                     "rust".to_string(),
-                );
+                )
+                .with_end_line(func_node.end_position().row + 1)
+                .with_complexity(count_branches(&func_node, BRANCH_KINDS))
+                .with_async(is_async_function(&func_node))
+                .with_signature(signature)
+                .with_docstring(documentation.unwrap_or_default());
 
                 nodes.push(method_node_obj);
+                push_type_uses(
+                    &method_id,
+                    &Self::signature_types(&func_node, source),
+                    edges,
+                );
 
                 // Create edge from trait to method
-                let edge = Edge::new(EdgeType::Contains, trait_id.to_string(), method_id);
+                let edge = Edge::new(EdgeType::Contains, trait_id.to_string(), method_id)
+                    .with_context(Self::member_kind(&func_node).to_string());
                 edges.push(edge);
             }
         }
     }
 
+    /// "method" when the function takes `self`, "associated" for associated functions
+    fn member_kind(func_node: &TSNode) -> &'static str {
+        let takes_self = find_child_by_kind(func_node, "parameters")
+            .is_some_and(|params| find_child_by_kind(&params, "self_parameter").is_some());
+        if takes_self {
+            "method"
+        } else {
+            "associated"
+        }
+    }
+
+    /// Base name of an impl target: `Foo<T>` -> `Foo`, `crate::a::Foo` -> `Foo`
+    fn impl_type_name<'a>(&self, type_node: &TSNode, source: &'a [u8]) -> Option<&'a str> {
+        match type_node.kind() {
+            "type_identifier" => Some(extract_text(type_node, source)),
+            "generic_type" => type_node
+                .child_by_field_name("type")
+                .and_then(|inner| self.impl_type_name(&inner, source)),
+            "scoped_type_identifier" => type_node
+                .child_by_field_name("name")
+                .map(|name| extract_text(&name, source)),
+            _ => None,
+        }
+    }
+
     fn process_impl(
         &self,
         impl_node: &TSNode,
         source: &[u8],
         file_path: &Path,
+        type_map: &HashMap<String, String>,
         nodes: &mut Vec<Node>,
         edges: &mut Vec<Edge>,
     ) {
-        // Extract impl blocks for structs/enums/traits
-        if let Some(type_node) = find_child_by_kind(impl_node, "type_identifier") {
-            let type_name = extract_text(&type_node, source);
-
-            // Extract methods in impl block
-            if let Some(declaration_list) = find_child_by_kind(impl_node, "declaration_list") {
-                self.extract_impl_methods(
-                    &declaration_list,
-                    source,
-                    file_path,
-                    &type_name,
-                    nodes,
-                    edges,
+        let Some(type_name) = impl_node
+            .child_by_field_name("type")
+            .and_then(|type_node| self.impl_type_name(&type_node, source))
+        else {
+            return;
+        };
+        // Impls of types defined elsewhere have nothing local to attach to
+        let type_id = type_map.get(type_name).map(String::as_str);
+
+        // `impl Trait for Type`
+        if let Some(trait_name) = impl_node
+            .child_by_field_name("trait")
+            .and_then(|trait_node| self.impl_type_name(&trait_node, source))
+        {
+            if let Some(type_id) = type_id {
+                let trait_id = type_map
+                    .get(trait_name)
+                    .cloned()
+                    .unwrap_or_else(|| format!("external:interface:{}:0", trait_name));
+                // The impl block, not the type's own declaration, names the trait
+                edges.push(
+                    Edge::new(EdgeType::Implements, type_id.to_string(), trait_id)
+                        .with_line(impl_node.start_position().row + 1),
                 );
             }
         }
+
+        if let Some(declaration_list) = find_child_by_kind(impl_node, "declaration_list") {
+            self.extract_impl_methods(
+                &declaration_list,
+                source,
+                file_path,
+                type_name,
+                type_id,
+                nodes,
+                edges,
+            );
+        }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn extract_impl_methods(
         &self,
         declaration_list: &TSNode,
         source: &[u8],
         file_path: &Path,
         type_name: &str,
+        type_id: Option<&str>,
         nodes: &mut Vec<Node>,
-        _edges: &mut Vec<Edge>,
+        edges: &mut Vec<Edge>,
     ) {
         let function_nodes = find_children_by_kind(declaration_list, "function_item");
 
@@ -498,13 +785,24 @@ impl RustParser {
                     line_number,
                     "rust".to_string(),
                 )
+                .with_end_line(func_node.end_position().row + 1)
+                .with_complexity(count_branches(&func_node, BRANCH_KINDS))
+                .with_async(is_async_function(&func_node))
                 .with_signature(signature)
                 .with_docstring(documentation.unwrap_or_default());
 
                 nodes.push(method_node_obj);
+                push_type_uses(
+                    &method_id,
+                    &Self::signature_types(&func_node, source),
+                    edges,
+                );
 
-                // Try to create edge from type to method (if type exists in nodes)
-                // This would require a more sophisticated approach to track type definitions
+                if let Some(type_id) = type_id {
+                    let edge = Edge::new(EdgeType::Contains, type_id.to_string(), method_id)
+                        .with_context(Self::member_kind(&func_node).to_string());
+                    edges.push(edge);
+                }
             }
         }
     }
@@ -528,15 +826,20 @@ impl LanguageParser for RustParser {
         // Extract different types of nodes
         self.extract_modules(&root, &source, file_path, &mut nodes, &mut edges);
         self.extract_functions(&root, &source, file_path, &mut nodes, &mut edges);
-        self.extract_structs(&root, &source, file_path, &mut nodes, &mut edges);
+        let type_map = self.collect_types(&root, &source, file_path);
+        self.extract_structs(&root, &source, file_path, &type_map, &mut nodes, &mut edges);
+        resolve_local_types(&nodes, &mut edges);
 
         // Extract function call sites for advanced resolution
         let call_sites = self.extract_call_sites(&root, &source, file_path);
 
+        assign_edge_lines(&nodes, &mut edges);
+
         Ok(ParseResult {
             nodes,
             edges,
             call_sites: Some(call_sites),
+            syntax_errors: syntax_errors(&root),
         })
     }
 
@@ -2,10 +2,25 @@ use anyhow::Result;
 use std::path::Path;
 use tree_sitter::Node as TSNode;
 
-use super::common::{extract_text, find_child_by_kind, generate_node_id, TreeSitterParser};
+use super::common::{
+    assign_edge_lines, count_branches, extract_text, find_child_by_kind, generate_node_id,
+    is_async_function, push_composition, push_test_cases, push_type_uses, resolve_local_types,
+    signature_type_names, syntax_errors, TreeSitterParser,
+};
 use super::{LanguageParser, ParseResult};
 use crate::core::{CallSite, CallSiteExtractor, Edge, EdgeType, Node, NodeType};
 
+/// Branch points counted toward a function's `complexity`
+const BRANCH_KINDS: &[&str] = &[
+    "if_statement",
+    "for_statement",
+    "for_in_statement",
+    "while_statement",
+    "do_statement",
+    "switch_case",
+    "ternary_expression",
+];
+
 pub struct TypeScriptParser {
     #[allow(dead_code)]
     parser: TreeSitterParser,
@@ -57,7 +72,8 @@ impl TypeScriptParser {
             file_path.to_path_buf(),
             line_number,
             "typescript".to_string(),
-        );
+        )
+        .with_end_line(import_node.end_position().row + 1);
 
         nodes.push(import_node_obj);
     }
@@ -79,6 +95,7 @@ impl TypeScriptParser {
         }
     }
 
+    /// Returns the class node's ID
     fn process_class(
         &self,
         class_node: &TSNode,
@@ -86,51 +103,54 @@ impl TypeScriptParser {
         file_path: &Path,
         nodes: &mut Vec<Node>,
         edges: &mut Vec<Edge>,
-    ) {
-        if let Some(name_node) = find_child_by_kind(class_node, "type_identifier") {
-            let class_name = extract_text(&name_node, source);
-            let line_number = class_node.start_position().row + 1;
-            let class_id = generate_node_id(file_path, "class", class_name, line_number);
-
-            let class_node_obj = Node::new(
-                class_id.clone(),
-                class_name.to_string(),
-                NodeType::Class,
-                file_path.to_path_buf(),
-                line_number,
-                "typescript".to_string(),
-            );
-
-            if let Some(class_heritage) = find_child_by_kind(class_node, "class_heritage") {
-                for heritage_clause in class_heritage.children(&mut class_heritage.walk()) {
-                    if heritage_clause.kind() == "extends_clause" {
-                        if let Some(parent_type) =
-                            find_child_by_kind(&heritage_clause, "type_identifier")
-                        {
-                            let parent_class = extract_text(&parent_type, source);
-                            let parent_id = format!("external:class:{}:0", parent_class);
-                            let inheritance_edge =
-                                Edge::new(EdgeType::Inheritance, class_id.clone(), parent_id);
-                            edges.push(inheritance_edge);
-                        }
-                    } else if heritage_clause.kind() == "implements_clause" {
-                        if let Some(interface_type) =
-                            find_child_by_kind(&heritage_clause, "type_identifier")
-                        {
-                            let interface_name = extract_text(&interface_type, source);
-                            let interface_id = format!("external:interface:{}:0", interface_name);
-                            let implements_edge =
-                                Edge::new(EdgeType::Implements, class_id.clone(), interface_id);
-                            edges.push(implements_edge);
-                        }
+    ) -> Option<String> {
+        let name_node = find_child_by_kind(class_node, "type_identifier")?;
+        let class_name = extract_text(&name_node, source);
+        let line_number = class_node.start_position().row + 1;
+        let class_id = generate_node_id(file_path, "class", class_name, line_number);
+
+        let class_node_obj = Node::new(
+            class_id.clone(),
+            class_name.to_string(),
+            NodeType::Class,
+            file_path.to_path_buf(),
+            line_number,
+            "typescript".to_string(),
+        )
+        .with_end_line(class_node.end_position().row + 1);
+
+        if let Some(class_heritage) = find_child_by_kind(class_node, "class_heritage") {
+            for heritage_clause in class_heritage.children(&mut class_heritage.walk()) {
+                if heritage_clause.kind() == "extends_clause" {
+                    if let Some(parent_type) =
+                        find_child_by_kind(&heritage_clause, "type_identifier")
+                    {
+                        let parent_class = extract_text(&parent_type, source);
+                        let parent_id = format!("external:class:{}:0", parent_class);
+                        let inheritance_edge =
+                            Edge::new(EdgeType::Inheritance, class_id.clone(), parent_id)
+                                .with_line(heritage_clause.start_position().row + 1);
+                        edges.push(inheritance_edge);
+                    }
+                } else if heritage_clause.kind() == "implements_clause" {
+                    if let Some(interface_type) =
+                        find_child_by_kind(&heritage_clause, "type_identifier")
+                    {
+                        let interface_name = extract_text(&interface_type, source);
+                        let interface_id = format!("external:interface:{}:0", interface_name);
+                        let implements_edge =
+                            Edge::new(EdgeType::Implements, class_id.clone(), interface_id)
+                                .with_line(heritage_clause.start_position().row + 1);
+                        edges.push(implements_edge);
                     }
                 }
             }
+        }
 
-            nodes.push(class_node_obj);
+        nodes.push(class_node_obj);
 
-            self.extract_class_methods(class_node, source, file_path, &class_id, nodes, edges);
-        }
+        self.extract_class_methods(class_node, source, file_path, &class_id, nodes, edges);
+        Some(class_id)
     }
 
     fn extract_class_methods(
@@ -154,6 +174,9 @@ impl TypeScriptParser {
                             nodes,
                             edges,
                         );
+                        Self::push_parameter_properties(
+                            &child, class_node, source, class_id, edges,
+                        );
                     }
                     "public_field_definition" | "private_field_definition" => {
                         if let Some(name_node) = find_child_by_kind(&child, "property_identifier") {
@@ -170,6 +193,7 @@ impl TypeScriptParser {
                                 line_number,
                                 "typescript".to_string(),
                             )
+                            .with_end_line(child.end_position().row + 1)
                             .with_visibility(
                                 if child.kind() == "private_field_definition" {
                                     "private"
@@ -184,6 +208,16 @@ impl TypeScriptParser {
                             let contains_edge =
                                 Edge::new(EdgeType::Contains, class_id.to_string(), field_id);
                             edges.push(contains_edge);
+
+                            if let Some(type_node) = child.child_by_field_name("type") {
+                                let type_names = signature_type_names(
+                                    class_node,
+                                    &[type_node],
+                                    source,
+                                    &["type_identifier"],
+                                );
+                                push_composition(class_id, &type_names, line_number, edges);
+                            }
                         }
                     }
                     _ => {}
@@ -209,6 +243,7 @@ impl TypeScriptParser {
         }
     }
 
+    /// Returns the interface node's ID
     fn process_interface(
         &self,
         interface_node: &TSNode,
@@ -216,24 +251,257 @@ impl TypeScriptParser {
         file_path: &Path,
         nodes: &mut Vec<Node>,
         _edges: &mut Vec<Edge>,
+    ) -> Option<String> {
+        let name_node = find_child_by_kind(interface_node, "type_identifier")?;
+        let interface_name = extract_text(&name_node, source);
+        let line_number = interface_node.start_position().row + 1;
+        let interface_id = generate_node_id(file_path, "interface", interface_name, line_number);
+
+        let interface_node_obj = Node::new(
+            interface_id.clone(),
+            interface_name.to_string(),
+            NodeType::Interface,
+            file_path.to_path_buf(),
+            line_number,
+            "typescript".to_string(),
+        )
+        .with_end_line(interface_node.end_position().row + 1);
+
+        nodes.push(interface_node_obj);
+        Some(interface_id)
+    }
+
+    /// Top-level enums, type aliases and namespaces, exported or not
+    fn extract_type_declarations(
+        &self,
+        root: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
     ) {
-        if let Some(name_node) = find_child_by_kind(interface_node, "type_identifier") {
-            let interface_name = extract_text(&name_node, source);
-            let line_number = interface_node.start_position().row + 1;
-            let interface_id =
-                generate_node_id(file_path, "interface", interface_name, line_number);
-
-            let interface_node_obj = Node::new(
-                interface_id.clone(),
-                interface_name.to_string(),
-                NodeType::Interface,
+        let mut cursor = root.walk();
+
+        for child in root.children(&mut cursor) {
+            let declaration = Self::unwrap_declaration(&child);
+            match declaration.kind() {
+                "enum_declaration" => {
+                    self.process_enum(&declaration, source, file_path, nodes);
+                }
+                "type_alias_declaration" => {
+                    self.process_type_alias(&declaration, source, file_path, nodes);
+                }
+                "internal_module" | "module" => {
+                    self.process_namespace(&declaration, source, file_path, None, nodes, edges);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The declaration behind `export ...`, or the namespace a statement wraps
+    /// (`namespace Foo {}` parses as an expression statement)
+    fn unwrap_declaration<'a>(node: &TSNode<'a>) -> TSNode<'a> {
+        match node.kind() {
+            "export_statement" => node.child_by_field_name("declaration").unwrap_or(*node),
+            "expression_statement" => node
+                .named_child(0)
+                .filter(|inner| inner.kind() == "internal_module")
+                .unwrap_or(*node),
+            _ => *node,
+        }
+    }
+
+    /// `enum Color { ... }` or `const enum Color { ... }`; returns the enum node's ID
+    fn process_enum(
+        &self,
+        enum_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+    ) -> Option<String> {
+        let name_node = enum_node.child_by_field_name("name")?;
+        let enum_name = extract_text(&name_node, source);
+        let line_number = enum_node.start_position().row + 1;
+        let enum_id = generate_node_id(file_path, "enum", enum_name, line_number);
+
+        let is_const = enum_node
+            .children(&mut enum_node.walk())
+            .any(|child| child.kind() == "const");
+        let signature = if is_const {
+            format!("const enum {}", enum_name)
+        } else {
+            format!("enum {}", enum_name)
+        };
+
+        nodes.push(
+            Node::new(
+                enum_id.clone(),
+                enum_name.to_string(),
+                NodeType::Enum,
                 file_path.to_path_buf(),
                 line_number,
                 "typescript".to_string(),
-            );
+            )
+            .with_end_line(enum_node.end_position().row + 1)
+            .with_signature(signature),
+        );
+        Some(enum_id)
+    }
+
+    /// `type Name<T> = ...` becomes a class-like node; the `type ... = ...` signature marks it
+    /// as an alias for formatters. Returns the alias node's ID
+    fn process_type_alias(
+        &self,
+        alias_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        nodes: &mut Vec<Node>,
+    ) -> Option<String> {
+        let name_node = alias_node.child_by_field_name("name")?;
+        let value_node = alias_node.child_by_field_name("value")?;
+        let alias_name = extract_text(&name_node, source);
+        let line_number = alias_node.start_position().row + 1;
+        let alias_id = generate_node_id(file_path, "type", alias_name, line_number);
+
+        let type_parameters = alias_node
+            .child_by_field_name("type_parameters")
+            .map(|params| extract_text(&params, source))
+            .unwrap_or("");
+        let value = extract_text(&value_node, source)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        nodes.push(
+            Node::new(
+                alias_id.clone(),
+                alias_name.to_string(),
+                NodeType::Class,
+                file_path.to_path_buf(),
+                line_number,
+                "typescript".to_string(),
+            )
+            .with_end_line(alias_node.end_position().row + 1)
+            .with_signature(format!(
+                "type {}{} = {}",
+                alias_name, type_parameters, value
+            )),
+        );
+        Some(alias_id)
+    }
+
+    /// `namespace Foo { ... }` becomes a module that contains its members, nested namespaces
+    /// included. Returns the namespace node's ID
+    fn process_namespace(
+        &self,
+        namespace_node: &TSNode,
+        source: &[u8],
+        file_path: &Path,
+        parent_id: Option<&str>,
+        nodes: &mut Vec<Node>,
+        edges: &mut Vec<Edge>,
+    ) -> Option<String> {
+        let name_node = namespace_node.child_by_field_name("name")?;
+        // `declare module "foo"` names a package, not a namespace
+        if name_node.kind() == "string" {
+            return None;
+        }
+        let namespace_name = extract_text(&name_node, source);
+        let line_number = namespace_node.start_position().row + 1;
+        let namespace_id = generate_node_id(file_path, "namespace", namespace_name, line_number);
+
+        nodes.push(
+            Node::new(
+                namespace_id.clone(),
+                namespace_name.to_string(),
+                NodeType::Module,
+                file_path.to_path_buf(),
+                line_number,
+                "typescript".to_string(),
+            )
+            .with_end_line(namespace_node.end_position().row + 1)
+            .with_signature(format!("namespace {}", namespace_name)),
+        );
+        if let Some(parent_id) = parent_id {
+            edges.push(Edge::new(
+                EdgeType::Contains,
+                parent_id.to_string(),
+                namespace_id.clone(),
+            ));
+        }
 
-            nodes.push(interface_node_obj);
+        let Some(body) = namespace_node.child_by_field_name("body") else {
+            return Some(namespace_id);
+        };
+        let mut cursor = body.walk();
+        for child in body.children(&mut cursor) {
+            let member = Self::unwrap_declaration(&child);
+            let member_ids = match member.kind() {
+                "class_declaration" => {
+                    vec![self.process_class(&member, source, file_path, nodes, edges)]
+                }
+                "interface_declaration" => {
+                    vec![self.process_interface(&member, source, file_path, nodes, edges)]
+                }
+                "enum_declaration" => vec![self.process_enum(&member, source, file_path, nodes)],
+                "type_alias_declaration" => {
+                    vec![self.process_type_alias(&member, source, file_path, nodes)]
+                }
+                "function_declaration" => {
+                    // Emits its own Contains edge
+                    self.process_function(
+                        &member,
+                        source,
+                        file_path,
+                        Some(&namespace_id),
+                        nodes,
+                        edges,
+                    );
+                    Vec::new()
+                }
+                "internal_module" | "module" => {
+                    // Emits its own Contains edge
+                    self.process_namespace(
+                        &member,
+                        source,
+                        file_path,
+                        Some(&namespace_id),
+                        nodes,
+                        edges,
+                    );
+                    Vec::new()
+                }
+                "lexical_declaration" | "variable_declaration" => {
+                    Self::collect_descendants_by_kind(&member, "variable_declarator")
+                        .into_iter()
+                        .filter_map(|declarator| {
+                            let value = declarator.child_by_field_name("value")?;
+                            (value.kind() == "arrow_function").then(|| {
+                                self.process_arrow_function(
+                                    &declarator,
+                                    &value,
+                                    source,
+                                    file_path,
+                                    nodes,
+                                    edges,
+                                )
+                            })
+                        })
+                        .collect()
+                }
+                _ => Vec::new(),
+            };
+            for member_id in member_ids.into_iter().flatten() {
+                edges.push(Edge::new(
+                    EdgeType::Contains,
+                    namespace_id.clone(),
+                    member_id,
+                ));
+            }
         }
+
+        Some(namespace_id)
     }
 
     fn extract_functions(
@@ -394,9 +662,13 @@ impl TypeScriptParser {
                 line_number,
                 "typescript".to_string(),
             )
+            .with_end_line(func_node.end_position().row + 1)
+            .with_complexity(count_branches(func_node, BRANCH_KINDS))
+            .with_async(is_async_function(func_node))
             .with_signature(signature);
 
             nodes.push(func_node_obj);
+            push_type_uses(&func_id, &Self::signature_types(func_node, source), edges);
 
             if let Some(class_id) = class_id {
                 let contains_edge =
@@ -427,6 +699,13 @@ impl TypeScriptParser {
             if let Some(params) = find_child_by_kind(method_node, "formal_parameters") {
                 signature = format!("{}({})", method_name, extract_text(&params, source));
             }
+            // Accessors keep their keyword: `get total()`
+            if let Some(accessor) = ["get", "set"]
+                .into_iter()
+                .find(|kind| find_child_by_kind(method_node, kind).is_some())
+            {
+                signature = format!("{} {}", accessor, signature);
+            }
 
             let method_node_obj = Node::new(
                 method_id.clone(),
@@ -436,9 +715,17 @@ impl TypeScriptParser {
                 line_number,
                 "typescript".to_string(),
             )
+            .with_end_line(method_node.end_position().row + 1)
+            .with_complexity(count_branches(method_node, BRANCH_KINDS))
+            .with_async(is_async_function(method_node))
             .with_signature(signature);
 
             nodes.push(method_node_obj);
+            push_type_uses(
+                &method_id,
+                &Self::signature_types(method_node, source),
+                edges,
+            );
 
             if let Some(class_id) = class_id {
                 let contains_edge =
@@ -453,37 +740,80 @@ impl TypeScriptParser {
     fn process_arrow_function(
         &self,
         declarator: &TSNode,
-        _arrow_func: &TSNode,
+        arrow_func: &TSNode,
         source: &[u8],
         file_path: &Path,
         nodes: &mut Vec<Node>,
-        _edges: &mut Vec<Edge>,
-    ) {
+        edges: &mut Vec<Edge>,
+    ) -> Option<String> {
         // Prefer field-based name extraction for robustness
-        let name_node_opt = declarator
+        let name_node = declarator
             .child_by_field_name("name")
-            .or_else(|| find_child_by_kind(declarator, "identifier"));
+            .or_else(|| find_child_by_kind(declarator, "identifier"))?;
 
-        if let Some(name_node) = name_node_opt {
-            let func_name = extract_text(&name_node, source);
-            let line_number = declarator.start_position().row + 1;
-            let func_id = generate_node_id(file_path, "function", func_name, line_number);
+        let func_name = extract_text(&name_node, source);
+        let line_number = declarator.start_position().row + 1;
+        let func_id = generate_node_id(file_path, "function", func_name, line_number);
 
-            let func_node_obj = Node::new(
-                func_id.clone(),
-                func_name.to_string(),
-                NodeType::Function,
-                file_path.to_path_buf(),
-                line_number,
-                "typescript".to_string(),
-            );
+        let func_node_obj = Node::new(
+            func_id.clone(),
+            func_name.to_string(),
+            NodeType::Function,
+            file_path.to_path_buf(),
+            line_number,
+            "typescript".to_string(),
+        )
+        .with_end_line(declarator.end_position().row + 1)
+        .with_complexity(count_branches(arrow_func, BRANCH_KINDS))
+        .with_async(is_async_function(arrow_func));
 
-            nodes.push(func_node_obj);
+        nodes.push(func_node_obj);
+        push_type_uses(&func_id, &Self::signature_types(arrow_func, source), edges);
 
-            // Note: Function calls are now extracted separately via extract_call_sites
+        // Note: Function calls are now extracted separately via extract_call_sites
+        Some(func_id)
+    }
+
+    /// `Composes` edges for constructor parameters that declare a field, as in
+    /// `constructor(private repo: OrderRepository)`
+    fn push_parameter_properties(
+        method_node: &TSNode,
+        class_node: &TSNode,
+        source: &[u8],
+        class_id: &str,
+        edges: &mut Vec<Edge>,
+    ) {
+        let is_constructor = method_node
+            .child_by_field_name("name")
+            .is_some_and(|name| extract_text(&name, source) == "constructor");
+        if !is_constructor {
+            return;
+        }
+        let Some(params) = method_node.child_by_field_name("parameters") else {
+            return;
+        };
+        for param in params.named_children(&mut params.walk()) {
+            let declares_field = find_child_by_kind(&param, "accessibility_modifier").is_some()
+                || find_child_by_kind(&param, "readonly").is_some();
+            let Some(type_node) = param.child_by_field_name("type").filter(|_| declares_field)
+            else {
+                continue;
+            };
+            let type_names =
+                signature_type_names(class_node, &[type_node], source, &["type_identifier"]);
+            push_composition(class_id, &type_names, param.start_position().row + 1, edges);
         }
     }
 
+    /// Types named in parameter annotations and the return type
+    fn signature_types(func_node: &TSNode, source: &[u8]) -> Vec<String> {
+        let parts: Vec<TSNode> = ["parameters", "return_type"]
+            .iter()
+            .filter_map(|field| func_node.child_by_field_name(field))
+            .collect();
+        signature_type_names(func_node, &parts, source, &["type_identifier"])
+    }
+
     /// Extract call sites using the new optimized CallSiteExtractor
     fn extract_call_sites(
         &self,
@@ -510,15 +840,28 @@ impl LanguageParser for TypeScriptParser {
         self.extract_imports(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
         self.extract_classes(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
         self.extract_interfaces(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
+        self.extract_type_declarations(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
         self.extract_functions(&root_node, source_bytes, file_path, &mut nodes, &mut edges);
+        push_test_cases(
+            &root_node,
+            source_bytes,
+            file_path,
+            "typescript",
+            BRANCH_KINDS,
+            &mut nodes,
+        );
+        resolve_local_types(&nodes, &mut edges);
 
         // Extract call sites using the new system
         let call_sites = self.extract_call_sites(&root_node, source_bytes, file_path);
 
+        assign_edge_lines(&nodes, &mut edges);
+
         Ok(ParseResult {
             nodes,
             edges,
             call_sites: Some(call_sites),
+            syntax_errors: syntax_errors(&root_node),
         })
     }
 
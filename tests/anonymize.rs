@@ -0,0 +1,70 @@
+use embargo::core::anonymize::anonymize;
+use embargo::core::graph::{Edge, EdgeType, GraphBuilder, Node, NodeType};
+use std::path::{Path, PathBuf};
+
+fn node(name: &str, ty: NodeType, file: &str, line: usize) -> Node {
+    Node::new(
+        format!("{}:{:?}:{}:{}", file, ty, name, line),
+        name.to_string(),
+        ty,
+        PathBuf::from(file),
+        line,
+        "python".to_string(),
+    )
+}
+
+#[test]
+fn anonymize_replaces_names_and_paths_but_keeps_structure() {
+    let mut gb = GraphBuilder::new();
+    let service = node(
+        "BillingService",
+        NodeType::Class,
+        "src/billing/service.py",
+        1,
+    );
+    let charge = node("charge", NodeType::Function, "src/billing/service.py", 3)
+        .with_signature("def charge(self, invoice: Invoice) -> bool".to_string())
+        .with_docstring("Charge the customer's card".to_string());
+    let invoice = node("Invoice", NodeType::Class, "src/billing/models.py", 1);
+    let checkout = node("checkout", NodeType::Function, "src/shop/cart.py", 5);
+    for n in [&service, &charge, &invoice, &checkout] {
+        gb.add_node(n.clone());
+    }
+    gb.add_edge(Edge::new(
+        EdgeType::Contains,
+        service.id.clone(),
+        charge.id.clone(),
+    ));
+    gb.add_edge(Edge::new(EdgeType::Call, checkout.id.clone(), charge.id.clone()).with_weight(2));
+    let mut graph = gb.build();
+
+    let pseudonyms = anonymize(&mut graph);
+
+    let names: Vec<&str> = graph.node_weights().map(|n| n.name.as_str()).collect();
+    assert_eq!(names, ["Class_1", "fn_1", "Class_2", "fn_2"]);
+    let charge = graph.node_weights().nth(1).unwrap();
+    assert_eq!(charge.file_path, Path::new("dir_1/dir_2/file_1.py"));
+    assert_eq!(
+        charge.signature.as_deref(),
+        Some("def fn_1(self, id_1: Class_2) -> bool")
+    );
+    assert_eq!(charge.docstring, None);
+    assert!(!charge.id.contains("charge") && !charge.id.contains("billing"));
+    let checkout = graph.node_weights().nth(3).unwrap();
+    assert_eq!(checkout.file_path, Path::new("dir_1/dir_3/file_3.py"));
+
+    assert_eq!(graph.edge_count(), 2);
+    let call = graph
+        .edge_weights()
+        .find(|e| e.edge_type == EdgeType::Call)
+        .unwrap();
+    assert_eq!(
+        (call.source_id.as_str(), call.target_id.as_str()),
+        (checkout.id.as_str(), charge.id.as_str())
+    );
+    assert_eq!(call.weight, 2);
+
+    assert_eq!(pseudonyms.original("Class_1"), Some("BillingService"));
+    assert_eq!(pseudonyms.original("dir_2"), Some("billing"));
+    assert_eq!(pseudonyms.original("id_1"), Some("invoice"));
+}
@@ -0,0 +1,46 @@
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn path_prints_only_the_call_chain_on_stdout() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let src = dir.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(
+        src.join("app.py"),
+        r#"def main():
+    load()
+
+def load():
+    save()
+
+def save():
+    pass
+"#,
+    )
+    .unwrap();
+    let output_file = dir.path().join("EMBARGO.md");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_embargo"))
+        .current_dir(dir.path())
+        .arg("--input")
+        .arg(&src)
+        .arg("--output")
+        .arg(&output_file)
+        .args(["--path", "main", "save", "--no-cache"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", stdout);
+    assert_eq!(lines[0], "Path from main to save:");
+    let hops: Vec<&str> = lines[1].trim().split(" → ").collect();
+    assert_eq!(hops.len(), 3, "{}", stdout);
+    for (hop, name) in hops.iter().zip(["main", "load", "save"]) {
+        assert!(hop.starts_with(&format!("{} (", name)), "{}", stdout);
+    }
+    // The graph itself is not rendered, to stdout or to disk
+    assert!(!output_file.exists());
+}
@@ -0,0 +1,41 @@
+use embargo::config::Config;
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn config_discovers_embargo_toml_and_rejects_unknown_keys() {
+    let dir = tempfile::TempDir::new().unwrap();
+    assert!(Config::discover(dir.path()).unwrap().is_none());
+
+    fs::write(
+        dir.path().join("embargo.toml"),
+        r#"
+# Shared settings for CI
+languages = ["rust", "python"]
+exclude = ["vendor/**"]
+format = "json-compact"
+output = "docs/EMBARGO.md"
+clusters = ["src/billing/**=BILLING"]
+"#,
+    )
+    .unwrap();
+
+    let (path, config) = Config::discover(dir.path()).unwrap().unwrap();
+    assert_eq!(path, dir.path().join("embargo.toml"));
+    assert_eq!(
+        config.languages,
+        Some(vec!["rust".to_string(), "python".to_string()])
+    );
+    assert_eq!(config.exclude, vec!["vendor/**".to_string()]);
+    assert_eq!(config.format.as_deref(), Some("json-compact"));
+    assert_eq!(config.verbosity, None);
+    assert_eq!(config.output, Some(PathBuf::from("docs/EMBARGO.md")));
+    assert_eq!(
+        config.clusters,
+        Some(vec!["src/billing/**=BILLING".to_string()])
+    );
+
+    let typo = dir.path().join("typo.toml");
+    fs::write(&typo, "langauges = [\"go\"]\n").unwrap();
+    assert!(Config::from_file(&typo).is_err());
+}
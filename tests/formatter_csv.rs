@@ -0,0 +1,46 @@
+use embargo::core::graph::{Edge, EdgeType, GraphBuilder, Node, NodeType};
+use embargo::formatters::CsvFormatter;
+use std::path::PathBuf;
+
+fn function(id: &str, name: &str, line: usize) -> Node {
+    Node::new(
+        id.to_string(),
+        name.to_string(),
+        NodeType::Function,
+        PathBuf::from("/repo/app.py"),
+        line,
+        "python".to_string(),
+    )
+}
+
+#[test]
+fn csv_formatter_writes_edge_list_and_quoted_node_table() {
+    let mut builder = GraphBuilder::new();
+    builder.add_node(function("main", "main", 1));
+    builder
+        .add_node(function("load", "load", 7).with_signature("load(path, mode=\"r\")".to_string()));
+    builder.add_edge(
+        Edge::new(EdgeType::Call, "main".to_string(), "load".to_string())
+            .with_context("load(a, b)".to_string()),
+    );
+    let graph = builder.build();
+    let formatter = CsvFormatter::new();
+
+    let edges = formatter.format_to_string(&graph).unwrap();
+    let lines: Vec<&str> = edges.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "source_id,source_name,target_id,target_name,edge_type,context,line",
+            "main,main,load,load,Call,\"load(a, b)\",1",
+        ]
+    );
+
+    let nodes = formatter.format_nodes_to_string(&graph).unwrap();
+    assert!(nodes
+        .starts_with("id,name,node_type,file_path,line_number,language,visibility,signature\n"));
+    assert!(nodes.contains("main,main,Function,/repo/app.py,1,python,,\n"));
+    // Commas and quotes inside a field are quoted, with quotes doubled
+    assert!(nodes
+        .contains("load,load,Function,/repo/app.py,7,python,,\"load(path, mode=\"\"r\"\")\"\n"));
+}
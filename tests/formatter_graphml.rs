@@ -0,0 +1,49 @@
+use embargo::core::graph::{Edge, EdgeType, GraphBuilder, Node, NodeType};
+use embargo::formatters::GraphMlFormatter;
+use std::path::PathBuf;
+
+#[test]
+fn graphml_formatter_escapes_ids_and_attributes() {
+    let mut gb = GraphBuilder::new();
+    let vec_fn = Node::new(
+        "src_vec.hpp:function:operator<<:3".to_string(),
+        "operator<<".to_string(),
+        NodeType::Function,
+        PathBuf::from("src/a&b/vec.hpp"),
+        3,
+        "cpp".to_string(),
+    )
+    .with_visibility("public".to_string());
+    let main_fn = Node::new(
+        "src_main.cpp:function:main:1".to_string(),
+        "main".to_string(),
+        NodeType::Function,
+        PathBuf::from("src/main.cpp"),
+        1,
+        "cpp".to_string(),
+    );
+    gb.add_node(vec_fn.clone());
+    gb.add_node(main_fn.clone());
+    gb.add_edge(
+        Edge::new(EdgeType::Call, main_fn.id.clone(), vec_fn.id.clone())
+            .with_context("line:\"4\"".to_string()),
+    );
+    let graph = gb.build();
+
+    let xml = GraphMlFormatter::new().format_to_string(&graph).unwrap();
+
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+    assert!(xml.contains(
+        "<key id=\"line_number\" for=\"node\" attr.name=\"line_number\" attr.type=\"int\"/>"
+    ));
+    assert!(xml.contains("<node id=\"src_vec.hpp:function:operator&lt;&lt;:3\">"));
+    assert!(xml.contains("<data key=\"name\">operator&lt;&lt;</data>"));
+    assert!(xml.contains("<data key=\"file_path\">src/a&amp;b/vec.hpp</data>"));
+    assert!(xml.contains("<data key=\"visibility\">public</data>"));
+    assert!(xml.contains(
+        "<edge id=\"e0\" source=\"src_main.cpp:function:main:1\" target=\"src_vec.hpp:function:operator&lt;&lt;:3\">"
+    ));
+    assert!(xml.contains("<data key=\"edge_type\">Call</data>"));
+    assert!(xml.contains("<data key=\"context\">line:&quot;4&quot;</data>"));
+    assert!(xml.trim_end().ends_with("</graphml>"));
+}
@@ -0,0 +1,77 @@
+use embargo::core::graph::{Edge, EdgeType, GraphBuilder, Node, NodeType};
+use embargo::formatters::HtmlFormatter;
+use std::path::PathBuf;
+
+fn node(id: &str, name: &str, node_type: NodeType, file: &str, line: usize) -> Node {
+    Node::new(
+        id.to_string(),
+        name.to_string(),
+        node_type,
+        PathBuf::from(file),
+        line,
+        "python".to_string(),
+    )
+}
+
+#[test]
+fn html_formatter_renders_collapsible_tree_with_calls() {
+    let mut builder = GraphBuilder::new();
+    builder.add_node(node(
+        "cart",
+        "Cart<T>",
+        NodeType::Class,
+        "/repo/src/shop/cart.py",
+        3,
+    ));
+    builder.add_node(
+        node(
+            "add",
+            "add",
+            NodeType::Function,
+            "/repo/src/shop/cart.py",
+            5,
+        )
+        .with_signature("add(self, item: Item) -> bool".to_string()),
+    );
+    builder.add_node(node(
+        "main",
+        "main",
+        NodeType::Function,
+        "/repo/src/app.py",
+        1,
+    ));
+    builder.add_edge(Edge::new(
+        EdgeType::Contains,
+        "cart".to_string(),
+        "add".to_string(),
+    ));
+    builder.add_edge(Edge::new(
+        EdgeType::Call,
+        "main".to_string(),
+        "add".to_string(),
+    ));
+    let graph = builder.build();
+
+    let html = HtmlFormatter::new().format_to_string(&graph).unwrap();
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h1>/repo/src/</h1>"));
+    assert!(html.contains("<input id=\"filter\""));
+    // Self-contained: no external stylesheets or scripts
+    assert!(!html.contains("<link") && !html.contains("src=\"http"));
+
+    // Directories and files nest, and the class is escaped and color-classed
+    let shop = html.find("<summary>shop/</summary>").unwrap();
+    let cart_file = html.find("<summary>cart.py</summary>").unwrap();
+    let class = html
+        .find("class=\"entity class\" data-name=\"cart&lt;t&gt;\"")
+        .unwrap();
+    let method = html
+        .find("class=\"entity function\" data-name=\"add\"")
+        .unwrap();
+    assert!(shop < cart_file && cart_file < class && class < method);
+    assert!(html.contains("add(self, item: Item) -&gt; bool"));
+
+    // main's call list links to the callee's location
+    assert!(html.contains("<li>add <span class=\"loc\">/repo/src/shop/cart.py:5</span></li>"));
+}
@@ -45,3 +45,112 @@ fn json_compact_formatter_outputs_valid_json() {
     let edge = &v["edges"][0];
     assert_eq!(edge[2].as_u64().unwrap(), 1);
 }
+
+#[test]
+fn json_compact_formatter_writes_end_lines_of_multi_line_definitions() {
+    let mut gb = GraphBuilder::new();
+    gb.add_node(node("A", "mod_a", NodeType::Module));
+    gb.add_node(node("B", "func_b", NodeType::Function).with_end_line(5));
+    let graph = gb.build();
+
+    let v: Value = serde_json::from_str(
+        &JsonCompactFormatter::new()
+            .format_to_string(&graph)
+            .unwrap(),
+    )
+    .unwrap();
+
+    // Only multi-line definitions carry an end line
+    assert!(v["nodes"][0].get("e").is_none());
+    assert_eq!(v["nodes"][1]["e"].as_u64(), Some(5));
+}
+
+#[test]
+fn json_compact_formatter_appends_call_lines_to_edges() {
+    let mut gb = GraphBuilder::new();
+    gb.add_node(node("B", "func_b", NodeType::Function));
+    gb.add_node(node("C", "var_c", NodeType::Variable));
+    gb.add_edge(Edge::new(EdgeType::Call, "B".to_string(), "C".to_string()).with_line(3));
+    gb.add_edge(Edge::new(EdgeType::Uses, "B".to_string(), "C".to_string()));
+    let graph = gb.build();
+
+    let v: Value = serde_json::from_str(
+        &JsonCompactFormatter::new()
+            .format_to_string(&graph)
+            .unwrap(),
+    )
+    .unwrap();
+
+    // Edge is [src_id, tgt_id, type_code, line] when the line is known
+    let edges = v["edges"].as_array().unwrap();
+    let call = edges.iter().find(|e| e[2] == 1).unwrap();
+    assert_eq!(call[3].as_u64(), Some(3));
+    let uses = edges.iter().find(|e| e[2] != 1).unwrap();
+    assert_eq!(uses.as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn json_compact_formatter_adds_file_metrics_when_enabled() {
+    let in_file = |id: &str, name: &str, ty: NodeType, file: &str| {
+        let mut n = node(id, name, ty);
+        n.file_path = PathBuf::from(file);
+        n
+    };
+    let mut gb = GraphBuilder::new();
+    gb.add_node(in_file("C", "Server", NodeType::Class, "/src/server.rs"));
+    gb.add_node(in_file("A", "serve", NodeType::Function, "/src/server.rs"));
+    gb.add_node(in_file("B", "route", NodeType::Function, "/src/server.rs"));
+    gb.add_node(in_file("D", "parse", NodeType::Function, "/src/util.rs"));
+    gb.add_node(in_file(
+        "E",
+        "parse_inner",
+        NodeType::Function,
+        "/src/util.rs",
+    ));
+    gb.add_edge(Edge::new(
+        EdgeType::Contains,
+        "C".to_string(),
+        "A".to_string(),
+    ));
+    // serve → route → parse ⇄ parse_inner: the cycle counts as one step
+    for (source, target) in [("A", "B"), ("B", "D"), ("D", "E"), ("E", "D")] {
+        gb.add_edge(Edge::new(
+            EdgeType::Call,
+            source.to_string(),
+            target.to_string(),
+        ));
+    }
+    let graph = gb.build();
+
+    let plain: Value = serde_json::from_str(
+        &JsonCompactFormatter::new()
+            .format_to_string(&graph)
+            .unwrap(),
+    )
+    .unwrap();
+    assert!(plain.get("file_metrics").is_none());
+
+    let v: Value = serde_json::from_str(
+        &JsonCompactFormatter::new()
+            .with_file_metrics(true)
+            .format_to_string(&graph)
+            .unwrap(),
+    )
+    .unwrap();
+    let metrics = |path: &str| {
+        let files = v["files"].as_array().unwrap();
+        let id = files.iter().position(|f| f == path).unwrap();
+        v["file_metrics"][id].clone()
+    };
+
+    let server = metrics("/src/server.rs");
+    assert_eq!(server["nodes"]["class"], 1);
+    assert_eq!(server["nodes"]["function"], 2);
+    assert_eq!(server["edges_out"], 2);
+    assert_eq!(server["max_call_depth"], 3);
+    assert_eq!(server["languages"], serde_json::json!(["rust"]));
+
+    let util = metrics("/src/util.rs");
+    assert_eq!(util["edges_out"], 2);
+    assert_eq!(util["max_call_depth"], 1);
+}
@@ -0,0 +1,77 @@
+use embargo::core::graph::{Edge, EdgeType, GraphBuilder, Node, NodeType};
+use embargo::core::{DependencyGraph, DependencyGraphExt};
+use embargo::formatters::JsonFullFormatter;
+use petgraph::visit::EdgeRef;
+use std::path::PathBuf;
+
+#[test]
+fn json_full_round_trips_every_field() {
+    let mut gb = GraphBuilder::new();
+    let service = Node::new(
+        "src_svc.py:class:Service:1".to_string(),
+        "Service".to_string(),
+        NodeType::Class,
+        PathBuf::from("src/svc.py"),
+        1,
+        "python".to_string(),
+    )
+    .with_docstring("Handles requests".to_string());
+    let handle = Node::new(
+        "src_svc.py:function:handle:4".to_string(),
+        "handle".to_string(),
+        NodeType::Function,
+        PathBuf::from("src/svc.py"),
+        4,
+        "python".to_string(),
+    )
+    .with_signature("handle(self, req: Request) -> Response".to_string())
+    .with_visibility("public".to_string())
+    .with_async(true);
+    gb.add_node(service.clone());
+    gb.add_node(handle.clone());
+    gb.add_edge(Edge::new(
+        EdgeType::Contains,
+        service.id.clone(),
+        handle.id.clone(),
+    ));
+    gb.add_edge(
+        Edge::new(EdgeType::Call, handle.id.clone(), handle.id.clone())
+            .with_context("ast_node:call".to_string()),
+    );
+    let graph = gb.build();
+
+    let json = JsonFullFormatter::new().format_to_string(&graph).unwrap();
+    let reloaded = DependencyGraph::from_json(&json).unwrap();
+
+    assert_eq!(reloaded.node_count(), 2);
+    assert_eq!(reloaded.edge_count(), 2);
+    for (original, restored) in graph.node_weights().zip(reloaded.node_weights()) {
+        assert_eq!(original.id, restored.id);
+        assert_eq!(original.name, restored.name);
+        assert_eq!(original.node_type, restored.node_type);
+        assert_eq!(original.file_path, restored.file_path);
+        assert_eq!(original.line_number, restored.line_number);
+        assert_eq!(original.language, restored.language);
+        assert_eq!(original.signature, restored.signature);
+        assert_eq!(original.docstring, restored.docstring);
+        assert_eq!(original.visibility, restored.visibility);
+        assert_eq!(original.is_async, restored.is_async);
+    }
+    for (original, restored) in graph.edge_references().zip(reloaded.edge_references()) {
+        assert_eq!(original.source(), restored.source());
+        assert_eq!(original.target(), restored.target());
+        assert_eq!(original.weight().edge_type, restored.weight().edge_type);
+        assert_eq!(original.weight().context, restored.weight().context);
+    }
+
+    // Re-serializing the reloaded graph is byte-for-byte identical
+    assert_eq!(reloaded.to_json().unwrap(), json);
+}
+
+#[test]
+fn json_full_rejects_edges_to_unknown_nodes() {
+    let json = r#"{"nodes": [], "edges": [
+        {"edge_type": "Call", "source_id": "a", "target_id": "b", "context": null}
+    ]}"#;
+    assert!(DependencyGraph::from_json(json).is_err());
+}
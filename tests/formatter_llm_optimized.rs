@@ -58,3 +58,972 @@ fn llm_optimized_compact_mode_excludes_extras() {
     assert!(!s.contains("## DEPENDENCY_PATTERNS"));
     assert!(s.contains("# CODE_GRAPH"));
 }
+
+#[test]
+fn kotlin_formatter_flags_data_classes() {
+    let mut gb = GraphBuilder::new();
+    let mut user = node("U", "User", NodeType::Class);
+    user.language = "kotlin".to_string();
+    gb.add_node(user.with_signature("data class User".to_string()));
+    gb.add_node(node("S", "Session", NodeType::Class).with_signature("class Session".to_string()));
+    let graph = gb.build();
+
+    let tmp = tempfile::NamedTempFile::new().unwrap();
+    let path = tmp.path().to_path_buf();
+
+    LLMOptimizedFormatter::for_kotlin()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_file(&graph, &path)
+        .unwrap();
+    let s = std::fs::read_to_string(&path).unwrap();
+
+    assert!(s.contains("User[DATA]"));
+    assert!(!s.contains("Session"));
+
+    // Mixed Kotlin and Python repositories get the Python adapter
+    let s = LLMOptimizedFormatter::for_python()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("User[DATA]"));
+}
+
+#[test]
+fn php_formatter_flags_traits() {
+    let mut gb = GraphBuilder::new();
+    let mut loggable = node("T", "Loggable", NodeType::Class);
+    loggable.language = "php".to_string();
+    gb.add_node(loggable.with_signature("trait Loggable".to_string()));
+    gb.add_node(
+        node("C", "Controller", NodeType::Class).with_signature("class Controller".to_string()),
+    );
+    let graph = gb.build();
+
+    let s = LLMOptimizedFormatter::for_php()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_string(&graph)
+        .unwrap();
+
+    assert!(s.contains("Loggable[TRAIT]"));
+    assert!(!s.contains("Controller"));
+
+    // Mixed PHP and Python repositories get the Python adapter
+    let s = LLMOptimizedFormatter::for_python()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("Loggable[TRAIT]"));
+}
+
+#[test]
+fn llm_optimized_tags_structs_and_aliases() {
+    let mut gb = GraphBuilder::new();
+    gb.add_node(node("P", "Point", NodeType::Class).with_signature("struct Point".to_string()));
+    gb.add_node(
+        node("N", "Name", NodeType::Class).with_signature("type Name = string".to_string()),
+    );
+    let mut pair =
+        node("T", "Pair", NodeType::Class).with_signature("type Pair = { a: number }".to_string());
+    pair.language = "typescript".to_string();
+    gb.add_node(pair);
+    gb.add_node(node("S", "Service", NodeType::Class));
+    let graph = gb.build();
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_string(&graph)
+        .unwrap();
+
+    assert!(s.contains("Point[STRUCT]"));
+    assert!(s.contains("Name[ALIAS]"));
+    assert!(s.contains("Pair[TYPE]"));
+    assert!(!s.contains("Service"));
+}
+
+#[test]
+fn llm_optimized_appends_docstring_summaries_when_enabled() {
+    let mut gb = GraphBuilder::new();
+    gb.add_node(
+        node("F", "load", NodeType::Function).with_docstring(
+            r#"
+    Read the "main" config file from disk and validate every section of it
+    Returns None when missing"#
+                .to_string(),
+        ),
+    );
+    gb.add_node(
+        node("C", "Config", NodeType::Class).with_docstring("Parsed settings.".to_string()),
+    );
+    let graph = gb.build();
+
+    let render = |docstrings: bool| {
+        LLMOptimizedFormatter::new()
+            .with_verbosity(OutputVerbosity::Compact)
+            .with_docstrings(docstrings)
+            .format_to_string(&graph)
+            .unwrap()
+    };
+
+    let with_docs = render(true);
+    assert!(
+        with_docs.contains("// \"Read the 'main' config file from disk and validate every...\"")
+    );
+    assert!(with_docs.contains("Config// \"Parsed settings.\""));
+    assert!(!with_docs.contains("Returns None"));
+
+    let without_docs = render(false);
+    assert!(!without_docs.contains("//"));
+    assert!(!without_docs.contains("Config"));
+}
+
+#[test]
+fn llm_optimized_flags_unused_code_when_enabled() {
+    let mut gb = GraphBuilder::new();
+    gb.add_node(node("M", "main", NodeType::Function));
+    gb.add_node(node("H", "helper", NodeType::Function));
+    gb.add_node(node("O", "orphan", NodeType::Function));
+    gb.add_edge(Edge::new(EdgeType::Call, "M".to_string(), "H".to_string()));
+    let graph = gb.build();
+
+    let render = |unused: bool| {
+        LLMOptimizedFormatter::new()
+            .with_verbosity(OutputVerbosity::Verbose)
+            .with_unused(unused)
+            .format_to_string(&graph)
+            .unwrap()
+    };
+
+    let flagged = render(true);
+    assert!(flagged.contains("orphan()[DEAD?]"));
+    assert!(!flagged.contains("helper()[DEAD?]"));
+    let unused_section = flagged.split("## POSSIBLY_UNUSED\n").nth(1).unwrap();
+    assert!(unused_section.starts_with("orphan ("));
+
+    let plain = render(false);
+    assert!(!plain.contains("DEAD?"));
+    assert!(!plain.contains("POSSIBLY_UNUSED"));
+}
+
+#[test]
+fn llm_optimized_shows_repeated_call_counts() {
+    let mut gb = GraphBuilder::new();
+    for (id, name) in [("A", "run"), ("B", "log"), ("C", "exit")] {
+        gb.add_node(node(id, name, NodeType::Function));
+    }
+    gb.add_edge(Edge::new(EdgeType::Call, "A".to_string(), "B".to_string()).with_weight(5));
+    gb.add_edge(Edge::new(EdgeType::Call, "A".to_string(), "C".to_string()));
+    let graph = gb.build();
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_string(&graph)
+        .unwrap();
+
+    assert!(s.contains("log×5"));
+    assert!(s.contains("exit"));
+    assert!(!s.contains("exit×"));
+}
+
+#[test]
+fn llm_optimized_tags_async_functions() {
+    let mut gb = GraphBuilder::new();
+    gb.add_node(node("F", "fetch", NodeType::Function).with_async(true));
+    let graph = gb.build();
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_string(&graph)
+        .unwrap();
+
+    assert!(s.contains("ASYNC"));
+}
+
+#[test]
+fn llm_optimized_tags_python_test_functions_once() {
+    let mut gb = GraphBuilder::new();
+    let mut test_fn = node("T", "test_checkout", NodeType::Function).with_test(true);
+    test_fn.language = "python".to_string();
+    gb.add_node(test_fn);
+    let graph = gb.build();
+
+    let s = LLMOptimizedFormatter::for_python()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_string(&graph)
+        .unwrap();
+
+    let line = s.lines().find(|l| l.contains("test_checkout(")).unwrap();
+    assert_eq!(line.matches("TEST").count(), 1, "{}", line);
+}
+
+#[test]
+fn llm_optimized_tags_entry_patterns_and_route_handlers() {
+    let mut gb = GraphBuilder::new();
+    let at_line = |mut n: Node, line: usize| {
+        n.line_number = line;
+        n
+    };
+    gb.add_node(at_line(node("D", "dispatch", NodeType::Function), 1));
+    gb.add_node(at_line(node("H", "handle_order", NodeType::Function), 2));
+    gb.add_node(at_line(
+        node("C", "create", NodeType::Function).with_decorators(vec!["router.post".to_string()]),
+        3,
+    ));
+    gb.add_node(at_line(node("S", "save", NodeType::Function), 4));
+    for target in ["H", "C", "S"] {
+        gb.add_edge(Edge::new(
+            EdgeType::Call,
+            "D".to_string(),
+            target.to_string(),
+        ));
+    }
+    let graph = gb.build();
+
+    let tagged = |formatter: LLMOptimizedFormatter| {
+        let s = formatter
+            .with_verbosity(OutputVerbosity::Compact)
+            .format_to_string(&graph)
+            .unwrap();
+        ["dispatch", "handle_order", "create", "save"]
+            .into_iter()
+            .filter(|name| s.contains(&format!("{}()[ENTRY", name)))
+            .collect::<Vec<_>>()
+    };
+
+    // Called from the same file, so only the route decorator makes `create` an entry
+    assert_eq!(tagged(LLMOptimizedFormatter::new()), vec!["create"]);
+    let with_patterns = LLMOptimizedFormatter::new()
+        .with_entry_patterns(vec!["handle_*".to_string()])
+        .unwrap();
+    assert_eq!(tagged(with_patterns), vec!["handle_order", "create"]);
+}
+
+#[test]
+fn llm_optimized_orders_calls_by_configured_priorities() {
+    let mut gb = GraphBuilder::new();
+    for (id, name) in [
+        ("A", "run"),
+        ("B", "audit"),
+        ("C", "log_event"),
+        ("D", "charge"),
+    ] {
+        gb.add_node(node(id, name, NodeType::Function));
+    }
+    for to in ["B", "C", "D"] {
+        gb.add_edge(Edge::new(EdgeType::Call, "A".to_string(), to.to_string()));
+    }
+    let graph = gb.build();
+
+    let render = |formatter: LLMOptimizedFormatter| {
+        formatter
+            .with_verbosity(OutputVerbosity::Compact)
+            .format_to_string(&graph)
+            .unwrap()
+    };
+
+    let s = render(LLMOptimizedFormatter::new());
+    assert!(s.contains("run()→{charge,log_event,audit}"), "{}", s);
+    // The first matching pattern wins; unmatched calls keep the adapter's priority
+    let prioritized = LLMOptimizedFormatter::new()
+        .with_call_priorities(vec![
+            ("log_*".to_string(), 9),
+            ("*_event".to_string(), 0),
+            ("audit".to_string(), 0),
+        ])
+        .unwrap();
+    let s = render(prioritized);
+    assert!(s.contains("run()→{audit,charge,log_event}"), "{}", s);
+}
+
+#[test]
+fn llm_optimized_tags_overriding_methods() {
+    let mut gb = GraphBuilder::new();
+    let mut parent = node("P", "render", NodeType::Function);
+    parent.line_number = 3;
+    gb.add_node(parent);
+    gb.add_node(node("C", "render", NodeType::Function));
+    gb.add_edge(Edge::new(
+        EdgeType::Overrides,
+        "C".to_string(),
+        "P".to_string(),
+    ));
+    let graph = gb.build();
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_string(&graph)
+        .unwrap();
+
+    assert_eq!(s.matches("OVERRIDE").count(), 1);
+}
+
+#[test]
+fn llm_optimized_lists_thrown_exceptions_in_verbose_mode() {
+    let mut gb = GraphBuilder::new();
+    gb.add_node(node("F", "load", NodeType::Function));
+    gb.add_node(node("E1", "IOException", NodeType::Class));
+    gb.add_node(node("E2", "ParseError", NodeType::Class));
+    gb.add_edge(Edge::new(
+        EdgeType::Throws,
+        "F".to_string(),
+        "E1".to_string(),
+    ));
+    gb.add_edge(Edge::new(
+        EdgeType::Throws,
+        "F".to_string(),
+        "E2".to_string(),
+    ));
+    let graph = gb.build();
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Verbose)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("load()!{IOException,ParseError}"));
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Standard)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(!s.contains("!{"));
+}
+
+#[test]
+fn llm_optimized_tags_functions_above_complexity_threshold() {
+    let mut gb = GraphBuilder::new();
+    gb.add_node(node("F", "dispatch", NodeType::Function).with_complexity(12));
+    gb.add_node(node("G", "simple", NodeType::Function).with_complexity(3));
+    let graph = gb.build();
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("CX:12"));
+    assert!(!s.contains("CX:3"));
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .with_complexity_threshold(2)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("CX:3"));
+}
+
+#[test]
+fn llm_optimized_tags_functions_with_many_callers_as_hot() {
+    let mut gb = GraphBuilder::new();
+    for (id, name) in [
+        ("A", "handle_a"),
+        ("B", "handle_b"),
+        ("C", "handle_c"),
+        ("L", "log"),
+        ("I", "build_index"),
+    ] {
+        gb.add_node(node(id, name, NodeType::Function));
+    }
+    for caller in ["A", "B", "C"] {
+        gb.add_edge(Edge::new(
+            EdgeType::Call,
+            caller.to_string(),
+            "L".to_string(),
+        ));
+    }
+    gb.add_edge(Edge::new(EdgeType::Call, "A".to_string(), "I".to_string()));
+    let graph = gb.build();
+
+    // Three callers clear the default floor of two; names no longer matter
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("log()[HOT]"));
+    assert!(!s.contains("build_index()[HOT"));
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .with_hot_threshold(3)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(!s.contains("HOT"));
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .with_hot_threshold(0)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("build_index()[HOT"));
+}
+
+#[test]
+fn llm_optimized_expands_calls_to_max_depth() {
+    let mut gb = GraphBuilder::new();
+    for (id, name) in [
+        ("A", "run"),
+        ("B", "load"),
+        ("C", "parse"),
+        ("D", "tokenize"),
+    ] {
+        gb.add_node(node(id, name, NodeType::Function));
+    }
+    for (from, to) in [("A", "B"), ("B", "C"), ("C", "D"), ("C", "A")] {
+        gb.add_edge(Edge::new(EdgeType::Call, from.to_string(), to.to_string()));
+    }
+    let graph = gb.build();
+
+    let render = |depth: usize| {
+        LLMOptimizedFormatter::new()
+            .with_verbosity(OutputVerbosity::Compact)
+            .with_max_call_depth(depth)
+            .format_to_string(&graph)
+            .unwrap()
+    };
+
+    assert!(render(1).contains("run()[RECURSIVE]→{load}"));
+    assert!(render(2).contains("run()[RECURSIVE]→{load→{parse→{+2 more}}}"));
+    // The recursive call back into `run` is not expanded again
+    assert!(render(3).contains("run()[RECURSIVE]→{load→{parse→{run,tokenize}}}"));
+}
+
+#[test]
+fn llm_optimized_format_to_string_matches_file_output() {
+    let mut gb = GraphBuilder::new();
+    gb.add_node(node("F", "foo", NodeType::Function));
+    let graph = gb.build();
+
+    let fmt = LLMOptimizedFormatter::new();
+    let rendered = fmt.format_to_string(&graph).unwrap();
+
+    let tmp = tempfile::NamedTempFile::new().unwrap();
+    fmt.format_to_file(&graph, tmp.path()).unwrap();
+    assert_eq!(rendered, std::fs::read_to_string(tmp.path()).unwrap());
+    assert!(rendered.contains("NODES:1 EDGES:0"));
+}
+
+#[test]
+fn llm_optimized_verbose_lists_call_cycles() {
+    let mut gb = GraphBuilder::new();
+    gb.add_node(node("P", "ping", NodeType::Function));
+    gb.add_node(node("Q", "pong", NodeType::Function));
+    gb.add_edge(Edge::new(EdgeType::Call, "P".to_string(), "Q".to_string()));
+    gb.add_edge(Edge::new(EdgeType::Call, "Q".to_string(), "P".to_string()));
+    let graph = gb.build();
+
+    let verbose = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Verbose)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(verbose.contains("## CYCLES\nping→pong→ping\n"));
+
+    let standard = LLMOptimizedFormatter::new()
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(!standard.contains("## CYCLES"));
+}
+
+#[test]
+fn llm_optimized_tags_recursive_functions() {
+    let mut gb = GraphBuilder::new();
+    for (id, name) in [("M", "main"), ("F", "fact"), ("P", "ping"), ("Q", "pong")] {
+        gb.add_node(node(id, name, NodeType::Function));
+    }
+    for (source, target) in [("M", "F"), ("F", "F"), ("M", "P"), ("P", "Q"), ("Q", "P")] {
+        gb.add_edge(Edge::new(
+            EdgeType::Call,
+            source.to_string(),
+            target.to_string(),
+        ));
+    }
+    let graph = gb.build();
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_string(&graph)
+        .unwrap();
+
+    // Self-calls and mutual recursion count; merely calling into a cycle does not
+    assert_eq!(s.matches("RECURSIVE").count(), 3);
+    for name in ["fact", "ping", "pong"] {
+        assert!(s.contains(&format!("{}()[RECURSIVE]", name)), "{}", s);
+    }
+}
+
+#[test]
+fn llm_optimized_qualifies_method_names_when_enabled() {
+    let mut gb = GraphBuilder::new();
+    let at_line = |mut n: Node, line: usize| {
+        n.line_number = line;
+        n
+    };
+    gb.add_node(at_line(node("U", "User", NodeType::Class), 1));
+    gb.add_node(at_line(
+        node("UP", "process", NodeType::Function).with_signature("process(self)".to_string()),
+        2,
+    ));
+    gb.add_node(at_line(node("O", "Order", NodeType::Class), 3));
+    gb.add_node(at_line(node("OP", "process", NodeType::Function), 4));
+    // No containment edge, only a qualified id
+    gb.add_node(at_line(
+        node(
+            "mod.rs:method:Cart::process:5",
+            "process",
+            NodeType::Function,
+        ),
+        5,
+    ));
+    gb.add_node(at_line(node("R", "run", NodeType::Function), 6));
+    gb.add_edge(Edge::new(
+        EdgeType::Contains,
+        "U".to_string(),
+        "UP".to_string(),
+    ));
+    gb.add_edge(Edge::new(
+        EdgeType::Contains,
+        "O".to_string(),
+        "OP".to_string(),
+    ));
+    for target in ["UP", "OP"] {
+        gb.add_edge(Edge::new(
+            EdgeType::Call,
+            "R".to_string(),
+            target.to_string(),
+        ));
+    }
+    let graph = gb.build();
+
+    let render = |qualified: bool| {
+        LLMOptimizedFormatter::new()
+            .with_verbosity(OutputVerbosity::Compact)
+            .with_qualified_names(qualified)
+            .format_to_string(&graph)
+            .unwrap()
+    };
+
+    let qualified = render(true);
+    assert!(qualified.contains("User.process(self)"), "{}", qualified);
+    assert!(qualified.contains("Order.process()"), "{}", qualified);
+    assert!(qualified.contains("Cart.process()"), "{}", qualified);
+    assert!(
+        qualified.contains("run()→{Order.process,User.process}"),
+        "{}",
+        qualified
+    );
+
+    let bare = render(false);
+    assert!(!bare.contains("User.process"));
+    assert!(bare.contains("run()→{process,process}"), "{}", bare);
+}
+
+#[test]
+fn llm_optimized_collapses_java_and_csharp_accessor_pairs() {
+    let mut gb = GraphBuilder::new();
+    let accessors = [
+        (
+            "java",
+            "User",
+            &[
+                "getName",
+                "setName",
+                "isActive",
+                "setActive",
+                "getId",
+                "settle",
+            ][..],
+        ),
+        ("csharp", "Order", &["get_Total", "set_Total"][..]),
+    ];
+    let mut line = 1;
+    for (language, class, methods) in accessors {
+        let mut class_node = node(class, class, NodeType::Class);
+        class_node.language = language.to_string();
+        gb.add_node(class_node);
+        for method in methods {
+            let id = format!("{}.{}", class, method);
+            let mut method_node = node(&id, method, NodeType::Function);
+            method_node.language = language.to_string();
+            method_node.line_number = line;
+            line += 1;
+            gb.add_node(method_node);
+            gb.add_edge(Edge::new(EdgeType::Contains, class.to_string(), id));
+        }
+    }
+    let graph = gb.build();
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .with_collapse_accessors(true)
+        .with_qualified_names(true)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("User.name{get,set}"), "{}", s);
+    assert!(s.contains("User.active{get,set}"), "{}", s);
+    assert!(s.contains("Order.Total{get,set}"), "{}", s);
+    // No setter to pair with, and not an accessor name at all
+    assert!(s.contains("User.getId()"), "{}", s);
+    assert!(s.contains("User.settle()"), "{}", s);
+    assert!(!s.contains("getName"), "{}", s);
+}
+
+#[test]
+fn llm_optimized_root_is_common_to_every_path() {
+    let root_for = |files: &[&str]| {
+        let mut gb = GraphBuilder::new();
+        for (i, file) in files.iter().enumerate() {
+            let mut f = node(&format!("F{}", i), &format!("f{}", i), NodeType::Function);
+            f.file_path = PathBuf::from(file);
+            gb.add_node(f);
+        }
+        let s = LLMOptimizedFormatter::new()
+            .with_verbosity(OutputVerbosity::Verbose)
+            .format_to_string(&gb.build())
+            .unwrap();
+        s.lines()
+            .find_map(|line| line.strip_prefix("ROOT: "))
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    assert_eq!(root_for(&["src/a.rs", "src/b/c.rs", "lib/x.rs"]), "");
+    assert_eq!(
+        root_for(&[
+            r"C:\repo\src\app\a.rs",
+            r"C:\repo\src\app\sub\b.rs",
+            r"C:\repo\src\lib\c.rs",
+        ]),
+        r"C:\repo\src\"
+    );
+}
+
+#[test]
+fn llm_optimized_folds_small_clusters_into_misc() {
+    let mut gb = GraphBuilder::new();
+    let files = [
+        ("S1", "charge", "/app/services/billing.py"),
+        ("S2", "refund", "/app/services/billing.py"),
+        ("S3", "invoice", "/app/services/invoices.py"),
+        ("E1", "Order", "/app/entities/order.py"),
+        ("U1", "slugify", "/app/util/text.py"),
+    ];
+    for (id, name, path) in files {
+        let mut n = node(id, name, NodeType::Function);
+        n.file_path = PathBuf::from(path);
+        gb.add_node(n);
+    }
+    let graph = gb.build();
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("### DATA_ENTITIES"));
+    assert!(!s.contains("### MISC"));
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .with_min_cluster_size(2)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("### CORE_SERVICES\nNODES:3"));
+    assert!(!s.contains("### DATA_ENTITIES"));
+    assert!(!s.contains("### UTILITY_LAYER"));
+    // Folded nodes are still listed, after every regular cluster
+    let misc = s.find("### MISC\nNODES:2").unwrap();
+    assert!(misc > s.find("### CORE_SERVICES").unwrap());
+    assert!(s[misc..].contains("Order"));
+    assert!(s[misc..].contains("slugify"));
+}
+
+#[test]
+fn llm_optimized_ranks_coupled_cluster_pairs_with_cohesion() {
+    let mut gb = GraphBuilder::new();
+    let files = [
+        ("S1", "charge", "/app/services/billing.py"),
+        ("S2", "refund", "/app/services/billing.py"),
+        ("E1", "Order", "/app/entities/order.py"),
+        ("U1", "slugify", "/app/util/text.py"),
+    ];
+    for (id, name, path) in files {
+        let mut n = node(id, name, NodeType::Function);
+        n.file_path = PathBuf::from(path);
+        gb.add_node(n);
+    }
+    for (source, target) in [("S1", "S2"), ("S1", "E1"), ("E1", "S2"), ("S2", "U1")] {
+        gb.add_edge(Edge::new(
+            EdgeType::Call,
+            source.to_string(),
+            target.to_string(),
+        ));
+    }
+    let graph = gb.build();
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Verbose)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("CORE_SERVICES: 0.25 (1/4)\n"));
+    assert!(s.contains("DATA_ENTITIES: 0.00 (0/2)\n"));
+    // Both directions count towards one pair, so it outranks the single edge
+    assert!(s.contains(
+        "### MOST_COUPLED_PAIRS\nCORE_SERVICES↔DATA_ENTITIES: 2\nCORE_SERVICES↔UTILITY_LAYER: 1\n"
+    ));
+}
+
+#[test]
+fn llm_optimized_uses_configured_semantic_prefixes() {
+    let mut gb = GraphBuilder::new();
+    for (id, name, path) in [
+        ("H1", "get_user", "/srv/app/handlers/users.go"),
+        ("R1", "find_user", "/srv/app/repositories/users.go"),
+        ("M1", "main", "/srv/app/cmd/main.go"),
+    ] {
+        let mut n = node(id, name, NodeType::Function);
+        n.file_path = PathBuf::from(path);
+        gb.add_node(n);
+    }
+    let graph = gb.build();
+
+    let s = LLMOptimizedFormatter::new()
+        .with_semantic_prefixes(vec![
+            ("handlers/".to_string(), "H".to_string()),
+            ("repositories/".to_string(), "RP".to_string()),
+        ])
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("├─ handlers/ → H[1]"));
+    assert!(s.contains("└─ repositories/ → RP[1]"));
+    assert!(s.contains("cmd/ → U[1]"));
+    assert!(s.contains("- **RP[N]** = repositories/"));
+    assert!(!s.contains("Ribbon/Toolbar"));
+
+    // Without a mapping the built-in taxonomy still applies
+    let s = LLMOptimizedFormatter::new()
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("Ribbon/Toolbar"));
+}
+
+#[test]
+fn llm_optimized_clusters_by_configured_path_globs() {
+    let mut gb = GraphBuilder::new();
+    for (id, name, path) in [
+        ("B1", "charge", "/srv/app/src/billing/charge.py"),
+        ("B2", "Invoice", "/srv/app/src/billing/models/invoice.py"),
+        ("A1", "login", "/srv/app/src/auth/login.py"),
+        ("S1", "notify", "/srv/app/src/services/mail.py"),
+    ] {
+        let mut n = node(id, name, NodeType::Function);
+        n.file_path = PathBuf::from(path);
+        gb.add_node(n);
+    }
+    let graph = gb.build();
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .with_cluster_patterns(vec![
+            ("src/billing/**".to_string(), "BILLING".to_string()),
+            ("src/auth/**".to_string(), "AUTH".to_string()),
+        ])
+        .unwrap()
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("### BILLING\nNODES:2"), "{}", s);
+    assert!(s.contains("### AUTH\nNODES:1"));
+    // Unmatched files no longer fall back to the built-in heuristics
+    assert!(s.contains("### UNCLASSIFIED\nNODES:1"));
+    assert!(!s.contains("CORE_SERVICES"));
+
+    assert!(LLMOptimizedFormatter::new()
+        .with_cluster_patterns(vec![("src/[billing".to_string(), "BILLING".to_string())])
+        .is_err());
+}
+
+#[test]
+fn llm_optimized_flattens_single_child_directory_chains() {
+    let mut gb = GraphBuilder::new();
+    for (id, name, path) in [
+        (
+            "A1",
+            "serve",
+            "/repo/src/main/java/com/example/app/Server.java",
+        ),
+        (
+            "A2",
+            "User",
+            "/repo/src/main/java/com/example/app/model/User.java",
+        ),
+        (
+            "T1",
+            "testServe",
+            "/repo/src/test/java/com/example/ServerTest.java",
+        ),
+    ] {
+        let mut n = node(id, name, NodeType::Function);
+        n.file_path = PathBuf::from(path);
+        gb.add_node(n);
+    }
+    let graph = gb.build();
+
+    let nested = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_string(&graph)
+        .unwrap();
+    let flat = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .with_flatten_namespaces(true)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(nested.contains("│  └─ java/ → U[2]\n"));
+    assert!(flat.contains(
+        "├─ main/java/com/example/app/ → U[2]\n\
+         │  └─ model/ → U[1]\n\
+         └─ test/java/com/example/ → U[1]\n"
+    ));
+}
+
+#[test]
+fn llm_optimized_lists_files_by_call_layer_when_enabled() {
+    let mut gb = GraphBuilder::new();
+    let func = |id: &str, file: &str| {
+        let mut n = node(id, id, NodeType::Function);
+        n.file_path = PathBuf::from(format!("/app/{}", file));
+        n
+    };
+    gb.add_node(func("main", "main.py"));
+    gb.add_node(func("route", "api/routes.py"));
+    gb.add_node(func("handle", "api/handlers.py"));
+    gb.add_node(func("query", "db/repo.py"));
+    gb.add_node(func("helper", "util.py"));
+    for (from, to) in [
+        ("main", "route"),
+        ("route", "handle"),
+        ("handle", "route"),
+        ("handle", "query"),
+        ("main", "query"),
+    ] {
+        gb.add_edge(Edge::new(EdgeType::Call, from.to_string(), to.to_string()));
+    }
+    let graph = gb.build();
+
+    let render = |layered: bool| {
+        LLMOptimizedFormatter::new()
+            .with_verbosity(OutputVerbosity::Compact)
+            .with_layered(layered)
+            .format_to_string(&graph)
+            .unwrap()
+    };
+
+    assert!(!render(false).contains("## LAYERS"));
+    // routes and handlers call each other, so they share a layer; the repo sits below
+    // its deepest caller, and util.py takes no part in any cross-file call
+    let s = render(true);
+    assert!(
+        s.contains("## LAYERS\nL0: main.py\nL1: api/handlers.py,api/routes.py\nL2: db/repo.py\n"),
+        "{}",
+        s
+    );
+    assert!(!s.contains("util.py,") && !s.contains(": util.py"));
+}
+
+#[test]
+fn llm_optimized_abbreviates_whole_signature_tokens_only() {
+    let mut gb = GraphBuilder::new();
+    gb.add_node(node("F", "wrap", NodeType::Function).with_signature(
+        "fn wrap(&self, name: String, thing: MyStringThing) -> Option<usize>".to_string(),
+    ));
+    let graph = gb.build();
+
+    let render = |compress: bool| {
+        LLMOptimizedFormatter::new()
+            .with_verbosity(OutputVerbosity::Compact)
+            .with_signature_compression(compress)
+            .format_to_string(&graph)
+            .unwrap()
+    };
+
+    let s = render(true);
+    assert!(
+        s.contains("fn wrap(name: str,thing: MyStringThing)→?int>"),
+        "{}",
+        s
+    );
+    let s = render(false);
+    assert!(
+        s.contains("fn wrap(&self, name: String, thing: MyStringThing) -> Option<usize>"),
+        "{}",
+        s
+    );
+}
+
+#[test]
+fn llm_optimized_hides_denylisted_and_infrequent_calls() {
+    let mut gb = GraphBuilder::new();
+    for (id, name, ty) in [
+        ("A", "run", NodeType::Function),
+        ("B", "charge", NodeType::Function),
+        ("C", "len", NodeType::Function),
+        ("D", "from", NodeType::Function),
+        ("S", "String", NodeType::Class),
+        ("E", "audit", NodeType::Function),
+    ] {
+        gb.add_node(node(id, name, ty));
+    }
+    gb.add_edge(Edge::new(
+        EdgeType::Contains,
+        "S".to_string(),
+        "D".to_string(),
+    ));
+    gb.add_edge(Edge::new(EdgeType::Call, "A".to_string(), "B".to_string()).with_weight(3));
+    gb.add_edge(Edge::new(EdgeType::Call, "A".to_string(), "C".to_string()).with_weight(4));
+    gb.add_edge(Edge::new(EdgeType::Call, "A".to_string(), "D".to_string()).with_weight(2));
+    gb.add_edge(Edge::new(EdgeType::Call, "A".to_string(), "E".to_string()));
+    let graph = gb.build();
+
+    let render = |formatter: LLMOptimizedFormatter| {
+        formatter
+            .with_verbosity(OutputVerbosity::Compact)
+            .format_to_string(&graph)
+            .unwrap()
+    };
+
+    let s = render(
+        LLMOptimizedFormatter::new()
+            .with_hide_calls(vec!["len".to_string(), "String::from".to_string()]),
+    );
+    assert!(s.contains("run()→{audit,charge×3}"), "{}", s);
+    // The owner must match for a qualified name
+    let s = render(LLMOptimizedFormatter::new().with_hide_calls(vec!["Vec::from".to_string()]));
+    assert!(s.contains("from×2"), "{}", s);
+    let s = render(LLMOptimizedFormatter::new().with_min_edge_weight(3));
+    assert!(s.contains("run()→{len×4,charge×3}"), "{}", s);
+}
+
+#[test]
+fn llm_optimized_measures_call_depth_of_dense_graphs_quickly() {
+    // Every function calls every later one, and the last calls back into the first:
+    // far too many simple paths to enumerate one by one
+    let mut gb = GraphBuilder::new();
+    let ids: Vec<String> = (0..40).map(|i| format!("F{}", i)).collect();
+    for (i, id) in ids.iter().enumerate() {
+        let mut function = node(id, &format!("f{}", i), NodeType::Function);
+        function.line_number = i + 1;
+        gb.add_node(function);
+    }
+    for (i, caller) in ids.iter().enumerate() {
+        for callee in &ids[i + 1..] {
+            gb.add_edge(Edge::new(EdgeType::Call, caller.clone(), callee.clone()));
+        }
+    }
+    gb.add_node(node("G", "entry", NodeType::Function));
+    gb.add_edge(Edge::new(EdgeType::Call, "G".to_string(), ids[0].clone()));
+    gb.add_edge(Edge::new(EdgeType::Call, ids[39].clone(), ids[10].clone()));
+    let graph = gb.build();
+
+    let started = std::time::Instant::now();
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+
+    // entry, f0 .. f9, then the f10 .. f39 cycle as one level
+    assert!(s.contains("CALL_DEPTH:12"), "{}", s);
+}
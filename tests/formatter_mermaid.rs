@@ -0,0 +1,97 @@
+use embargo::core::graph::{Edge, EdgeType, GraphBuilder, Node, NodeType};
+use embargo::formatters::MermaidFormatter;
+use std::path::PathBuf;
+
+fn node(id: &str, name: &str, ty: NodeType, line: usize) -> Node {
+    Node::new(
+        id.to_string(),
+        name.to_string(),
+        ty,
+        PathBuf::from("/tmp/app.py"),
+        line,
+        "python".to_string(),
+    )
+}
+
+#[test]
+fn mermaid_formatter_emits_flowchart_with_sanitized_ids() {
+    let mut gb = GraphBuilder::new();
+    let base = node("_tmp_app.py:class:Base:1", "Base", NodeType::Class, 1);
+    let child = node("_tmp_app.py:class:Child:5", "Child", NodeType::Class, 5);
+    let run = node("_tmp_app.py:function:run:10", "run", NodeType::Function, 10);
+    let helper = node(
+        "_tmp_app.py:function:helper:20",
+        "helper",
+        NodeType::Function,
+        20,
+    );
+    for n in [&base, &child, &run, &helper] {
+        gb.add_node((*n).clone());
+    }
+    gb.add_edge(Edge::new(
+        EdgeType::Inheritance,
+        child.id.clone(),
+        base.id.clone(),
+    ));
+    gb.add_edge(Edge::new(EdgeType::Call, run.id.clone(), helper.id.clone()));
+    let graph = gb.build();
+
+    let tmp = tempfile::NamedTempFile::new().unwrap();
+    MermaidFormatter::new()
+        .format_to_file(&graph, tmp.path())
+        .unwrap();
+    let s = std::fs::read_to_string(tmp.path()).unwrap();
+
+    assert!(s.starts_with("```mermaid\ngraph LR\n"));
+    assert!(s.contains("subgraph file0[\"/tmp/app.py\"]"));
+    assert!(s.contains("n__tmp_app_py_function_run_10 --> n__tmp_app_py_function_helper_20"));
+    assert!(s.contains("n__tmp_app_py_class_Child_5 -.-> n__tmp_app_py_class_Base_1"));
+    assert!(!s.contains(":function:"));
+}
+
+#[test]
+fn mermaid_formatter_truncates_to_max_nodes() {
+    let mut gb = GraphBuilder::new();
+    for i in 0..5 {
+        gb.add_node(node(
+            &format!("F{}", i),
+            &format!("f{}", i),
+            NodeType::Function,
+            i + 1,
+        ));
+    }
+    let graph = gb.build();
+
+    let tmp = tempfile::NamedTempFile::new().unwrap();
+    MermaidFormatter::new()
+        .with_max_nodes(2)
+        .format_to_file(&graph, tmp.path())
+        .unwrap();
+    let s = std::fs::read_to_string(tmp.path()).unwrap();
+
+    assert!(s.contains("f0()"));
+    assert!(s.contains("f1()"));
+    assert!(!s.contains("f2()"));
+    assert!(s.contains("3 more nodes omitted"));
+}
+
+#[test]
+fn mermaid_formatter_keeps_ids_distinct_when_sanitizing_collides() {
+    let mut gb = GraphBuilder::new();
+    let slash = node("a/b.py:function:f:1", "f", NodeType::Function, 1);
+    let underscore = node("a_b.py:function:f:1", "f", NodeType::Function, 2);
+    gb.add_node(slash.clone());
+    gb.add_node(underscore.clone());
+    gb.add_edge(Edge::new(
+        EdgeType::Call,
+        slash.id.clone(),
+        underscore.id.clone(),
+    ));
+    let graph = gb.build();
+
+    let s = MermaidFormatter::new().format_to_string(&graph).unwrap();
+
+    assert!(s.contains("    n_a_b_py_function_f_1(\"f()\")\n"));
+    assert!(s.contains("    n_a_b_py_function_f_1_2(\"f()\")\n"));
+    assert!(s.contains("n_a_b_py_function_f_1 --> n_a_b_py_function_f_1_2"));
+}
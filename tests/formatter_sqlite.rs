@@ -0,0 +1,85 @@
+use embargo::core::graph::{Edge, EdgeType, GraphBuilder, Node, NodeType};
+use embargo::formatters::SqliteFormatter;
+use rusqlite::Connection;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+fn function(id: &str, name: &str, file: &str, line: usize) -> Node {
+    Node::new(
+        id.to_string(),
+        name.to_string(),
+        NodeType::Function,
+        PathBuf::from(file),
+        line,
+        "python".to_string(),
+    )
+}
+
+#[test]
+fn sqlite_formatter_writes_queryable_nodes_and_edges() {
+    let mut builder = GraphBuilder::new();
+    builder.add_node(
+        function("handler", "handler", "/repo/services/api.py", 3)
+            .with_signature("handler(request)".to_string())
+            .with_async(true)
+            .with_decorators(vec!["app.get".to_string()]),
+    );
+    builder.add_node(function("helper", "helper", "/repo/services/util.py", 1));
+    builder.add_node(function("orphan", "orphan", "/repo/lib/misc.py", 8));
+    builder.add_edge(
+        Edge::new(EdgeType::Call, "handler".to_string(), "helper".to_string()).with_weight(2),
+    );
+    let graph = builder.build();
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("graph.db");
+    // Writing twice replaces the database instead of failing on existing tables
+    SqliteFormatter::new()
+        .format_to_file(&graph, &db_path)
+        .unwrap();
+    SqliteFormatter::new()
+        .format_to_file(&graph, &db_path)
+        .unwrap();
+
+    let conn = Connection::open(&db_path).unwrap();
+    let node_count: i64 = conn
+        .query_row("SELECT COUNT(*) FROM nodes", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(node_count, 3);
+
+    let (signature, is_async, decorators): (String, bool, String) = conn
+        .query_row(
+            "SELECT signature, is_async, decorators FROM nodes WHERE name = 'handler'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(signature, "handler(request)");
+    assert!(is_async);
+    assert_eq!(decorators, "[\"app.get\"]");
+
+    let weight: i64 = conn
+        .query_row(
+            "SELECT weight FROM edges WHERE edge_type = 'Call' AND source_id = 'handler' \
+             AND target_id = 'helper'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(weight, 2);
+
+    // Functions under services/ that nothing calls
+    let mut stmt = conn
+        .prepare(
+            "SELECT name FROM nodes n WHERE node_type = 'Function' \
+             AND file_path LIKE '%/services/%' AND NOT EXISTS \
+             (SELECT 1 FROM edges e WHERE e.target_id = n.id AND e.edge_type = 'Call')",
+        )
+        .unwrap();
+    let uncalled: Vec<String> = stmt
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .map(|name| name.unwrap())
+        .collect();
+    assert_eq!(uncalled, vec!["handler"]);
+}
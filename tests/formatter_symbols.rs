@@ -0,0 +1,80 @@
+use embargo::core::graph::{Edge, EdgeType, GraphBuilder, Node, NodeType};
+use embargo::formatters::SymbolsFormatter;
+use std::path::PathBuf;
+
+fn node(id: &str, name: &str, node_type: NodeType, file: &str, line: usize) -> Node {
+    Node::new(
+        id.to_string(),
+        name.to_string(),
+        node_type,
+        PathBuf::from(file),
+        line,
+        "python".to_string(),
+    )
+}
+
+#[test]
+fn symbols_formatter_lists_each_files_symbols_with_their_container() {
+    let mut builder = GraphBuilder::new();
+    builder
+        .add_node(node("cart", "Cart", NodeType::Class, "src/shop/cart.py", 3).with_end_line(12));
+    builder.add_node(node(
+        "total",
+        "total",
+        NodeType::Function,
+        "src/shop/cart.py",
+        9,
+    ));
+    builder
+        .add_node(node("add", "add", NodeType::Function, "src/shop/cart.py", 5).with_end_line(7));
+    builder.add_node(node(
+        "items",
+        "items",
+        NodeType::Variable,
+        "src/shop/cart.py",
+        4,
+    ));
+    builder.add_node(node("main", "main", NodeType::Function, "src/app.py", 3));
+    builder.add_node(node("os", "import os", NodeType::Module, "src/app.py", 1));
+    builder.add_node(
+        node("ext", "requests", NodeType::Class, "src/app.py", 0)
+            .with_visibility("external".to_string()),
+    );
+    builder.add_edge(Edge::new(
+        EdgeType::Contains,
+        "cart".to_string(),
+        "add".to_string(),
+    ));
+    builder.add_edge(Edge::new(
+        EdgeType::Contains,
+        "cart".to_string(),
+        "total".to_string(),
+    ));
+    builder.add_edge(Edge::new(
+        EdgeType::Contains,
+        "cart".to_string(),
+        "items".to_string(),
+    ));
+    builder.add_edge(Edge::new(
+        EdgeType::Call,
+        "main".to_string(),
+        "add".to_string(),
+    ));
+    let graph = builder.build();
+
+    let output = SymbolsFormatter::new().format_to_string(&graph).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    let expected = serde_json::json!([
+        {"file": "src/app.py", "symbols": [
+            {"name": "main", "kind": "function", "line": 3, "end_line": 3, "container": null}
+        ]},
+        {"file": "src/shop/cart.py", "symbols": [
+            {"name": "Cart", "kind": "class", "line": 3, "end_line": 12, "container": null},
+            {"name": "items", "kind": "field", "line": 4, "end_line": 4, "container": "Cart"},
+            {"name": "add", "kind": "method", "line": 5, "end_line": 7, "container": "Cart"},
+            {"name": "total", "kind": "method", "line": 9, "end_line": 9, "container": "Cart"}
+        ]}
+    ]);
+    assert_eq!(parsed, expected);
+}
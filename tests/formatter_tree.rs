@@ -0,0 +1,85 @@
+use embargo::core::graph::{Edge, EdgeType, GraphBuilder, Node, NodeType};
+use embargo::formatters::TreeFormatter;
+use std::path::PathBuf;
+
+fn node(id: &str, name: &str, node_type: NodeType, file: &str, line: usize) -> Node {
+    Node::new(
+        id.to_string(),
+        name.to_string(),
+        node_type,
+        PathBuf::from(file),
+        line,
+        "python".to_string(),
+    )
+}
+
+#[test]
+fn tree_formatter_nests_members_under_files_and_classes() {
+    let mut builder = GraphBuilder::new();
+    builder.add_node(node(
+        "shop",
+        "Shop",
+        NodeType::Class,
+        "/repo/src/shop/cart.py",
+        3,
+    ));
+    builder.add_node(node(
+        "add",
+        "add",
+        NodeType::Function,
+        "/repo/src/shop/cart.py",
+        5,
+    ));
+    builder.add_node(node(
+        "total",
+        "total",
+        NodeType::Function,
+        "/repo/src/shop/cart.py",
+        9,
+    ));
+    builder.add_node(node(
+        "main",
+        "main",
+        NodeType::Function,
+        "/repo/src/app.py",
+        1,
+    ));
+    builder.add_node(node("os", "os", NodeType::Module, "/repo/src/app.py", 1));
+    builder.add_edge(Edge::new(
+        EdgeType::Contains,
+        "shop".to_string(),
+        "add".to_string(),
+    ));
+    builder.add_edge(Edge::new(
+        EdgeType::Contains,
+        "shop".to_string(),
+        "total".to_string(),
+    ));
+    builder.add_edge(Edge::new(
+        EdgeType::Call,
+        "main".to_string(),
+        "add".to_string(),
+    ));
+    let graph = builder.build();
+
+    let output = TreeFormatter::new().format_to_string(&graph).unwrap();
+
+    let expected = "\
+/repo/src/
+├─ shop/
+│  └─ cart.py
+│     └─ class Shop :3
+│        ├─ fn add() :5
+│        └─ fn total() :9
+└─ app.py
+   └─ fn main() :1
+";
+    assert_eq!(output, expected);
+    assert!(!output.contains('\x1b'));
+
+    let colored = TreeFormatter::new()
+        .with_color(true)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(colored.contains("\x1b[33mclass Shop\x1b[0m"));
+}
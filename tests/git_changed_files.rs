@@ -0,0 +1,43 @@
+use embargo::core::git::changed_files;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args([
+            "-c",
+            "user.name=embargo",
+            "-c",
+            "user.email=embargo@example.com",
+        ])
+        .args(args)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn changed_files_lists_modified_paths_below_dir() {
+    let repo = tempfile::TempDir::new().unwrap();
+    let src = repo.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("a.py"), "def a():\n    pass\n").unwrap();
+    fs::write(src.join("b.py"), "def b():\n    pass\n").unwrap();
+    fs::write(repo.path().join("README.md"), "readme\n").unwrap();
+    git(repo.path(), &["init", "-q"]);
+    git(repo.path(), &["add", "-A"]);
+    git(repo.path(), &["commit", "-qm", "initial"]);
+
+    assert!(changed_files(&src, "HEAD").unwrap().is_empty());
+
+    fs::write(src.join("a.py"), "def a():\n    return 1\n").unwrap();
+    fs::write(repo.path().join("README.md"), "changed\n").unwrap();
+    fs::remove_file(src.join("b.py")).unwrap();
+
+    // README is outside `src` and b.py no longer exists
+    assert_eq!(changed_files(&src, "HEAD").unwrap(), vec![src.join("a.py")]);
+    assert!(changed_files(&src, "no-such-ref").is_err());
+}
@@ -1,7 +1,8 @@
 use embargo::core::{
     graph::{Edge, EdgeType, GraphBuilder, Node, NodeType},
-    DependencyGraph,
+    DependencyGraph, DependencyGraphExt, GraphStats, Hotspot, VisibilityLevel,
 };
+use petgraph::graph::NodeIndex;
 use std::path::PathBuf;
 
 fn make_node(id: &str, name: &str, ty: NodeType) -> Node {
@@ -48,3 +49,385 @@ fn add_edge_returns_none_when_missing_nodes() {
     let e = Edge::new(EdgeType::Call, n1.id.clone(), "missing".to_string());
     assert!(gb.add_edge(e).is_none());
 }
+
+#[test]
+fn find_cycles_reports_components_and_self_loops_by_edge_type() {
+    let mut gb = GraphBuilder::new();
+    for name in ["a", "b", "c", "d", "r"] {
+        gb.add_node(make_node(name, name, NodeType::Function));
+    }
+    let call = |s: &str, t: &str| Edge::new(EdgeType::Call, s.to_string(), t.to_string());
+    gb.add_edge(call("a", "b"));
+    gb.add_edge(call("b", "c"));
+    gb.add_edge(call("c", "a"));
+    gb.add_edge(call("c", "d"));
+    gb.add_edge(call("r", "r"));
+    // Containment loops are not dependency cycles
+    gb.add_edge(Edge::new(
+        EdgeType::Contains,
+        "d".to_string(),
+        "a".to_string(),
+    ));
+    let graph = gb.build();
+
+    let names = |cycle: &Vec<NodeIndex>| -> Vec<String> {
+        cycle.iter().map(|&i| graph[i].name.clone()).collect()
+    };
+
+    let cycles = graph.find_cycles(&[EdgeType::Call]);
+    let rendered: Vec<Vec<String>> = cycles.iter().map(names).collect();
+    assert_eq!(rendered, vec![vec!["a", "b", "c"], vec!["r"]]);
+
+    let with_contains = graph.find_cycles(&[EdgeType::Call, EdgeType::Contains]);
+    assert_eq!(names(&with_contains[0]), vec!["a", "b", "c", "d"]);
+}
+
+#[test]
+fn graph_stats_counts_types_callers_and_call_depth() {
+    let mut gb = GraphBuilder::new();
+    for (id, name) in [("m", "main"), ("a", "load"), ("b", "parse"), ("c", "emit")] {
+        gb.add_node(make_node(id, name, NodeType::Function));
+    }
+    gb.add_node(make_node("cls", "Parser", NodeType::Class));
+    // main -> load -> parse, main -> parse, main -> emit, parse -> load (cycle)
+    for (from, to) in [("m", "a"), ("a", "b"), ("m", "b"), ("m", "c"), ("b", "a")] {
+        gb.add_edge(Edge::new(EdgeType::Call, from.to_string(), to.to_string()));
+    }
+    gb.add_edge(Edge::new(
+        EdgeType::Contains,
+        "cls".to_string(),
+        "b".to_string(),
+    ));
+    let graph = gb.build();
+
+    let stats = GraphStats::compute(&graph);
+    assert_eq!(stats.nodes_by_type["Function"], 4);
+    assert_eq!(stats.nodes_by_type["Class"], 1);
+    assert_eq!(stats.edges_by_type["Call"], 5);
+    assert_eq!(stats.files_by_language["rust"], 1);
+    assert_eq!(stats.most_called[0], ("load".to_string(), 2));
+    assert_eq!(stats.most_called[1], ("parse".to_string(), 2));
+    assert_eq!(stats.max_call_depth, 2);
+    assert!(stats.to_string().contains("Call depth: avg 2.00, max 2"));
+}
+
+#[test]
+fn hotspots_rank_functions_by_callers_complexity_and_calls() {
+    let mut gb = GraphBuilder::new();
+    gb.add_node(make_node("m", "main", NodeType::Function).with_complexity(1));
+    gb.add_node(make_node("a", "load", NodeType::Function).with_complexity(4));
+    gb.add_node(make_node("b", "parse", NodeType::Function).with_complexity(8));
+    gb.add_node(make_node("c", "emit", NodeType::Function));
+    gb.add_node(
+        make_node("x", "println", NodeType::Function).with_visibility("external".to_string()),
+    );
+    // main -> load, parse, emit; load -> parse (twice), println; emit -> println
+    for (from, to) in [
+        ("m", "a"),
+        ("m", "b"),
+        ("m", "c"),
+        ("a", "b"),
+        ("a", "x"),
+        ("c", "x"),
+    ] {
+        gb.add_edge(Edge::new(EdgeType::Call, from.to_string(), to.to_string()));
+    }
+    gb.add_edge(Edge::new(EdgeType::Call, "a".to_string(), "b".to_string()));
+    let graph = gb.build();
+
+    let hotspots = Hotspot::top(&graph, 3);
+    let ranked: Vec<(&str, usize, usize, usize)> = hotspots
+        .iter()
+        .map(|h| (h.name.as_str(), h.callers, h.complexity, h.callees))
+        .collect();
+    // parse: 3/3 + 8/8 + 0/3; load: 1/3 + 4/8 + 2/3; main: 0/3 + 1/8 + 3/3
+    assert_eq!(
+        ranked,
+        [("parse", 3, 8, 0), ("load", 1, 4, 2), ("main", 0, 1, 3)]
+    );
+    assert!((hotspots[0].score - 2.0).abs() < 1e-9);
+    assert!((hotspots[1].score - 1.5).abs() < 1e-9);
+    assert_eq!(
+        hotspots[0].to_string(),
+        "parse 2.00 (callers 3, complexity 8, calls 0) /tmp/file.rs:1"
+    );
+}
+
+#[test]
+fn reachable_from_follows_only_requested_edge_types() {
+    let mut gb = GraphBuilder::new();
+    for name in ["main", "load", "parse", "unused", "Store"] {
+        gb.add_node(make_node(name, name, NodeType::Function));
+    }
+    let call = |s: &str, t: &str| Edge::new(EdgeType::Call, s.to_string(), t.to_string());
+    gb.add_edge(call("main", "load"));
+    gb.add_edge(call("load", "parse"));
+    gb.add_edge(call("parse", "load"));
+    gb.add_edge(call("unused", "main"));
+    gb.add_edge(Edge::new(
+        EdgeType::Uses,
+        "load".to_string(),
+        "Store".to_string(),
+    ));
+    let graph = gb.build();
+
+    let names = |indices: &[NodeIndex]| -> Vec<String> {
+        indices.iter().map(|&i| graph[i].name.clone()).collect()
+    };
+
+    let calls_only = graph.reachable_from("main", &[EdgeType::Call]);
+    assert_eq!(names(&calls_only), vec!["main", "load", "parse"]);
+
+    let with_uses = graph.reachable_from("main", &[EdgeType::Call, EdgeType::Uses]);
+    assert_eq!(with_uses.len(), 4);
+    assert!(graph.reachable_from("nope", &[EdgeType::Call]).is_empty());
+
+    let pruned = graph.induced_subgraph(&calls_only);
+    assert_eq!(pruned.node_count(), 3);
+    // main->load, load->parse, parse->load survive; edges to unused/Store do not
+    assert_eq!(pruned.edge_count(), 3);
+}
+
+#[test]
+fn shortest_path_takes_fewest_call_hops() {
+    let mut gb = GraphBuilder::new();
+    for name in ["a", "b", "c", "d", "T"] {
+        gb.add_node(make_node(name, name, NodeType::Function));
+    }
+    let call = |s: &str, t: &str| Edge::new(EdgeType::Call, s.to_string(), t.to_string());
+    gb.add_edge(call("a", "b"));
+    gb.add_edge(call("b", "c"));
+    gb.add_edge(call("c", "d"));
+    gb.add_edge(call("a", "c"));
+    gb.add_edge(Edge::new(EdgeType::Uses, "a".to_string(), "T".to_string()));
+    gb.add_edge(call("T", "d"));
+    let graph = gb.build();
+
+    let idx = |name: &str| {
+        graph
+            .node_indices()
+            .find(|&i| graph[i].name == name)
+            .unwrap()
+    };
+    let path = graph
+        .shortest_path(idx("a"), idx("d"), &[EdgeType::Call])
+        .unwrap();
+    let names: Vec<&str> = path.iter().map(|&i| graph[i].name.as_str()).collect();
+    assert_eq!(names, vec!["a", "c", "d"]);
+
+    assert!(graph
+        .shortest_path(idx("d"), idx("a"), &[EdgeType::Call])
+        .is_none());
+    assert!(graph
+        .shortest_path(idx("a"), idx("T"), &[EdgeType::Call])
+        .is_none());
+}
+
+#[test]
+fn unreferenced_nodes_skips_called_public_and_entry_points() {
+    let mut gb = GraphBuilder::new();
+    for (id, name) in [
+        ("m", "main"),
+        ("h", "helper"),
+        ("o", "orphan"),
+        ("tm", "testimony"),
+    ] {
+        gb.add_node(make_node(id, name, NodeType::Function));
+    }
+    // Tests are entry points because the parser says so, not because of their name
+    gb.add_node(make_node("t", "test_orphan", NodeType::Function).with_test(true));
+    gb.add_node(
+        make_node("api", "handler", NodeType::Function).with_visibility("public".to_string()),
+    );
+    gb.add_node(make_node("Used", "Used", NodeType::Class));
+    gb.add_node(make_node("used_m", "run", NodeType::Function));
+    gb.add_node(make_node("Lonely", "Lonely", NodeType::Class));
+    gb.add_node(make_node("Base", "Base", NodeType::Class));
+    gb.add_node(make_node("Child", "Child", NodeType::Class));
+    gb.add_node(make_node("If", "Plugin", NodeType::Interface));
+    gb.add_node(make_node("if_m", "activate", NodeType::Function));
+
+    let edge = |t: EdgeType, s: &str, d: &str| Edge::new(t, s.to_string(), d.to_string());
+    gb.add_edge(edge(EdgeType::Call, "m", "h"));
+    gb.add_edge(edge(EdgeType::Call, "m", "used_m"));
+    gb.add_edge(edge(EdgeType::Contains, "Used", "used_m"));
+    gb.add_edge(edge(EdgeType::Inheritance, "Child", "Base"));
+    gb.add_edge(edge(EdgeType::Contains, "If", "if_m"));
+    let graph = gb.build();
+
+    let mut names: Vec<String> = graph
+        .unreferenced_nodes()
+        .into_iter()
+        .map(|idx| graph[idx].name.clone())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["Child", "Lonely", "orphan", "testimony"]);
+}
+
+#[test]
+fn retain_visible_drops_less_visible_nodes_and_their_members() {
+    let visible = |id: &str, visibility: &str| {
+        make_node(id, id, NodeType::Function).with_visibility(visibility.to_string())
+    };
+    let mut gb = GraphBuilder::new();
+    gb.add_node(make_node("Api", "Api", NodeType::Class).with_visibility("public".to_string()));
+    gb.add_node(visible("serve", "public"));
+    gb.add_node(visible("_hook", "protected"));
+    gb.add_node(visible("__init__", "dunder"));
+    gb.add_node(visible("helper", "package"));
+    gb.add_node(make_node("Impl", "Impl", NodeType::Class).with_visibility("private".to_string()));
+    // Public, but only reachable through the private class
+    gb.add_node(visible("run", "public"));
+    gb.add_node(make_node("import", "os", NodeType::Module));
+    for member in ["serve", "_hook", "__init__", "helper"] {
+        gb.add_edge(Edge::new(
+            EdgeType::Contains,
+            "Api".to_string(),
+            member.to_string(),
+        ));
+    }
+    gb.add_edge(Edge::new(
+        EdgeType::Contains,
+        "Impl".to_string(),
+        "run".to_string(),
+    ));
+    gb.add_edge(Edge::new(
+        EdgeType::Call,
+        "serve".to_string(),
+        "helper".to_string(),
+    ));
+    gb.add_edge(Edge::new(
+        EdgeType::Call,
+        "serve".to_string(),
+        "_hook".to_string(),
+    ));
+    let graph = gb.build();
+
+    let names = |graph: &DependencyGraph| {
+        let mut names: Vec<String> = graph.node_weights().map(|n| n.name.clone()).collect();
+        names.sort();
+        names
+    };
+
+    let public = graph.retain_visible(VisibilityLevel::Public);
+    assert_eq!(names(&public), vec!["Api", "__init__", "os", "serve"]);
+    // Edges into dropped nodes go with them
+    assert_eq!(public.edge_count(), 2);
+
+    let protected = graph.retain_visible(VisibilityLevel::Protected);
+    assert_eq!(
+        names(&protected),
+        vec!["Api", "__init__", "_hook", "os", "serve"]
+    );
+    assert_eq!(protected.edge_count(), 4);
+
+    assert_eq!(
+        graph.retain_visible(VisibilityLevel::Private).node_count(),
+        graph.node_count()
+    );
+}
+
+#[test]
+fn top_level_modules_groups_by_first_directory_below_the_shared_root() {
+    let mut gb = GraphBuilder::new();
+    for (id, path) in [
+        ("main", "/repo/src/main.rs"),
+        ("graph", "/repo/src/core/graph.rs"),
+        ("scanner", "/repo/src/core/walk/scanner.rs"),
+        ("python", "/repo/src/parsers/python.rs"),
+    ] {
+        let mut node = make_node(id, id, NodeType::Function);
+        node.file_path = PathBuf::from(path);
+        gb.add_node(node);
+    }
+    let graph = gb.build();
+
+    let modules: Vec<(String, Vec<String>)> = graph
+        .top_level_modules()
+        .into_iter()
+        .map(|(module, nodes)| {
+            let ids = nodes.iter().map(|&idx| graph[idx].id.clone()).collect();
+            (module, ids)
+        })
+        .collect();
+    assert_eq!(
+        modules,
+        [
+            ("_root".to_string(), vec!["main".to_string()]),
+            (
+                "core".to_string(),
+                vec!["graph".to_string(), "scanner".to_string()]
+            ),
+            ("parsers".to_string(), vec!["python".to_string()]),
+        ]
+    );
+}
+
+#[test]
+fn sorted_graph_does_not_depend_on_insertion_order() {
+    let nodes = [
+        ("b.rs", "B", "run", 3),
+        ("a.rs", "A2", "load", 9),
+        ("a.rs", "A1", "main", 2),
+        ("b.rs", "B0", "Runner", 1),
+    ];
+    let edges = [
+        (EdgeType::Call, "A1", "A2"),
+        (EdgeType::Call, "A1", "B"),
+        (EdgeType::Contains, "B0", "B"),
+        (EdgeType::Uses, "A1", "B"),
+    ];
+
+    let build = |reverse: bool| {
+        let mut gb = GraphBuilder::new();
+        let mut node_order: Vec<_> = nodes.iter().collect();
+        let mut edge_order: Vec<_> = edges.iter().collect();
+        if reverse {
+            node_order.reverse();
+            edge_order.reverse();
+        }
+        for (file, id, name, line) in node_order {
+            gb.add_node(Node::new(
+                id.to_string(),
+                name.to_string(),
+                NodeType::Function,
+                PathBuf::from(file),
+                *line,
+                "rust".to_string(),
+            ));
+        }
+        for (edge_type, source, target) in edge_order {
+            gb.add_edge(Edge::new(
+                *edge_type,
+                source.to_string(),
+                target.to_string(),
+            ));
+        }
+        gb.build().sorted()
+    };
+
+    let (forward, backward) = (build(false), build(true));
+    assert_eq!(forward.to_json().unwrap(), backward.to_json().unwrap());
+    let ids: Vec<&str> = forward.node_weights().map(|n| n.id.as_str()).collect();
+    assert_eq!(ids, vec!["A1", "A2", "B0", "B"]);
+    let edges: Vec<(EdgeType, &str)> = forward
+        .edge_weights()
+        .map(|e| (e.edge_type, e.target_id.as_str()))
+        .collect();
+    assert_eq!(
+        edges,
+        vec![
+            (EdgeType::Call, "A2"),
+            (EdgeType::Call, "B"),
+            (EdgeType::Uses, "B"),
+            (EdgeType::Contains, "B")
+        ]
+    );
+    // Endpoints follow their nodes to the new indices
+    let contains = forward.edge_indices().next_back().unwrap();
+    let (source, target) = forward.edge_endpoints(contains).unwrap();
+    assert_eq!(
+        (forward[source].id.as_str(), forward[target].id.as_str()),
+        ("B0", "B")
+    );
+}
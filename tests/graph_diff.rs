@@ -0,0 +1,64 @@
+use embargo::core::diff::GraphDiff;
+use embargo::core::graph::{Edge, EdgeType, GraphBuilder, Node, NodeType};
+use embargo::core::DependencyGraph;
+use std::path::PathBuf;
+
+fn func(name: &str, file: &str, line: usize) -> Node {
+    Node::new(
+        format!("{}:function:{}", file, name),
+        name.to_string(),
+        NodeType::Function,
+        PathBuf::from(file),
+        line,
+        "rust".to_string(),
+    )
+}
+
+fn graph(nodes: &[Node], calls: &[(usize, usize)]) -> DependencyGraph {
+    let mut gb = GraphBuilder::new();
+    for node in nodes {
+        gb.add_node(node.clone());
+    }
+    for &(source, target) in calls {
+        gb.add_edge(Edge::new(
+            EdgeType::Call,
+            nodes[source].id.clone(),
+            nodes[target].id.clone(),
+        ));
+    }
+    gb.build()
+}
+
+#[test]
+fn diff_lists_added_and_removed_nodes_and_edges_by_file() {
+    let main = func("main", "src/main.rs", 1);
+    let parse = func("parse", "src/parse.rs", 4);
+    let load = func("load", "src/main.rs", 12);
+    // `main` moved down a line; with stable ids it is the same node
+    let moved_main = Node {
+        line_number: 2,
+        ..main.clone()
+    };
+
+    let old = graph(&[main, parse.clone()], &[(0, 1)]);
+    let new = graph(&[moved_main, parse, load], &[(0, 2), (0, 1)]);
+
+    let diff = GraphDiff::between(&old, &new);
+    assert_eq!(diff.added_nodes.len(), 1);
+    assert!(diff.removed_nodes.is_empty());
+    assert!(diff.removed_edges.is_empty());
+    assert_eq!(
+        diff.render(),
+        r#"src/main.rs
+  + edge Call main→load
+  + fn load (src/main.rs:12)
+
+Nodes: +1 -0, edges: +1 -0
+"#
+    );
+
+    let reverse = GraphDiff::between(&new, &old).render();
+    assert!(reverse.contains("  - fn load (src/main.rs:12)\n"));
+    assert!(reverse.contains("  - edge Call main→load\n"));
+    assert_eq!(GraphDiff::between(&old, &old).render(), "No differences\n");
+}
@@ -25,3 +25,501 @@ fn analyzer_end_to_end_on_small_rust_file() {
     assert!(s.contains("NODES:"));
     assert!(s.contains("EDGES:"));
 }
+
+#[test]
+fn analyzer_analyzes_explicit_file_list() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let a = dir.path().join("a.rs");
+    let b = dir.path().join("b.rs");
+    let notes = dir.path().join("notes.txt");
+    fs::write(&a, "fn alpha() {}\n").unwrap();
+    fs::write(&b, "fn beta() {}\n").unwrap();
+    fs::write(&notes, "not code\n").unwrap();
+
+    let mut analyzer = CodebaseAnalyzer::new();
+    let graph = analyzer
+        .analyze_files(&[a.clone(), a, notes], &["rust"])
+        .unwrap();
+
+    let names: Vec<_> = graph.node_weights().map(|n| n.name.as_str()).collect();
+    assert_eq!(names, vec!["alpha"]);
+}
+
+#[test]
+fn analyzer_times_each_phase() {
+    use embargo::parsers::cache::ParseCache;
+    use std::time::Duration;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("a.py"),
+        "def a():\n    b()\n\ndef b():\n    pass\n",
+    )
+    .unwrap();
+
+    let mut analyzer = CodebaseAnalyzer::new()
+        .with_parse_cache(ParseCache::in_memory_only())
+        .with_quiet(true);
+    analyzer.analyze(dir.path(), &["python"]).unwrap();
+
+    let timings = analyzer.timings();
+    for phase in [timings.scan, timings.parse, timings.index, timings.resolve] {
+        assert!(phase > Duration::ZERO, "{:?}", timings);
+    }
+}
+
+#[test]
+fn analyzer_reports_syntax_errors_per_file_including_cache_hits() {
+    use embargo::parsers::cache::ParseCache;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    fs::write(dir.path().join("ok.py"), "def a():\n    pass\n").unwrap();
+    fs::write(
+        dir.path().join("broken.py"),
+        "def a():\n    pass\n\ndef b(:\n    pass\n",
+    )
+    .unwrap();
+
+    let mut analyzer = CodebaseAnalyzer::new()
+        .with_parse_cache(ParseCache::in_memory_only())
+        .with_quiet(true);
+    for _ in 0..2 {
+        analyzer.analyze(dir.path(), &["python"]).unwrap();
+        let errors = analyzer.syntax_errors();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].0.ends_with("broken.py"));
+        assert_eq!(errors[0].1[0].line, 4);
+    }
+}
+
+#[test]
+fn analyzer_merges_roots_and_resolves_calls_across_them() {
+    use embargo::core::EdgeType;
+    use petgraph::visit::EdgeRef;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let web = dir.path().join("web");
+    let billing = dir.path().join("billing");
+    fs::create_dir_all(&web).unwrap();
+    fs::create_dir_all(&billing).unwrap();
+    fs::write(
+        web.join("checkout.py"),
+        "def checkout():\n    charge_card()\n",
+    )
+    .unwrap();
+    fs::write(
+        billing.join("payments.py"),
+        "def charge_card():\n    pass\n",
+    )
+    .unwrap();
+
+    let mut analyzer = CodebaseAnalyzer::new();
+    let graph = analyzer
+        .analyze_roots(&[web, billing.clone(), billing], &["python"])
+        .unwrap();
+
+    let functions: Vec<_> = graph
+        .node_weights()
+        .filter(|n| n.name == "charge_card")
+        .collect();
+    assert_eq!(functions.len(), 1, "overlapping roots are scanned once");
+
+    let cross_call = graph.edge_references().any(|e| {
+        e.weight().edge_type == EdgeType::Call
+            && graph[e.source()].name == "checkout"
+            && graph[e.target()].name == "charge_card"
+    });
+    assert!(cross_call);
+}
+
+#[test]
+fn analyzer_uses_registered_parsers_for_custom_languages() {
+    use embargo::core::resolver::CallType;
+    use embargo::core::{CallSite, EdgeType, FileScanner, Node, NodeType};
+    use embargo::parsers::{LanguageParser, ParseResult, ParserFactory};
+    use petgraph::visit::EdgeRef;
+    use std::path::Path;
+
+    /// `step NAME` defines a step; `run NAME` calls one from the step above it
+    struct StepParser;
+
+    impl LanguageParser for StepParser {
+        fn parse_file(&self, file_path: &Path) -> anyhow::Result<ParseResult> {
+            let mut nodes: Vec<Node> = Vec::new();
+            let mut call_sites = Vec::new();
+            for (index, line) in fs::read_to_string(file_path)?.lines().enumerate() {
+                match line.split_once(' ') {
+                    Some(("step", name)) => nodes.push(Node::new(
+                        format!("{}:function:{}", file_path.display(), name),
+                        name.to_string(),
+                        NodeType::Function,
+                        file_path.to_path_buf(),
+                        index + 1,
+                        "steps".to_string(),
+                    )),
+                    Some(("run", name)) => call_sites.push(CallSite {
+                        caller_id: nodes.last().unwrap().id.clone(),
+                        called_name: name.to_string(),
+                        call_type: CallType::SimpleCall,
+                        context: None,
+                        line_number: index + 1,
+                        caller_class: None,
+                        arg_count: None,
+                    }),
+                    _ => {}
+                }
+            }
+            Ok(ParseResult {
+                nodes,
+                edges: Vec::new(),
+                call_sites: Some(call_sites),
+                syntax_errors: Vec::new(),
+            })
+        }
+
+        fn language_name(&self) -> &str {
+            "steps"
+        }
+    }
+
+    let dir = tempfile::TempDir::new().unwrap();
+    fs::write(dir.path().join("deploy.steps"), "step deploy\nrun build\n").unwrap();
+    fs::write(dir.path().join("build.steps"), "step build\n").unwrap();
+    fs::write(dir.path().join("tool.py"), "def tool():\n    pass\n").unwrap();
+
+    let mut parser_factory = ParserFactory::new();
+    parser_factory.register("steps", Box::new(|| Ok(Box::new(StepParser))));
+    let mut analyzer = CodebaseAnalyzer::new()
+        .with_file_scanner(FileScanner::new().with_language_extensions("steps", &["steps"]))
+        .with_parser_factory(parser_factory);
+    let graph = analyzer.analyze(dir.path(), &["steps", "python"]).unwrap();
+
+    let mut names: Vec<&str> = graph.node_weights().map(|n| n.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, ["build", "deploy", "tool"]);
+
+    // Custom call sites go through the same resolver as the built-in languages
+    assert!(graph.edge_references().any(|e| {
+        e.weight().edge_type == EdgeType::Call
+            && graph[e.source()].name == "deploy"
+            && graph[e.target()].name == "build"
+    }));
+}
+
+#[test]
+fn import_edges_link_files_to_what_they_import() {
+    use embargo::core::EdgeType;
+    use petgraph::visit::EdgeRef;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let files = [
+        ("pkg/__init__.py", ""),
+        ("pkg/models.py", "class User:\n    pass\n"),
+        ("pkg/helpers.py", "def slug(s):\n    return s\n"),
+        (
+            "app.py",
+            "import os\nfrom pkg.models import User\nfrom pkg import helpers\nimport pkg\n",
+        ),
+        (
+            "pkg/views.py",
+            "from . import helpers\nfrom .models import User\n",
+        ),
+        ("web/util.ts", "export function fmt() {}\n"),
+        (
+            "web/main.ts",
+            "import { fmt } from './util';\nimport React from 'react';\n",
+        ),
+    ];
+    for (name, code) in files {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, code).unwrap();
+    }
+
+    let analyze = |import_edges: bool| {
+        CodebaseAnalyzer::new()
+            .with_import_edges(import_edges)
+            .analyze(dir.path(), &["python", "typescript"])
+            .unwrap()
+    };
+    let relative = |path: &std::path::Path| {
+        path.strip_prefix(dir.path())
+            .unwrap()
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+
+    let graph = analyze(true);
+    let mut imports: Vec<(String, String)> = graph
+        .edge_references()
+        .filter(|e| e.weight().edge_type == EdgeType::Import)
+        .map(|e| {
+            let target = &graph[e.target()];
+            // Local targets are file nodes; anything else stays on the import statement
+            let target = if target.line_number == 0 {
+                relative(&target.file_path)
+            } else {
+                target.name.clone()
+            };
+            (relative(&graph[e.source()].file_path), target)
+        })
+        .collect();
+    imports.sort();
+    let expected = [
+        ("app.py", "import os"),
+        ("app.py", "pkg/__init__.py"),
+        ("app.py", "pkg/helpers.py"),
+        ("app.py", "pkg/models.py"),
+        ("pkg/views.py", "pkg/helpers.py"),
+        ("pkg/views.py", "pkg/models.py"),
+        ("web/main.ts", "import React from 'react';"),
+        ("web/main.ts", "web/util.ts"),
+    ];
+    assert_eq!(
+        imports,
+        expected
+            .iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect::<Vec<_>>()
+    );
+
+    let without = analyze(false);
+    assert!(without
+        .edge_weights()
+        .all(|e| e.edge_type != EdgeType::Import));
+    // One node per file taking part in an import
+    assert_eq!(without.node_count() + 7, graph.node_count());
+}
+
+#[test]
+fn composition_edges_link_classes_to_the_types_they_hold() {
+    use embargo::core::EdgeType;
+    use petgraph::visit::EdgeRef;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let files = [
+        (
+            "orders/OrderRepository.java",
+            "public class OrderRepository {}\n",
+        ),
+        ("orders/Clock.java", "public class Clock {}\n"),
+        ("billing/Clock.java", "public class Clock {}\n"),
+        ("billing/Invoice.java", "public class Invoice {}\n"),
+        (
+            "orders/OrderService.java",
+            r#"public class OrderService {
+    private OrderRepository repo;
+    private OrderRepository backup;
+    private Clock clock;
+    private Invoice last;
+    private String name;
+}
+"#,
+        ),
+        (
+            "web/Page.ts",
+            r#"class Page {
+  constructor(private service: Renderer) {}
+}
+class Renderer {}
+"#,
+        ),
+    ];
+    for (name, code) in files {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, code).unwrap();
+    }
+
+    let analyze = |composition_edges: bool| {
+        CodebaseAnalyzer::new()
+            .with_composition_edges(composition_edges)
+            .analyze(dir.path(), &["java", "typescript"])
+            .unwrap()
+    };
+    let relative = |path: &std::path::Path| {
+        path.strip_prefix(dir.path())
+            .unwrap()
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+
+    let graph = analyze(true);
+    let mut held: Vec<(String, String, Option<usize>)> = graph
+        .edge_references()
+        .filter(|e| e.weight().edge_type == EdgeType::Composes)
+        .map(|e| {
+            (
+                graph[e.source()].name.clone(),
+                relative(&graph[e.target()].file_path),
+                e.weight().line,
+            )
+        })
+        .collect();
+    held.sort();
+    // One edge per held type, from its first field; the Clock next to the service wins
+    // and String, declared nowhere, is dropped
+    let expected = [
+        ("OrderService", "billing/Invoice.java", Some(5)),
+        ("OrderService", "orders/Clock.java", Some(4)),
+        ("OrderService", "orders/OrderRepository.java", Some(2)),
+        ("Page", "web/Page.ts", Some(2)),
+    ];
+    assert_eq!(
+        held,
+        expected
+            .iter()
+            .map(|(a, b, line)| (a.to_string(), b.to_string(), *line))
+            .collect::<Vec<_>>()
+    );
+
+    assert!(analyze(false)
+        .edge_weights()
+        .all(|e| e.edge_type != EdgeType::Composes));
+}
+
+#[test]
+fn repeated_analyses_render_byte_identical_output() {
+    use embargo::core::DependencyGraphExt;
+    use embargo::formatters::{
+        CsvFormatter, EmbargoFormatter, GraphMlFormatter, HtmlFormatter, JsonCompactFormatter,
+        JsonFullFormatter, MermaidFormatter, SymbolsFormatter, TreeFormatter,
+    };
+    use embargo::parsers::cache::ParseCache;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let files = [
+        (
+            "app/main.py",
+            r#"from app.models import User
+
+def main():
+    user = User()
+    user.save()
+    audit(user)
+
+def audit(user):
+    print(user)
+"#,
+        ),
+        (
+            "app/models.py",
+            r#"class Base:
+    def save(self):
+        pass
+
+class User(Base):
+    def save(self):
+        validate(self)
+
+def validate(obj):
+    raise ValueError()
+"#,
+        ),
+        (
+            "app/util.py",
+            r#"def helper():
+    return validate(1)
+
+def validate(x):
+    return x
+"#,
+        ),
+        (
+            "svc/Order.java",
+            r#"public class Order {
+    private Repo repo;
+    void place() { repo.save(); check(); }
+    void check() {}
+}
+"#,
+        ),
+        (
+            "svc/Repo.java",
+            "public class Repo {\n    void save() {}\n}\n",
+        ),
+        (
+            "web/page.ts",
+            r#"class Page {
+  render() { return format(1); }
+}
+function format(x: number) { return x; }
+"#,
+        ),
+        (
+            "lib/core.rs",
+            r#"pub struct Engine;
+impl Engine {
+    pub fn run(&self) { step(); step(); }
+}
+fn step() {}
+"#,
+        ),
+    ];
+    for (name, code) in files {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, code).unwrap();
+    }
+
+    let render = || {
+        let graph = CodebaseAnalyzer::new()
+            .with_parse_cache(ParseCache::in_memory_only())
+            .with_composition_edges(true)
+            .with_import_edges(true)
+            .analyze(dir.path(), &["python", "java", "typescript", "rust"])
+            .unwrap();
+        [
+            ("graph", graph.to_json().unwrap()),
+            (
+                "llm-optimized",
+                LLMOptimizedFormatter::new()
+                    .format_to_string(&graph)
+                    .unwrap(),
+            ),
+            (
+                "markdown",
+                EmbargoFormatter::new().format_to_string(&graph).unwrap(),
+            ),
+            (
+                "json",
+                JsonFullFormatter::new().format_to_string(&graph).unwrap(),
+            ),
+            (
+                "json-compact",
+                JsonCompactFormatter::new()
+                    .format_to_string(&graph)
+                    .unwrap(),
+            ),
+            (
+                "mermaid",
+                MermaidFormatter::new().format_to_string(&graph).unwrap(),
+            ),
+            ("csv", CsvFormatter::new().format_to_string(&graph).unwrap()),
+            (
+                "html",
+                HtmlFormatter::new().format_to_string(&graph).unwrap(),
+            ),
+            (
+                "graphml",
+                GraphMlFormatter::new().format_to_string(&graph).unwrap(),
+            ),
+            (
+                "tree",
+                TreeFormatter::new().format_to_string(&graph).unwrap(),
+            ),
+            (
+                "symbols",
+                SymbolsFormatter::new().format_to_string(&graph).unwrap(),
+            ),
+        ]
+    };
+
+    let first = render();
+    for _ in 0..5 {
+        let again = render();
+        for ((format, expected), (_, actual)) in first.iter().zip(&again) {
+            assert_eq!(expected, actual, "{} output differs between runs", format);
+        }
+    }
+}
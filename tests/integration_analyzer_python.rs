@@ -20,3 +20,343 @@ fn analyzer_on_python_app_directory() {
     let s = std::fs::read_to_string(&out).unwrap();
     assert!(s.contains("# CODE_GRAPH"));
 }
+
+#[test]
+fn analyzer_links_overriding_methods_to_nearest_parent() {
+    use embargo::core::EdgeType;
+    use petgraph::visit::EdgeRef;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("shapes.py"),
+        r#"
+class Shape:
+    def area(self):
+        return 0
+
+    def name(self):
+        return "shape"
+
+class Rect(Shape):
+    def area(self):
+        return 1
+
+class Square(Rect):
+    def area(self):
+        return 2
+
+    def name(self):
+        return "square"
+
+class Widget(Base):
+    def area(self):
+        return 3
+"#,
+    )
+    .unwrap();
+
+    let graph = CodebaseAnalyzer::new()
+        .analyze(dir.path(), &["python"])
+        .unwrap();
+
+    let mut overrides: Vec<(String, String)> = graph
+        .edge_references()
+        .filter(|e| e.weight().edge_type == EdgeType::Overrides)
+        .map(|e| {
+            let owner = |idx| {
+                graph
+                    .edges_directed(idx, petgraph::Direction::Incoming)
+                    .find(|c| c.weight().edge_type == EdgeType::Contains)
+                    .map(|c| graph[c.source()].name.clone())
+                    .unwrap()
+            };
+            (
+                format!("{}.{}", owner(e.source()), graph[e.source()].name),
+                format!("{}.{}", owner(e.target()), graph[e.target()].name),
+            )
+        })
+        .collect();
+    overrides.sort();
+
+    // Square.area stops at Rect; Square.name skips Rect, which does not define it.
+    // Widget's parent is not in the graph, so nothing is linked.
+    let expected = [
+        ("Rect.area", "Shape.area"),
+        ("Square.area", "Rect.area"),
+        ("Square.name", "Shape.name"),
+    ];
+    assert_eq!(
+        overrides,
+        expected
+            .iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn analyzer_resolves_simple_calls_to_the_imported_module() {
+    use embargo::core::EdgeType;
+    use petgraph::visit::EdgeRef;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let files = [
+        ("alpha.py", "def process():\n    return 'alpha'\n"),
+        ("beta.py", "def process():\n    return 'beta'\n"),
+        (
+            "main.py",
+            "from beta import process\n\ndef run():\n    process()\n",
+        ),
+        (
+            "local.py",
+            "def process():\n    return 'local'\n\ndef run_local():\n    process()\n",
+        ),
+    ];
+    for (name, code) in files {
+        std::fs::write(dir.path().join(name), code).unwrap();
+    }
+
+    let graph = CodebaseAnalyzer::new()
+        .analyze(dir.path(), &["python"])
+        .unwrap();
+
+    let callee_file = |caller: &str| {
+        let call = graph
+            .edge_references()
+            .find(|e| e.weight().edge_type == EdgeType::Call && graph[e.source()].name == caller)
+            .unwrap();
+        graph[call.target()]
+            .file_path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    // Only beta is imported; alpha's process is an unrelated namesake
+    assert_eq!(callee_file("run"), "beta.py");
+    // A definition in the caller's own file wins over any other
+    assert_eq!(callee_file("run_local"), "local.py");
+}
+
+#[test]
+fn stable_ids_survive_line_shifts() {
+    use embargo::core::DependencyGraphExt;
+
+    let code = r#"class Cache:
+    def get(self, key):
+        return load(key)
+
+def load(key):
+    return key
+
+def load(key, default):
+    return default
+"#;
+    let ids = |source: &str, stable: bool| {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("cache.py"), source).unwrap();
+        let mut graph = CodebaseAnalyzer::new()
+            .analyze(dir.path(), &["python"])
+            .unwrap();
+        if stable {
+            graph.stabilize_ids();
+        }
+        let root = dir.path().to_string_lossy().replace(['/', '\\'], "_");
+        let mut ids: Vec<String> = graph
+            .node_weights()
+            .map(|n| n.id.replace(&root, "ROOT"))
+            .collect();
+        ids.sort();
+        // Edge endpoints follow the new ids
+        for edge in graph.edge_weights() {
+            assert!(graph.node_weights().any(|n| n.id == edge.source_id));
+            assert!(graph.node_weights().any(|n| n.id == edge.target_id));
+        }
+        ids
+    };
+
+    let shifted = format!("\n\n{}", code);
+    assert_ne!(ids(code, false), ids(&shifted, false));
+
+    let stable = ids(code, true);
+    assert_eq!(stable, ids(&shifted, true));
+    assert_eq!(
+        stable,
+        [
+            "ROOT_cache.py:class:Cache",
+            "ROOT_cache.py:function:Cache.get",
+            "ROOT_cache.py:function:load",
+            "ROOT_cache.py:function:load#2",
+        ]
+    );
+}
+
+#[test]
+fn cached_callers_are_re_resolved_when_definitions_change() {
+    use embargo::core::EdgeType;
+    use embargo::parsers::cache::ParseCache;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let cache_file = dir.path().join(".embargo-cache");
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(
+        src.join("main.py"),
+        "def run():\n    helper()\n    other()\n",
+    )
+    .unwrap();
+
+    let call_edges = |cache: ParseCache| {
+        let mut analyzer = CodebaseAnalyzer::new().with_parse_cache(cache);
+        let graph = analyzer.analyze(&src, &["python"]).unwrap();
+        let mut edges: Vec<String> = graph
+            .edge_weights()
+            .filter(|e| e.edge_type == EdgeType::Call)
+            .map(|e| format!("{} -> {}", e.source_id, e.target_id))
+            .collect();
+        edges.sort();
+        edges
+    };
+    // Each step reuses the cache from the previous one; main.py itself never changes,
+    // yet its calls must match what a from-scratch analysis finds
+    let check = |util: &str| {
+        std::fs::write(src.join("util.py"), util).unwrap();
+        let incremental = call_edges(ParseCache::from_file(cache_file.clone()));
+        assert_eq!(incremental, call_edges(ParseCache::in_memory_only()));
+        incremental
+    };
+
+    let before = check("def other():\n    pass\n");
+    assert_eq!(before.len(), 1);
+
+    // A new definition gives the cached caller a target
+    let added = check("def other():\n    pass\n\ndef helper():\n    pass\n");
+    assert_eq!(added.len(), 2);
+
+    // Moving it changes its id, which the cached edge must follow
+    let moved = check("\n\ndef other():\n    pass\n\ndef helper():\n    pass\n");
+    assert_eq!(moved.len(), 2);
+    assert!(moved.iter().all(|edge| !added.contains(edge)));
+
+    // Removing it leaves the call unresolved again
+    let removed = check("def other():\n    return 1\n");
+    assert_eq!(removed.len(), 1);
+}
+
+#[test]
+fn analyzer_handles_non_ascii_identifiers() {
+    use embargo::core::EdgeType;
+    use embargo::formatters::OutputVerbosity;
+    use petgraph::visit::EdgeRef;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("señal.py"),
+        r#"
+class Señal:
+    """Señal — documentación, 日本語"""
+
+    def método(self, ñ):
+        return ñ
+
+    def llamar(self):
+        return self.método("héllo")
+
+def größe(ä):
+    # Near misses like this one go through fuzzy matching
+    return grösse(ä)
+"#,
+    )
+    .unwrap();
+
+    let mut analyzer = CodebaseAnalyzer::new();
+    let graph = analyzer.analyze(dir.path(), &["python"]).unwrap();
+
+    let calls: Vec<(&str, &str)> = graph
+        .edge_references()
+        .filter(|e| e.weight().edge_type == EdgeType::Call)
+        .map(|e| {
+            (
+                graph[e.source()].name.as_str(),
+                graph[e.target()].name.as_str(),
+            )
+        })
+        .collect();
+    assert!(calls.contains(&("llamar", "método")), "{:?}", calls);
+    assert!(calls.contains(&("größe", "größe")), "{:?}", calls);
+
+    let s = LLMOptimizedFormatter::new()
+        .with_verbosity(OutputVerbosity::Compact)
+        .with_docstrings(true)
+        .format_to_string(&graph)
+        .unwrap();
+    assert!(s.contains("// \"Señal — documentación, 日本語\""), "{}", s);
+}
+
+#[test]
+fn analyzer_merges_external_nodes_shared_across_files() {
+    use petgraph::Direction;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    for (name, class) in [("a.py", "Alpha"), ("b.py", "Beta"), ("c.py", "Gamma")] {
+        std::fs::write(
+            dir.path().join(name),
+            format!("class {}(Logger):\n    pass\n", class),
+        )
+        .unwrap();
+    }
+
+    let graph = CodebaseAnalyzer::new()
+        .analyze(dir.path(), &["python"])
+        .unwrap();
+
+    let loggers: Vec<_> = graph
+        .node_indices()
+        .filter(|&idx| graph[idx].id == "external:class:Logger:0")
+        .collect();
+    assert_eq!(loggers.len(), 1);
+    // Every subclass points at the one remaining node
+    assert_eq!(
+        graph
+            .edges_directed(loggers[0], Direction::Incoming)
+            .count(),
+        3
+    );
+}
+
+#[test]
+fn relativized_graphs_match_across_checkouts() {
+    use embargo::core::{DependencyGraphExt, EdgeType};
+    use std::path::Path;
+
+    let graph = |checkout: &Path| {
+        std::fs::create_dir_all(checkout.join("db")).unwrap();
+        std::fs::write(checkout.join("app.py"), "def main():\n    query()\n").unwrap();
+        std::fs::write(checkout.join("db/repo.py"), "def query():\n    pass\n").unwrap();
+        let mut graph = CodebaseAnalyzer::new()
+            .analyze(checkout, &["python"])
+            .unwrap();
+        graph.relativize_paths(checkout);
+        graph
+    };
+
+    let checkouts = [
+        tempfile::TempDir::new().unwrap(),
+        tempfile::TempDir::new().unwrap(),
+    ];
+    let first = graph(checkouts[0].path());
+    assert_eq!(
+        first.to_json().unwrap(),
+        graph(checkouts[1].path()).to_json().unwrap()
+    );
+
+    let query = first.node_weights().find(|n| n.name == "query").unwrap();
+    assert_eq!(query.file_path, Path::new("db/repo.py"));
+    assert_eq!(query.id, "db_repo.py:function:query:1");
+    let call = first
+        .edge_weights()
+        .find(|e| e.edge_type == EdgeType::Call)
+        .unwrap();
+    assert_eq!(call.target_id, query.id);
+}
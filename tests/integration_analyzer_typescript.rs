@@ -20,3 +20,44 @@ fn analyzer_on_typescript_app_directory() {
     let s = std::fs::read_to_string(&out).unwrap();
     assert!(s.contains("# CODE_GRAPH"));
 }
+
+#[test]
+fn collapse_accessors_merges_get_and_set_of_a_property() {
+    use embargo::formatters::OutputVerbosity;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("cart.ts"),
+        r#"
+class Cart {
+  private items: number[] = [];
+  get total() { return sum(this.items); }
+  set total(value: number) { this.items = [value]; }
+  get size() { return this.items.length; }
+}
+function sum(xs: number[]) { return 0; }
+"#,
+    )
+    .unwrap();
+
+    let graph = CodebaseAnalyzer::new()
+        .analyze(dir.path(), &["typescript"])
+        .unwrap();
+    let render = |collapse: bool| {
+        LLMOptimizedFormatter::new()
+            .with_verbosity(OutputVerbosity::Compact)
+            .with_collapse_accessors(collapse)
+            .format_to_string(&graph)
+            .unwrap()
+    };
+
+    let collapsed = render(true);
+    assert!(collapsed.contains("total{get,set}→{sum}"), "{}", collapsed);
+    assert!(!collapsed.contains("set total"), "{}", collapsed);
+    // A getter without a setter stays as it is
+    assert!(collapsed.contains("get size("), "{}", collapsed);
+
+    let separate = render(false);
+    assert!(separate.contains("get total("), "{}", separate);
+    assert!(separate.contains("set total("), "{}", separate);
+}
@@ -1,6 +1,8 @@
 use embargo::parsers::rust::RustParser;
 use embargo::parsers::{cache::ParseCache, LanguageParser};
+use std::collections::HashSet;
 use std::fs;
+use std::path::PathBuf;
 use std::time::Duration;
 
 #[test]
@@ -32,3 +34,65 @@ fn parse_cache_stores_and_detects_updates() {
     cache.store(&file, &new_result).unwrap();
     assert!(cache.get(&file).is_some());
 }
+
+#[test]
+fn file_backed_cache_round_trips_and_prunes_deleted_files() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let kept = dir.path().join("kept.rs");
+    let deleted = dir.path().join("deleted.rs");
+    fs::write(&kept, "fn kept() {}\n").unwrap();
+    fs::write(&deleted, "fn gone() {}\n").unwrap();
+    let cache_file = dir.path().join(".embargo-cache");
+
+    let parser = RustParser::new().unwrap();
+    let cache = ParseCache::from_file(cache_file.clone());
+    cache
+        .store(&kept, &parser.parse_file(&kept).unwrap())
+        .unwrap();
+    cache
+        .store(&deleted, &parser.parse_file(&deleted).unwrap())
+        .unwrap();
+    cache.persist().unwrap();
+
+    // A fresh load sees both entries as up to date
+    let reloaded = ParseCache::from_file(cache_file.clone());
+    assert!(!reloaded.needs_update(&kept).unwrap());
+    assert!(reloaded.get(&deleted).is_some());
+
+    fs::remove_file(&deleted).unwrap();
+    let live: HashSet<PathBuf> = [kept.clone()].into_iter().collect();
+    reloaded.retain_files(&live);
+    reloaded.persist().unwrap();
+
+    let pruned = ParseCache::from_file(cache_file);
+    assert!(pruned.get(&kept).is_some());
+    assert!(pruned.get(&deleted).is_none());
+}
+
+#[test]
+fn memory_cache_evicts_past_its_capacity() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let cache = ParseCache::in_memory_only();
+    let parser = RustParser::new().unwrap();
+
+    // One past the 1000-entry memory limit forces an eviction
+    let files: Vec<PathBuf> = (0..1001)
+        .map(|i| {
+            let file = dir.path().join(format!("f{}.rs", i));
+            fs::write(&file, format!("fn f{}() {{}}\n", i)).unwrap();
+            file
+        })
+        .collect();
+    for file in &files {
+        cache
+            .store(file, &parser.parse_file(file).unwrap())
+            .unwrap();
+    }
+
+    assert!(cache.get(&files[1000]).is_some());
+    let cached = files
+        .iter()
+        .filter(|file| cache.get(file).is_some())
+        .count();
+    assert_eq!(cached, 1000);
+}
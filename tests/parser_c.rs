@@ -0,0 +1,77 @@
+use embargo::core::{EdgeType, NodeType};
+use embargo::parsers::c::CParser;
+use embargo::parsers::LanguageParser;
+use std::fs;
+
+#[test]
+fn c_parser_extracts_functions_records_and_typedefs() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("board.h");
+    let code = r#"#ifndef BOARD_H
+#define BOARD_H
+#include <stdint.h>
+
+typedef int (*irq_handler)(int line, void *ctx);
+
+typedef struct {
+    uint32_t base;
+    irq_handler on_irq;
+} board_t;
+
+enum led { LED_RED, LED_GREEN };
+
+static int *scratch(unsigned n) { return 0; }
+
+int board_init(board_t *board) {
+    scratch(4);
+    return 0;
+}
+#endif
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = CParser::new().unwrap().parse_file(&file).unwrap();
+    let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+
+    assert_eq!(find("stdint.h").node_type, NodeType::Module);
+
+    // Function-pointer typedefs are a named type, not a function called `irq_handler`
+    let handler = find("irq_handler");
+    assert_eq!(handler.node_type, NodeType::Class);
+    assert_eq!(
+        handler.signature.as_deref(),
+        Some("typedef int (*irq_handler)(int line, void *ctx)")
+    );
+
+    let board = find("board_t");
+    assert_eq!(board.node_type, NodeType::Class);
+    assert_eq!(board.signature.as_deref(), Some("struct board_t"));
+    let fields: Vec<&str> = result
+        .edges
+        .iter()
+        .filter(|e| e.edge_type == EdgeType::Contains && e.source_id == board.id)
+        .map(|e| e.target_id.as_str())
+        .collect();
+    assert_eq!(
+        fields,
+        vec![find("base").id.as_str(), find("on_irq").id.as_str()]
+    );
+
+    assert_eq!(find("led").node_type, NodeType::Enum);
+
+    let scratch = find("scratch");
+    assert_eq!(scratch.node_type, NodeType::Function);
+    assert_eq!(scratch.visibility.as_deref(), Some("private"));
+    assert_eq!(
+        scratch.signature.as_deref(),
+        Some("static int *scratch(unsigned n)")
+    );
+    assert_eq!(find("board_init").visibility.as_deref(), Some("public"));
+
+    let call_sites = result.call_sites.unwrap();
+    let call = call_sites
+        .iter()
+        .find(|c| c.called_name == "scratch")
+        .unwrap();
+    assert_eq!(call.caller_id, find("board_init").id);
+}
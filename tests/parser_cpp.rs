@@ -0,0 +1,128 @@
+use embargo::core::{EdgeType, FunctionResolver, Lifecycle};
+use embargo::parsers::cpp::CppParser;
+use embargo::parsers::LanguageParser;
+use std::fs;
+
+#[test]
+fn cpp_parser_marks_constructors_and_destructors() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("widget.cpp");
+    let code = r#"class Widget {
+public:
+    Widget(int size) : size_(size) {}
+    ~Widget();
+    void release() {}
+private:
+    int size_;
+};
+
+Widget::~Widget() { release(); }
+
+void build() {
+    Widget* w = new Widget(3);
+    delete w;
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = CppParser::new().unwrap().parse_file(&file).unwrap();
+    let lifecycle = |name: &str| -> Vec<(usize, Option<Lifecycle>)> {
+        result
+            .nodes
+            .iter()
+            .filter(|n| n.name == name)
+            .map(|n| (n.line_number, n.lifecycle))
+            .collect()
+    };
+
+    assert_eq!(
+        lifecycle("Widget")[1..],
+        [(3, Some(Lifecycle::Constructor))]
+    );
+    assert_eq!(
+        lifecycle("~Widget"),
+        [
+            (4, Some(Lifecycle::Destructor)),
+            (10, Some(Lifecycle::Destructor))
+        ]
+    );
+    assert_eq!(lifecycle("release"), [(5, None)]);
+
+    // The out-of-line destructor belongs to the class like the inline members
+    let class = result.nodes.iter().find(|n| n.name == "Widget").unwrap();
+    let members = result
+        .edges
+        .iter()
+        .filter(|e| e.source_id == class.id)
+        .count();
+    assert_eq!(members, 4);
+
+    let mut resolver = FunctionResolver::new();
+    resolver
+        .build_indexes_with_edges(&result.nodes, &result.edges)
+        .unwrap();
+    let edges = resolver.resolve_calls(&result.call_sites.unwrap());
+    let constructor = result
+        .nodes
+        .iter()
+        .find(|n| n.lifecycle == Some(Lifecycle::Constructor))
+        .unwrap();
+    assert!(edges.iter().any(|e| e.target_id == constructor.id));
+}
+
+#[test]
+fn cpp_parser_links_template_arguments_in_fields_and_parameters() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("registry.h");
+    let code = r#"class User {};
+
+template <typename T>
+class Registry {
+    T fallback;
+    std::vector<User> users;
+    std::map<std::string, Session*> sessions;
+public:
+    void add(const User& user, std::shared_ptr<Session> session);
+};
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = CppParser::new().unwrap().parse_file(&file).unwrap();
+    let id = |name: &str| {
+        result
+            .nodes
+            .iter()
+            .find(|n| n.name == name)
+            .unwrap()
+            .id
+            .clone()
+    };
+    let uses = |source_id: &str| -> Vec<String> {
+        result
+            .edges
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Uses && e.source_id == source_id)
+            .map(|e| e.target_id.clone())
+            .collect()
+    };
+
+    // The template parameter is not a dependency; `User` is declared here
+    assert_eq!(
+        uses(&id("Registry")),
+        [
+            "external:class:vector:0".to_string(),
+            id("User"),
+            "external:class:map:0".to_string(),
+            "external:class:string:0".to_string(),
+            "external:class:Session:0".to_string(),
+        ]
+    );
+    assert_eq!(
+        uses(&id("add")),
+        [
+            id("User"),
+            "external:class:shared_ptr:0".to_string(),
+            "external:class:Session:0".to_string(),
+        ]
+    );
+}
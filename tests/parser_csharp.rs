@@ -0,0 +1,100 @@
+use embargo::core::{EdgeType, NodeType};
+use embargo::parsers::csharp::CSharpParser;
+use embargo::parsers::LanguageParser;
+use std::fs;
+
+#[test]
+fn csharp_parser_names_methods_and_links_signature_types() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("Users.cs");
+    let code = r#"
+class User {}
+
+class UserService {
+    public User Find(Id id, List<Role>? roles, int limit) { return null; }
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = CSharpParser::new().unwrap().parse_file(&file).unwrap();
+    let user = result.nodes.iter().find(|n| n.name == "User").unwrap();
+    // The return type must not be mistaken for the method name
+    let find = result.nodes.iter().find(|n| n.name == "Find").unwrap();
+
+    let used: Vec<&str> = result
+        .edges
+        .iter()
+        .filter(|e| e.edge_type == EdgeType::Uses && e.source_id == find.id)
+        .map(|e| e.target_id.as_str())
+        .collect();
+    assert_eq!(
+        used,
+        vec![
+            user.id.as_str(),
+            "external:class:Id:0",
+            "external:class:List:0",
+            "external:class:Role:0",
+        ]
+    );
+}
+
+#[test]
+fn csharp_parser_handles_records_async_methods_and_computed_properties() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("Orders.cs");
+    let code = r#"
+namespace Shop {
+    public record Order(string Id, decimal Total);
+
+    public class OrderService {
+        public async Task<Order> LoadAsync(string id) { return await repo.Get(id); }
+        public int Count { get; set; }
+        public Money Balance {
+            get { if (dirty) { Refresh(); } return balance; }
+            private set { balance = value; }
+        }
+        public string Label => "orders";
+    }
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = CSharpParser::new().unwrap().parse_file(&file).unwrap();
+    let node = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+
+    let order = node("Order");
+    assert_eq!(order.node_type, NodeType::Class);
+    assert_eq!(
+        order.signature.as_deref(),
+        Some("record Order(string Id, decimal Total)")
+    );
+    assert!(node("LoadAsync").is_async);
+
+    // Auto-properties stay state; accessors with bodies become functions
+    assert_eq!(node("Count").node_type, NodeType::Variable);
+    assert!(result.nodes.iter().all(|n| n.name != "Balance"));
+    let getter = node("get_Balance");
+    let setter = node("set_Balance");
+    assert_eq!(getter.node_type, NodeType::Function);
+    assert_eq!(getter.complexity, 1);
+    assert_eq!(getter.visibility.as_deref(), Some("public"));
+    assert_eq!(setter.visibility.as_deref(), Some("private"));
+    assert_eq!(node("get_Label").node_type, NodeType::Function);
+
+    let has_edge = |edge_type: EdgeType, source: &str, target: &str| {
+        result
+            .edges
+            .iter()
+            .any(|e| e.edge_type == edge_type && e.source_id == source && e.target_id == target)
+    };
+    assert!(has_edge(
+        EdgeType::Contains,
+        &node("OrderService").id,
+        &getter.id
+    ));
+    assert!(has_edge(
+        EdgeType::Uses,
+        &getter.id,
+        "external:class:Money:0"
+    ));
+}
@@ -0,0 +1,135 @@
+use embargo::core::{EdgeType, NodeType};
+use embargo::parsers::go::GoParser;
+use embargo::parsers::LanguageParser;
+use std::fs;
+
+#[test]
+fn go_parser_separates_structs_enums_and_aliases() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("shapes.go");
+    let code = r#"package shapes
+
+type Color int
+
+const (
+	Red Color = iota
+	Blue
+)
+
+type (
+	Point struct {
+		X, Y int
+	}
+	Shape interface {
+		Area() float64
+	}
+)
+
+type Name = string
+
+type Handler func(Point) error
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = GoParser::new().unwrap().parse_file(&file).unwrap();
+    let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+
+    assert_eq!(find("Color").node_type, NodeType::Enum);
+    assert_eq!(find("Shape").node_type, NodeType::Interface);
+
+    let point = find("Point");
+    assert_eq!(point.node_type, NodeType::Class);
+    assert_eq!(point.signature.as_deref(), Some("struct Point"));
+
+    let alias = find("Name");
+    assert_eq!(alias.node_type, NodeType::Class);
+    assert_eq!(alias.signature.as_deref(), Some("type Name = string"));
+
+    assert_eq!(
+        find("Handler").signature.as_deref(),
+        Some("type Handler func(Point) error")
+    );
+}
+
+#[test]
+fn go_parser_attaches_value_and_pointer_receivers_to_local_structs() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("user.go");
+    let code = r#"package users
+
+type User struct {
+	Name string
+}
+
+func (u User) Greeting(prefix string) string {
+	return prefix + u.Name
+}
+
+func (u *User) Rename(name string) {
+	u.Name = name
+}
+
+func (Session) Close() {}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = GoParser::new().unwrap().parse_file(&file).unwrap();
+    let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+    let user_id = &find("User").id;
+    let container_of = |name: &str| {
+        let method_id = &find(name).id;
+        result
+            .edges
+            .iter()
+            .find(|e| e.edge_type == EdgeType::Contains && &e.target_id == method_id)
+            .map(|e| e.source_id.clone())
+            .unwrap()
+    };
+
+    assert_eq!(&container_of("Greeting"), user_id);
+    assert_eq!(&container_of("Rename"), user_id);
+    assert_eq!(container_of("Close"), "external:struct:Session:0");
+    // The signature lists the method's parameters, not the receiver
+    let signature = find("Greeting").signature.clone().unwrap();
+    assert!(signature.contains("prefix string"));
+    assert!(!signature.contains("u User"));
+}
+
+#[test]
+fn go_parser_links_structs_to_their_field_types() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("cache.go");
+    let code = r#"package cache
+
+type Store struct{}
+
+type Cache[V any] struct {
+    *Base
+    store Store
+    entries map[string]Entry
+    hits, misses int
+    last V
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = GoParser::new().unwrap().parse_file(&file).unwrap();
+    let cache = result.nodes.iter().find(|n| n.name == "Cache").unwrap();
+    let store = result.nodes.iter().find(|n| n.name == "Store").unwrap();
+    let held: Vec<(&str, Option<usize>)> = result
+        .edges
+        .iter()
+        .filter(|e| e.edge_type == EdgeType::Composes && e.source_id == cache.id)
+        .map(|e| (e.target_id.as_str(), e.line))
+        .collect();
+
+    // Embedded structs count; builtins and the type parameter do not
+    assert_eq!(
+        held,
+        vec![
+            ("external:class:Base:0", Some(6)),
+            (store.id.as_str(), Some(7)),
+            ("external:class:Entry:0", Some(8)),
+        ]
+    );
+}
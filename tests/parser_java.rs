@@ -0,0 +1,217 @@
+use embargo::core::{EdgeType, NodeType};
+use embargo::parsers::java::JavaParser;
+use embargo::parsers::LanguageParser;
+use std::fs;
+
+#[test]
+fn java_parser_links_methods_to_signature_types() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("UserRepository.java");
+    let code = r#"
+class Id {}
+
+class UserRepository {
+    public <T> User findUser(Id id, Map.Entry<String, T> hint, int limit) {
+        return null;
+    }
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = JavaParser::new().unwrap().parse_file(&file).unwrap();
+    let id_class = result.nodes.iter().find(|n| n.name == "Id").unwrap();
+    let find_user = result.nodes.iter().find(|n| n.name == "findUser").unwrap();
+
+    let mut used: Vec<&str> = result
+        .edges
+        .iter()
+        .filter(|e| e.edge_type == EdgeType::Uses && e.source_id == find_user.id)
+        .map(|e| e.target_id.as_str())
+        .collect();
+    used.sort();
+
+    // Local types resolve to their node; the generic `T` and primitives are skipped
+    assert_eq!(
+        used,
+        vec![
+            id_class.id.as_str(),
+            "external:class:Entry:0",
+            "external:class:String:0",
+            "external:class:User:0",
+        ]
+    );
+}
+
+#[test]
+fn java_parser_records_generics_and_annotations() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("UserService.java");
+    let code = r#"
+@Service
+@RequestMapping("/users")
+class UserService<T extends Entity> {
+    @Autowired
+    private Store store;
+
+    @Override
+    public <K> T find(K key) {
+        return null;
+    }
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = JavaParser::new().unwrap().parse_file(&file).unwrap();
+    let service = result
+        .nodes
+        .iter()
+        .find(|n| n.name == "UserService")
+        .unwrap();
+    let store = result.nodes.iter().find(|n| n.name == "store").unwrap();
+    let find = result.nodes.iter().find(|n| n.name == "find").unwrap();
+
+    assert_eq!(
+        service.signature.as_deref(),
+        Some("class UserService<T extends Entity>")
+    );
+    assert!(find.signature.as_deref().unwrap().starts_with("<K> find("));
+    assert_eq!(service.decorators, vec!["Service", "RequestMapping"]);
+    assert_eq!(store.decorators, vec!["Autowired"]);
+    assert_eq!(find.decorators, vec!["Override"]);
+
+    let annotation_uses = |id: &str| -> Vec<&str> {
+        result
+            .edges
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Uses && e.source_id == id)
+            .map(|e| e.target_id.as_str())
+            .filter(|target| target.starts_with("external:annotation:"))
+            .collect()
+    };
+    assert_eq!(
+        annotation_uses(&service.id),
+        vec![
+            "external:annotation:Service:0",
+            "external:annotation:RequestMapping:0"
+        ]
+    );
+    assert_eq!(
+        annotation_uses(&store.id),
+        vec!["external:annotation:Autowired:0"]
+    );
+    assert_eq!(
+        annotation_uses(&find.id),
+        vec!["external:annotation:Override:0"]
+    );
+}
+
+#[test]
+fn java_parser_marks_junit_test_methods() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("CartTest.java");
+    let code = r#"
+class CartTest {
+    @Test
+    void addsItem() {}
+
+    @org.junit.jupiter.params.ParameterizedTest
+    void removesItem(int id) {}
+
+    @BeforeEach
+    void setUp() {}
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = JavaParser::new().unwrap().parse_file(&file).unwrap();
+    let mut tests: Vec<&str> = result
+        .nodes
+        .iter()
+        .filter(|n| n.is_test)
+        .map(|n| n.name.as_str())
+        .collect();
+    tests.sort();
+    assert_eq!(tests, vec!["addsItem", "removesItem"]);
+}
+
+#[test]
+fn java_parser_links_methods_to_thrown_exceptions() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("Store.java");
+    let code = r#"
+class NotReady extends RuntimeException {}
+
+class Store {
+    void load(String path) throws IOException, java.sql.SQLException {
+        if (path == null) throw new IllegalArgumentException("path");
+        Runnable later = () -> { throw new IllegalStateException(); };
+        try { read(path); } catch (Exception e) { throw e; }
+    }
+
+    Store() { throw new NotReady(); }
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = JavaParser::new().unwrap().parse_file(&file).unwrap();
+    let thrown = |name: &str| -> Vec<String> {
+        let func = result
+            .nodes
+            .iter()
+            .find(|n| n.name == name && n.node_type == NodeType::Function)
+            .unwrap();
+        result
+            .edges
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Throws && e.source_id == func.id)
+            .map(|e| e.target_id.clone())
+            .collect()
+    };
+    let not_ready = result.nodes.iter().find(|n| n.name == "NotReady").unwrap();
+
+    // Declared and thrown types, not the lambda's or a rethrown variable
+    assert_eq!(
+        thrown("load"),
+        vec![
+            "external:class:IOException:0",
+            "external:class:SQLException:0",
+            "external:class:IllegalArgumentException:0",
+        ]
+    );
+    assert_eq!(thrown("Store"), vec![not_ready.id.clone()]);
+    // External exception types get a placeholder so the edges survive graph building
+    assert!(result
+        .nodes
+        .iter()
+        .any(|n| n.id == "external:class:IOException:0"));
+}
+
+#[test]
+fn java_parser_records_the_line_each_edge_comes_from() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("Admin.java");
+    let code = r#"public class Admin
+        extends User
+        implements Auditable {
+    private String role;
+
+    void audit() {}
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = JavaParser::new().unwrap().parse_file(&file).unwrap();
+    let line_of = |edge_type: EdgeType, target: &str| {
+        result
+            .edges
+            .iter()
+            .find(|e| e.edge_type == edge_type && e.target_id.contains(target))
+            .and_then(|e| e.line)
+    };
+
+    assert_eq!(line_of(EdgeType::Inheritance, "User"), Some(2));
+    assert_eq!(line_of(EdgeType::Implements, "Auditable"), Some(3));
+    // Members are placed where they are declared, not at the class header
+    assert_eq!(line_of(EdgeType::Contains, "audit"), Some(6));
+    assert!(result.edges.iter().all(|e| e.line.is_some()));
+}
@@ -0,0 +1,123 @@
+use embargo::core::resolver::CallType;
+use embargo::core::{EdgeType, NodeType};
+use embargo::parsers::kotlin::KotlinParser;
+use embargo::parsers::LanguageParser;
+use std::fs;
+
+#[test]
+fn kotlin_parser_extracts_packages_types_and_functions() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("Shapes.kt");
+    let code = r#"package com.example.shapes
+
+import kotlin.math.PI
+
+interface Greeter {
+    fun greet(name: String): String
+}
+
+data class Point(val x: Int, val y: Int)
+
+sealed class Shape
+
+class Circle(val r: Double) : Shape(), Greeter {
+    override fun greet(name: String): String = "circle " + name
+
+    private fun area(): Double = PI * r * r
+
+    companion object {
+        fun unit(): Circle = Circle(1.0)
+    }
+}
+
+object Registry : Greeter {
+    override fun greet(name: String) = name
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let parser = KotlinParser::new().unwrap();
+    let result = parser.parse_file(&file).unwrap();
+
+    assert!(result
+        .nodes
+        .iter()
+        .any(|n| n.node_type == NodeType::Module && n.name == "com.example.shapes"));
+    assert!(result
+        .nodes
+        .iter()
+        .any(|n| n.node_type == NodeType::Module && n.name == "import kotlin.math.PI"));
+
+    let greeter = result.nodes.iter().find(|n| n.name == "Greeter").unwrap();
+    assert_eq!(greeter.node_type, NodeType::Interface);
+    let point = result.nodes.iter().find(|n| n.name == "Point").unwrap();
+    assert_eq!(point.signature.as_deref(), Some("data class Point"));
+    let shape = result.nodes.iter().find(|n| n.name == "Shape").unwrap();
+    assert_eq!(shape.signature.as_deref(), Some("sealed class Shape"));
+
+    let circle = result.nodes.iter().find(|n| n.name == "Circle").unwrap();
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.edge_type == EdgeType::Inheritance
+            && e.source_id == circle.id
+            && e.target_id == "external:class:Shape:0"));
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.edge_type == EdgeType::Implements
+            && e.source_id == circle.id
+            && e.target_id == "external:interface:Greeter:0"));
+
+    let area = result.nodes.iter().find(|n| n.name == "area").unwrap();
+    assert_eq!(area.signature.as_deref(), Some("area(): Double"));
+    assert_eq!(area.visibility.as_deref(), Some("private"));
+    // Companion members belong to the enclosing class
+    let unit = result.nodes.iter().find(|n| n.name == "unit").unwrap();
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.edge_type == EdgeType::Contains
+            && e.source_id == circle.id
+            && e.target_id == unit.id));
+
+    let registry = result.nodes.iter().find(|n| n.name == "Registry").unwrap();
+    assert_eq!(registry.node_type, NodeType::Class);
+    assert_eq!(registry.signature.as_deref(), Some("object Registry"));
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.edge_type == EdgeType::Implements
+            && e.source_id == registry.id
+            && e.target_id == "external:interface:Greeter:0"));
+}
+
+#[test]
+fn kotlin_parser_classifies_method_constructor_and_simple_calls() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("service.kt");
+    let code = r#"fun run(name: String): String {
+    val user = User(name)
+    log(user)
+    return name.uppercase()
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let parser = KotlinParser::new().unwrap();
+    let result = parser.parse_file(&file).unwrap();
+    let call_sites = result.call_sites.unwrap();
+
+    let call_type = |name: &str| {
+        call_sites
+            .iter()
+            .find(|c| c.called_name == name)
+            .map(|c| c.call_type.clone())
+    };
+    assert_eq!(call_type("User"), Some(CallType::ConstructorCall));
+    assert_eq!(call_type("log"), Some(CallType::SimpleCall));
+    assert_eq!(call_type("uppercase"), Some(CallType::MethodCall));
+
+    let run = result.nodes.iter().find(|n| n.name == "run").unwrap();
+    assert!(call_sites.iter().all(|c| c.caller_id == run.id));
+}
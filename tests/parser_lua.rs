@@ -0,0 +1,111 @@
+use embargo::core::resolver::CallType;
+use embargo::core::{EdgeType, NodeType};
+use embargo::parsers::lua::LuaParser;
+use embargo::parsers::LanguageParser;
+use std::fs;
+
+#[test]
+fn lua_parser_extracts_requires_tables_and_functions() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("greeter.lua");
+    let code = r#"local json = require("json")
+local async = require "plenary.async"
+
+local M = {}
+
+function M.new(name)
+  return setmetatable({ name = name }, M)
+end
+
+function M:greet(other)
+  return self.name .. " " .. other
+end
+
+local function helper(x)
+  return M.new(x)
+end
+
+M.run = function()
+  helper("a")
+end
+
+function setup() end
+
+return M
+"#;
+    fs::write(&file, code).unwrap();
+
+    let parser = LuaParser::new().unwrap();
+    let result = parser.parse_file(&file).unwrap();
+
+    let imports: Vec<&str> = result
+        .nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Module && n.id.contains(":import:"))
+        .map(|n| n.name.as_str())
+        .collect();
+    assert_eq!(imports, vec!["json", "plenary.async"]);
+
+    // The table is anchored at its constructor, not its first function
+    let table = result
+        .nodes
+        .iter()
+        .find(|n| n.node_type == NodeType::Module && n.name == "M")
+        .unwrap();
+    assert_eq!(table.line_number, 4);
+
+    let new = result.nodes.iter().find(|n| n.name == "new").unwrap();
+    assert_eq!(new.signature.as_deref(), Some("M.new(name)"));
+    let greet = result.nodes.iter().find(|n| n.name == "greet").unwrap();
+    assert_eq!(greet.signature.as_deref(), Some("M:greet(other)"));
+    let run = result.nodes.iter().find(|n| n.name == "run").unwrap();
+    assert_eq!(run.signature.as_deref(), Some("M.run()"));
+    for member in [new, greet, run] {
+        assert!(result
+            .edges
+            .iter()
+            .any(|e| e.edge_type == EdgeType::Contains
+                && e.source_id == table.id
+                && e.target_id == member.id));
+    }
+
+    let helper = result.nodes.iter().find(|n| n.name == "helper").unwrap();
+    assert_eq!(helper.signature.as_deref(), Some("helper(x)"));
+    assert_eq!(helper.visibility.as_deref(), Some("private"));
+    let setup = result.nodes.iter().find(|n| n.name == "setup").unwrap();
+    assert_eq!(setup.visibility.as_deref(), Some("public"));
+    assert!(!result
+        .edges
+        .iter()
+        .any(|e| e.target_id == helper.id || e.target_id == setup.id));
+}
+
+#[test]
+fn lua_parser_classifies_function_and_method_calls() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("service.lua");
+    let code = r#"local function run(client, payload)
+  local body = encode(payload)
+  client:send(body)
+  return log.info("sent")
+end
+"#;
+    fs::write(&file, code).unwrap();
+
+    let parser = LuaParser::new().unwrap();
+    let result = parser.parse_file(&file).unwrap();
+    let call_sites = result.call_sites.unwrap();
+
+    let call_type = |name: &str| {
+        call_sites
+            .iter()
+            .find(|c| c.called_name == name)
+            .map(|c| c.call_type.clone())
+    };
+    assert_eq!(call_type("encode"), Some(CallType::SimpleCall));
+    assert_eq!(call_type("send"), Some(CallType::MethodCall));
+    assert_eq!(call_type("info"), Some(CallType::MethodCall));
+
+    let run = result.nodes.iter().find(|n| n.name == "run").unwrap();
+    assert!(call_sites.iter().all(|c| c.caller_id == run.id));
+}
@@ -0,0 +1,179 @@
+use embargo::core::resolver::CallType;
+use embargo::core::scanner::FileScanner;
+use embargo::core::{EdgeType, NodeType};
+use embargo::parsers::objc::ObjcParser;
+use embargo::parsers::LanguageParser;
+use std::fs;
+
+#[test]
+fn objc_parser_extracts_imports_interfaces_and_methods() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("User.h");
+    let code = r#"#import <Foundation/Foundation.h>
+#import "Base.h"
+
+@protocol Greeting <NSObject>
+- (NSString *)greet:(NSString *)name;
+@end
+
+@interface User : Base <Greeting>
+- (void)setName:(NSString *)name age:(int)age;
++ (instancetype)userWithName:(NSString *)name;
+- (void)reload;
+@end
+"#;
+    fs::write(&file, code).unwrap();
+
+    let parser = ObjcParser::new().unwrap();
+    let result = parser.parse_file(&file).unwrap();
+
+    let imports: Vec<&str> = result
+        .nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Module)
+        .map(|n| n.name.as_str())
+        .collect();
+    assert_eq!(imports, vec!["Foundation/Foundation.h", "Base.h"]);
+
+    let greeting = result.nodes.iter().find(|n| n.name == "Greeting").unwrap();
+    assert_eq!(greeting.node_type, NodeType::Interface);
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.edge_type == EdgeType::Inheritance
+            && e.source_id == greeting.id
+            && e.target_id == "external:interface:NSObject:0"));
+
+    let user = result.nodes.iter().find(|n| n.name == "User").unwrap();
+    assert_eq!(user.node_type, NodeType::Class);
+    assert_eq!(
+        user.signature.as_deref(),
+        Some("@interface User : Base <Greeting>")
+    );
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.edge_type == EdgeType::Inheritance
+            && e.source_id == user.id
+            && e.target_id == "external:class:Base:0"));
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.edge_type == EdgeType::Implements
+            && e.source_id == user.id
+            && e.target_id == greeting.id));
+
+    // Methods are named by their full selector; `-`/`+` tell instance and class methods apart
+    let set_name = result
+        .nodes
+        .iter()
+        .find(|n| n.name == "setName:age:")
+        .unwrap();
+    assert_eq!(
+        set_name.signature.as_deref(),
+        Some("- (void)setName:(NSString *)name age:(int)age")
+    );
+    let factory = result
+        .nodes
+        .iter()
+        .find(|n| n.name == "userWithName:")
+        .unwrap();
+    assert!(factory.signature.as_deref().unwrap().starts_with("+ "));
+    let reload = result.nodes.iter().find(|n| n.name == "reload").unwrap();
+    for method in [set_name, factory, reload] {
+        assert!(result
+            .edges
+            .iter()
+            .any(|e| e.edge_type == EdgeType::Contains
+                && e.source_id == user.id
+                && e.target_id == method.id));
+    }
+}
+
+#[test]
+fn objc_parser_merges_extensions_into_the_implementation_and_names_message_sends() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("User.m");
+    let code = r#"#import "User.h"
+
+@interface User ()
+- (void)reload;
+@end
+
+@implementation User
+
++ (instancetype)userWithName:(NSString *)name {
+    User *user = [[User alloc] init];
+    [user setName:name age:3];
+    return user;
+}
+
+- (void)setName:(NSString *)name age:(int)age {
+    [self reload];
+}
+
+- (void)reload {
+}
+
+@end
+"#;
+    fs::write(&file, code).unwrap();
+
+    let parser = ObjcParser::new().unwrap();
+    let result = parser.parse_file(&file).unwrap();
+
+    // The class extension and @implementation share one class node, and `reload` is
+    // only listed once, at its definition
+    let classes: Vec<_> = result
+        .nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Class)
+        .collect();
+    assert_eq!(classes.len(), 1);
+    let reloads: Vec<_> = result.nodes.iter().filter(|n| n.name == "reload").collect();
+    assert_eq!(reloads.len(), 1);
+    assert_eq!(reloads[0].line_number, 19);
+
+    let call_sites = result.call_sites.unwrap();
+    let factory = result
+        .nodes
+        .iter()
+        .find(|n| n.name == "userWithName:")
+        .unwrap();
+    for selector in ["alloc", "init", "setName:age:"] {
+        let call = call_sites
+            .iter()
+            .find(|c| c.called_name == selector)
+            .unwrap();
+        assert_eq!(call.call_type, CallType::MethodCall);
+        assert_eq!(call.caller_id, factory.id);
+    }
+    let set_name = result
+        .nodes
+        .iter()
+        .find(|n| n.name == "setName:age:")
+        .unwrap();
+    assert!(call_sites
+        .iter()
+        .any(|c| c.called_name == "reload" && c.caller_id == set_name.id));
+}
+
+#[test]
+fn objc_headers_stay_with_c_when_both_languages_are_requested() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    fs::write(root.join("User.h"), "@interface User\n@end\n").unwrap();
+    fs::write(root.join("User.m"), "@implementation User\n@end\n").unwrap();
+
+    let header_language = |languages: &[&str]| {
+        FileScanner::new()
+            .scan_directory(root, languages)
+            .unwrap()
+            .into_iter()
+            .find(|f| f.path.ends_with("User.h"))
+            .map(|f| f.language)
+    };
+    assert_eq!(header_language(&["objc"]).as_deref(), Some("objc"));
+    assert_eq!(header_language(&["c", "objc"]).as_deref(), Some("c"));
+    assert_eq!(header_language(&["objc", "c"]).as_deref(), Some("c"));
+}
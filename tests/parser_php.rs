@@ -0,0 +1,137 @@
+use embargo::core::resolver::CallType;
+use embargo::core::{EdgeType, NodeType};
+use embargo::parsers::php::PhpParser;
+use embargo::parsers::LanguageParser;
+use std::fs;
+
+#[test]
+fn php_parser_extracts_namespaces_types_and_methods() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("UserController.php");
+    let code = r#"<?php
+namespace App\Http\Controllers;
+
+use Illuminate\Support\Str;
+
+interface Greeter
+{
+    public function greet(string $name): string;
+}
+
+trait Loggable
+{
+    protected function log($message) {}
+}
+
+final class UserController extends Controller implements Greeter
+{
+    use Loggable;
+
+    public function greet(string $name): string
+    {
+        $this->log($name);
+        return Str::upper($name);
+    }
+
+    private function build()
+    {
+        return new User();
+    }
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let parser = PhpParser::new().unwrap();
+    let result = parser.parse_file(&file).unwrap();
+
+    assert!(result
+        .nodes
+        .iter()
+        .any(|n| n.node_type == NodeType::Module && n.name == "App\\Http\\Controllers"));
+    assert!(result
+        .nodes
+        .iter()
+        .any(|n| n.node_type == NodeType::Module && n.name == "use Illuminate\\Support\\Str"));
+
+    let greeter = result.nodes.iter().find(|n| n.name == "Greeter").unwrap();
+    assert_eq!(greeter.node_type, NodeType::Interface);
+    let trait_node = result.nodes.iter().find(|n| n.name == "Loggable").unwrap();
+    assert_eq!(trait_node.node_type, NodeType::Class);
+    assert_eq!(trait_node.signature.as_deref(), Some("trait Loggable"));
+
+    let controller = result
+        .nodes
+        .iter()
+        .find(|n| n.name == "UserController")
+        .unwrap();
+    assert_eq!(
+        controller.signature.as_deref(),
+        Some("final class UserController")
+    );
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.edge_type == EdgeType::Inheritance
+            && e.source_id == controller.id
+            && e.target_id == "external:class:Controller:0"));
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.edge_type == EdgeType::Implements
+            && e.source_id == controller.id
+            && e.target_id == greeter.id));
+    assert!(result.edges.iter().any(|e| e.edge_type == EdgeType::Uses
+        && e.source_id == controller.id
+        && e.target_id == trait_node.id));
+
+    let log = result.nodes.iter().find(|n| n.name == "log").unwrap();
+    assert_eq!(log.visibility.as_deref(), Some("protected"));
+    let build = result.nodes.iter().find(|n| n.name == "build").unwrap();
+    assert_eq!(build.visibility.as_deref(), Some("private"));
+    let greet = result
+        .nodes
+        .iter()
+        .find(|n| n.name == "greet" && n.line_number > 15)
+        .unwrap();
+    assert_eq!(
+        greet.signature.as_deref(),
+        Some("greet(string $name): string")
+    );
+}
+
+#[test]
+fn php_parser_classifies_member_static_and_constructor_calls() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("service.php");
+    let code = r#"<?php
+class Service
+{
+    public function run()
+    {
+        $this->prepare();
+        Cache::forget('key');
+        $user = new User();
+        helper();
+    }
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let parser = PhpParser::new().unwrap();
+    let result = parser.parse_file(&file).unwrap();
+    let call_sites = result.call_sites.unwrap();
+
+    let call_type = |name: &str| {
+        call_sites
+            .iter()
+            .find(|c| c.called_name == name)
+            .map(|c| c.call_type.clone())
+    };
+    assert_eq!(call_type("prepare"), Some(CallType::MethodCall));
+    assert_eq!(call_type("Cache::forget"), Some(CallType::QualifiedCall));
+    assert_eq!(call_type("User"), Some(CallType::ConstructorCall));
+    assert_eq!(call_type("helper"), Some(CallType::SimpleCall));
+
+    let run = result.nodes.iter().find(|n| n.name == "run").unwrap();
+    assert!(call_sites.iter().all(|c| c.caller_id == run.id));
+}
@@ -155,6 +155,7 @@ class Service:
     let sig = process_fn.signature.as_ref().unwrap();
     assert!(sig.contains("data: List[str]"));
     assert!(sig.contains("count: int = 10"));
+    assert!(sig.ends_with(" -> Optional[str]"));
 
     // Check handle method signature
     let handle_fn = result
@@ -166,6 +167,7 @@ class Service:
     let sig = handle_fn.signature.as_ref().unwrap();
     assert!(sig.contains("self"));
     assert!(sig.contains("request: dict"));
+    assert!(sig.ends_with(" -> bool"));
 }
 
 #[test]
@@ -244,11 +246,7 @@ def my_function():
     let parser = PythonParser::new().unwrap();
     let result = parser.parse_file(&file).unwrap();
 
-    let my_class = result
-        .nodes
-        .iter()
-        .find(|n| n.name == "MyClass")
-        .unwrap();
+    let my_class = result.nodes.iter().find(|n| n.name == "MyClass").unwrap();
     assert!(my_class.docstring.is_some());
     assert!(my_class
         .docstring
@@ -256,11 +254,7 @@ def my_function():
         .unwrap()
         .contains("class docstring"));
 
-    let my_method = result
-        .nodes
-        .iter()
-        .find(|n| n.name == "my_method")
-        .unwrap();
+    let my_method = result.nodes.iter().find(|n| n.name == "my_method").unwrap();
     assert!(my_method.docstring.is_some());
     assert!(my_method
         .docstring
@@ -316,3 +310,196 @@ class Combined(Mixin1, Mixin2):
 
     assert_eq!(inheritance_from_combined.len(), 2);
 }
+
+#[test]
+fn python_parser_marks_async_functions() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("jobs.py");
+    let code = r#"
+async def fetch(url):
+    return await client.get(url)
+
+class Worker:
+    async def run(self):
+        await fetch("x")
+
+    def stop(self):
+        pass
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = PythonParser::new().unwrap().parse_file(&file).unwrap();
+    let is_async = |name: &str| {
+        result
+            .nodes
+            .iter()
+            .find(|n| n.name == name)
+            .unwrap()
+            .is_async
+    };
+
+    assert!(is_async("fetch"));
+    assert!(is_async("run"));
+    assert!(!is_async("stop"));
+}
+
+#[test]
+fn python_parser_counts_branch_complexity() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("branches.py");
+    let code = r#"
+def classify(values):
+    for v in values:
+        if v > 10:
+            return "big"
+        elif v > 5:
+            return "medium"
+    while values:
+        values.pop()
+    return "small" if values else "empty"
+
+def noop():
+    pass
+"#;
+    fs::write(&file, code).unwrap();
+
+    let parser = PythonParser::new().unwrap();
+    let result = parser.parse_file(&file).unwrap();
+
+    let classify = result.nodes.iter().find(|n| n.name == "classify").unwrap();
+    assert_eq!(classify.complexity, 5);
+    let noop = result.nodes.iter().find(|n| n.name == "noop").unwrap();
+    assert_eq!(noop.complexity, 0);
+}
+
+#[test]
+fn python_parser_records_decorator_names() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("api.py");
+    let code = r#"
+@app.get("/orders/{id}")
+@requires_auth
+async def get_order(id: int):
+    pass
+
+@dataclass
+class Order:
+    pass
+
+def plain():
+    pass
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = PythonParser::new().unwrap().parse_file(&file).unwrap();
+    let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+
+    assert_eq!(
+        find("get_order").decorators,
+        vec!["app.get", "requires_auth"]
+    );
+    assert_eq!(find("Order").decorators, vec!["dataclass"]);
+    assert!(find("plain").decorators.is_empty());
+}
+
+#[test]
+fn python_parser_records_definition_spans() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("spans.py");
+    let code = r#"import os
+
+class Cache:
+    def get(self, key):
+        if key:
+            return key
+        return None
+
+def one_liner(): return 1
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = PythonParser::new().unwrap().parse_file(&file).unwrap();
+    let span = |name: &str| {
+        let node = result.nodes.iter().find(|n| n.name == name).unwrap();
+        (node.line_number, node.end_line)
+    };
+    assert_eq!(span("Cache"), (3, 7));
+    assert_eq!(span("get"), (4, 7));
+    assert_eq!(span("one_liner"), (9, 9));
+}
+
+#[test]
+fn python_parser_marks_test_functions_only_in_test_files() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let code = r#"
+def test_login():
+    pass
+
+class TestSession:
+    def test_expiry(self):
+        pass
+
+    def helper(self):
+        pass
+"#;
+    let test_file = dir.path().join("test_auth.py");
+    let module_file = dir.path().join("auth.py");
+    fs::write(&test_file, code).unwrap();
+    fs::write(&module_file, code).unwrap();
+
+    let parser = PythonParser::new().unwrap();
+    let tests = |file: &std::path::Path| -> Vec<String> {
+        let result = parser.parse_file(file).unwrap();
+        result
+            .nodes
+            .into_iter()
+            .filter(|n| n.is_test)
+            .map(|n| n.name)
+            .collect()
+    };
+    assert_eq!(tests(&test_file), vec!["test_expiry", "test_login"]);
+    assert!(tests(&module_file).is_empty());
+}
+
+#[test]
+fn python_parser_links_functions_to_raised_exceptions() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("lookup.py");
+    let code = r#"
+class Missing(Exception):
+    pass
+
+def find(key):
+    if not key:
+        raise ValueError("empty")
+    def inner():
+        raise KeyError(key)
+    try:
+        return table[key]
+    except OSError:
+        raise
+    raise errors.Missing from None
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = PythonParser::new().unwrap().parse_file(&file).unwrap();
+    let thrown = |name: &str| -> Vec<String> {
+        let func = result.nodes.iter().find(|n| n.name == name).unwrap();
+        result
+            .edges
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Throws && e.source_id == func.id)
+            .map(|e| e.target_id.clone())
+            .collect()
+    };
+    let missing = result.nodes.iter().find(|n| n.name == "Missing").unwrap();
+
+    assert_eq!(
+        thrown("find"),
+        vec![
+            "external:class:ValueError:0".to_string(),
+            missing.id.clone()
+        ]
+    );
+    assert_eq!(thrown("inner"), vec!["external:class:KeyError:0"]);
+}
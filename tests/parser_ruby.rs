@@ -0,0 +1,109 @@
+use embargo::core::resolver::CallType;
+use embargo::core::{EdgeType, NodeType};
+use embargo::parsers::ruby::RubyParser;
+use embargo::parsers::LanguageParser;
+use std::fs;
+
+#[test]
+fn ruby_parser_extracts_modules_classes_methods_and_requires() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("billing.rb");
+    let code = r#"
+require 'json'
+require_relative 'base_service'
+
+module Billing
+  class Invoice < BaseService
+    def self.build(attrs)
+      Invoice.new(attrs)
+    end
+
+    def total
+      line_items.sum
+    end
+
+    private
+
+    def line_items
+      @items.map(&:amount)
+    end
+  end
+end
+"#;
+    fs::write(&file, code).unwrap();
+
+    let parser = RubyParser::new().unwrap();
+    let result = parser.parse_file(&file).unwrap();
+
+    let requires: Vec<_> = result
+        .nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Module && n.name.starts_with("require"))
+        .collect();
+    assert_eq!(requires.len(), 2);
+    assert!(result
+        .nodes
+        .iter()
+        .any(|n| n.node_type == NodeType::Module && n.name == "Billing"));
+
+    let class = result
+        .nodes
+        .iter()
+        .find(|n| n.node_type == NodeType::Class && n.name == "Invoice")
+        .expect("Invoice class");
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.edge_type == EdgeType::Inheritance
+            && e.source_id == class.id
+            && e.target_id == "external:class:BaseService:0"));
+
+    let build = result.nodes.iter().find(|n| n.name == "build").unwrap();
+    assert_eq!(build.signature.as_deref(), Some("self.build(attrs)"));
+    let total = result.nodes.iter().find(|n| n.name == "total").unwrap();
+    assert_eq!(total.visibility.as_deref(), Some("public"));
+    let line_items = result
+        .nodes
+        .iter()
+        .find(|n| n.name == "line_items")
+        .unwrap();
+    assert_eq!(line_items.visibility.as_deref(), Some("private"));
+
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.edge_type == EdgeType::Contains
+            && e.source_id == class.id
+            && e.target_id == line_items.id));
+}
+
+#[test]
+fn ruby_call_sites_distinguish_method_and_constructor_calls() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("calls.rb");
+    let code = r#"
+def run
+  user = User.new
+  user.save
+  notify(user)
+end
+"#;
+    fs::write(&file, code).unwrap();
+
+    let parser = RubyParser::new().unwrap();
+    let result = parser.parse_file(&file).unwrap();
+    let call_sites = result.call_sites.unwrap();
+
+    assert!(call_sites
+        .iter()
+        .any(|c| c.called_name == "User" && c.call_type == CallType::ConstructorCall));
+    assert!(call_sites
+        .iter()
+        .any(|c| c.called_name == "save" && c.call_type == CallType::MethodCall));
+    assert!(call_sites
+        .iter()
+        .any(|c| c.called_name == "notify" && c.call_type == CallType::SimpleCall));
+    assert!(call_sites
+        .iter()
+        .all(|c| c.caller_id.contains(":function:run:")));
+}
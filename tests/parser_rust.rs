@@ -54,3 +54,259 @@ fn rust_parser_extracts_core_entities() {
         .map(|v| !v.is_empty())
         .unwrap_or(false));
 }
+
+#[test]
+fn rust_parser_marks_async_fns() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("svc.rs");
+    fs::write(
+        &file,
+        "pub async fn serve() {}\nfn block() {}\nstruct S;\nimpl S { async fn tick(&self) {} }\n",
+    )
+    .unwrap();
+
+    let result = RustParser::new().unwrap().parse_file(&file).unwrap();
+    let is_async = |name: &str| {
+        result
+            .nodes
+            .iter()
+            .find(|n| n.name == name)
+            .unwrap()
+            .is_async
+    };
+
+    assert!(is_async("serve"));
+    assert!(is_async("tick"));
+    assert!(!is_async("block"));
+}
+
+#[test]
+fn rust_parser_links_impl_blocks_to_types_and_traits() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("shapes.rs");
+    let code = r#"
+        trait Shape {
+            fn area(&self) -> f64;
+            fn unit() -> Self where Self: Sized { unimplemented!() }
+        }
+
+        struct Square<T> { side: T }
+
+        impl<T> Square<T> {
+            fn new(side: T) -> Self { Square { side } }
+            fn side(&self) -> &T { &self.side }
+        }
+
+        impl Shape for Square<f64> {
+            fn area(&self) -> f64 { self.side * self.side }
+        }
+
+        impl std::fmt::Display for Square<f64> {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { Ok(()) }
+        }
+    "#;
+    fs::write(&file, code).unwrap();
+
+    let result = RustParser::new().unwrap().parse_file(&file).unwrap();
+    let id_of = |name: &str, node_type: NodeType| {
+        result
+            .nodes
+            .iter()
+            .find(|n| n.name == name && n.node_type == node_type)
+            .map(|n| n.id.clone())
+            .unwrap()
+    };
+    let shape = id_of("Shape", NodeType::Interface);
+    let square = id_of("Square", NodeType::Class);
+
+    let implements: Vec<_> = result
+        .edges
+        .iter()
+        .filter(|e| e.edge_type == EdgeType::Implements)
+        .map(|e| (e.source_id.as_str(), e.target_id.as_str()))
+        .collect();
+    assert!(implements.contains(&(square.as_str(), shape.as_str())));
+    assert!(implements.contains(&(square.as_str(), "external:interface:Display:0")));
+
+    let member_kind = |owner: &str, name: &str| {
+        result
+            .edges
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Contains && e.source_id == owner)
+            .find(|e| {
+                result.nodes.iter().any(|n| {
+                    n.id == e.target_id && n.name == name && n.node_type == NodeType::Function
+                })
+            })
+            .and_then(|e| e.context.clone())
+    };
+    assert_eq!(member_kind(&square, "new").as_deref(), Some("associated"));
+    assert_eq!(member_kind(&square, "side").as_deref(), Some("method"));
+    assert_eq!(member_kind(&square, "fmt").as_deref(), Some("method"));
+    assert_eq!(member_kind(&shape, "area").as_deref(), Some("method"));
+    assert_eq!(member_kind(&shape, "unit").as_deref(), Some("associated"));
+}
+
+#[test]
+fn rust_parser_links_functions_to_signature_types() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("repo.rs");
+    let code = r#"
+struct User;
+
+impl User {
+    fn rename(&mut self, name: Name) -> Self { todo!() }
+}
+
+fn load<T: Source>(source: &T, id: io::Result<UserId>) -> Option<User> { todo!() }
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = RustParser::new().unwrap().parse_file(&file).unwrap();
+    let user = result.nodes.iter().find(|n| n.name == "User").unwrap();
+    let uses_of = |name: &str| {
+        let func = result.nodes.iter().find(|n| n.name == name).unwrap();
+        result
+            .edges
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Uses && e.source_id == func.id)
+            .map(|e| e.target_id.clone())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        uses_of("load"),
+        vec![
+            "external:class:Result:0".to_string(),
+            "external:class:UserId:0".to_string(),
+            "external:class:Option:0".to_string(),
+            user.id.clone(),
+        ]
+    );
+    assert_eq!(uses_of("rename"), vec!["external:class:Name:0".to_string()]);
+}
+
+#[test]
+fn rust_parser_extracts_macro_rules_and_links_invocations() {
+    use embargo::core::CodebaseAnalyzer;
+    use petgraph::visit::EdgeRef;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    let code = r#"
+/// Squares an expression
+#[macro_export]
+macro_rules! square {
+    ($x:expr) => { $x * $x };
+}
+
+fn area(side: i32) -> i32 {
+    square!(side)
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = RustParser::new().unwrap().parse_file(&file).unwrap();
+    let square = result.nodes.iter().find(|n| n.name == "square").unwrap();
+    assert_eq!(square.node_type, NodeType::Function);
+    assert_eq!(square.signature.as_deref(), Some("macro_rules! square"));
+    assert_eq!(square.line_number, 4);
+
+    let graph = CodebaseAnalyzer::new()
+        .analyze(dir.path(), &["rust"])
+        .unwrap();
+    let invocation = graph
+        .edge_references()
+        .find(|e| e.weight().edge_type == EdgeType::Call)
+        .unwrap();
+    assert_eq!(graph[invocation.source()].name, "area");
+    assert_eq!(graph[invocation.target()].name, "square");
+}
+
+#[test]
+fn rust_parser_marks_test_functions_in_inline_test_modules() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    let code = r#"
+pub fn add(a: i32, b: i32) -> i32 { a + b }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> i32 { 1 }
+
+    #[test]
+    fn adds() { assert_eq!(add(fixture(), 1), 2); }
+
+    #[tokio::test]
+    #[ignore]
+    async fn adds_async() {}
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let parser = RustParser::new().unwrap();
+    let result = parser.parse_file(&file).unwrap();
+
+    let is_test = |name: &str| {
+        result
+            .nodes
+            .iter()
+            .find(|n| n.name == name)
+            .unwrap()
+            .is_test
+    };
+    assert!(is_test("adds"));
+    assert!(is_test("adds_async"));
+    assert!(!is_test("add"));
+    assert!(!is_test("fixture"));
+}
+
+#[test]
+fn rust_parser_extracts_module_level_consts_statics_and_type_aliases() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("config.rs");
+    let code = r#"
+pub const MAX_RETRIES: u32 = 5;
+pub(crate) static mut COUNTER: usize = 0;
+const TIMEOUT: std::time::Duration =
+    std::time::Duration::from_secs(30);
+pub type Result<T> = std::result::Result<T, Error>;
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = RustParser::new().unwrap().parse_file(&file).unwrap();
+    let node = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+
+    let max_retries = node("MAX_RETRIES");
+    assert_eq!(max_retries.node_type, NodeType::Variable);
+    assert_eq!(
+        max_retries.signature.as_deref(),
+        Some("const MAX_RETRIES: u32")
+    );
+    assert_eq!(max_retries.visibility.as_deref(), Some("public"));
+
+    let counter = node("COUNTER");
+    assert_eq!(
+        counter.signature.as_deref(),
+        Some("static mut COUNTER: usize")
+    );
+    assert_eq!(counter.visibility.as_deref(), Some("internal"));
+
+    let timeout = node("TIMEOUT");
+    assert_eq!(
+        timeout.signature.as_deref(),
+        Some("const TIMEOUT: std::time::Duration")
+    );
+    assert_eq!(timeout.visibility.as_deref(), Some("private"));
+    assert_eq!(timeout.end_line, timeout.line_number + 1);
+
+    let alias = node("Result");
+    assert_eq!(alias.node_type, NodeType::Class);
+    assert_eq!(
+        alias.signature.as_deref(),
+        Some("type Result<T> = std::result::Result<T, Error>")
+    );
+    assert_eq!(alias.visibility.as_deref(), Some("public"));
+}
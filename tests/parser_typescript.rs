@@ -56,3 +56,149 @@ const g = (x: number) => x + 1;
 
     assert!(result.call_sites.as_ref().is_some());
 }
+
+#[test]
+fn typescript_parser_marks_async_functions_methods_and_arrows() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("api.ts");
+    let code = r#"
+async function load(id: string) { return id; }
+const save = async (x: number) => x;
+class Client {
+  async send(msg: string) {}
+  close() {}
+}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = TypeScriptParser::new().unwrap().parse_file(&file).unwrap();
+    let is_async = |name: &str| {
+        result
+            .nodes
+            .iter()
+            .find(|n| n.name == name)
+            .unwrap()
+            .is_async
+    };
+
+    assert!(is_async("load"));
+    assert!(is_async("save"));
+    assert!(is_async("send"));
+    assert!(!is_async("close"));
+}
+
+#[test]
+fn typescript_parser_extracts_enums_type_aliases_and_namespaces() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("types.ts");
+    let code = r#"
+enum Color { Red, Green }
+export const enum Direction { Up, Down }
+export type Pair<T> = {
+  first: T;
+  second: T;
+};
+
+namespace Shapes {
+  export interface Shape { area(): number }
+  export class Circle {}
+  export function unit(): Circle { return new Circle(); }
+  export const scale = (n: number) => n * 2;
+  export namespace Units {
+    export enum Metric { Cm }
+  }
+}
+declare module "untyped-lib" {}
+"#;
+    fs::write(&file, code).unwrap();
+
+    let result = TypeScriptParser::new().unwrap().parse_file(&file).unwrap();
+    let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+
+    assert_eq!(find("Color").node_type, NodeType::Enum);
+    assert_eq!(
+        find("Direction").signature.as_deref(),
+        Some("const enum Direction")
+    );
+
+    let pair = find("Pair");
+    assert_eq!(pair.node_type, NodeType::Class);
+    assert_eq!(
+        pair.signature.as_deref(),
+        Some("type Pair<T> = { first: T; second: T; }")
+    );
+
+    let shapes = find("Shapes");
+    assert_eq!(shapes.node_type, NodeType::Module);
+    let contained: Vec<&str> = result
+        .edges
+        .iter()
+        .filter(|e| e.edge_type == EdgeType::Contains && e.source_id == shapes.id)
+        .map(|e| {
+            result
+                .nodes
+                .iter()
+                .find(|n| n.id == e.target_id)
+                .unwrap()
+                .name
+                .as_str()
+        })
+        .collect();
+    assert_eq!(contained, vec!["Shape", "Circle", "unit", "scale", "Units"]);
+
+    let units = find("Units");
+    let metric = find("Metric");
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.edge_type == EdgeType::Contains
+            && e.source_id == units.id
+            && e.target_id == metric.id));
+
+    // Ambient module declarations name packages, not namespaces
+    assert!(!result.nodes.iter().any(|n| n.name.contains("untyped-lib")));
+}
+
+#[test]
+fn typescript_parser_marks_it_and_test_callbacks_as_tests() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("math.test.ts");
+    let code = r#"
+import { add } from "./math";
+
+function helper() { return 1; }
+
+describe("math", () => {
+  it("adds numbers", () => {
+    expect(add(1, helper())).toBe(2);
+  });
+  test.skip("subtracts", async () => {});
+});
+"#;
+    fs::write(&file, code).unwrap();
+
+    let parser = TypeScriptParser::new().unwrap();
+    let result = parser.parse_file(&file).unwrap();
+
+    let tests: Vec<&str> = result
+        .nodes
+        .iter()
+        .filter(|n| n.is_test)
+        .map(|n| n.name.as_str())
+        .collect();
+    assert_eq!(tests, vec!["adds numbers", "subtracts"]);
+    assert!(result
+        .nodes
+        .iter()
+        .any(|n| n.name == "helper" && !n.is_test));
+
+    // Calls inside the callback belong to the test node
+    let adds = result
+        .nodes
+        .iter()
+        .find(|n| n.name == "adds numbers")
+        .unwrap();
+    let call_sites = result.call_sites.unwrap();
+    let add_call = call_sites.iter().find(|c| c.called_name == "add").unwrap();
+    assert_eq!(add_call.caller_id, adds.id);
+}
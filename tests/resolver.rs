@@ -1,5 +1,5 @@
 use embargo::core::resolver::{CallSite, CallType, FunctionResolver};
-use embargo::core::{graph::Node, EdgeType, NodeType};
+use embargo::core::{graph::Node, Edge, EdgeType, NodeType};
 use std::path::PathBuf;
 
 fn func(id: &str, name: &str) -> Node {
@@ -29,6 +29,8 @@ fn resolver_simple_call_matches_function() {
         call_type: CallType::SimpleCall,
         context: None,
         line_number: 42,
+        caller_class: None,
+        arg_count: None,
     };
 
     let edges = resolver.resolve_calls(&[call]);
@@ -38,3 +40,427 @@ fn resolver_simple_call_matches_function() {
     assert_eq!(e.source_id, nodes[0].id);
     assert_eq!(e.target_id, nodes[1].id);
 }
+
+fn class(id: &str, name: &str) -> Node {
+    Node::new(
+        id.to_string(),
+        name.to_string(),
+        NodeType::Class,
+        PathBuf::from("/tmp/models.py"),
+        1,
+        "python".to_string(),
+    )
+}
+
+#[test]
+fn resolver_method_call_prefers_receiver_class() {
+    let nodes = vec![
+        class("order", "Order"),
+        func("order_save", "save"),
+        class("user", "User"),
+        func("user_save", "save"),
+        class("service", "UserService"),
+        func("register", "register"),
+        func("audit", "audit"),
+    ];
+    let edges = vec![
+        Edge::new(EdgeType::Contains, "order".into(), "order_save".into()),
+        Edge::new(EdgeType::Contains, "user".into(), "user_save".into()),
+        Edge::new(EdgeType::Contains, "service".into(), "register".into()),
+        Edge::new(EdgeType::Contains, "service".into(), "audit".into()),
+    ];
+
+    let mut resolver = FunctionResolver::new();
+    resolver.build_indexes_with_edges(&nodes, &edges).unwrap();
+
+    let method_call = |name: &str, receiver: &str| CallSite {
+        caller_id: "register".to_string(),
+        called_name: name.to_string(),
+        call_type: CallType::MethodCall,
+        context: Some(format!("ast_node:call;receiver:{}", receiver)),
+        line_number: 3,
+        caller_class: None,
+        arg_count: None,
+    };
+
+    let resolved =
+        resolver.resolve_calls(&[method_call("save", "user"), method_call("audit", "self")]);
+    let targets: Vec<_> = resolved.iter().map(|e| e.target_id.as_str()).collect();
+    assert_eq!(targets, vec!["user_save", "audit"]);
+}
+
+#[test]
+fn resolver_leaves_methods_on_unrelated_receivers_unresolved() {
+    let nodes = vec![
+        class("sequence", "MutableSequence"),
+        func("sequence_append", "append"),
+        func("quote", "quote"),
+        func("main", "main"),
+    ];
+    let edges = vec![Edge::new(
+        EdgeType::Contains,
+        "sequence".into(),
+        "sequence_append".into(),
+    )];
+
+    let mut resolver = FunctionResolver::new();
+    resolver.build_indexes_with_edges(&nodes, &edges).unwrap();
+
+    let call = |caller: &str, name: &str, call_type: CallType, context: Option<&str>| CallSite {
+        caller_id: caller.to_string(),
+        called_name: name.to_string(),
+        call_type,
+        context: context.map(str::to_string),
+        line_number: 2,
+        caller_class: None,
+        arg_count: Some(1),
+    };
+    // `xs = []; xs.append(1)` and a bare `append(1)` outside the class: neither is
+    // `MutableSequence.append`, though it lives in the same file
+    let resolved = resolver.resolve_calls(&[
+        call(
+            "quote",
+            "append",
+            CallType::MethodCall,
+            Some("ast_node:call;receiver:xs"),
+        ),
+        call("main", "append", CallType::SimpleCall, None),
+    ]);
+    assert!(resolved.is_empty(), "{:?}", resolved);
+}
+
+#[test]
+fn resolver_merges_repeated_calls_into_weighted_edge() {
+    let nodes = vec![
+        func("id:function:main:1", "main"),
+        func("id:function:log:2", "log"),
+        func("id:function:exit:3", "exit"),
+    ];
+
+    let mut resolver = FunctionResolver::new();
+    resolver.build_indexes(&nodes).unwrap();
+
+    let call = |name: &str, line: usize| CallSite {
+        caller_id: nodes[0].id.clone(),
+        called_name: name.to_string(),
+        call_type: CallType::SimpleCall,
+        context: None,
+        line_number: line,
+        caller_class: None,
+        arg_count: None,
+    };
+    let calls = [
+        call("log", 4),
+        call("log", 5),
+        call("exit", 6),
+        call("log", 7),
+    ];
+
+    let edges = resolver.resolve_calls(&calls);
+    assert_eq!(edges.len(), 2);
+    assert_eq!(edges[0].target_id, nodes[1].id);
+    assert_eq!(edges[0].weight, 3);
+    assert_eq!(edges[0].context.as_deref(), Some("line:4"));
+    assert_eq!(edges[1].target_id, nodes[2].id);
+    assert_eq!(edges[1].weight, 1);
+}
+
+#[test]
+fn call_site_caller_ids_match_parsed_function_ids() {
+    use embargo::parsers::ParserFactory;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let sources = [
+        ("python", "app/service.py", "def handle():\n    load()\n"),
+        ("rust", "src/service.rs", "fn handle() {\n    load();\n}\n"),
+        (
+            "go",
+            "pkg/service.go",
+            "package pkg\n\nfunc handle() {\n\tload()\n}\n",
+        ),
+        (
+            "typescript",
+            "src/service.ts",
+            "function handle() {\n  load();\n}\n",
+        ),
+    ];
+
+    for (language, relative, code) in sources {
+        let file = dir.path().join(relative);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, code).unwrap();
+
+        let parser = ParserFactory::new().get_parser(language).unwrap();
+        let result = parser.parse_file(&file).unwrap();
+        let handle = result.nodes.iter().find(|n| n.name == "handle").unwrap();
+        let call = result
+            .call_sites
+            .unwrap()
+            .into_iter()
+            .find(|c| c.called_name == "load")
+            .unwrap();
+        assert_eq!(call.caller_id, handle.id, "{}", language);
+        assert_eq!(call.arg_count, Some(0), "{}", language);
+    }
+}
+
+#[test]
+fn resolver_reports_unresolved_call_names() {
+    let nodes = vec![
+        func("id:function:foo:1", "foo"),
+        func("id:function:bar:2", "bar"),
+    ];
+    let mut resolver = FunctionResolver::new();
+    resolver.build_indexes(&nodes).unwrap();
+
+    let call = |name: &str, line: usize| CallSite {
+        caller_id: nodes[0].id.clone(),
+        called_name: name.to_string(),
+        call_type: CallType::SimpleCall,
+        context: None,
+        line_number: line,
+        caller_class: None,
+        arg_count: None,
+    };
+    let calls = [
+        call("bar", 3),
+        call("println", 4),
+        call("format", 5),
+        call("println", 6),
+    ];
+
+    let (edges, report) = resolver.resolve_calls_with_report(&calls);
+    assert_eq!(edges.len(), 1);
+    assert_eq!(report.total, 4);
+    assert_eq!(report.resolved_count(), 1);
+    assert_eq!(report.resolved_ratio(), 0.25);
+    assert_eq!(
+        report.unresolved,
+        [("println".to_string(), 2), ("format".to_string(), 1)]
+    );
+}
+
+#[test]
+fn rust_scoped_calls_resolve_to_the_named_module() {
+    use embargo::parsers::ParserFactory;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let sources = [
+        ("src/billing.rs", "pub fn charge() {}\n"),
+        ("src/shipping.rs", "pub fn charge() {}\n"),
+        (
+            "src/main.rs",
+            r#"fn checkout() {
+    crate::billing::charge();
+    let _ = std::mem::take(&mut 0);
+}
+"#,
+        ),
+    ];
+
+    let parser = ParserFactory::new().get_parser("rust").unwrap();
+    let mut nodes = Vec::new();
+    let mut call_sites = Vec::new();
+    for (relative, code) in sources {
+        let file = dir.path().join(relative);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, code).unwrap();
+        let result = parser.parse_file(&file).unwrap();
+        nodes.extend(result.nodes);
+        call_sites.extend(result.call_sites.unwrap());
+    }
+
+    let mut resolver = FunctionResolver::new();
+    resolver.build_indexes(&nodes).unwrap();
+    let (edges, report) = resolver.resolve_calls_with_report(&call_sites);
+
+    let charge = nodes
+        .iter()
+        .find(|n| n.name == "charge" && n.file_path.ends_with("billing.rs"))
+        .unwrap();
+    assert_eq!(edges.len(), 1);
+    assert_eq!(edges[0].target_id, charge.id);
+    assert_eq!(report.unresolved, [("std::mem::take".to_string(), 1)]);
+}
+
+#[test]
+fn python_self_calls_resolve_within_the_callers_class() {
+    use embargo::parsers::ParserFactory;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let sources = [
+        (
+            "app/user.py",
+            "class User:\n    def save(self):\n        pass\n",
+        ),
+        (
+            "app/order.py",
+            r#"class Order:
+    def save(self):
+        pass
+
+    def submit(self):
+        def commit():
+            self.save()
+        commit()
+"#,
+        ),
+    ];
+
+    let parser = ParserFactory::new().get_parser("python").unwrap();
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+    let mut call_sites = Vec::new();
+    for (relative, code) in sources {
+        let file = dir.path().join(relative);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, code).unwrap();
+        let result = parser.parse_file(&file).unwrap();
+        nodes.extend(result.nodes);
+        edges.extend(result.edges);
+        call_sites.extend(result.call_sites.unwrap());
+    }
+
+    // `commit` is nested in `submit`, so no Contains edge ties it to `Order`; the call
+    // site itself records the enclosing class
+    let call = call_sites.iter().find(|c| c.called_name == "save").unwrap();
+    assert_eq!(call.caller_class.as_deref(), Some("Order"));
+
+    let mut resolver = FunctionResolver::new();
+    resolver.build_indexes_with_edges(&nodes, &edges).unwrap();
+    let resolved = resolver.resolve_calls(std::slice::from_ref(call));
+
+    let order_save = nodes
+        .iter()
+        .find(|n| n.name == "save" && n.file_path.ends_with("order.py"))
+        .unwrap();
+    assert_eq!(resolved.len(), 1);
+    assert_eq!(resolved[0].target_id, order_save.id);
+}
+
+#[test]
+fn resolver_picks_the_overload_matching_the_argument_count() {
+    let overload =
+        |id: &str, signature: &str| func(id, "add").with_signature(signature.to_string());
+    let nodes = vec![
+        func("id:function:run:1", "run"),
+        overload("id:function:add:2", "add((int a))"),
+        overload("id:function:add:3", "add((int a, int b))"),
+        overload("id:function:add:4", "add((int a, int b, int... rest))"),
+    ];
+    let mut resolver = FunctionResolver::new();
+    resolver.build_indexes(&nodes).unwrap();
+
+    let call = |arg_count: Option<usize>| CallSite {
+        caller_id: nodes[0].id.clone(),
+        called_name: "add".to_string(),
+        call_type: CallType::SimpleCall,
+        context: None,
+        line_number: 5,
+        caller_class: None,
+        arg_count,
+    };
+    let target = |arg_count| {
+        let edges = resolver.resolve_calls(&[call(arg_count)]);
+        edges[0].target_id.clone()
+    };
+
+    assert_eq!(target(Some(1)), "id:function:add:2");
+    assert_eq!(target(Some(2)), "id:function:add:3");
+    assert_eq!(target(Some(5)), "id:function:add:4");
+    // Unknown arity, e.g. `add(*xs)`: the first definition, as before
+    assert_eq!(target(None), "id:function:add:2");
+}
+
+#[test]
+fn csharp_class_calls_resolve_within_the_namespaces_in_scope() {
+    use embargo::parsers::ParserFactory;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let gateway = |namespace: &str| {
+        format!(
+            r#"namespace {} {{
+    public class Gateway {{
+        public static void Charge(int amount) {{ }}
+        public static void Refund() {{ }}
+    }}
+}}
+"#,
+            namespace
+        )
+    };
+    // Same class name in two namespaces; directory names give no hint which is which
+    let sources = [
+        ("lib/a/Gateway.cs", gateway("Acme.Legacy")),
+        ("lib/b/Gateway.cs", gateway("Acme.Payments")),
+        (
+            "app/Ledger.cs",
+            r#"namespace Acme.Billing {
+    public class Ledger {
+        public static void Record() { }
+    }
+}
+"#
+            .to_string(),
+        ),
+        (
+            "app/Invoice.cs",
+            r#"using Acme.Payments;
+using Old = Acme.Legacy.Gateway;
+
+namespace Acme.Billing {
+    public class Invoice {
+        public void Send() {
+            Gateway.Charge(10);
+            Acme.Legacy.Gateway.Charge(1);
+            Old.Refund();
+            Ledger.Record();
+        }
+    }
+}
+"#
+            .to_string(),
+        ),
+    ];
+
+    let parser = ParserFactory::new().get_parser("csharp").unwrap();
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+    let mut call_sites = Vec::new();
+    for (relative, code) in sources {
+        let file = dir.path().join(relative);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, code).unwrap();
+        let result = parser.parse_file(&file).unwrap();
+        nodes.extend(result.nodes);
+        edges.extend(result.edges);
+        call_sites.extend(result.call_sites.unwrap());
+    }
+
+    let mut resolver = FunctionResolver::new();
+    resolver.build_indexes_with_edges(&nodes, &edges).unwrap();
+    let resolved = resolver.resolve_calls(&call_sites);
+
+    let method = |name: &str, file: &str| {
+        nodes
+            .iter()
+            .find(|n| n.name == name && n.file_path.ends_with(file))
+            .unwrap()
+            .id
+            .clone()
+    };
+    let mut targets: Vec<(usize, String)> = resolved
+        .iter()
+        .map(|e| (e.line.unwrap(), e.target_id.clone()))
+        .collect();
+    targets.sort();
+    assert_eq!(
+        targets,
+        [
+            (7, method("Charge", "b/Gateway.cs")),
+            (8, method("Charge", "a/Gateway.cs")),
+            (9, method("Refund", "a/Gateway.cs")),
+            (10, method("Record", "Ledger.cs")),
+        ]
+    );
+}
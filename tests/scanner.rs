@@ -1,6 +1,6 @@
-use embargo::core::scanner::FileScanner;
+use embargo::core::scanner::{FileScanner, SkipReason, TestFiles};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 fn touch<P: AsRef<Path>>(p: P) {
     fs::write(p, "// test").unwrap();
@@ -28,3 +28,198 @@ fn scanner_filters_by_language_extensions() {
     langs.sort();
     assert_eq!(langs, vec!["javascript", "python", "rust"]);
 }
+
+#[test]
+fn scanner_honors_gitignore_and_exclude_globs() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::create_dir_all(root.join("vendor/lib")).unwrap();
+    fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+
+    touch(root.join("src/app.ts"));
+    touch(root.join("src/app.test.ts"));
+    touch(root.join("vendor/lib/dep.ts"));
+    touch(root.join("node_modules/pkg/index.ts"));
+    fs::write(root.join(".gitignore"), "node_modules/\n").unwrap();
+
+    let excludes = vec!["vendor/**".to_string(), "*.test.ts".to_string()];
+    let scanner = FileScanner::new().with_excludes(&excludes).unwrap();
+    let files = scanner.scan_directory(root, &["typescript"]).unwrap();
+    let names: Vec<_> = files
+        .iter()
+        .map(|f| f.path.strip_prefix(root).unwrap().to_path_buf())
+        .collect();
+    assert_eq!(names, vec![Path::new("src/app.ts").to_path_buf()]);
+
+    // Opting out of .gitignore brings node_modules back
+    let scanner = FileScanner::new()
+        .with_excludes(&excludes)
+        .unwrap()
+        .with_gitignore(false);
+    let files = scanner.scan_directory(root, &["typescript"]).unwrap();
+    assert!(files
+        .iter()
+        .any(|f| f.path.ends_with("node_modules/pkg/index.ts")));
+    assert_eq!(files.len(), 2);
+}
+
+#[test]
+fn scanner_applies_exclude_globs_to_listed_files() {
+    let excludes = vec!["vendor/**".to_string(), "*.test.ts".to_string()];
+    let scanner = FileScanner::new().with_excludes(&excludes).unwrap();
+    let listed = [
+        PathBuf::from("src/app.ts"),
+        PathBuf::from("src/app.test.ts"),
+        PathBuf::from("vendor/lib/dep.ts"),
+        PathBuf::from("/checkout/vendor/lib/dep.ts"),
+        PathBuf::from("/checkout/src/main.ts"),
+    ];
+    let files = scanner.scan_files(&listed, &["typescript"]);
+    let paths: Vec<_> = files.iter().map(|f| f.path.as_path()).collect();
+    assert_eq!(
+        paths,
+        vec![Path::new("src/app.ts"), Path::new("/checkout/src/main.ts")]
+    );
+}
+
+#[test]
+fn scanner_reports_languages_present() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    touch(root.join("main.go"));
+    touch(root.join("util.go"));
+    touch(root.join("lib.rs"));
+    touch(root.join("notes.txt"));
+
+    let scanner = FileScanner::new();
+    let all = scanner
+        .scan_directory(root, &["python", "rust", "go", "java"])
+        .unwrap();
+    assert_eq!(FileScanner::languages_present(&all), vec!["go", "rust"]);
+
+    // Listing languages next to `auto` narrows what detection may pick
+    let subset = scanner.scan_directory(root, &["rust", "java"]).unwrap();
+    assert_eq!(FileScanner::languages_present(&subset), vec!["rust"]);
+}
+
+#[test]
+fn test_file_detection_follows_language_conventions() {
+    let is_test = |path: &str, language: &str| FileScanner::is_test_file(Path::new(path), language);
+
+    assert!(is_test("pkg/server_test.go", "go"));
+    assert!(!is_test("pkg/server.go", "go"));
+
+    assert!(is_test("app/test_models.py", "python"));
+    assert!(is_test("app/models_test.py", "python"));
+    assert!(is_test("conftest.py", "python"));
+    assert!(!is_test("app/testing.py", "python"));
+
+    assert!(is_test("src/api.test.ts", "typescript"));
+    assert!(is_test("src/api.spec.tsx", "typescript"));
+    assert!(is_test("src/__tests__/api.js", "javascript"));
+    assert!(!is_test("src/contest.ts", "typescript"));
+
+    assert!(is_test("src/main/java/UserServiceTest.java", "java"));
+    assert!(is_test("src/test/java/Fixtures.java", "java"));
+    assert!(!is_test("src/main/java/UserService.java", "java"));
+    assert!(is_test("Api.Tests/OrderTests.cs", "csharp"));
+    assert!(is_test("tests/UserTest.php", "php"));
+
+    assert!(is_test("tests/integration.rs", "rust"));
+    assert!(is_test("src/parser/tests.rs", "rust"));
+    assert!(!is_test("src/parser/mod.rs", "rust"));
+
+    assert!(is_test("spec/user_spec.rb", "ruby"));
+    assert!(is_test("test_buffer.c", "c"));
+    assert!(is_test("buffer_test.cc", "cpp"));
+}
+
+#[test]
+fn scanner_excludes_or_keeps_only_tests() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path().join("tests");
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::create_dir_all(root.join("tests")).unwrap();
+    touch(root.join("src/lib.rs"));
+    touch(root.join("tests/api.rs"));
+    touch(root.join("main.go"));
+    touch(root.join("main_test.go"));
+
+    let scan = |test_files: TestFiles| {
+        let mut names: Vec<String> = FileScanner::new()
+            .with_test_files(test_files)
+            .scan_directory(&root, &["rust", "go"])
+            .unwrap()
+            .into_iter()
+            .map(|f| {
+                f.path
+                    .strip_prefix(&root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        names.sort();
+        names
+    };
+
+    // The root itself sits in a directory named `tests`; only paths below it count
+    assert_eq!(scan(TestFiles::Exclude), vec!["main.go", "src/lib.rs"]);
+    assert_eq!(scan(TestFiles::Only), vec!["main_test.go", "tests/api.rs"]);
+    assert_eq!(scan(TestFiles::Include).len(), 4);
+}
+
+#[test]
+fn scanner_skips_large_minified_and_binary_files() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    touch(root.join("app.js"));
+    touch(root.join("vendor.min.js"));
+    fs::write(root.join("bundle.js"), "x".repeat(2000)).unwrap();
+    fs::write(root.join("blob.js"), b"\x7fELF\x00\x01").unwrap();
+
+    let scanner = FileScanner::new().with_max_file_size(Some(1000));
+    let files = scanner.scan_directory(root, &["javascript"]).unwrap();
+    let names: Vec<_> = files
+        .iter()
+        .map(|f| f.path.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(names, vec!["app.js"]);
+
+    let skipped: Vec<_> = scanner
+        .take_skipped()
+        .into_iter()
+        .map(|s| {
+            (
+                s.path.file_name().unwrap().to_string_lossy().into_owned(),
+                s.reason,
+            )
+        })
+        .collect();
+    assert_eq!(
+        skipped,
+        vec![
+            ("blob.js".to_string(), SkipReason::Binary),
+            ("bundle.js".to_string(), SkipReason::TooLarge(2000)),
+            ("vendor.min.js".to_string(), SkipReason::Minified),
+        ]
+    );
+    // Taken once; explicit file lists go through the same checks
+    assert!(scanner.take_skipped().is_empty());
+    let listed = scanner.scan_files(
+        &[root.join("bundle.js"), root.join("app.js")],
+        &["javascript"],
+    );
+    assert_eq!(listed.len(), 1);
+    assert_eq!(scanner.take_skipped().len(), 1);
+
+    let unlimited = FileScanner::new().with_max_file_size(None);
+    assert_eq!(
+        unlimited
+            .scan_directory(root, &["javascript"])
+            .unwrap()
+            .len(),
+        2
+    );
+}